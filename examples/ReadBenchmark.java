@@ -0,0 +1,124 @@
+/*
+ * Copyright (C) 2026 Neeme Praks
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package examples;
+
+import dev.nemecec.jrserial.SerialPort;
+import org.slf4j.Logger;
+import org.slf4j.LoggerFactory;
+
+import java.io.IOException;
+import java.lang.management.GarbageCollectorMXBean;
+import java.lang.management.ManagementFactory;
+import java.nio.ByteBuffer;
+
+/**
+ * Benchmark comparing the read paths: {@code read(byte[])}, {@code readCritical(byte[])} and
+ * {@code read(ByteBuffer)} with a direct buffer.
+ *
+ * <p>The port must echo what it sends, through a loopback plug (TX wired to RX) or a peer that
+ * echoes. Each read path receives the same amount of data, one buffer at a time; the time per
+ * call, throughput and garbage collection time are reported for each.
+ */
+public class ReadBenchmark {
+
+  private static final Logger LOG = LoggerFactory.getLogger(ReadBenchmark.class);
+
+  private static final int BUFFER_SIZE = 4096;
+  private static final int TOTAL_BYTES = 4 * 1024 * 1024;
+  private static final int WARMUP_BYTES = 256 * 1024;
+
+  private interface ReadPath {
+    int read(SerialPort port) throws IOException;
+  }
+
+  public static void main(String[] args) throws IOException {
+    if (args.length < 1) {
+      LOG.warn("Usage: ReadBenchmark <port> [baudRate]");
+      return;
+    }
+    int baudRate = args.length > 1 ? Integer.parseInt(args[1]) : 921600;
+
+    try (SerialPort port = SerialPort.builder()
+        .portName(args[0])
+        .baudRate(baudRate)
+        .timeout(1000)
+        .build()) {
+
+      port.open();
+
+      byte[] array = new byte[BUFFER_SIZE];
+      ByteBuffer direct = ByteBuffer.allocateDirect(BUFFER_SIZE);
+
+      run(port, "read(byte[])", p -> p.read(array, 0, array.length));
+      run(port, "readCritical(byte[])", p -> p.readCritical(array, 0, array.length));
+      run(port, "read(ByteBuffer)", p -> {
+        direct.clear();
+        return p.read(direct);
+      });
+    }
+  }
+
+  private static void run(SerialPort port, String name, ReadPath path) throws IOException {
+    receive(port, path, WARMUP_BYTES);
+
+    long gcBefore = gcMillis();
+    long start = System.nanoTime();
+    long calls = receive(port, path, TOTAL_BYTES);
+    long elapsed = System.nanoTime() - start;
+    long gcMillis = gcMillis() - gcBefore;
+
+    LOG.info("{}: {} calls, {} ns/call, {} KiB/s, {} ms in GC",
+        name,
+        calls,
+        elapsed / Math.max(calls, 1),
+        (long) (TOTAL_BYTES / 1024.0 / (elapsed / 1e9)),
+        gcMillis);
+  }
+
+  /**
+   * Echo {@code total} bytes through the port one buffer at a time, reading each buffer back
+   * with {@code path} before sending the next.
+   *
+   * @return the number of read calls that returned data
+   */
+  private static long receive(SerialPort port, ReadPath path, int total) throws IOException {
+    port.clearInput();
+    byte[] block = new byte[BUFFER_SIZE];
+    long calls = 0;
+    for (int sent = 0; sent < total; sent += block.length) {
+      int length = Math.min(block.length, total - sent);
+      port.write(block, 0, length);
+      int received = 0;
+      while (received < length) {
+        int n = path.read(port);
+        if (n == 0) {
+          throw new IOException("Only " + (sent + received) + " of " + total + " bytes came back");
+        }
+        received += n;
+        calls++;
+      }
+    }
+    return calls;
+  }
+
+  private static long gcMillis() {
+    long total = 0;
+    for (GarbageCollectorMXBean gc : ManagementFactory.getGarbageCollectorMXBeans()) {
+      total += Math.max(gc.getCollectionTime(), 0);
+    }
+    return total;
+  }
+}
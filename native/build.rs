@@ -0,0 +1,66 @@
+// Copyright (C) 2026 Neeme Praks
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Captures build information reported by `getNativeVersion()`.
+
+use std::path::Path;
+use std::process::Command;
+
+fn main() {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+    let manifest_dir = Path::new(&manifest_dir);
+
+    let lock_file = manifest_dir.join("Cargo.lock");
+    println!("cargo:rerun-if-changed={}", lock_file.display());
+    let serialport_version = std::fs::read_to_string(&lock_file)
+        .ok()
+        .and_then(|lock| locked_version(&lock, "serialport"))
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=JRSERIAL_SERIALPORT_VERSION={}", serialport_version);
+
+    // Git is not available in every build environment (e.g. cross containers or source archives)
+    let git_dir = manifest_dir.join("../.git");
+    for file in ["HEAD", "logs/HEAD"] {
+        let path = git_dir.join(file);
+        if path.exists() {
+            println!("cargo:rerun-if-changed={}", path.display());
+        }
+    }
+    let git_hash = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .current_dir(manifest_dir)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|hash| hash.trim().to_string())
+        .filter(|hash| !hash.is_empty())
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=JRSERIAL_GIT_HASH={}", git_hash);
+
+    println!("cargo:rustc-env=JRSERIAL_TARGET={}", std::env::var("TARGET").unwrap());
+}
+
+/// Find the version of a package in Cargo.lock
+fn locked_version(lock: &str, package: &str) -> Option<String> {
+    let name_line = format!("name = \"{}\"", package);
+    let mut lines = lock.lines();
+    while let Some(line) = lines.next() {
+        if line.trim() == name_line {
+            let version = lines.next()?.trim().strip_prefix("version = \"")?;
+            return Some(version.trim_end_matches('"').to_string());
+        }
+    }
+    None
+}
@@ -12,22 +12,29 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use jni::objects::{JByteArray, JClass, JString};
+use jni::objects::{
+    JBooleanArray, JByteArray, JByteBuffer, JClass, JIntArray, JLongArray, JObjectArray, JString,
+    ReleaseMode,
+};
 use jni::sys::{jboolean, jint, jlong, jstring};
 use jni::JNIEnv;
 use serialport::{DataBits, FlowControl, Parity, SerialPortType, StopBits};
 // On Linux, TTYPort requires SerialPort trait in scope for method calls
 #[cfg(target_os = "linux")]
 use serialport::SerialPort;
-use std::cell::RefCell;
-use std::io::{Read, Write};
-use std::time::Duration;
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, VecDeque};
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, OnceLock};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
 
 // ============================================================================
 // Error Context Tracking
 // ============================================================================
 
-/// Stores context about the last error that occurred in native code.
+/// Stores context about an error that occurred in native code.
 /// This provides detailed diagnostic information for debugging.
 #[derive(Clone)]
 struct ErrorContext {
@@ -36,38 +43,187 @@ struct ErrorContext {
     line: u32,
 }
 
+impl ErrorContext {
+    fn format(&self) -> String {
+        format!("{} (at {}:{})", self.message, self.file, self.line)
+    }
+
+    /// Format as `message\tfile\tline`. The message comes first since only it may contain
+    /// tabs, so the fields can be split from the end.
+    fn details(&self) -> String {
+        format!("{}\t{}\t{}", self.message, self.file, self.line)
+    }
+}
+
+/// Recent errors on the current thread, oldest first.
+struct ErrorHistory {
+    entries: VecDeque<ErrorContext>,
+    /// True until the newest entry has been reported through an exception
+    last_pending: bool,
+}
+
+/// Default number of errors retained per thread
+const DEFAULT_ERROR_HISTORY_SIZE: usize = 8;
+
+/// Number of errors retained per thread, shared by all threads
+static ERROR_HISTORY_SIZE: AtomicUsize = AtomicUsize::new(DEFAULT_ERROR_HISTORY_SIZE);
+
+/// When set, unknown data bits, stop bits, parity and flow control codes make an open
+/// fail instead of falling back to the defaults
+static STRICT_CONFIG_VALIDATION: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    static ERROR_HISTORY: RefCell<ErrorHistory> = const {
+        RefCell::new(ErrorHistory { entries: VecDeque::new(), last_pending: false })
+    };
+}
+
+/// Reasons for an open failure reported by `getLastOpenFailure`
+const OPEN_FAILURE_OTHER: jint = 0;
+const OPEN_FAILURE_BUSY: jint = 1;
+const OPEN_FAILURE_NOT_FOUND: jint = 2;
+const OPEN_FAILURE_PERMISSION: jint = 3;
+
+thread_local! {
+    /// Why the last open on the current thread failed
+    static LAST_OPEN_FAILURE: Cell<jint> = const { Cell::new(OPEN_FAILURE_OTHER) };
+}
+
 thread_local! {
-    static LAST_ERROR: RefCell<Option<ErrorContext>> = const { RefCell::new(None) };
+    /// Per-port problems noticed by the last port enumeration on the current thread
+    static ENUMERATION_WARNINGS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Classify why opening a port failed.
+///
+/// On POSIX systems, serialport reports both EBUSY from open() (the port was opened
+/// exclusively by someone else) and a failure to take the exclusive lock as `NoDevice`.
+/// On Windows, `NoDevice` also covers missing ports and denied access, so it cannot be
+/// told apart and is reported as other.
+fn open_failure_reason(e: &serialport::Error) -> jint {
+    match e.kind() {
+        #[cfg(unix)]
+        serialport::ErrorKind::NoDevice => OPEN_FAILURE_BUSY,
+        serialport::ErrorKind::Io(std::io::ErrorKind::ResourceBusy) => OPEN_FAILURE_BUSY,
+        serialport::ErrorKind::Io(std::io::ErrorKind::NotFound) => OPEN_FAILURE_NOT_FOUND,
+        serialport::ErrorKind::Io(std::io::ErrorKind::PermissionDenied) => OPEN_FAILURE_PERMISSION,
+        _ => OPEN_FAILURE_OTHER,
+    }
 }
 
-/// Sets the last error with automatic file and line capture.
+/// Records an error with automatic file and line capture.
 /// Use this macro at error sites to record diagnostic information.
 macro_rules! set_error {
     ($msg:expr) => {
-        LAST_ERROR.with(|e| {
-            *e.borrow_mut() = Some(ErrorContext {
-                message: $msg.to_string(),
-                file: file!(),
-                line: line!(),
-            });
-        });
+        push_error(ErrorContext {
+            message: $msg.to_string(),
+            file: file!(),
+            line: line!(),
+        })
+    };
+}
+
+/// Records an error against an open port as well as the thread's history.
+/// Use this macro in functions that operate on a valid (non-null) port handle.
+macro_rules! set_port_error {
+    ($handle:expr, $msg:expr) => {{
+        let ctx = ErrorContext {
+            message: $msg.to_string(),
+            file: file!(),
+            line: line!(),
+        };
+        record_port_error($handle, ctx.clone());
+        push_error(ctx);
+    }};
+}
+
+/// Stores an error as the last error of the port behind `handle`.
+fn record_port_error(handle: jlong, ctx: ErrorContext) {
+    let Some(port) = port_handle(handle) else {
+        return;
+    };
+    *port.last_error.lock().unwrap_or_else(|e| e.into_inner()) = Some(ctx);
+}
+
+/// Locks the port behind `handle` for the rest of the calling function, first taking the
+/// given I/O turn (`Read`, `Write` or both as `Read + Write`) for a call that waits with the
+/// port unlocked. Records an error and returns `$failed` if the handle does not refer to an
+/// open port, for example because another thread closed it.
+macro_rules! lock_port {
+    ($handle:expr, Read + Write, $what:expr, $failed:expr) => {
+        lock_port!(@locked lock_port_with_turns($handle, &[IoTurn::Read, IoTurn::Write]), $what, $failed)
+    };
+    ($handle:expr, $what:expr, $failed:expr) => {
+        lock_port!(@locked lock_port($handle), $what, $failed)
+    };
+    ($handle:expr, $turn:ident, $what:expr, $failed:expr) => {
+        lock_port!(@locked lock_port_with_turn($handle, Some(IoTurn::$turn)), $what, $failed)
+    };
+    (@locked $locked:expr, $what:expr, $failed:expr) => {
+        match $locked {
+            Some(port) => port,
+            None => {
+                set_error!(concat!($what, ": port is not open"));
+                return $failed;
+            }
+        }
     };
 }
 
-/// Clears the last error. Call this at the start of operations to ensure
+/// Appends an error to the history, dropping the oldest entries beyond the configured size.
+fn push_error(ctx: ErrorContext) {
+    let size = ERROR_HISTORY_SIZE.load(Ordering::Relaxed);
+    ERROR_HISTORY.with(|h| {
+        let mut h = h.borrow_mut();
+        h.entries.push_back(ctx);
+        while h.entries.len() > size {
+            h.entries.pop_front();
+        }
+        h.last_pending = true;
+    });
+}
+
+/// Clears the whole error history. Call this at the start of operations to ensure
 /// stale errors don't persist.
 fn clear_error() {
-    LAST_ERROR.with(|e| {
-        *e.borrow_mut() = None;
+    ERROR_HISTORY.with(|h| {
+        let mut h = h.borrow_mut();
+        h.entries.clear();
+        h.last_pending = false;
     });
 }
 
-/// Gets the last error as a formatted string, or None if no error.
+/// Marks the last error as reported, so it is not attached to a later failure.
+/// The error stays in the history.
+fn acknowledge_error() {
+    ERROR_HISTORY.with(|h| h.borrow_mut().last_pending = false);
+}
+
+/// Gets the last error as a formatted string, or None if no error is pending.
 fn get_last_error_string() -> Option<String> {
-    LAST_ERROR.with(|e| {
-        e.borrow().as_ref().map(|ctx| {
-            format!("{} (at {}:{})", ctx.message, ctx.file, ctx.line)
-        })
+    ERROR_HISTORY.with(|h| {
+        let h = h.borrow();
+        if !h.last_pending {
+            return None;
+        }
+        h.entries.back().map(ErrorContext::format)
+    })
+}
+
+/// Gets the details of the newest retained error, even if it was already reported, or None
+/// if there is none.
+fn get_newest_error_details() -> Option<String> {
+    ERROR_HISTORY.with(|h| h.borrow().entries.back().map(ErrorContext::details))
+}
+
+/// Gets all retained errors, oldest first, joined by newlines, or None if there are none.
+fn get_error_history_string() -> Option<String> {
+    ERROR_HISTORY.with(|h| {
+        let h = h.borrow();
+        if h.entries.is_empty() {
+            return None;
+        }
+        Some(h.entries.iter().map(ErrorContext::format).collect::<Vec<_>>().join("\n"))
     })
 }
 
@@ -90,7 +246,7 @@ fn normalize_timeout_ms(timeout_ms: u64) -> Duration {
             Duration::from_millis(0)
         } else {
             // Round up to nearest 100ms
-            let rounded = ((timeout_ms + 99) / 100) * 100;
+            let rounded = timeout_ms.div_ceil(100) * 100;
             Duration::from_millis(rounded)
         }
     }
@@ -114,603 +270,1099 @@ enum Rs485ControlMode {
 
 /// Which pin to use for manual RS-485 control
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
 enum Rs485ControlPin {
     RTS,
     DTR,
 }
 
-// Platform-specific port wrapper implementations
-// On Linux, we store TTYPort directly to access RS-485 kernel mode
-// On other platforms, we use Box<dyn SerialPort>
-
-#[cfg(target_os = "linux")]
-#[path = "platform_linux.rs"]
-mod platform;
-
-#[cfg(not(target_os = "linux"))]
-#[path = "platform_other.rs"]
-mod platform;
-
-use platform::PortWrapper;
-
-/// Convert Java String to Rust String
-fn jstring_to_string(env: &mut JNIEnv, jstr: JString) -> Result<String, String> {
-    env.get_string(&jstr)
-        .map(|s| s.into())
-        .map_err(|e| format!("Failed to convert JString: {}", e))
-}
-
-/// Create Java String from Rust String
-fn string_to_jstring(env: &mut JNIEnv, s: &str) -> jstring {
-    env.new_string(s)
-        .map(|js| js.into_raw())
-        .unwrap_or(std::ptr::null_mut())
+/// Which directions of I/O a port is opened for
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum AccessMode {
+    ReadWrite,
+    /// Reads only; writes (and RS-485 direction control) are rejected
+    ReadOnly,
+    /// Writes only; reads are rejected
+    WriteOnly,
 }
 
-/// Open a serial port and return a pointer to the boxed PortWrapper
-/// rs485_mode: 0 = None, 1 = Auto, 2 = Manual
-/// rs485_pin: 0 = RTS, 1 = DTR
-#[no_mangle]
-pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_open(
-    mut env: JNIEnv,
-    _class: JClass,
-    port_name: JString,
-    baud_rate: jint,
-    data_bits: jint,
-    stop_bits: jint,
-    parity: jint,
-    timeout_ms: jint,
-    rs485_mode: jint,
-    rs485_pin: jint,
-) -> jlong {
-    let port_name = match jstring_to_string(&mut env, port_name) {
-        Ok(s) => s,
-        Err(e) => {
-            set_error!(format!("Invalid port name: {}", e));
-            return 0;
+impl AccessMode {
+    /// Fail unless this mode allows the given direction of I/O
+    fn check(self, write: bool) -> Result<(), std::io::Error> {
+        let denied = match self {
+            AccessMode::ReadWrite => None,
+            AccessMode::ReadOnly if write => Some("port was opened read-only"),
+            AccessMode::WriteOnly if !write => Some("port was opened write-only"),
+            _ => None,
+        };
+        match denied {
+            Some(reason) => Err(std::io::Error::new(std::io::ErrorKind::PermissionDenied, reason)),
+            None => Ok(()),
         }
-    };
+    }
+}
 
-    let data_bits = match data_bits {
-        5 => DataBits::Five,
-        6 => DataBits::Six,
-        7 => DataBits::Seven,
-        8 => DataBits::Eight,
-        _ => DataBits::Eight,
-    };
+/// How a write reacts when the port reports `WouldBlock`
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum WriteBlockingPolicy {
+    /// Retry until the port timeout expires (default)
+    Block,
+    /// Fail immediately with a distinct error code
+    Error,
+    /// Return the number of bytes written so far (possibly 0)
+    Partial,
+}
 
-    let stop_bits = match stop_bits {
-        1 => StopBits::One,
-        2 => StopBits::Two,
-        _ => StopBits::One,
-    };
+/// Return code used by write functions when the port would block
+/// and the write blocking policy is `Error`.
+const WRITE_WOULD_BLOCK: jint = -2;
 
-    let parity = match parity {
-        0 => Parity::None,
-        1 => Parity::Odd,
-        2 => Parity::Even,
-        _ => Parity::None,
-    };
+/// Return code used by read functions when the read was cancelled
+const READ_CANCELLED: jint = -2;
 
-    let control_mode = match rs485_mode {
-        0 => Rs485ControlMode::None,
-        1 => Rs485ControlMode::Auto,
-        2 => Rs485ControlMode::Manual,
-        _ => Rs485ControlMode::None,
-    };
+/// Return code used by the direct buffer functions when the JVM does not expose the buffer's
+/// address; the caller falls back to copying through a byte array
+const DIRECT_BUFFER_UNAVAILABLE: jint = -3;
 
-    let control_pin = match rs485_pin {
-        0 => Rs485ControlPin::RTS,
-        1 => Rs485ControlPin::DTR,
-        _ => Rs485ControlPin::RTS,
-    };
+/// Check the length of a read into a Java array, before the port is locked
+/// Returns None for a length of 0, which needs no read: an empty read is driver-dependent,
+/// so it is not issued. Limit the length with `cap_read_len` once the port is locked.
+fn check_read_len(length: jint) -> Result<Option<usize>, String> {
+    match length {
+        0 => Ok(None),
+        length if length < 0 => Err(format!("invalid length {}", length)),
+        length => Ok(Some(length as usize)),
+    }
+}
 
-    let timeout = normalize_timeout_ms(timeout_ms as u64);
+/// Limit a read length to the port's maximum read chunk (0 = no limit)
+fn cap_read_len(requested: usize, max_read_chunk: usize) -> usize {
+    if max_read_chunk == 0 {
+        requested
+    } else {
+        requested.min(max_read_chunk)
+    }
+}
 
-    let builder = serialport::new(port_name, baud_rate as u32)
-        .data_bits(data_bits)
-        .stop_bits(stop_bits)
-        .parity(parity)
-        .flow_control(FlowControl::None)
-        .timeout(timeout);
+/// Marks the error of a read woken up by `cancel_read`, so that it is not confused with a
+/// wait or read interrupted by a signal (the JVM uses signals for garbage collection and
+/// profiling)
+#[derive(Debug)]
+struct ReadCancelled;
 
-    // Platform-specific port opening
-    #[cfg(target_os = "linux")]
-    let port_result = builder.open_native();
+impl std::fmt::Display for ReadCancelled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Read cancelled")
+    }
+}
 
-    #[cfg(not(target_os = "linux"))]
-    let port_result = builder.open();
+impl std::error::Error for ReadCancelled {}
 
-    match port_result {
-        Ok(port) => {
-            let mut wrapper = PortWrapper::new(port);
+/// Error returned by a read woken up by `cancel_read`
+#[cfg(target_os = "linux")]
+fn read_cancelled_error() -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::Interrupted, ReadCancelled)
+}
 
-            // Configure RS-485 mode if requested
-            if control_mode != Rs485ControlMode::None {
-                if let Err(e) = wrapper.configure_rs485(control_mode, control_pin) {
-                    set_error!(format!("Failed to configure RS-485: {}", e));
-                    return 0;
-                }
-            }
+/// Check whether a read failed because it was cancelled
+fn is_read_cancelled(e: &std::io::Error) -> bool {
+    e.get_ref().is_some_and(|inner| inner.is::<ReadCancelled>())
+}
 
-            let boxed = Box::new(wrapper);
-            Box::into_raw(boxed) as jlong
-        }
-        Err(e) => {
-            set_error!(format!("Failed to open port: {}", e));
-            0
-        }
+/// Map a read error to the JNI return code, distinguishing cancellation
+fn read_error_code(e: &std::io::Error) -> jint {
+    if is_read_cancelled(e) {
+        READ_CANCELLED
+    } else {
+        -1
     }
 }
 
-/// Close the serial port
-#[no_mangle]
-pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_close(
-    _env: JNIEnv,
-    _class: JClass,
-    handle: jlong,
-) {
-    if handle != 0 {
-        unsafe {
-            let _ = Box::from_raw(handle as *mut PortWrapper);
-        }
+/// Report the ways a read tells that the other end has gone away as one `UnexpectedEof`
+/// error: a read that returns no data although the port polled readable, or a hang-up,
+/// which `serialport` reports as `BrokenPipe`. A PTY whose master was closed, or a USB
+/// adapter whose driver hung up on unplug, keeps returning end of file from then on.
+fn detect_eof(result: std::io::Result<usize>, requested: usize) -> std::io::Result<usize> {
+    match result {
+        Ok(0) if requested > 0 => Err(eof_error()),
+        Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => Err(eof_error()),
+        other => other,
     }
 }
 
-/// Write data to the serial port with automatic RS-485 control
-#[no_mangle]
-pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_write(
-    env: JNIEnv,
-    _class: JClass,
-    handle: jlong,
-    data: JByteArray,
-    offset: jint,
-    length: jint,
-) -> jint {
-    if handle == 0 {
-        set_error!("Write failed: port handle is null");
-        return -1;
-    }
+fn eof_error() -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::UnexpectedEof,
+        "port closed by peer (end of file)",
+    )
+}
 
-    let mut buffer = vec![0i8; length as usize];
-    if let Err(e) = env.get_byte_array_region(&data, offset, &mut buffer[..]) {
-        set_error!(format!("Write failed: could not read buffer: {}", e));
-        return -1;
-    }
+/// Check whether a read result is the end of file reported by `detect_eof`
+fn is_eof<T>(result: &std::io::Result<T>) -> bool {
+    matches!(result, Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof)
+}
 
-    // Convert i8 to u8 for writing
-    let u8_buffer: Vec<u8> = buffer.iter().map(|&b| b as u8).collect();
+/// Pause between retries of a read that failed with a transient error
+const READ_RETRY_BACKOFF: Duration = Duration::from_millis(1);
 
-    unsafe {
-        let wrapper = &mut *(handle as *mut PortWrapper);
-        match wrapper.write_rs485(&u8_buffer) {
-            Ok(n) => n as jint,
-            Err(e) => {
-                set_error!(format!("Write failed: {}", e));
-                -1
+/// Check whether a failed read is worth retrying. Some USB serial drivers report a
+/// sporadic EIO that clears on the next read; a vanished device (ENODEV), a timeout,
+/// end of file or a cancellation is never retried.
+///
+/// Only EIO is retried, so the errno must survive the read. Linux reads the descriptor
+/// directly for that; elsewhere the serialport crate drops the errno and nothing is retried.
+fn is_transient_read_error(e: &std::io::Error) -> bool {
+    #[cfg(target_os = "linux")]
+    return e.raw_os_error() == Some(libc::EIO);
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = e;
+        false
+    }
+}
+
+/// Run a read, retrying it up to `retries` times while it fails with a transient error.
+/// Returns the result of the last attempt and the number of retries made.
+fn read_with_retries<T>(
+    retries: u32,
+    mut read: impl FnMut() -> std::io::Result<T>,
+) -> (std::io::Result<T>, u32) {
+    let mut attempts = 0;
+    loop {
+        let result = read();
+        match &result {
+            Err(e) if attempts < retries && is_transient_read_error(e) => {
+                attempts += 1;
+                std::thread::sleep(READ_RETRY_BACKOFF);
             }
+            _ => return (result, attempts),
         }
     }
 }
 
-/// Read data from the serial port
-#[no_mangle]
-pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_read(
-    env: JNIEnv,
-    _class: JClass,
-    handle: jlong,
-    buffer: JByteArray,
-    offset: jint,
-    length: jint,
-) -> jint {
-    if handle == 0 {
-        set_error!("Read failed: port handle is null");
-        return -1;
+/// Write data, handling `WouldBlock` according to the given policy.
+///
+/// With `Block`, the write is retried until the port timeout expires; a timeout
+/// of 0 retries indefinitely, matching the POSIX "no timeout" meaning.
+fn write_with_policy<W: Write + ?Sized>(
+    port: &mut W,
+    data: &[u8],
+    policy: WriteBlockingPolicy,
+    timeout: Duration,
+) -> std::io::Result<usize> {
+    let start = Instant::now();
+    loop {
+        match port.write(data) {
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => match policy {
+                WriteBlockingPolicy::Block => {
+                    if !timeout.is_zero() && start.elapsed() >= timeout {
+                        return Err(std::io::Error::new(
+                            std::io::ErrorKind::TimedOut,
+                            "Write timed out while port would block",
+                        ));
+                    }
+                    std::thread::sleep(Duration::from_millis(1));
+                }
+                WriteBlockingPolicy::Error => return Err(e),
+                WriteBlockingPolicy::Partial => return Ok(0),
+            },
+            result => return result,
+        }
     }
+}
 
-    let mut read_buffer = vec![0u8; length as usize];
+/// Write as much of `data` as possible before `deadline`, honoring the write blocking policy
+/// and pacing.
+///
+/// The port timeout is narrowed to the exact remaining time for each write and restored
+/// afterwards; a pacing gap waits for the previous chunk to drain, but never past the
+/// deadline. Returns the bytes accepted by the driver, which is fewer than `data.len()` if
+/// the deadline passed first.
+fn write_until(
+    port: &mut dyn serialport::SerialPort,
+    data: &[u8],
+    policy: WriteBlockingPolicy,
+    pacing: WritePacing,
+    deadline: Instant,
+) -> std::io::Result<usize> {
+    let original_timeout = port.timeout();
+    let chunk_bytes = if pacing.chunk_bytes == 0 { data.len().max(1) } else { pacing.chunk_bytes };
+    let mut written = 0;
 
-    let bytes_read = unsafe {
-        let wrapper = &mut *(handle as *mut PortWrapper);
-        match wrapper.port.read(&mut read_buffer) {
-            Ok(n) => n,
-            Err(e) => {
-                set_error!(format!("Read failed: {}", e));
-                return -1;
+    let result = 'chunks: loop {
+        if written == data.len() {
+            break Ok(written);
+        }
+        if written > 0 {
+            // Let the previous chunk leave before the gap starts, as write_paced does
+            loop {
+                match port.bytes_to_write() {
+                    Ok(0) => break,
+                    Ok(_) if Instant::now() >= deadline => break 'chunks Ok(written),
+                    Ok(_) => std::thread::sleep(Duration::from_millis(1)),
+                    Err(_) => break 'chunks Ok(written),
+                }
+            }
+            std::thread::sleep(pacing.gap.min(deadline.saturating_duration_since(Instant::now())));
+        }
+
+        let end = (written + chunk_bytes).min(data.len());
+        while written < end {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                break 'chunks Ok(written);
+            }
+            if let Err(e) = port.set_timeout(remaining) {
+                break 'chunks Err(e.into());
+            }
+            match write_with_policy(port, &data[written..end], policy, remaining) {
+                // Only the Partial policy accepts nothing without an error
+                Ok(0) => break 'chunks Ok(written),
+                Ok(n) => written += n,
+                // Below the timer's resolution; the deadline check above ends the write
+                Err(e) if e.kind() == std::io::ErrorKind::TimedOut => {}
+                Err(e) if written == 0 => break 'chunks Err(e),
+                Err(_) => break 'chunks Ok(written),
             }
         }
     };
 
-    if bytes_read > 0 {
-        // Convert u8 to i8 for JNI
-        let i8_buffer: Vec<i8> = read_buffer[..bytes_read].iter().map(|&b| b as i8).collect();
+    let _ = port.set_timeout(original_timeout);
+    result
+}
 
-        if let Err(e) = env.set_byte_array_region(&buffer, offset, &i8_buffer) {
-            set_error!(format!("Read failed: could not write to buffer: {}", e));
-            return -1;
+/// Longest a write waits for its output to drain before releasing the RS-485 bus, by default
+const DEFAULT_DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Wait for the output queue to empty before `deadline`, then discard whatever is left.
+///
+/// Unlike a plain drain, this cannot hang on a transmitter held off by flow control.
+/// Returns the number of bytes discarded, 0 if everything was sent.
+fn drain_until(
+    port: &mut dyn serialport::SerialPort,
+    deadline: Instant,
+) -> std::io::Result<usize> {
+    loop {
+        let pending = port.bytes_to_write()?;
+        if pending == 0 {
+            // The queue is empty; wait for the last character to leave the shift register
+            port.flush()?;
+            return Ok(0);
         }
+        if Instant::now() >= deadline {
+            port.clear(serialport::ClearBuffer::Output)?;
+            return Ok(pending as usize);
+        }
+        std::thread::sleep(Duration::from_millis(1));
     }
+}
 
-    bytes_read as jint
+/// Highest input and output queue depths observed since open or the last reset
+#[derive(Clone, Copy, Default)]
+struct QueueHighWater {
+    input: u32,
+    output: u32,
 }
 
-/// Get the number of bytes available to read
-#[no_mangle]
-pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_bytesAvailable(
-    _env: JNIEnv,
-    _class: JClass,
-    handle: jlong,
-) -> jint {
-    if handle == 0 {
-        return 0;
+impl QueueHighWater {
+    fn observe_input(&mut self, depth: u32) {
+        self.input = self.input.max(depth);
     }
 
-    unsafe {
-        let wrapper = &mut *(handle as *mut PortWrapper);
-        match wrapper.port.bytes_to_read() {
-            Ok(n) => n as jint,
-            Err(e) => {
-                set_error!(format!("Failed to get bytes available: {}", e));
-                0
-            }
-        }
+    fn observe_output(&mut self, depth: u32) {
+        self.output = self.output.max(depth);
     }
 }
 
-/// Flush the output buffer
-#[no_mangle]
-pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_flush(
-    _env: JNIEnv,
-    _class: JClass,
-    handle: jlong,
-) -> jboolean {
-    if handle == 0 {
-        set_error!("Flush failed: port handle is null");
-        return 0;
+/// Splitting of large writes into chunks separated by gaps, for receivers with small buffers
+#[derive(Clone, Copy, Default)]
+struct WritePacing {
+    /// Maximum bytes per chunk, or 0 for no pacing
+    chunk_bytes: usize,
+    /// Pause between chunks, measured from the moment the previous chunk has been sent
+    gap: Duration,
+}
+
+/// Burst allowance of a throughput limit, as a fraction of a second of output at the limit
+const THROUGHPUT_BURST_SECS: f64 = 0.1;
+
+/// Token bucket limiting the sustained write rate of a port.
+///
+/// A write takes tokens for all of its bytes at once, even if that drives the balance
+/// negative, and the next write waits until the debt is repaid. Writes are therefore throttled
+/// between frames, never within one. Unused tokens accumulate up to THROUGHPUT_BURST_SECS of
+/// output, so a short write after an idle period goes out without delay.
+#[derive(Clone, Copy, Default)]
+struct ThroughputLimit {
+    /// Maximum sustained rate in bytes per second, or 0 for unlimited
+    bytes_per_sec: u32,
+    /// Bytes that may be written without waiting; negative while a large write is repaid
+    tokens: f64,
+    /// When `tokens` was last brought up to date
+    updated: Option<Instant>,
+}
+
+impl ThroughputLimit {
+    fn new(bytes_per_sec: u32) -> Self {
+        let mut limit = Self {
+            bytes_per_sec,
+            ..Self::default()
+        };
+        limit.tokens = limit.capacity();
+        limit
     }
 
-    unsafe {
-        let wrapper = &mut *(handle as *mut PortWrapper);
-        match wrapper.port.flush() {
-            Ok(_) => 1,
-            Err(e) => {
-                set_error!(format!("Flush failed: {}", e));
-                0
-            }
+    fn capacity(&self) -> f64 {
+        f64::from(self.bytes_per_sec) * THROUGHPUT_BURST_SECS
+    }
+
+    /// How long the next write must wait to keep within the limit
+    fn delay(&mut self, now: Instant) -> Duration {
+        if self.bytes_per_sec == 0 {
+            return Duration::ZERO;
+        }
+        if let Some(updated) = self.updated {
+            let refill = now.duration_since(updated).as_secs_f64() * f64::from(self.bytes_per_sec);
+            self.tokens = (self.tokens + refill).min(self.capacity());
+        }
+        self.updated = Some(now);
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / f64::from(self.bytes_per_sec))
         }
     }
-}
 
-// ============================================================================
-// Port Enumeration with Symlink/PTY/Bluetooth Detection
-// ============================================================================
+    /// Take the tokens for `written` bytes
+    fn consume(&mut self, written: usize) {
+        if self.bytes_per_sec != 0 {
+            self.tokens -= written as f64;
+        }
+    }
+}
 
-/// Information about a serial port's device type
-struct PortTypeInfo {
-    is_symlink: bool,
-    is_pseudo_terminal: bool,
-    is_bluetooth: bool,
+/// Background thread that releases the RS-485 direction pin when it is left held with no
+/// write for a timeout, so a hung application cannot leave a shared bus driven.
+///
+/// The wrapper arms the timer whenever a write leaves the pin held and disarms it while a
+/// write is under way or once the pin is released. When the timer runs out, the pin is
+/// driven to receive through the thread's own clone of the port and a warning is recorded;
+/// the timer stays disarmed until the next write holds the pin again, so an idle port trips
+/// once. Dropping the watchdog stops and joins the thread.
+struct FailsafeWatchdog {
+    state: Arc<(Mutex<FailsafeState>, Condvar)>,
+    timeout: Duration,
+    thread: Option<JoinHandle<()>>,
 }
 
-/// Check if a path is a symlink and/or resolves to a pseudo-terminal.
-/// Returns (is_symlink, is_pseudo_terminal).
-#[cfg(unix)]
-fn get_port_type_info(path: &str) -> PortTypeInfo {
-    use std::fs;
-    use std::path::Path;
+#[derive(Default)]
+struct FailsafeState {
+    /// When the pin is released, None while disarmed
+    deadline: Option<Instant>,
+    /// Set when the pin was released, until the wrapper takes note with `take_tripped`
+    tripped: bool,
+    stopped: bool,
+}
 
-    let path = Path::new(path);
+impl FailsafeWatchdog {
+    fn spawn(
+        timeout: Duration,
+        mut release: impl FnMut() -> serialport::Result<()> + Send + 'static,
+        last_warning: Arc<Mutex<Option<String>>>,
+    ) -> Result<Self, std::io::Error> {
+        let state = Arc::new((Mutex::new(FailsafeState::default()), Condvar::new()));
+        let thread_state = Arc::clone(&state);
+        let thread = std::thread::Builder::new()
+            .name("jrserial-failsafe".to_string())
+            .spawn(move || {
+                let (lock, condvar) = &*thread_state;
+                let Ok(mut state) = lock.lock() else {
+                    return;
+                };
+                while !state.stopped {
+                    let Some(deadline) = state.deadline else {
+                        state = match condvar.wait(state) {
+                            Ok(state) => state,
+                            Err(_) => return,
+                        };
+                        continue;
+                    };
+                    let now = Instant::now();
+                    if now < deadline {
+                        state = match condvar.wait_timeout(state, deadline - now) {
+                            Ok((state, _)) => state,
+                            Err(_) => return,
+                        };
+                        continue;
+                    }
 
-    // Check if it's a symlink
-    let is_symlink = fs::symlink_metadata(path)
-        .map(|m| m.file_type().is_symlink())
-        .unwrap_or(false);
+                    // Released with the state locked, so a write that has disarmed the
+                    // timer either sees the trip or is never cut short by it
+                    let message = match release() {
+                        Ok(()) => format!(
+                            "failsafe: no write within {} ms, RS-485 direction pin released",
+                            timeout.as_millis()
+                        ),
+                        Err(e) => format!(
+                            "failsafe: no write within {} ms, could not release the RS-485 \
+                             direction pin: {}",
+                            timeout.as_millis(),
+                            e
+                        ),
+                    };
+                    state.deadline = None;
+                    state.tripped = true;
+                    if let Ok(mut warning) = last_warning.lock() {
+                        *warning = Some(message);
+                    }
+                }
+            })?;
+        Ok(Self {
+            state,
+            timeout,
+            thread: Some(thread),
+        })
+    }
 
-    // Get the resolved path (follows symlinks)
-    let resolved_path = if is_symlink {
-        fs::read_link(path)
-            .map(|p| p.to_string_lossy().to_string())
-            .unwrap_or_default()
-    } else {
-        path.to_string_lossy().to_string()
-    };
+    /// Start the timer over, after a write that leaves the pin held
+    fn arm(&self) {
+        self.set_deadline(Some(Instant::now() + self.timeout));
+    }
 
-    // Check if the path (or resolved target) is a pseudo-terminal
-    // PTYs are typically /dev/pts/N or /dev/pty*
-    let path_str = path.to_string_lossy();
-    let is_pseudo_terminal = resolved_path.contains("/dev/pts/")
-        || resolved_path.contains("/dev/pt")
-        || path_str.contains("/dev/pts/")
-        || path_str.contains("/dev/pt");
+    /// Stop the timer, while a write is under way or once the pin is released
+    fn disarm(&self) {
+        self.set_deadline(None);
+    }
 
-    // Check if this is a Bluetooth serial port
-    // macOS: /dev/cu.Bluetooth-*, /dev/tty.Bluetooth-*
-    // Linux: /dev/rfcomm*
-    let path_lower = path_str.to_lowercase();
-    let is_bluetooth = path_lower.contains("bluetooth")
-        || path_str.starts_with("/dev/rfcomm");
+    fn set_deadline(&self, deadline: Option<Instant>) {
+        let (lock, condvar) = &*self.state;
+        if let Ok(mut state) = lock.lock() {
+            state.deadline = deadline;
+            condvar.notify_all();
+        }
+    }
 
-    PortTypeInfo {
-        is_symlink,
-        is_pseudo_terminal,
-        is_bluetooth,
+    /// Whether the pin was released since the last call
+    fn take_tripped(&self) -> bool {
+        let (lock, _) = &*self.state;
+        lock.lock().is_ok_and(|mut state| std::mem::take(&mut state.tripped))
     }
 }
 
-/// On non-Unix platforms, symlink/PTY detection is not applicable
-/// but Bluetooth detection still works via pattern matching
-#[cfg(not(unix))]
-fn get_port_type_info(path: &str) -> PortTypeInfo {
-    let path_lower = path.to_lowercase();
-    let is_bluetooth = path_lower.contains("bluetooth");
-
-    PortTypeInfo {
-        is_symlink: false,
-        is_pseudo_terminal: false,
-        is_bluetooth,
+impl Drop for FailsafeWatchdog {
+    fn drop(&mut self) {
+        let (lock, condvar) = &*self.state;
+        if let Ok(mut state) = lock.lock() {
+            state.stopped = true;
+            condvar.notify_all();
+        }
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
     }
 }
 
-/// List available serial ports with detailed info.
-/// Returns tab-separated lines: name\tsymlink\tpty\tbluetooth\n
-/// where each flag is "1" or "0"
-#[no_mangle]
-pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_listPorts(
-    mut env: JNIEnv,
-    _class: JClass,
-) -> jstring {
-    let ports = match serialport::available_ports() {
-        Ok(ports) => ports,
-        Err(e) => {
-            set_error!(format!("Failed to list ports: {}", e));
-            return std::ptr::null_mut();
-        }
-    };
+/// Link type of capture files: DLT_USER0, for which Wireshark lets a custom dissector be
+/// assigned
+const CAPTURE_LINKTYPE: u32 = 147;
 
-    let result: String = ports
-        .iter()
-        .map(|p| {
-            let info = get_port_type_info(&p.port_name);
-            // Use native Bluetooth detection from serialport-rs, with pattern fallback
-            let is_bluetooth = matches!(p.port_type, SerialPortType::BluetoothPort)
-                || info.is_bluetooth;
-            format!(
-                "{}\t{}\t{}\t{}",
-                p.port_name,
-                if info.is_symlink { "1" } else { "0" },
-                if info.is_pseudo_terminal { "1" } else { "0" },
-                if is_bluetooth { "1" } else { "0" }
-            )
-        })
-        .collect::<Vec<_>>()
-        .join("\n");
+/// Largest packet in a capture file, including the direction byte; longer reads and writes
+/// are split over several packets
+const CAPTURE_SNAPLEN: usize = 65535;
 
-    string_to_jstring(&mut env, &result)
+/// Capture packet direction: data read from the port
+const CAPTURE_RECEIVED: u8 = 0;
+
+/// Capture packet direction: data written to the port
+const CAPTURE_TRANSMITTED: u8 = 1;
+
+/// Records port traffic to a libpcap file.
+///
+/// Each read and write becomes a packet timestamped to the microsecond, holding a direction
+/// byte (CAPTURE_RECEIVED or CAPTURE_TRANSMITTED) followed by the data. The file is written
+/// through a buffer, so recording usually costs only a copy on the I/O path; the buffer is
+/// flushed by `finish`.
+struct TrafficCapture {
+    file: std::io::BufWriter<std::fs::File>,
 }
 
-/// Set timeout
-#[no_mangle]
-pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_setTimeout(
-    _env: JNIEnv,
-    _class: JClass,
-    handle: jlong,
-    timeout_ms: jint,
-) -> jboolean {
-    if handle == 0 {
-        set_error!("Set timeout failed: port handle is null");
-        return 0;
+impl TrafficCapture {
+    /// Create (or truncate) the capture file and write the file header
+    fn create(path: &str) -> Result<Self, std::io::Error> {
+        let file = std::fs::File::create(path)?;
+        let mut file = std::io::BufWriter::with_capacity(64 * 1024, file);
+        file.write_all(&pcap_file_header())?;
+        Ok(Self { file })
     }
 
-    let timeout = normalize_timeout_ms(timeout_ms as u64);
-
-    unsafe {
-        let wrapper = &mut *(handle as *mut PortWrapper);
-        match wrapper.port.set_timeout(timeout) {
-            Ok(_) => 1,
-            Err(e) => {
-                set_error!(format!("Set timeout failed: {}", e));
-                0
-            }
+    /// Record one read or write
+    fn record(&mut self, direction: u8, data: &[u8]) -> Result<(), std::io::Error> {
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default();
+        for chunk in data.chunks(CAPTURE_SNAPLEN - 1) {
+            self.file.write_all(&pcap_packet_header(timestamp, chunk.len() + 1))?;
+            self.file.write_all(&[direction])?;
+            self.file.write_all(chunk)?;
         }
+        Ok(())
     }
-}
 
-/// Clear input buffer
-#[no_mangle]
-pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_clearInput(
-    _env: JNIEnv,
-    _class: JClass,
-    handle: jlong,
-) -> jboolean {
-    if handle == 0 {
-        set_error!("Clear input failed: port handle is null");
-        return 0;
+    /// Flush the buffered packets to the file and close it
+    fn finish(mut self) -> Result<(), std::io::Error> {
+        self.file.flush()
     }
+}
 
-    unsafe {
-        let wrapper = &mut *(handle as *mut PortWrapper);
-        match wrapper.port.clear(serialport::ClearBuffer::Input) {
-            Ok(_) => 1,
-            Err(e) => {
-                set_error!(format!("Clear input failed: {}", e));
-                0
-            }
+/// Record traffic to the capture if one is running. A capture that fails to write is
+/// stopped and a warning recorded, so a full disk does not fail the port's I/O.
+fn capture_traffic(
+    capture: &mut Option<TrafficCapture>,
+    last_warning: &Mutex<Option<String>>,
+    direction: u8,
+    data: &[u8],
+) {
+    let Some(active) = capture else {
+        return;
+    };
+    if data.is_empty() {
+        return;
+    }
+    if let Err(e) = active.record(direction, data) {
+        *capture = None;
+        if let Ok(mut warning) = last_warning.lock() {
+            *warning = Some(format!("capture stopped: {}", e));
         }
     }
 }
 
-/// Clear output buffer
-#[no_mangle]
-pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_clearOutput(
-    _env: JNIEnv,
-    _class: JClass,
-    handle: jlong,
-) -> jboolean {
-    if handle == 0 {
-        set_error!("Clear output failed: port handle is null");
-        return 0;
-    }
+/// libpcap file header: version 2.4, microsecond timestamps, little-endian
+fn pcap_file_header() -> [u8; 24] {
+    let mut header = [0u8; 24];
+    header[0..4].copy_from_slice(&0xa1b2_c3d4u32.to_le_bytes());
+    header[4..6].copy_from_slice(&2u16.to_le_bytes());
+    header[6..8].copy_from_slice(&4u16.to_le_bytes());
+    // Bytes 8..16: time zone offset and timestamp accuracy, both always zero
+    header[16..20].copy_from_slice(&(CAPTURE_SNAPLEN as u32).to_le_bytes());
+    header[20..24].copy_from_slice(&CAPTURE_LINKTYPE.to_le_bytes());
+    header
+}
 
-    unsafe {
-        let wrapper = &mut *(handle as *mut PortWrapper);
-        match wrapper.port.clear(serialport::ClearBuffer::Output) {
-            Ok(_) => 1,
-            Err(e) => {
-                set_error!(format!("Clear output failed: {}", e));
-                0
-            }
-        }
-    }
+/// libpcap packet header for a packet of `len` bytes captured whole
+fn pcap_packet_header(timestamp: Duration, len: usize) -> [u8; 16] {
+    let mut header = [0u8; 16];
+    header[0..4].copy_from_slice(&(timestamp.as_secs() as u32).to_le_bytes());
+    header[4..8].copy_from_slice(&timestamp.subsec_micros().to_le_bytes());
+    header[8..12].copy_from_slice(&(len as u32).to_le_bytes());
+    header[12..16].copy_from_slice(&(len as u32).to_le_bytes());
+    header
 }
 
-/// Clear both input and output buffers
-#[no_mangle]
-pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_clearAll(
-    _env: JNIEnv,
-    _class: JClass,
-    handle: jlong,
-) -> jboolean {
-    if handle == 0 {
-        set_error!("Clear all failed: port handle is null");
-        return 0;
+/// Each byte value with the order of its bits reversed, for software bit reversal
+static BIT_REVERSE: [u8; 256] = bit_reverse_table();
+
+const fn bit_reverse_table() -> [u8; 256] {
+    let mut table = [0u8; 256];
+    let mut i = 0;
+    while i < 256 {
+        table[i] = (i as u8).reverse_bits();
+        i += 1;
     }
+    table
+}
 
-    unsafe {
-        let wrapper = &mut *(handle as *mut PortWrapper);
-        match wrapper.port.clear(serialport::ClearBuffer::All) {
-            Ok(_) => 1,
-            Err(e) => {
-                set_error!(format!("Clear all failed: {}", e));
-                0
-            }
-        }
+/// Reverse the bit order of each byte in place
+fn reverse_bits_in_place(data: &mut [u8]) {
+    for byte in data {
+        *byte = BIT_REVERSE[*byte as usize];
     }
 }
 
-/// Set RTS (Request To Send) pin state - for manual RS-485 control
-/// Note: This is only needed if you're NOT using automatic RS-485 control
-/// Set to true before transmitting, false after transmitting
-/// Returns: 1 on success, 0 on failure
-#[no_mangle]
-pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_setRTS(
-    _env: JNIEnv,
-    _class: JClass,
-    handle: jlong,
-    level: jboolean,
-) -> jboolean {
-    if handle == 0 {
-        set_error!("Set RTS failed: port handle is null");
-        return 0;
+/// Write data in paced chunks, honoring the write blocking policy for each chunk.
+///
+/// Each chunk is drained before the gap starts, so the gap appears on the wire rather
+/// than being absorbed by the driver's transmit buffer. Stops early and returns the
+/// bytes written so far if a chunk is only partially accepted or fails after progress.
+fn write_paced<W: Write + ?Sized>(
+    port: &mut W,
+    data: &[u8],
+    policy: WriteBlockingPolicy,
+    timeout: Duration,
+    pacing: WritePacing,
+) -> std::io::Result<usize> {
+    if pacing.chunk_bytes == 0 || data.len() <= pacing.chunk_bytes {
+        return write_with_policy(port, data, policy, timeout);
     }
 
-    unsafe {
-        let wrapper = &mut *(handle as *mut PortWrapper);
-        match wrapper.port.write_request_to_send(level != 0) {
-            Ok(_) => 1,
-            Err(e) => {
-                set_error!(format!("Set RTS failed: {}", e));
-                0
+    let mut written = 0;
+    for chunk in data.chunks(pacing.chunk_bytes) {
+        if written > 0 {
+            port.flush()?;
+            std::thread::sleep(pacing.gap);
+        }
+        match write_with_policy(port, chunk, policy, timeout) {
+            Ok(n) => {
+                written += n;
+                if n < chunk.len() {
+                    break;
+                }
             }
+            Err(e) if written == 0 => return Err(e),
+            Err(_) => break,
         }
     }
+    Ok(written)
 }
 
-/// Set DTR (Data Terminal Ready) pin state - alternative for manual RS-485 control
-/// Note: This is only needed if you're NOT using automatic RS-485 control
-/// Returns: 1 on success, 0 on failure
-#[no_mangle]
-pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_setDTR(
-    _env: JNIEnv,
-    _class: JClass,
-    handle: jlong,
-    level: jboolean,
-) -> jboolean {
-    if handle == 0 {
-        set_error!("Set DTR failed: port handle is null");
-        return 0;
+/// Repeat `write` on the rest of `data` until all of it is accepted.
+///
+/// A driver may accept only part of a buffer per call; a half-duplex frame must be
+/// complete before the direction pin is released. Stops early on a write that accepts
+/// nothing (a deadline passing, or the `Partial` policy meeting a full queue) or on an
+/// error after progress; returns the bytes written.
+fn write_fully(
+    data: &[u8],
+    mut write: impl FnMut(&[u8]) -> std::io::Result<usize>,
+) -> std::io::Result<usize> {
+    let mut written = 0;
+    while written < data.len() {
+        match write(&data[written..]) {
+            Ok(0) => break,
+            Ok(n) => written += n,
+            Err(e) if written == 0 => return Err(e),
+            Err(_) => break,
+        }
     }
+    Ok(written)
+}
 
-    unsafe {
-        let wrapper = &mut *(handle as *mut PortWrapper);
-        match wrapper.port.write_data_terminal_ready(level != 0) {
-            Ok(_) => 1,
-            Err(e) => {
-                set_error!(format!("Set DTR failed: {}", e));
-                0
-            }
-        }
+/// Modem status bits reported by `getPortStatus`
+const MODEM_CTS: u32 = 1 << 0;
+const MODEM_DSR: u32 = 1 << 1;
+const MODEM_RI: u32 = 1 << 2;
+const MODEM_CD: u32 = 1 << 3;
+
+/// Connection states reported by `getConnectionState`
+const CONNECTION_CONNECTED: u8 = 0;
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+const CONNECTION_RECONNECTING: u8 = 1;
+const CONNECTION_FAILED: u8 = 2;
+
+/// Feature bits reported by `getCapabilities`
+const CAPABILITY_KERNEL_RS485: jint = 1 << 0;
+const CAPABILITY_CUSTOM_BAUD_RATE: jint = 1 << 1;
+const CAPABILITY_CUSTOM_DIVISOR: jint = 1 << 2;
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+const CAPABILITY_READ_CANCELLATION: jint = 1 << 3;
+const CAPABILITY_AUTO_RECONNECT: jint = 1 << 4;
+const CAPABILITY_NINE_BIT: jint = 1 << 5;
+const CAPABILITY_MODEM_EVENT_COUNTERS: jint = 1 << 6;
+const CAPABILITY_TERMIOS_CONTROL: jint = 1 << 7;
+const CAPABILITY_SOFTWARE_FLOW_CONTROL: jint = 1 << 8;
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+const CAPABILITY_REALTIME_SCHEDULING: jint = 1 << 9;
+const CAPABILITY_ACCESS_CHECK: jint = 1 << 10;
+const CAPABILITY_DTR_FLOW_CONTROL: jint = 1 << 11;
+
+/// Effective RS-485 direction control reported by `getRs485ActualMode`
+const RS485_ACTUAL_NONE: u8 = 0;
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+const RS485_ACTUAL_KERNEL_AUTO: u8 = 1;
+const RS485_ACTUAL_MANUAL_BY_FALLBACK: u8 = 2;
+const RS485_ACTUAL_MANUAL_BY_REQUEST: u8 = 3;
+const RS485_ACTUAL_MANUAL_BY_PIN: u8 = 4;
+
+/// Write stall reasons reported by `getWriteStallReason`
+const STALL_IDLE: jint = 0;
+const STALL_BUFFER_FULL: jint = 1;
+const STALL_FLOW_CONTROL: jint = 2;
+
+/// Determine why writes on a port may not be progressing.
+///
+/// With hardware flow control and CTS deasserted, the peer is holding off transmission.
+/// Otherwise, pending bytes in the output queue mean the line is still busy draining.
+fn write_stall_reason(wrapper: &mut PortWrapper) -> std::io::Result<jint> {
+    if wrapper.port.flow_control()? == FlowControl::Hardware
+        && !wrapper.port.read_clear_to_send()?
+    {
+        return Ok(STALL_FLOW_CONTROL);
     }
+    if wrapper.port.bytes_to_write()? > 0 {
+        return Ok(STALL_BUFFER_FULL);
+    }
+    Ok(STALL_IDLE)
 }
 
-/// Check if kernel RS-485 mode is active (Linux only)
-/// Returns: 1 if kernel mode is active, 0 otherwise
-#[no_mangle]
-pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_isKernelRs485Active(
-    _env: JNIEnv,
-    _class: JClass,
-    handle: jlong,
-) -> jboolean {
-    if handle == 0 {
-        return 0;
+/// Describe a write timeout, telling a flow-control stall apart from other failures
+fn describe_write_timeout(wrapper: &mut PortWrapper, e: &std::io::Error) -> String {
+    match write_stall_reason(wrapper) {
+        Ok(STALL_FLOW_CONTROL) => format!(
+            "{} (stalled by hardware flow control: CTS is deasserted by the peer)",
+            e
+        ),
+        Ok(STALL_BUFFER_FULL) => format!("{} (output buffer is not draining)", e),
+        _ => e.to_string(),
     }
+}
 
-    unsafe {
-        let wrapper = &mut *(handle as *mut PortWrapper);
-        #[cfg(target_os = "linux")]
-        {
-            if wrapper.is_kernel_rs485_active() { 1 } else { 0 }
+/// Turn the outcome of a write into the value a write JNI function returns, recording the
+/// error (prefixed with `context`, e.g. "Write line") on failure
+fn write_result_code(
+    handle: jlong,
+    wrapper: &mut PortWrapper,
+    result: Result<usize, std::io::Error>,
+    context: &str,
+) -> jint {
+    match result {
+        Ok(n) => n as jint,
+        Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+            set_port_error!(handle, format!("{} would block: {}", context, e));
+            WRITE_WOULD_BLOCK
         }
-        #[cfg(not(target_os = "linux"))]
-        {
-            let _ = wrapper; // Suppress unused warning
-            0 // Kernel RS-485 is only available on Linux
+        Err(e) if e.kind() == std::io::ErrorKind::TimedOut => {
+            let message = describe_write_timeout(wrapper, &e);
+            set_port_error!(handle, format!("{} failed: {}", context, message));
+            -1
+        }
+        Err(e) => {
+            set_port_error!(handle, format!("{} failed: {}", context, e));
+            -1
         }
     }
 }
 
-/// Set RS-485 timing delays (Linux kernel mode only)
-/// delay_before_send_micros: Delay in microseconds before sending (RTS assertion to data)
-/// delay_after_send_micros: Delay in microseconds after sending (data to RTS de-assertion)
-/// Returns: 1 on success, 0 on failure or if not on Linux
-#[no_mangle]
-pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_setRs485Delays(
-    _env: JNIEnv,
-    _class: JClass,
-    handle: jlong,
-    delay_before_send_micros: jint,
-    delay_after_send_micros: jint,
-) -> jboolean {
-    if handle == 0 {
-        return 0;
+// Platform-specific port wrapper implementations
+// On Linux, we store TTYPort directly to access RS-485 kernel mode
+// On other platforms, we use Box<dyn SerialPort>
+
+#[cfg(target_os = "linux")]
+#[path = "platform_linux.rs"]
+mod platform;
+
+#[cfg(not(target_os = "linux"))]
+#[path = "platform_other.rs"]
+mod platform;
+
+use platform::PortWrapper;
+
+/// Convert Java String to Rust String
+///
+/// JNI hands strings over as modified UTF-8, where characters outside the BMP are
+/// encoded as surrogate pairs. Decoding is strict: a string that cannot be decoded
+/// (e.g. one containing an unpaired surrogate) is rejected instead of being lossily
+/// replaced, since a port name altered that way would silently open a different path.
+fn jstring_to_string(env: &mut JNIEnv, jstr: JString) -> Result<String, String> {
+    let java_str = env
+        .get_string(&jstr)
+        .map_err(|e| format!("Failed to convert JString: {}", e))?;
+    cesu8::from_java_cesu8(java_str.to_bytes())
+        .map(|s| s.into_owned())
+        .map_err(|_| "Failed to convert JString: not valid modified UTF-8".to_string())
+}
+
+/// Create Java String from Rust String
+fn string_to_jstring(env: &mut JNIEnv, s: &str) -> jstring {
+    env.new_string(s)
+        .map(|js| js.into_raw())
+        .unwrap_or(std::ptr::null_mut())
+}
+
+/// Convert the JNI data bits value (5-8) to DataBits, defaulting to 8
+fn data_bits_from_jint(value: jint) -> DataBits {
+    match value {
+        5 => DataBits::Five,
+        6 => DataBits::Six,
+        7 => DataBits::Seven,
+        8 => DataBits::Eight,
+        _ => DataBits::Eight,
     }
+}
 
-    unsafe {
-        let wrapper = &mut *(handle as *mut PortWrapper);
-        #[cfg(target_os = "linux")]
-        {
-            wrapper.set_rs485_delays(delay_before_send_micros as u32, delay_after_send_micros as u32);
-            1
+/// Convert the JNI stop bits value (1 or 2) to StopBits, defaulting to 1
+fn stop_bits_from_jint(value: jint) -> StopBits {
+    match value {
+        1 => StopBits::One,
+        2 => StopBits::Two,
+        _ => StopBits::One,
+    }
+}
+
+/// Convert the JNI parity value (0 = None, 1 = Odd, 2 = Even) to Parity
+fn parity_from_jint(value: jint) -> Parity {
+    match value {
+        0 => Parity::None,
+        1 => Parity::Odd,
+        2 => Parity::Even,
+        _ => Parity::None,
+    }
+}
+
+/// Convert the JNI flow control value (0 = None, 1 = Software, 2 = Hardware) to FlowControl
+fn flow_control_from_jint(value: jint) -> FlowControl {
+    match value {
+        0 => FlowControl::None,
+        1 => FlowControl::Software,
+        2 => FlowControl::Hardware,
+        _ => FlowControl::None,
+    }
+}
+
+/// In strict mode, reject line setting codes that the converters above would silently
+/// replace with a default. Flow control is None for opens that do not take it.
+fn check_line_settings(
+    data_bits: jint,
+    stop_bits: jint,
+    parity: jint,
+    flow_control: Option<jint>,
+) -> Result<(), String> {
+    if !STRICT_CONFIG_VALIDATION.load(Ordering::Relaxed) {
+        return Ok(());
+    }
+    if !(5..=8).contains(&data_bits) {
+        return Err(format!("Invalid data bits: {} (expected 5, 6, 7 or 8)", data_bits));
+    }
+    if !(1..=2).contains(&stop_bits) {
+        return Err(format!("Invalid stop bits: {} (expected 1 or 2)", stop_bits));
+    }
+    if !(0..=2).contains(&parity) {
+        return Err(format!("Invalid parity: {} (expected 0, 1 or 2)", parity));
+    }
+    match flow_control {
+        Some(value) if !(0..=2).contains(&value) => {
+            Err(format!("Invalid flow control: {} (expected 0, 1 or 2)", value))
         }
-        #[cfg(not(target_os = "linux"))]
-        {
-            let _ = (wrapper, delay_before_send_micros, delay_after_send_micros);
-            0 // RS-485 delays only available on Linux with kernel mode
+        _ => Ok(()),
+    }
+}
+
+/// Convert the JNI access mode value (0 = ReadWrite, 1 = ReadOnly, 2 = WriteOnly) to AccessMode
+fn access_mode_from_jint(value: jint) -> AccessMode {
+    match value {
+        1 => AccessMode::ReadOnly,
+        2 => AccessMode::WriteOnly,
+        _ => AccessMode::ReadWrite,
+    }
+}
+
+/// Convert the JNI RS-485 mode value (0 = None, 1 = Auto, 2 = Manual) to Rs485ControlMode
+fn rs485_mode_from_jint(value: jint) -> Rs485ControlMode {
+    match value {
+        0 => Rs485ControlMode::None,
+        1 => Rs485ControlMode::Auto,
+        2 => Rs485ControlMode::Manual,
+        _ => Rs485ControlMode::None,
+    }
+}
+
+/// Convert the JNI RS-485 pin value (0 = RTS, 1 = DTR) to Rs485ControlPin
+fn rs485_pin_from_jint(value: jint) -> Rs485ControlPin {
+    match value {
+        0 => Rs485ControlPin::RTS,
+        1 => Rs485ControlPin::DTR,
+        _ => Rs485ControlPin::RTS,
+    }
+}
+
+/// Add the `\\.\` device namespace prefix to Windows `COMn` names with n >= 10.
+///
+/// Windows only resolves `COM1`..`COM9` as bare names; higher-numbered ports must be
+/// opened as `\\.\COM10`. Names that are already prefixed or are not `COMn` are
+/// returned unchanged.
+#[cfg(any(windows, test))]
+fn windows_com_port_name(name: &str) -> String {
+    // Slice with get() so that names with multi-byte characters cannot panic
+    let is_high_com_port = name
+        .get(..3)
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case("COM"))
+        && name[3..].chars().all(|c| c.is_ascii_digit())
+        && name[3..].parse::<u32>().map(|n| n >= 10).unwrap_or(false);
+
+    if is_high_com_port {
+        format!(r"\\.\{}", name)
+    } else {
+        name.to_string()
+    }
+}
+
+/// Apply platform-specific port name normalization before opening
+fn normalize_port_name(name: String) -> String {
+    #[cfg(windows)]
+    {
+        windows_com_port_name(&name)
+    }
+
+    #[cfg(not(windows))]
+    {
+        name
+    }
+}
+
+/// Sleep for the given duration, skipping the syscall entirely when it is zero.
+/// Sub-millisecond durations are honored, subject to the OS scheduler's timer resolution.
+fn sleep_unless_zero(duration: Duration) {
+    if !duration.is_zero() {
+        std::thread::sleep(duration);
+    }
+}
+
+/// DMX512 line rate; slots are sent as 8N2 at this rate
+const DMX_BAUD_RATE: u32 = 250_000;
+
+/// DMX512 break length. The standard requires at least 88 µs and allows up to 1 s; 176 µs is
+/// the common transmitter default, leaving margin for receivers that sample the line late.
+const DMX_BREAK: Duration = Duration::from_micros(176);
+
+/// DMX512 mark-after-break length. The standard requires at least 8 µs (receivers built to
+/// the 1986 revision need 4 µs) and allows up to 1 s; 12 µs leaves margin for slow receivers.
+/// The write syscall after it only lengthens the mark, which is harmless.
+const DMX_MARK_AFTER_BREAK: Duration = Duration::from_micros(12);
+
+/// Largest DMX512 packet: the start code followed by 512 slots
+const MAX_DMX_PACKET_LEN: usize = 513;
+
+/// Wait at least `duration` by spinning, so that short waits are not stretched by the
+/// scheduler's timer slack the way `thread::sleep` would be
+fn spin_wait(duration: Duration) {
+    let start = Instant::now();
+    while start.elapsed() < duration {
+        std::hint::spin_loop();
+    }
+}
+
+/// Longest stretch of a precise wait that is spun rather than slept
+const PRECISE_WAIT_SPIN: Duration = Duration::from_millis(1);
+
+/// Wait `duration` with microsecond precision: sleep through most of it, then spin the last
+/// stretch, which the scheduler's timer slack could otherwise overshoot
+fn precise_wait(duration: Duration) {
+    let start = Instant::now();
+    if duration > PRECISE_WAIT_SPIN {
+        std::thread::sleep(duration - PRECISE_WAIT_SPIN);
+    }
+    spin_wait(duration.saturating_sub(start.elapsed()));
+}
+
+/// Time the line spends idle for `bits` bit periods at `baud_rate`, rounded up to the
+/// microsecond so the preamble is never shorter than requested
+fn preamble_duration(bits: u32, baud_rate: u32) -> Duration {
+    if bits == 0 || baud_rate == 0 {
+        return Duration::ZERO;
+    }
+    Duration::from_micros((u64::from(bits) * 1_000_000).div_ceil(u64::from(baud_rate)))
+}
+
+/// Letter for `parity` in the usual framing shorthand, e.g. the `N` in 8N1
+fn parity_letter(parity: Parity) -> char {
+    match parity {
+        Parity::None => 'N',
+        Parity::Odd => 'O',
+        Parity::Even => 'E',
+    }
+}
+
+/// Drain the output, then generate the DMX512 break and mark-after-break that start a packet.
+/// The port must be at 250000 baud with 8N2 framing; receivers treat a single stop bit or a
+/// parity bit as a framing error.
+fn send_dmx_break(port: &mut dyn serialport::SerialPort) -> std::io::Result<()> {
+    let baud_rate = port.baud_rate()?;
+    let data_bits = port.data_bits()?;
+    let parity = port.parity()?;
+    let stop_bits = port.stop_bits()?;
+    if baud_rate != DMX_BAUD_RATE
+        || data_bits != DataBits::Eight
+        || parity != Parity::None
+        || stop_bits != StopBits::Two
+    {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!(
+                "DMX512 requires {} baud 8N2, port is at {} baud {}{}{}",
+                DMX_BAUD_RATE,
+                baud_rate,
+                u8::from(data_bits),
+                parity_letter(parity),
+                u8::from(stop_bits)
+            ),
+        ));
+    }
+    // A break while the previous packet is still queued would cut it off
+    port.flush()?;
+    port.set_break()?;
+    spin_wait(DMX_BREAK);
+    port.clear_break()?;
+    spin_wait(DMX_MARK_AFTER_BREAK);
+    Ok(())
+}
+
+/// Read back the line settings the driver applied and describe each one that differs from
+/// the requested value. Some drivers silently coerce combinations they do not support
+/// (e.g. 5 data bits with 2 stop bits). Settings the platform cannot read back are skipped.
+fn line_setting_discrepancies(
+    wrapper: &PortWrapper,
+    baud_rate: u32,
+    data_bits: DataBits,
+    stop_bits: StopBits,
+    parity: Parity,
+    flow_control: FlowControl,
+) -> Vec<String> {
+    let port = &wrapper.port;
+    let mut discrepancies = Vec::new();
+    if let Ok(actual) = port.baud_rate() {
+        if actual != baud_rate {
+            discrepancies.push(format!(
+                "baud rate: requested {}, driver applied {}",
+                baud_rate, actual
+            ));
+        }
+    }
+    if let Ok(actual) = port.data_bits() {
+        if actual != data_bits {
+            discrepancies.push(format!(
+                "data bits: requested {}, driver applied {}",
+                u8::from(data_bits),
+                u8::from(actual)
+            ));
+        }
+    }
+    if let Ok(actual) = port.stop_bits() {
+        if actual != stop_bits {
+            discrepancies.push(format!(
+                "stop bits: requested {}, driver applied {}",
+                u8::from(stop_bits),
+                u8::from(actual)
+            ));
+        }
+    }
+    if let Ok(actual) = port.parity() {
+        if actual != parity {
+            discrepancies.push(format!("parity: requested {}, driver applied {}", parity, actual));
+        }
+    }
+    if let Ok(actual) = port.flow_control() {
+        if actual != flow_control {
+            discrepancies.push(format!(
+                "flow control: requested {}, driver applied {}",
+                flow_control, actual
+            ));
         }
     }
+    discrepancies
 }
 
-/// Open a serial port with extended RS-485 configuration
-/// flow_control: 0 = None, 1 = Software (XON/XOFF), 2 = Hardware (RTS/CTS)
-/// dtr_on_open: true to assert DTR on open, false to suppress (for Arduino)
+/// Give the device time to settle after open, before the first I/O.
+/// Some USB-serial chips lose the first bytes sent right after open (or after DTR changes).
+fn settle_after_open(settle_ms: jint) {
+    if settle_ms > 0 {
+        std::thread::sleep(Duration::from_millis(settle_ms as u64));
+    }
+}
+
+/// Open a serial port and return a pointer to the boxed PortWrapper
 /// rs485_mode: 0 = None, 1 = Auto, 2 = Manual
 /// rs485_pin: 0 = RTS, 1 = DTR
-/// rts_active_high: true if RTS is active high during transmission
-/// rx_during_tx: true to enable receiving during transmission
-/// termination_enabled: true to enable bus termination
-/// delay_before_micros: delay in microseconds before sending
-/// delay_after_micros: delay in microseconds after sending
+/// settle_ms: time to wait after the port is configured before returning, 0 for none
 #[no_mangle]
-pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_openWithRs485Config(
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_open(
     mut env: JNIEnv,
     _class: JClass,
     port_name: JString,
@@ -718,17 +1370,14 @@ pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_openWithRs485C
     data_bits: jint,
     stop_bits: jint,
     parity: jint,
-    flow_control: jint,
-    dtr_on_open: jboolean,
     timeout_ms: jint,
     rs485_mode: jint,
     rs485_pin: jint,
-    rts_active_high: jboolean,
-    rx_during_tx: jboolean,
-    termination_enabled: jboolean,
-    delay_before_micros: jint,
-    delay_after_micros: jint,
+    settle_ms: jint,
 ) -> jlong {
+    // A failure of this open must not be attributed to an earlier one
+    LAST_OPEN_FAILURE.set(OPEN_FAILURE_OTHER);
+
     let port_name = match jstring_to_string(&mut env, port_name) {
         Ok(s) => s,
         Err(e) => {
@@ -737,59 +1386,35 @@ pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_openWithRs485C
         }
     };
 
-    let data_bits = match data_bits {
-        5 => DataBits::Five,
-        6 => DataBits::Six,
-        7 => DataBits::Seven,
-        8 => DataBits::Eight,
-        _ => DataBits::Eight,
-    };
+    if let Err(e) = check_line_settings(data_bits, stop_bits, parity, None) {
+        set_error!(format!("Failed to open port: {}", e));
+        return 0;
+    }
 
-    let stop_bits = match stop_bits {
-        1 => StopBits::One,
-        2 => StopBits::Two,
-        _ => StopBits::One,
-    };
+    let data_bits = data_bits_from_jint(data_bits);
 
-    let parity = match parity {
-        0 => Parity::None,
-        1 => Parity::Odd,
-        2 => Parity::Even,
-        _ => Parity::None,
-    };
+    let stop_bits = stop_bits_from_jint(stop_bits);
 
-    let flow_control = match flow_control {
-        0 => FlowControl::None,
-        1 => FlowControl::Software,
-        2 => FlowControl::Hardware,
-        _ => FlowControl::None,
-    };
+    let parity = parity_from_jint(parity);
 
-    let control_mode = match rs485_mode {
-        0 => Rs485ControlMode::None,
-        1 => Rs485ControlMode::Auto,
-        2 => Rs485ControlMode::Manual,
-        _ => Rs485ControlMode::None,
-    };
+    let control_mode = rs485_mode_from_jint(rs485_mode);
 
-    let control_pin = match rs485_pin {
-        0 => Rs485ControlPin::RTS,
-        1 => Rs485ControlPin::DTR,
-        _ => Rs485ControlPin::RTS,
-    };
+    let control_pin = rs485_pin_from_jint(rs485_pin);
 
     let timeout = normalize_timeout_ms(timeout_ms as u64);
 
-    let builder = serialport::new(port_name, baud_rate as u32)
+    let port_name = normalize_port_name(port_name);
+    let builder = serialport::new(port_name.as_str(), baud_rate as u32)
         .data_bits(data_bits)
         .stop_bits(stop_bits)
         .parity(parity)
-        .flow_control(flow_control)
+        .flow_control(FlowControl::None)
         .timeout(timeout);
 
     // Platform-specific port opening
     #[cfg(target_os = "linux")]
-    let port_result = builder.open_native();
+    let (port_result, original_termios) =
+        platform::open_capturing_termios(&port_name, || builder.open_native());
 
     #[cfg(not(target_os = "linux"))]
     let port_result = builder.open();
@@ -797,114 +1422,6505 @@ pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_openWithRs485C
     match port_result {
         Ok(port) => {
             let mut wrapper = PortWrapper::new(port);
-
-            // Suppress DTR if requested (prevents Arduino reset)
-            if dtr_on_open == 0 {
-                if let Err(e) = wrapper.port.write_data_terminal_ready(false) {
-                    set_error!(format!("Failed to suppress DTR: {}", e));
-                    return 0;
-                }
+            #[cfg(target_os = "linux")]
+            {
+                wrapper.original_termios = original_termios;
             }
+            wrapper.config_discrepancies = line_setting_discrepancies(
+                &wrapper,
+                baud_rate as u32,
+                data_bits,
+                stop_bits,
+                parity,
+                FlowControl::None,
+            );
 
-            // Configure extended RS-485 mode if requested
+            // Configure RS-485 mode if requested
             if control_mode != Rs485ControlMode::None {
-                if let Err(e) = wrapper.configure_rs485_extended(
-                    control_mode,
-                    control_pin,
-                    rts_active_high != 0,
-                    rx_during_tx != 0,
-                    termination_enabled != 0,
-                    delay_before_micros as u32,
-                    delay_after_micros as u32,
-                ) {
+                if let Err(e) = wrapper.configure_rs485(control_mode, control_pin) {
                     set_error!(format!("Failed to configure RS-485: {}", e));
                     return 0;
                 }
             }
 
-            let boxed = Box::new(wrapper);
-            Box::into_raw(boxed) as jlong
+            settle_after_open(settle_ms);
+
+            register_port(wrapper)
         }
         Err(e) => {
+            LAST_OPEN_FAILURE.set(open_failure_reason(&e));
             set_error!(format!("Failed to open port: {}", e));
             0
         }
     }
 }
 
-/// Set RS-485 configuration at runtime
-/// enabled: true to enable RS-485 mode
-/// rs485_pin: 0 = RTS, 1 = DTR
-/// rts_active_high: true if RTS is active high during transmission
-/// rx_during_tx: true to enable receiving during transmission
-/// termination_enabled: true to enable bus termination
-/// delay_before_micros: delay in microseconds before sending
-/// delay_after_micros: delay in microseconds after sending
-/// Returns: 1 on success, 0 on failure
+/// Check whether an error means the port simply has no such ioctl (e.g. pseudo-terminals
+/// without modem lines), rather than that the device is broken or gone.
+#[cfg(target_os = "linux")]
+fn is_unsupported_ioctl(e: &std::io::Error) -> bool {
+    matches!(e.raw_os_error(), Some(libc::ENOTTY) | Some(libc::EINVAL))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn is_unsupported_ioctl(e: &std::io::Error) -> bool {
+    matches!(
+        e.kind(),
+        std::io::ErrorKind::InvalidInput | std::io::ErrorKind::Unsupported
+    )
+}
+
+/// Sanity-check a freshly opened port without writing to it: the input and output
+/// queues must be queryable, and the modem lines readable if the port has them.
+fn verify_port_health(wrapper: &mut PortWrapper) -> Result<(), String> {
+    wrapper
+        .port
+        .bytes_to_read()
+        .map_err(|e| format!("could not query input queue: {}", e))?;
+    wrapper
+        .port
+        .bytes_to_write()
+        .map_err(|e| format!("could not query output queue: {}", e))?;
+    match wrapper.modem_status() {
+        Ok(_) => Ok(()),
+        Err(e) if is_unsupported_ioctl(&e) => Ok(()),
+        Err(e) => Err(format!("could not read modem lines: {}", e)),
+    }
+}
+
+/// Open a serial port like openWithRs485Config, then verify it responds before returning it
+/// The check only reads queue depths and modem lines; nothing is written to the port.
+/// Returns: the handle, or 0 if opening or verification failed
 #[no_mangle]
-pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_setRs485Config(
-    _env: JNIEnv,
-    _class: JClass,
-    handle: jlong,
-    enabled: jboolean,
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_openAndVerify(
+    env: JNIEnv,
+    class: JClass,
+    port_name: JString,
+    baud_rate: jint,
+    data_bits: jint,
+    stop_bits: jint,
+    parity: jint,
+    flow_control: jint,
+    dtr_on_open: jboolean,
+    timeout_ms: jint,
+    rs485_mode: jint,
     rs485_pin: jint,
     rts_active_high: jboolean,
     rx_during_tx: jboolean,
     termination_enabled: jboolean,
     delay_before_micros: jint,
     delay_after_micros: jint,
-) -> jboolean {
+    access_mode: jint,
+    baud_tolerance_percent: jint,
+    settle_ms: jint,
+) -> jlong {
+    let handle = Java_dev_nemecec_jrserial_NativeSerialPort_openWithRs485Config(
+        env,
+        class,
+        port_name,
+        baud_rate,
+        data_bits,
+        stop_bits,
+        parity,
+        flow_control,
+        dtr_on_open,
+        timeout_ms,
+        rs485_mode,
+        rs485_pin,
+        rts_active_high,
+        rx_during_tx,
+        termination_enabled,
+        delay_before_micros,
+        delay_after_micros,
+        access_mode,
+        baud_tolerance_percent,
+        settle_ms,
+    );
     if handle == 0 {
         return 0;
     }
 
-    let control_mode = if enabled != 0 {
-        Rs485ControlMode::Auto
-    } else {
-        Rs485ControlMode::None
-    };
-
-    let control_pin = match rs485_pin {
-        0 => Rs485ControlPin::RTS,
-        1 => Rs485ControlPin::DTR,
-        _ => Rs485ControlPin::RTS,
+    // Release the lock before a failed port is closed
+    let health = match lock_port(handle) {
+        Some(mut wrapper) => verify_port_health(&mut wrapper),
+        None => return 0,
     };
-
-    unsafe {
-        let wrapper = &mut *(handle as *mut PortWrapper);
-        match wrapper.configure_rs485_extended(
-            control_mode,
-            control_pin,
-            rts_active_high != 0,
-            rx_during_tx != 0,
-            termination_enabled != 0,
-            delay_before_micros as u32,
-            delay_after_micros as u32,
-        ) {
-            Ok(_) => 1,
-            Err(e) => {
-                set_error!(format!("Failed to set RS-485 config: {}", e));
-                0
-            }
+    match health {
+        Ok(()) => handle,
+        Err(e) => {
+            release_port(handle);
+            set_error!(format!("Port verification failed: {}", e));
+            0
         }
     }
 }
 
+/// What a handle refers to.
+///
+/// The port sits behind a lock that calls on the handle hold while they use it, so calls from
+/// several threads take turns instead of racing. A call that waits for input, or for output to
+/// drain, lets go of the lock while it waits and holds the turn for its direction instead, so
+/// that a thread blocked in a read does not hold up writes and configuration on another. On
+/// Linux, a plain write likewise waits for room in the output queue with the lock released.
+/// A write that drives the RS-485 direction pin, or needs a DSR check, preamble or pacing
+/// around its frame, keeps the lock until the frame has gone out, so that none of these is
+/// reconfigured under it. Closing waits for the turns and then takes the port out under the
+/// lock: a call that looked the handle up before the close then finds it closed instead of
+/// using a freed port.
+struct PortHandle {
+    /// The open port, or None once closed
+    port: Mutex<Option<PortWrapper>>,
+    /// Held by a call that may wait for input with the port unlocked, so that reads take turns
+    read_turn: Mutex<()>,
+    /// Held by a call that may wait for output with the port unlocked
+    write_turn: Mutex<()>,
+    /// Set when the port starts closing, so that waits with the port unlocked give up
+    closing: AtomicBool,
+    /// Last error recorded for this port, kept outside the lock so that it can be recorded
+    /// and read while a call holds it
+    last_error: Mutex<Option<ErrorContext>>,
+    /// Duplicate of the port's read cancellation eventfd, so that a read can be cancelled
+    /// without the lock; closed when the last reference to the handle goes away
+    #[cfg(target_os = "linux")]
+    cancel_fd: std::os::unix::io::RawFd,
+}
+
+impl PortHandle {
+    fn new(wrapper: PortWrapper) -> Self {
+        #[cfg(target_os = "linux")]
+        let cancel_fd = match wrapper.cancel_fd() {
+            fd if fd >= 0 => unsafe { libc::fcntl(fd, libc::F_DUPFD_CLOEXEC, 0) },
+            fd => fd,
+        };
+        PortHandle {
+            port: Mutex::new(Some(wrapper)),
+            read_turn: Mutex::new(()),
+            write_turn: Mutex::new(()),
+            closing: AtomicBool::new(false),
+            last_error: Mutex::new(None),
+            #[cfg(target_os = "linux")]
+            cancel_fd,
+        }
+    }
+
+    /// Lock the port, recovering it if a panic poisoned the lock
+    fn lock(&self) -> MutexGuard<'_, Option<PortWrapper>> {
+        self.port.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Take the turn for one direction of I/O, recovering it if a panic poisoned it
+    fn take_turn(&self, turn: IoTurn) -> MutexGuard<'_, ()> {
+        let lock = match turn {
+            IoTurn::Read => &self.read_turn,
+            IoTurn::Write => &self.write_turn,
+        };
+        lock.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl Drop for PortHandle {
+    fn drop(&mut self) {
+        if self.cancel_fd >= 0 {
+            unsafe {
+                libc::close(self.cancel_fd);
+            }
+        }
+    }
+}
+
+/// Direction of I/O that a call may wait for with the port unlocked
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum IoTurn {
+    Read,
+    Write,
+}
+
+/// Extend a guard borrowed from a `PortHandle` to the lifetime of a `LockedPort`.
+///
+/// SAFETY: the caller keeps the handle alive at the same address, in an `Arc`, for as long as
+/// the guard exists
+unsafe fn extend_guard<T>(guard: MutexGuard<'_, T>) -> MutexGuard<'static, T> {
+    std::mem::transmute::<MutexGuard<'_, T>, MutexGuard<'static, T>>(guard)
+}
+
+/// How often a wait with the port unlocked checks again on platforms that cannot block until
+/// the port has input
+#[cfg(not(target_os = "linux"))]
+const UNLOCKED_POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+/// Longest a write waiting for room in the output queue with the port unlocked goes without
+/// checking whether the port started closing
+#[cfg(target_os = "linux")]
+const WRITE_ROOM_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// An open port locked for the duration of a call, with the call's I/O turns if it has any
+struct LockedPort {
+    // Declared first so that they are dropped before the handle they borrow from. The guard
+    // is only None while a wait runs with the port unlocked.
+    guard: Option<MutexGuard<'static, Option<PortWrapper>>>,
+    _read_turn: Option<MutexGuard<'static, ()>>,
+    _write_turn: Option<MutexGuard<'static, ()>>,
+    handle: Arc<PortHandle>,
+    /// Whether the write turn is held
+    #[cfg(target_os = "linux")]
+    has_write_turn: bool,
+}
+
+impl std::ops::Deref for LockedPort {
+    type Target = PortWrapper;
+
+    fn deref(&self) -> &PortWrapper {
+        self.guard
+            .as_ref()
+            .and_then(|guard| guard.as_ref())
+            .expect("locked port is open")
+    }
+}
+
+impl std::ops::DerefMut for LockedPort {
+    fn deref_mut(&mut self) -> &mut PortWrapper {
+        self.guard
+            .as_mut()
+            .and_then(|guard| guard.as_mut())
+            .expect("locked port is open")
+    }
+}
+
+impl LockedPort {
+    /// Run `wait` with the port unlocked, then lock it again.
+    ///
+    /// Only for calls holding an I/O turn, which keeps the port from being closed meanwhile.
+    /// Fails if the port started closing during the wait.
+    fn unlocked<R>(&mut self, wait: impl FnOnce() -> R) -> std::io::Result<R> {
+        self.unlock();
+        let result = wait();
+        self.relock()?;
+        Ok(result)
+    }
+
+    /// Unlock the port for a wait; see `unlocked`
+    fn unlock(&mut self) {
+        self.guard = None;
+    }
+
+    /// Lock the port again after `unlock`, failing if it started closing meanwhile
+    fn relock(&mut self) -> std::io::Result<()> {
+        // SAFETY: `handle` keeps the PortHandle alive, and the guard is dropped before it
+        self.guard = Some(unsafe { extend_guard(self.handle.lock()) });
+        if self.handle.closing.load(Ordering::Acquire) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::NotConnected,
+                "port was closed while waiting",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Wait with the port unlocked until it has input or `deadline` passes; None waits
+    /// indefinitely. Returns false on timeout. On Linux, a cancelled wait returns the error from
+    /// `read_cancelled_error`.
+    fn wait_for_input(&mut self, deadline: Option<Instant>) -> std::io::Result<bool> {
+        loop {
+            if self.has_input()? {
+                return Ok(true);
+            }
+            let remaining = deadline.map(|deadline| deadline.saturating_duration_since(Instant::now()));
+            if remaining.is_some_and(|remaining| remaining.is_zero()) {
+                return Ok(false);
+            }
+
+            #[cfg(target_os = "linux")]
+            {
+                let port_fd = self.raw_fd();
+                let cancel_fd = self.handle.cancel_fd;
+                let timeout_ms = remaining.map_or(-1, |remaining| {
+                    remaining.as_millis().clamp(1, libc::c_int::MAX as u128) as libc::c_int
+                });
+                match self.unlocked(|| platform::wait_readable(port_fd, cancel_fd, timeout_ms))? {
+                    Err(e) if e.kind() == std::io::ErrorKind::TimedOut => {}
+                    result => result?,
+                }
+            }
+            #[cfg(not(target_os = "linux"))]
+            {
+                let pause = remaining.map_or(UNLOCKED_POLL_INTERVAL, |remaining| {
+                    remaining.min(UNLOCKED_POLL_INTERVAL)
+                });
+                self.unlocked(|| std::thread::sleep(pause))?;
+            }
+        }
+    }
+
+    /// Read like `PortWrapper::read_data`, but wait for input with the port unlocked, for up to
+    /// the port timeout
+    fn read_data(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let deadline = Instant::now() + self.port.timeout();
+        self.read_data_before(buf, deadline)
+    }
+
+    /// Read like `PortWrapper::read_data`, but wait for input with the port unlocked until
+    /// `deadline` instead of the port timeout
+    fn read_data_before(&mut self, buf: &mut [u8], deadline: Instant) -> std::io::Result<usize> {
+        if !self.wait_for_input(Some(deadline))? {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::TimedOut,
+                "Operation timed out",
+            ));
+        }
+        PortWrapper::read_data(self, buf)
+    }
+
+    /// Read like `PortWrapper::read_one`, but wait for input with the port unlocked
+    fn read_one(&mut self, timeout: Option<Duration>) -> std::io::Result<Option<u8>> {
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        if !self.wait_for_input(deadline)? {
+            return Ok(None);
+        }
+        PortWrapper::read_one(self, Some(Duration::ZERO))
+    }
+
+    /// Wait until at least `count` bytes are queued for reading or `timeout` elapses, with the
+    /// port unlocked. Returns the number of queued bytes.
+    ///
+    /// While the input queue is empty this waits for input. Once some bytes are queued, it
+    /// sleeps for roughly the time the missing bytes take on the wire before checking again.
+    fn wait_for_bytes(&mut self, count: u32, timeout: Duration) -> std::io::Result<u32> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let available = self.port.bytes_to_read()?;
+            self.observe_input_depth(available);
+            let available = available + self.read_residual.len() as u32;
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if available >= count || remaining.is_zero() {
+                return Ok(available);
+            }
+
+            if available == 0 {
+                self.wait_for_input(Some(deadline))?;
+            } else {
+                // ~10 bits per character including start and stop bits
+                let baud = self.port.baud_rate()?.max(1) as u64;
+                let missing = (count - available) as u64;
+                let wire_time = Duration::from_micros(missing * 10 * 1_000_000 / baud);
+                let pause = wire_time.clamp(Duration::from_millis(1), remaining);
+                self.unlocked(|| std::thread::sleep(pause))?;
+            }
+        }
+    }
+
+    /// Wait for the output queue to empty before `deadline`, then for the last character to
+    /// leave the transmitter, with the port unlocked between checks. Unlike `drain_until`,
+    /// output still queued at the deadline is kept. Returns false on timeout.
+    fn wait_output_empty(&mut self, deadline: Instant) -> std::io::Result<bool> {
+        loop {
+            if self.port.bytes_to_write()? == 0 {
+                self.port.flush()?;
+                return Ok(true);
+            }
+            if Instant::now() >= deadline {
+                return Ok(false);
+            }
+            self.unlocked(|| std::thread::sleep(Duration::from_millis(1)))?;
+        }
+    }
+
+    /// Write like `PortWrapper::write_rs485`. With the write turn, a write that only hands its
+    /// data to the driver waits for room in the output queue with the port unlocked, so that
+    /// one held up by flow control or a peer that stopped reading does not hold up reads and
+    /// configuration on another thread.
+    fn write_rs485(&mut self, data: &[u8]) -> std::io::Result<usize> {
+        #[cfg(target_os = "linux")]
+        if self.has_write_turn && self.writes_in_pieces() {
+            let timeout = self.port.timeout();
+            // A timeout of 0 waits indefinitely, as in write_with_policy
+            let deadline = (!timeout.is_zero()).then(|| Instant::now() + timeout);
+            return match self.write_in_pieces(data, deadline)? {
+                0 if !data.is_empty() => Err(std::io::Error::new(
+                    std::io::ErrorKind::TimedOut,
+                    "Operation timed out",
+                )),
+                written => Ok(written),
+            };
+        }
+        PortWrapper::write_rs485(self, data)
+    }
+
+    /// Write like `PortWrapper::write_rs485_before`, waiting for room with the port unlocked
+    /// like `write_rs485`
+    fn write_rs485_before(&mut self, data: &[u8], deadline: Instant) -> std::io::Result<usize> {
+        #[cfg(target_os = "linux")]
+        if self.has_write_turn && self.writes_in_pieces() {
+            return self.write_in_pieces(data, Some(deadline));
+        }
+        PortWrapper::write_rs485_before(self, data, deadline)
+    }
+
+    /// Write `data` in the pieces the driver accepts, waiting for room with the port unlocked
+    /// in between, until all of it is written or `deadline` passes; None waits indefinitely.
+    /// Returns the bytes written. Wakes up regularly while waiting, so that closing the port
+    /// ends the write.
+    #[cfg(target_os = "linux")]
+    fn write_in_pieces(
+        &mut self,
+        data: &[u8],
+        deadline: Option<Instant>,
+    ) -> std::io::Result<usize> {
+        let mut written = 0;
+        loop {
+            if written == data.len() {
+                return Ok(written);
+            }
+            // Room only grows while the port is locked, so a piece that polled writable fits
+            match platform::wait_writable(self.raw_fd(), 0) {
+                Ok(true) => match self.write_available(&data[written..], written == 0) {
+                    Ok(n) => written += n,
+                    Err(e) if written == 0 => return Err(e),
+                    Err(_) => return Ok(written),
+                },
+                Ok(false) => {}
+                Err(e) if written == 0 => return Err(e),
+                Err(_) => return Ok(written),
+            }
+            if written == data.len() {
+                return Ok(written);
+            }
+
+            let remaining =
+                deadline.map(|deadline| deadline.saturating_duration_since(Instant::now()));
+            if remaining.is_some_and(|remaining| remaining.is_zero()) {
+                return Ok(written);
+            }
+            let pause = remaining.map_or(WRITE_ROOM_POLL_INTERVAL, |remaining| {
+                remaining.min(WRITE_ROOM_POLL_INTERVAL)
+            });
+            let timeout_ms = pause.as_micros().div_ceil(1000) as libc::c_int;
+            let port_fd = self.raw_fd();
+            match self.unlocked(|| platform::wait_writable(port_fd, timeout_ms)) {
+                Ok(Ok(_)) => {}
+                Ok(Err(e)) | Err(e) if written == 0 => return Err(e),
+                _ => return Ok(written),
+            }
+        }
+    }
+}
+
+/// Wait until at least one of the ports has input or `timeout` elapses. The ports come in
+/// unlocked, holding their read turns, and are only locked, in order, while they are checked.
+/// Returns one flag per port, in the same order; all flags are false on timeout.
+fn wait_any_readable(ports: &mut [LockedPort], timeout: Duration) -> std::io::Result<Vec<bool>> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        for port in ports.iter_mut() {
+            port.relock()?;
+        }
+        let mut readable = Vec::with_capacity(ports.len());
+        for port in ports.iter_mut() {
+            readable.push(port.has_input()?);
+        }
+        #[cfg(target_os = "linux")]
+        let fds: Vec<_> = ports.iter().map(|port| port.raw_fd()).collect();
+        ports.iter_mut().for_each(LockedPort::unlock);
+
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if readable.contains(&true) || remaining.is_zero() {
+            return Ok(readable);
+        }
+        #[cfg(target_os = "linux")]
+        platform::poll_readable(&fds, remaining)?;
+        #[cfg(not(target_os = "linux"))]
+        std::thread::sleep(remaining.min(UNLOCKED_POLL_INTERVAL));
+    }
+}
+
+/// Lock the port behind a handle, waiting for any call in progress on another thread that
+/// holds the lock. Returns None if the handle does not refer to an open port.
+fn lock_port(handle: jlong) -> Option<LockedPort> {
+    lock_port_with_turn(handle, None)
+}
+
+/// Lock the port behind a handle like `lock_port`, taking the turn for a direction of I/O
+/// first if the call may wait for it with the port unlocked
+fn lock_port_with_turn(handle: jlong, turn: Option<IoTurn>) -> Option<LockedPort> {
+    lock_port_with_turns(handle, turn.as_slice())
+}
+
+/// Lock the port behind a handle like `lock_port_with_turn`, for a call that both writes and
+/// reads with the port unlocked. The turns are taken in the same order as in `close_port`,
+/// read before write, so that two such calls cannot each wait for the other's turn.
+fn lock_port_with_turns(handle: jlong, turns: &[IoTurn]) -> Option<LockedPort> {
+    let port = port_handle(handle)?;
+    // SAFETY: the guards borrow from the PortHandle that `handle` keeps alive at the same
+    // address, and LockedPort drops them first
+    let take = |turn| turns.contains(&turn).then(|| unsafe { extend_guard(port.take_turn(turn)) });
+    let read_turn = take(IoTurn::Read);
+    let write_turn = take(IoTurn::Write);
+    let guard = unsafe { extend_guard(port.lock()) };
+    guard.as_ref()?;
+    Some(LockedPort {
+        guard: Some(guard),
+        #[cfg(target_os = "linux")]
+        has_write_turn: write_turn.is_some(),
+        _read_turn: read_turn,
+        _write_turn: write_turn,
+        handle: port,
+    })
+}
+
+/// Lock for a background thread to take around changes to the port behind `port`. It gives
+/// up rather than waits when the port is locked, since the call holding the lock may be
+/// stopping that thread, and once the port is closing.
+#[cfg(target_os = "linux")]
+fn port_locker(port: &Arc<PortHandle>) -> platform::PortLocker {
+    let port = Arc::downgrade(port);
+    Box::new(move |f: &mut dyn FnMut()| {
+        let Some(port) = port.upgrade() else {
+            return false;
+        };
+        let Ok(guard) = port.port.try_lock() else {
+            return false;
+        };
+        if guard.is_none() || port.closing.load(Ordering::Acquire) {
+            return false;
+        }
+        f();
+        true
+    })
+}
+
+/// Open ports by handle, so that handles can be checked and closed together (e.g. from a
+/// JVM shutdown hook). Handles are never reused, so a stale handle is simply not found.
+static OPEN_HANDLES: OnceLock<Mutex<HashMap<jlong, Arc<PortHandle>>>> = OnceLock::new();
+
+/// Next handle to hand out; 0 is the null handle
+static NEXT_HANDLE: AtomicI64 = AtomicI64::new(1);
+
+/// Lock the open handle registry, recovering it if a panic poisoned the lock
+fn open_handles() -> MutexGuard<'static, HashMap<jlong, Arc<PortHandle>>> {
+    OPEN_HANDLES
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+/// Look up the port behind a handle without locking it
+fn port_handle(handle: jlong) -> Option<Arc<PortHandle>> {
+    open_handles().get(&handle).cloned()
+}
+
+/// Record a newly opened port in the registry and return its handle
+fn register_port(wrapper: PortWrapper) -> jlong {
+    let handle = NEXT_HANDLE.fetch_add(1, Ordering::Relaxed);
+    open_handles().insert(handle, Arc::new(PortHandle::new(wrapper)));
+    handle
+}
+
+/// Close a port if its handle is registered. Returns false for unknown handles,
+/// which makes closing the same handle twice harmless.
+fn release_port(handle: jlong) -> bool {
+    let Some(port) = open_handles().remove(&handle) else {
+        return false;
+    };
+    close_port(&port);
+    true
+}
+
+/// Close a port taken out of the registry, once no other call is using it
+fn close_port(port: &PortHandle) {
+    // Make calls waiting with the port unlocked give up their turns: a blocked read is woken
+    // up, a nextFrame wakes when the frame reader stops, and other waits check the flag
+    port.closing.store(true, Ordering::Release);
+    #[cfg(target_os = "linux")]
+    let _ = PortWrapper::cancel_read(port.cancel_fd);
+    if let Some(wrapper) = port.lock().as_mut() {
+        wrapper.stop_frame_reader();
+    }
+    let _read_turn = port.take_turn(IoTurn::Read);
+    let _write_turn = port.take_turn(IoTurn::Write);
+    let wrapper = port.lock().take();
+    drop(wrapper);
+}
+
+/// Close the serial port
+/// Handles that are not open (including ones already closed) are ignored. A call in
+/// progress on another thread is finished first, except that a call waiting for input, for
+/// output to drain or (on Linux) for room in the output queue gives up; on Linux, a blocked
+/// read is cancelled.
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_close(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) {
+    if handle != 0 {
+        release_port(handle);
+    }
+}
+
+/// Close every port that is still open
+/// Returns: the number of ports closed
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_closeAll(
+    _env: JNIEnv,
+    _class: JClass,
+) -> jint {
+    // Take the handles out first, so the lock is not held while ports are closing
+    let ports: Vec<Arc<PortHandle>> = open_handles().drain().map(|(_, port)| port).collect();
+    for port in &ports {
+        close_port(port);
+    }
+    ports.len() as jint
+}
+
+/// Write data to the serial port with automatic RS-485 control
+/// A length of 0 returns 0 without touching the port or the RS-485 direction pin.
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_write(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    data: JByteArray,
+    offset: jint,
+    length: jint,
+) -> jint {
+    if handle == 0 {
+        set_error!("Write failed: port handle is null");
+        return -1;
+    }
+    // Avoid pulsing the RS-485 direction pin for nothing
+    if length == 0 {
+        return 0;
+    }
+
+    let mut buffer = vec![0i8; length as usize];
+    if let Err(e) = env.get_byte_array_region(&data, offset, &mut buffer[..]) {
+        set_port_error!(handle, format!("Write failed: could not read buffer: {}", e));
+        return -1;
+    }
+
+    // Convert i8 to u8 for writing
+    let u8_buffer: Vec<u8> = buffer.iter().map(|&b| b as u8).collect();
+
+    let mut wrapper = lock_port!(handle, Write, "Write failed", -1);
+    let result = wrapper.write_rs485(&u8_buffer);
+    write_result_code(handle, &mut wrapper, result, "Write")
+}
+
+/// Write data followed by the configured line terminator with automatic RS-485 control
+/// Data and terminator go out as one write, so RS-485 direction is held across both.
+/// Returns: number of bytes written including the terminator, -1 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_writeLine(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    data: JByteArray,
+    offset: jint,
+    length: jint,
+) -> jint {
+    if handle == 0 {
+        set_error!("Write line failed: port handle is null");
+        return -1;
+    }
+
+    let mut buffer = vec![0i8; length as usize];
+    if let Err(e) = env.get_byte_array_region(&data, offset, &mut buffer[..]) {
+        set_port_error!(handle, format!("Write line failed: could not read buffer: {}", e));
+        return -1;
+    }
+
+    let mut wrapper = lock_port!(handle, Write, "Write line failed", -1);
+    let mut line: Vec<u8> = buffer.iter().map(|&b| b as u8).collect();
+    line.extend_from_slice(&wrapper.write_terminator);
+    if line.is_empty() {
+        return 0;
+    }
+    let result = wrapper.write_rs485(&line);
+    write_result_code(handle, &mut wrapper, result, "Write line")
+}
+
+/// Write data followed by its CRC with automatic RS-485 control
+/// crc_type: 0 = CRC-16/CCITT-FALSE, 1 = CRC-32
+/// Data and CRC go out as one write, so RS-485 direction is held across both.
+/// Returns: number of bytes written including the CRC, -1 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_writeWithCrc(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    data: JByteArray,
+    offset: jint,
+    length: jint,
+    crc_type: jint,
+    little_endian: jboolean,
+) -> jint {
+    if handle == 0 {
+        set_error!("Write with CRC failed: port handle is null");
+        return -1;
+    }
+    let Some(crc_type) = CrcType::from_jint(crc_type) else {
+        set_port_error!(handle, format!("Write with CRC failed: invalid CRC type {}", crc_type));
+        return -1;
+    };
+
+    let mut frame = match read_array_region(&env, &data, offset, length) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            set_port_error!(handle, format!("Write with CRC failed: {}", e));
+            return -1;
+        }
+    };
+    frame.extend(crc_type.to_bytes(&frame, little_endian != 0));
+
+    let mut wrapper = lock_port!(handle, Write, "Write with CRC failed", -1);
+    let result = wrapper.write_rs485(&frame);
+    write_result_code(handle, &mut wrapper, result, "Write with CRC")
+}
+
+/// Set the bytes appended to each line written with writeLine
+/// An empty terminator makes writeLine behave like write.
+/// Returns: 1 on success, 0 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_setWriteTerminator(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    terminator: JByteArray,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Set write terminator failed: port handle is null");
+        return 0;
+    }
+
+    let bytes = match env.convert_byte_array(&terminator) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            set_port_error!(
+                handle,
+                format!("Set write terminator failed: could not read terminator: {}", e)
+            );
+            return 0;
+        }
+    };
+
+    let mut wrapper = lock_port!(handle, "Set write terminator failed", 0);
+    wrapper.write_terminator = bytes;
+    1
+}
+
+/// Encode the low `size` bytes of `value` in big- or little-endian order.
+/// Returns None if the size is not 1-8 bytes or the value does not fit in it.
+fn encode_unsigned(value: u64, size: usize, big_endian: bool) -> Option<Vec<u8>> {
+    if !(1..=8).contains(&size) || (size < 8 && value >> (size * 8) != 0) {
+        return None;
+    }
+    let bytes = if big_endian {
+        value.to_be_bytes()[8 - size..].to_vec()
+    } else {
+        value.to_le_bytes()[..size].to_vec()
+    };
+    Some(bytes)
+}
+
+/// Write an unsigned integer of `size` bytes in big- or little-endian order
+/// The bytes go out as one write, so RS-485 direction toggles once.
+/// Returns: number of bytes written, -2 if the write would block, -1 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_writeUnsigned(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    value: jlong,
+    size: jint,
+    big_endian: jboolean,
+) -> jint {
+    if handle == 0 {
+        set_error!("Write unsigned failed: port handle is null");
+        return -1;
+    }
+    let Some(bytes) = encode_unsigned(value as u64, size.max(0) as usize, big_endian != 0) else {
+        set_port_error!(
+            handle,
+            format!("Write unsigned failed: {} does not fit in {} bytes", value, size)
+        );
+        return -1;
+    };
+
+    let mut wrapper = lock_port!(handle, Write, "Write unsigned failed", -1);
+    let result = wrapper.write_rs485(&bytes);
+    write_result_code(handle, &mut wrapper, result, "Write unsigned")
+}
+
+/// Send a DMX512 packet: a break, a mark-after-break, then `length` bytes holding the start
+/// code and up to 512 slots. The port must be at 250000 baud (8N2).
+/// Returns: number of bytes written, -1 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_sendDmxFrame(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    data: JByteArray,
+    offset: jint,
+    length: jint,
+) -> jint {
+    if handle == 0 {
+        set_error!("Send DMX frame failed: port handle is null");
+        return -1;
+    }
+    if length < 1 || length as usize > MAX_DMX_PACKET_LEN {
+        set_port_error!(
+            handle,
+            format!("Send DMX frame failed: invalid packet length {}", length)
+        );
+        return -1;
+    }
+
+    let mut buffer = vec![0i8; length as usize];
+    if let Err(e) = env.get_byte_array_region(&data, offset, &mut buffer[..]) {
+        set_port_error!(handle, format!("Send DMX frame failed: could not read buffer: {}", e));
+        return -1;
+    }
+    let u8_buffer: Vec<u8> = buffer.iter().map(|&b| b as u8).collect();
+
+    let mut wrapper = lock_port!(handle, "Send DMX frame failed", -1);
+    match wrapper.send_dmx_frame(&u8_buffer) {
+        Ok(n) => n as jint,
+        Err(e) => {
+            set_port_error!(handle, format!("Send DMX frame failed: {}", e));
+            -1
+        }
+    }
+}
+
+/// Write data before an absolute deadline, in milliseconds since the Unix epoch
+/// In RS-485 modes the output is drained and the direction pin released even if the
+/// deadline passes mid-write; bytes still queued at the deadline are discarded and not counted.
+/// Returns: number of bytes written (fewer than length if the deadline passed), -1 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_writeWithDeadline(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    data: JByteArray,
+    offset: jint,
+    length: jint,
+    deadline_ms: jlong,
+) -> jint {
+    if handle == 0 {
+        set_error!("Write with deadline failed: port handle is null");
+        return -1;
+    }
+    if length == 0 {
+        return 0;
+    }
+
+    let mut buffer = vec![0i8; length as usize];
+    if let Err(e) = env.get_byte_array_region(&data, offset, &mut buffer[..]) {
+        set_port_error!(
+            handle,
+            format!("Write with deadline failed: could not read buffer: {}", e)
+        );
+        return -1;
+    }
+    let u8_buffer: Vec<u8> = buffer.iter().map(|&b| b as u8).collect();
+
+    // Convert the wall-clock deadline to a monotonic one, so clock changes can't stretch it
+    let now_ms = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as i64);
+    let remaining = Duration::from_millis(deadline_ms.saturating_sub(now_ms).max(0) as u64);
+    let deadline = Instant::now() + remaining;
+
+    let mut wrapper = lock_port!(handle, Write, "Write with deadline failed", -1);
+    match wrapper.write_rs485_before(&u8_buffer, deadline) {
+        Ok(n) => n as jint,
+        Err(e) => {
+            set_port_error!(handle, format!("Write with deadline failed: {}", e));
+            -1
+        }
+    }
+}
+
+/// Write 9-bit characters for multidrop protocols (Linux only)
+/// Each byte is sent with mark parity if its ninth_bits entry is true, space parity otherwise.
+/// Returns: number of bytes written, -1 on failure or if not on Linux
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_write9bit(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    data: JByteArray,
+    ninth_bits: JBooleanArray,
+) -> jint {
+    if handle == 0 {
+        set_error!("Write 9-bit failed: port handle is null");
+        return -1;
+    }
+
+    let data = match env.convert_byte_array(&data) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            set_port_error!(handle, format!("Write 9-bit failed: could not read data: {}", e));
+            return -1;
+        }
+    };
+    let mut flags = vec![0u8; data.len()];
+    match env.get_array_length(&ninth_bits) {
+        Ok(n) if n as usize == data.len() => {}
+        Ok(n) => {
+            set_port_error!(handle, format!(
+                "Write 9-bit failed: {} ninth bits for {} bytes",
+                n,
+                data.len()
+            ));
+            return -1;
+        }
+        Err(e) => {
+            set_port_error!(handle, format!("Write 9-bit failed: could not read ninth bits: {}", e));
+            return -1;
+        }
+    }
+    if let Err(e) = env.get_boolean_array_region(&ninth_bits, 0, &mut flags) {
+        set_port_error!(handle, format!("Write 9-bit failed: could not read ninth bits: {}", e));
+        return -1;
+    }
+
+    let mut port = lock_port!(handle, "Write 9-bit failed", -1);
+    let wrapper = &mut *port;
+    #[cfg(target_os = "linux")]
+    {
+        let ninth_bits: Vec<bool> = flags.iter().map(|&b| b != 0).collect();
+        match wrapper.write_9bit(&data, &ninth_bits) {
+            Ok(_) => data.len() as jint,
+            Err(e) => {
+                set_port_error!(handle, format!("Write 9-bit failed: {}", e));
+                -1
+            }
+        }
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = (wrapper, flags);
+        set_port_error!(handle, "Write 9-bit failed: only supported on Linux");
+        -1
+    }
+}
+
+/// Read data from the serial port
+/// A length of 0 returns 0 immediately, without a read syscall.
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_read(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    buffer: JByteArray,
+    offset: jint,
+    length: jint,
+) -> jint {
+    if handle == 0 {
+        set_error!("Read failed: port handle is null");
+        return -1;
+    }
+    let length = match check_read_len(length) {
+        Ok(Some(length)) => length,
+        Ok(None) => return 0,
+        Err(e) => {
+            set_port_error!(handle, format!("Read failed: {}", e));
+            return -1;
+        }
+    };
+
+    let mut wrapper = lock_port!(handle, Read, "Read failed", -1);
+    let mut read_buffer = vec![0u8; cap_read_len(length, wrapper.max_read_chunk)];
+
+    let bytes_read = match wrapper.read_data(&mut read_buffer) {
+        Ok(n) => n,
+        Err(e) => {
+            set_port_error!(handle, format!("Read failed: {}", e));
+            return read_error_code(&e);
+        }
+    };
+
+    if bytes_read > 0 {
+        // Convert u8 to i8 for JNI
+        let i8_buffer: Vec<i8> = read_buffer[..bytes_read].iter().map(|&b| b as i8).collect();
+
+        if let Err(e) = env.set_byte_array_region(&buffer, offset, &i8_buffer) {
+            set_port_error!(handle, format!("Read failed: could not write to buffer: {}", e));
+            return -1;
+        }
+    }
+
+    bytes_read as jint
+}
+
+/// Read data from the serial port directly into the Java array's backing storage
+///
+/// The blocking read goes into a per-port scratch buffer first, so the JNI critical
+/// region (during which the JVM may hold off garbage collection) only covers a single
+/// copy. This avoids the per-call allocation and i8/u8 conversion done by `read`.
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_readCritical(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    buffer: JByteArray,
+    offset: jint,
+    length: jint,
+) -> jint {
+    if handle == 0 {
+        set_error!("Read failed: port handle is null");
+        return -1;
+    }
+
+    let array_length = match env.get_array_length(&buffer) {
+        Ok(n) => n,
+        Err(e) => {
+            set_port_error!(handle, format!("Read failed: could not get buffer length: {}", e));
+            return -1;
+        }
+    };
+    if offset < 0 || length < 0 || offset as i64 + length as i64 > array_length as i64 {
+        set_port_error!(handle, format!(
+            "Read failed: offset={}, length={}, buffer.length={}",
+            offset, length, array_length
+        ));
+        return -1;
+    }
+
+    if length == 0 {
+        return 0;
+    }
+
+    let offset = offset as usize;
+
+    let mut wrapper = lock_port!(handle, Read, "Read failed", -1);
+    let length = cap_read_len(length as usize, wrapper.max_read_chunk);
+    if wrapper.read_scratch.len() < length {
+        wrapper.read_scratch.resize(length, 0);
+    }
+
+    let mut scratch = std::mem::take(&mut wrapper.read_scratch);
+    let read_result = wrapper.read_data(&mut scratch[..length]);
+    wrapper.read_scratch = scratch;
+    let bytes_read = match read_result {
+        Ok(n) => n,
+        Err(e) => {
+            set_port_error!(handle, format!("Read failed: {}", e));
+            return read_error_code(&e);
+        }
+    };
+
+    if bytes_read > 0 {
+        // Keep the critical region minimal: no JNI calls or blocking until it is released
+        let mut elements =
+            match unsafe { env.get_array_elements_critical(&buffer, ReleaseMode::CopyBack) } {
+                Ok(elements) => elements,
+                Err(e) => {
+                    set_port_error!(handle, format!("Read failed: could not pin buffer: {}", e));
+                    return -1;
+                }
+            };
+        let target = &mut elements[offset..offset + bytes_read];
+        // i8 and u8 share the same size and alignment
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                wrapper.read_scratch.as_ptr(),
+                target.as_mut_ptr() as *mut u8,
+                bytes_read,
+            );
+        }
+    }
+
+    bytes_read as jint
+}
+
+/// Read data from the serial port straight into caller-owned native memory
+///
+/// The address is used as-is, with no JNI array marshaling; the caller guarantees it points
+/// to at least `length` writable bytes that stay valid for the duration of the call.
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_readToAddress(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    address: jlong,
+    length: jint,
+) -> jint {
+    if handle == 0 {
+        set_error!("Read failed: port handle is null");
+        return -1;
+    }
+    if address == 0 {
+        set_port_error!(handle, "Read failed: address is null");
+        return -1;
+    }
+    if length < 0 {
+        set_port_error!(handle, format!("Read failed: length={}", length));
+        return -1;
+    }
+    if length == 0 {
+        return 0;
+    }
+
+    let mut wrapper = lock_port!(handle, Read, "Read failed", -1);
+    let length = cap_read_len(length as usize, wrapper.max_read_chunk);
+    // SAFETY: the Java caller vouches for `length` writable bytes at `address`
+    let target = unsafe { std::slice::from_raw_parts_mut(address as *mut u8, length) };
+    read_into_memory(handle, &mut wrapper, target)
+}
+
+/// Read into memory outside the Java heap, reporting failures as a read JNI function does
+fn read_into_memory(handle: jlong, wrapper: &mut LockedPort, target: &mut [u8]) -> jint {
+    match wrapper.read_data(target) {
+        Ok(n) => n as jint,
+        Err(e) => {
+            set_port_error!(handle, format!("Read failed: {}", e));
+            read_error_code(&e)
+        }
+    }
+}
+
+/// Write data to the serial port straight from caller-owned native memory
+///
+/// The bytes at `address + offset` are used in place, with no JNI array marshaling; the
+/// caller guarantees they stay valid and unmodified for the duration of the call.
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_writeFromAddress(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    address: jlong,
+    offset: jint,
+    length: jint,
+) -> jint {
+    if handle == 0 {
+        set_error!("Write failed: port handle is null");
+        return -1;
+    }
+    if address == 0 {
+        set_port_error!(handle, "Write failed: address is null");
+        return -1;
+    }
+    if offset < 0 || length < 0 {
+        set_port_error!(handle, format!("Write failed: offset={}, length={}", offset, length));
+        return -1;
+    }
+    // Avoid pulsing the RS-485 direction pin for nothing
+    if length == 0 {
+        return 0;
+    }
+
+    let mut wrapper = lock_port!(handle, Write, "Write failed", -1);
+    // SAFETY: the Java caller vouches for `length` readable bytes at `address + offset`
+    let data = unsafe {
+        std::slice::from_raw_parts((address as *const u8).add(offset as usize), length as usize)
+    };
+    let result = wrapper.write_rs485(data);
+    write_result_code(handle, &mut wrapper, result, "Write")
+}
+
+/// Look up the memory behind a direct `ByteBuffer` region, checked against its capacity.
+///
+/// Returns None if the JVM does not expose the address (a heap buffer, or a JVM without
+/// direct buffer support), after recording a warning the first time this happens on the port
+/// so the slower array fallback the caller takes is not silent.
+fn direct_buffer_region(
+    env: &JNIEnv,
+    wrapper: &mut PortWrapper,
+    buffer: &JByteBuffer,
+    offset: usize,
+    length: usize,
+) -> Option<Result<*mut u8, String>> {
+    let address = match env.get_direct_buffer_address(buffer) {
+        Ok(address) if !address.is_null() => address,
+        Ok(_) => return note_direct_buffer_fallback(wrapper, "address is null".to_string()),
+        Err(e) => return note_direct_buffer_fallback(wrapper, e.to_string()),
+    };
+    let capacity = match env.get_direct_buffer_capacity(buffer) {
+        Ok(capacity) => capacity,
+        Err(e) => return note_direct_buffer_fallback(wrapper, e.to_string()),
+    };
+    if offset + length > capacity {
+        return Some(Err(format!(
+            "offset={}, length={} exceeds buffer capacity {}",
+            offset, length, capacity
+        )));
+    }
+    // SAFETY: offset + length is within the buffer's capacity
+    Some(Ok(unsafe { address.add(offset) }))
+}
+
+/// Record a warning the first time a port falls back from a direct buffer to array copying
+fn note_direct_buffer_fallback<T>(wrapper: &mut PortWrapper, reason: String) -> Option<T> {
+    if !wrapper.direct_buffer_fallback_warned {
+        wrapper.direct_buffer_fallback_warned = true;
+        if let Ok(mut warning) = wrapper.last_warning.lock() {
+            *warning = Some(format!(
+                "direct buffer address unavailable ({}): falling back to copying through a \
+                 byte array",
+                reason
+            ));
+        }
+    }
+    None
+}
+
+/// Read data from the serial port straight into a direct `ByteBuffer`
+///
+/// Returns the number of bytes read, -2 if the read was cancelled, -1 on failure, or
+/// DIRECT_BUFFER_UNAVAILABLE if the buffer's address is not available, in which case nothing
+/// was read and the caller should read through a byte array instead.
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_readDirect(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    buffer: JByteBuffer,
+    offset: jint,
+    length: jint,
+) -> jint {
+    if handle == 0 {
+        set_error!("Read failed: port handle is null");
+        return -1;
+    }
+    if offset < 0 || length < 0 {
+        set_port_error!(handle, format!("Read failed: offset={}, length={}", offset, length));
+        return -1;
+    }
+
+    let mut wrapper = lock_port!(handle, Read, "Read failed", -1);
+    let region =
+        direct_buffer_region(&env, &mut wrapper, &buffer, offset as usize, length as usize);
+    let address = match region {
+        Some(Ok(address)) => address,
+        Some(Err(message)) => {
+            set_port_error!(handle, format!("Read failed: {}", message));
+            return -1;
+        }
+        None => return DIRECT_BUFFER_UNAVAILABLE,
+    };
+    // An empty read is driver-dependent, so don't issue one
+    if length == 0 {
+        return 0;
+    }
+    let length = cap_read_len(length as usize, wrapper.max_read_chunk);
+    // SAFETY: the region was checked against the buffer's capacity, and the Java caller keeps
+    // the buffer reachable for the duration of the call
+    let target = unsafe { std::slice::from_raw_parts_mut(address, length) };
+    read_into_memory(handle, &mut wrapper, target)
+}
+
+/// Write data to the serial port straight from a direct `ByteBuffer`
+///
+/// Returns the number of bytes written, -2 if the write would block, -1 on failure, or
+/// DIRECT_BUFFER_UNAVAILABLE if the buffer's address is not available, in which case nothing
+/// was written and the caller should write through a byte array instead.
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_writeDirect(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    buffer: JByteBuffer,
+    offset: jint,
+    length: jint,
+) -> jint {
+    if handle == 0 {
+        set_error!("Write failed: port handle is null");
+        return -1;
+    }
+    if offset < 0 || length < 0 {
+        set_port_error!(handle, format!("Write failed: offset={}, length={}", offset, length));
+        return -1;
+    }
+
+    let mut wrapper = lock_port!(handle, Write, "Write failed", -1);
+    let region =
+        direct_buffer_region(&env, &mut wrapper, &buffer, offset as usize, length as usize);
+    let address = match region {
+        Some(Ok(address)) => address,
+        Some(Err(message)) => {
+            set_port_error!(handle, format!("Write failed: {}", message));
+            return -1;
+        }
+        None => return DIRECT_BUFFER_UNAVAILABLE,
+    };
+    // Avoid pulsing the RS-485 direction pin for nothing
+    if length == 0 {
+        return 0;
+    }
+    // SAFETY: the region was checked against the buffer's capacity, and the Java caller keeps
+    // the buffer reachable for the duration of the call
+    let data = unsafe { std::slice::from_raw_parts(address, length as usize) };
+    let result = wrapper.write_rs485(data);
+    write_result_code(handle, &mut wrapper, result, "Write")
+}
+
+/// Reference point for monotonic timestamps reported to Java
+static MONOTONIC_EPOCH: OnceLock<Instant> = OnceLock::new();
+
+/// Nanoseconds elapsed on the native monotonic clock since its first use
+fn monotonic_nanos() -> i64 {
+    MONOTONIC_EPOCH.get_or_init(Instant::now).elapsed().as_nanos() as i64
+}
+
+/// Get the current value of the native monotonic clock in nanoseconds
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_monotonicNanos(
+    _env: JNIEnv,
+    _class: JClass,
+) -> jlong {
+    monotonic_nanos()
+}
+
+/// Largest number of bytes rendered by formatDump; the rest is summarized in one line
+const MAX_DUMP_BYTES: usize = 64 * 1024;
+
+/// Widest dump line accepted by formatDump, in bytes
+const MAX_DUMP_BYTES_PER_LINE: jint = 256;
+
+/// Render bytes as a hex dump with an ASCII column, one line per `bytes_per_line` bytes:
+/// `00000010  48 65 6c 6c 6f 0d 0a  |Hello..|`. Non-printable bytes show as `.`. At most
+/// `MAX_DUMP_BYTES` are rendered; `total_len` is the length of the whole input, and a final
+/// line tells how many bytes were left out.
+fn format_dump(data: &[u8], total_len: usize, bytes_per_line: usize) -> String {
+    use std::fmt::Write as _;
+
+    let shown = data.len().min(MAX_DUMP_BYTES);
+    let line_len = 8 + 2 + bytes_per_line * 3 + 2 + bytes_per_line + 2 + 1;
+    let mut out = String::with_capacity(shown.div_ceil(bytes_per_line) * line_len + 40);
+    for (i, line) in data[..shown].chunks(bytes_per_line).enumerate() {
+        let _ = write!(out, "{:08x} ", i * bytes_per_line);
+        for b in line {
+            let _ = write!(out, " {:02x}", b);
+        }
+        for _ in line.len()..bytes_per_line {
+            out.push_str("   ");
+        }
+        out.push_str("  |");
+        out.extend(line.iter().map(|&b| {
+            if b.is_ascii_graphic() || b == b' ' {
+                b as char
+            } else {
+                '.'
+            }
+        }));
+        out.push_str("|\n");
+    }
+    if total_len > shown {
+        let _ = writeln!(out, "... {} more bytes not shown", total_len - shown);
+    }
+    out
+}
+
+/// Format part of a byte array as a hex and ASCII dump for logging
+/// Only the first MAX_DUMP_BYTES bytes are copied and rendered.
+/// Returns: the dump, one line per bytes_per_line bytes, or null on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_formatDump(
+    mut env: JNIEnv,
+    _class: JClass,
+    data: JByteArray,
+    offset: jint,
+    length: jint,
+    bytes_per_line: jint,
+) -> jstring {
+    if bytes_per_line <= 0 || bytes_per_line > MAX_DUMP_BYTES_PER_LINE {
+        set_error!(format!("Format dump failed: invalid bytes per line {}", bytes_per_line));
+        return std::ptr::null_mut();
+    }
+
+    let total_len = length.max(0) as usize;
+    let mut buffer = vec![0i8; total_len.min(MAX_DUMP_BYTES)];
+    if let Err(e) = env.get_byte_array_region(&data, offset, &mut buffer[..]) {
+        set_error!(format!("Format dump failed: could not read buffer: {}", e));
+        return std::ptr::null_mut();
+    }
+
+    let bytes: Vec<u8> = buffer.iter().map(|&b| b as u8).collect();
+    let dump = format_dump(&bytes, total_len, bytes_per_line as usize);
+    string_to_jstring(&mut env, &dump)
+}
+
+/// CRC algorithms for writeWithCrc and readAndVerifyCrc
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum CrcType {
+    /// CRC-16/CCITT-FALSE: polynomial 0x1021, initial value 0xFFFF, not reflected
+    Ccitt,
+    /// CRC-32 as used by Ethernet and zlib: polynomial 0x04C11DB7 reflected, initial value
+    /// and final XOR 0xFFFFFFFF
+    Crc32,
+}
+
+impl CrcType {
+    fn from_jint(value: jint) -> Option<Self> {
+        match value {
+            0 => Some(CrcType::Ccitt),
+            1 => Some(CrcType::Crc32),
+            _ => None,
+        }
+    }
+
+    /// Length of the CRC in bytes
+    fn len(self) -> usize {
+        match self {
+            CrcType::Ccitt => 2,
+            CrcType::Crc32 => 4,
+        }
+    }
+
+    fn compute(self, data: &[u8]) -> u32 {
+        match self {
+            CrcType::Ccitt => u32::from(crc_ccitt(data)),
+            CrcType::Crc32 => crc32(data),
+        }
+    }
+
+    /// The CRC of `data` as bytes, in the given byte order
+    fn to_bytes(self, data: &[u8], little_endian: bool) -> Vec<u8> {
+        let crc = self.compute(data).to_le_bytes();
+        let mut bytes = crc[..self.len()].to_vec();
+        if !little_endian {
+            bytes.reverse();
+        }
+        bytes
+    }
+}
+
+static CRC_CCITT_TABLE: [u16; 256] = crc_ccitt_table();
+
+static CRC32_TABLE: [u32; 256] = crc32_table();
+
+const fn crc_ccitt_table() -> [u16; 256] {
+    let mut table = [0u16; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = (i as u16) << 8;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 0x8000 != 0 { (crc << 1) ^ 0x1021 } else { crc << 1 };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+const fn crc32_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+/// CRC-16/CCITT-FALSE of `data`
+fn crc_ccitt(data: &[u8]) -> u16 {
+    data.iter().fold(0xFFFF, |crc, &b| {
+        (crc << 8) ^ CRC_CCITT_TABLE[usize::from((crc >> 8) as u8 ^ b)]
+    })
+}
+
+/// CRC-32 (ISO-HDLC) of `data`
+fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(0xFFFF_FFFF, |crc, &b| {
+        (crc >> 8) ^ CRC32_TABLE[usize::from(crc as u8 ^ b)]
+    })
+}
+
+/// Read `length` bytes of a Java array from `offset`
+fn read_array_region(
+    env: &JNIEnv,
+    data: &JByteArray,
+    offset: jint,
+    length: jint,
+) -> Result<Vec<u8>, String> {
+    let mut buffer = vec![0i8; length.max(0) as usize];
+    env.get_byte_array_region(data, offset, &mut buffer[..])
+        .map_err(|e| format!("could not read buffer: {}", e))?;
+    Ok(buffer.iter().map(|&b| b as u8).collect())
+}
+
+/// Compute the CRC-32 (ISO-HDLC, as used by Ethernet and zlib) of part of an array
+/// Returns: the CRC (0 to 0xFFFFFFFF), or -1 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_crc32(
+    env: JNIEnv,
+    _class: JClass,
+    data: JByteArray,
+    offset: jint,
+    length: jint,
+) -> jlong {
+    match read_array_region(&env, &data, offset, length) {
+        Ok(bytes) => jlong::from(crc32(&bytes)),
+        Err(e) => {
+            set_error!(format!("CRC-32 failed: {}", e));
+            -1
+        }
+    }
+}
+
+/// Compute the CRC-16/CCITT-FALSE of part of an array
+/// Returns: the CRC (0 to 0xFFFF), or -1 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_crcCcitt(
+    env: JNIEnv,
+    _class: JClass,
+    data: JByteArray,
+    offset: jint,
+    length: jint,
+) -> jint {
+    match read_array_region(&env, &data, offset, length) {
+        Ok(bytes) => jint::from(crc_ccitt(&bytes)),
+        Err(e) => {
+            set_error!(format!("CRC-CCITT failed: {}", e));
+            -1
+        }
+    }
+}
+
+/// Read data from the serial port and record when the read returned
+/// timestamp_out[0] receives the native monotonic time in nanoseconds,
+/// captured immediately after the underlying read completed.
+/// A length of 0 returns 0 immediately, without a read syscall or a timestamp.
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_readTimestamped(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    buffer: JByteArray,
+    offset: jint,
+    length: jint,
+    timestamp_out: JLongArray,
+) -> jint {
+    if handle == 0 {
+        set_error!("Read failed: port handle is null");
+        return -1;
+    }
+    let length = match check_read_len(length) {
+        Ok(Some(length)) => length,
+        Ok(None) => return 0,
+        Err(e) => {
+            set_port_error!(handle, format!("Read failed: {}", e));
+            return -1;
+        }
+    };
+
+    let mut wrapper = lock_port!(handle, Read, "Read failed", -1);
+    let mut read_buffer = vec![0u8; cap_read_len(length, wrapper.max_read_chunk)];
+
+    let (bytes_read, timestamp) = match wrapper.read_data(&mut read_buffer) {
+        Ok(n) => (n, monotonic_nanos()),
+        Err(e) => {
+            set_port_error!(handle, format!("Read failed: {}", e));
+            return read_error_code(&e);
+        }
+    };
+
+    if bytes_read > 0 {
+        // Convert u8 to i8 for JNI
+        let i8_buffer: Vec<i8> = read_buffer[..bytes_read].iter().map(|&b| b as i8).collect();
+
+        if let Err(e) = env.set_byte_array_region(&buffer, offset, &i8_buffer) {
+            set_port_error!(handle, format!("Read failed: could not write to buffer: {}", e));
+            return -1;
+        }
+    }
+
+    if let Err(e) = env.set_long_array_region(&timestamp_out, 0, &[timestamp]) {
+        set_port_error!(handle, format!("Read failed: could not write timestamp: {}", e));
+        return -1;
+    }
+
+    bytes_read as jint
+}
+
+/// Read once from the serial port and scatter the data across several Java arrays
+/// Segment i is buffers[i][offsets[i]..offsets[i] + lengths[i]]; segments are filled
+/// in order, like readv(), so a short read leaves trailing segments untouched.
+/// The read waits for input until a deadline timeout_ms from now, with the port unlocked,
+/// then takes what has arrived; the port timeout is not used or changed.
+/// Returns: total bytes read, 0 on timeout, -2 if cancelled, -1 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_readScatter(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    buffers: JObjectArray,
+    offsets: JIntArray,
+    lengths: JIntArray,
+    timeout_ms: jint,
+) -> jint {
+    if handle == 0 {
+        set_error!("Read failed: port handle is null");
+        return -1;
+    }
+
+    let count = match env.get_array_length(&buffers) {
+        Ok(n) => n as usize,
+        Err(e) => {
+            set_port_error!(handle, format!("Read failed: could not get buffers length: {}", e));
+            return -1;
+        }
+    };
+    let offsets_len = env.get_array_length(&offsets).unwrap_or(-1);
+    let lengths_len = env.get_array_length(&lengths).unwrap_or(-1);
+    if offsets_len as usize != count || lengths_len as usize != count {
+        set_port_error!(handle, format!(
+            "Read failed: buffers ({}), offsets ({}) and lengths ({}) must have the same length",
+            count, offsets_len, lengths_len
+        ));
+        return -1;
+    }
+
+    let mut segment_offsets = vec![0 as jint; count];
+    let mut segment_lengths = vec![0 as jint; count];
+    if let Err(e) = env
+        .get_int_array_region(&offsets, 0, &mut segment_offsets)
+        .and_then(|_| env.get_int_array_region(&lengths, 0, &mut segment_lengths))
+    {
+        set_port_error!(handle, format!("Read failed: could not read segment layout: {}", e));
+        return -1;
+    }
+
+    let mut segments = Vec::with_capacity(count);
+    for i in 0..count {
+        let buffer = match env.get_object_array_element(&buffers, i as jint) {
+            Ok(obj) => JByteArray::from(obj),
+            Err(e) => {
+                set_port_error!(handle, format!("Read failed: could not get buffer {}: {}", i, e));
+                return -1;
+            }
+        };
+        let buffer_len = match env.get_array_length(&buffer) {
+            Ok(n) => n,
+            Err(e) => {
+                set_port_error!(handle, format!("Read failed: could not get buffer {} length: {}", i, e));
+                return -1;
+            }
+        };
+        let (offset, length) = (segment_offsets[i], segment_lengths[i]);
+        if offset < 0 || length < 0 || offset as i64 + length as i64 > buffer_len as i64 {
+            set_port_error!(handle, format!(
+                "Read failed: segment {} has offset={}, length={}, buffer.length={}",
+                i, offset, length, buffer_len
+            ));
+            return -1;
+        }
+        segments.push((buffer, offset, length as usize));
+    }
+
+    let total_length: usize = segments.iter().map(|(_, _, length)| length).sum();
+    let mut read_buffer = vec![0u8; total_length];
+
+    let mut wrapper = lock_port!(handle, Read, "Read failed", -1);
+    let deadline = Instant::now() + Duration::from_millis(timeout_ms.max(0) as u64);
+    let bytes_read = match wrapper.read_data_before(&mut read_buffer, deadline) {
+        Ok(n) => n,
+        Err(e) if e.kind() == std::io::ErrorKind::TimedOut => 0,
+        Err(e) => {
+            set_port_error!(handle, format!("Read failed: {}", e));
+            return read_error_code(&e);
+        }
+    };
+
+    let mut position = 0;
+    for (buffer, offset, length) in &segments {
+        if position == bytes_read {
+            break;
+        }
+        let chunk = (*length).min(bytes_read - position);
+        let i8_chunk: Vec<i8> = read_buffer[position..position + chunk]
+            .iter()
+            .map(|&b| b as i8)
+            .collect();
+        if let Err(e) = env.set_byte_array_region(buffer, *offset, &i8_chunk) {
+            set_port_error!(handle, format!("Read failed: could not write to buffer: {}", e));
+            return -1;
+        }
+        position += chunk;
+    }
+
+    bytes_read as jint
+}
+
+/// Cancel a read blocked on this port from another thread (Linux only)
+/// The blocked read returns -2 with a "Read cancelled" error. If no read is in
+/// progress, the next read on the port is cancelled instead.
+/// Returns: 1 on success, 0 on failure or if not on Linux
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_cancelRead(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Cancel read failed: port handle is null");
+        return 0;
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        // Signal through the handle's copy of the fd: the reading thread holds the port lock
+        let Some(port) = port_handle(handle) else {
+            set_error!("Cancel read failed: port is not open");
+            return 0;
+        };
+        match PortWrapper::cancel_read(port.cancel_fd) {
+            Ok(_) => 1,
+            Err(e) => {
+                set_port_error!(handle, format!("Cancel read failed: {}", e));
+                0
+            }
+        }
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        set_port_error!(handle, "Cancel read failed: only supported on Linux");
+        0
+    }
+}
+
+/// Get an eventfd that becomes readable when the port has input (Linux only)
+/// The first call starts a background thread polling the port; both are cleaned up on close.
+/// Once signalled, the event stays readable until reset with `resetReadableEvent`.
+/// Returns: the descriptor, or -1 on failure or if not on Linux
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_getReadableEventFd(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jint {
+    if handle == 0 {
+        set_error!("Get readable event fd failed: port handle is null");
+        return -1;
+    }
+
+    let mut wrapper = lock_port!(handle, "Get readable event fd failed", -1);
+    #[cfg(target_os = "linux")]
+    {
+        match wrapper.readable_event_fd() {
+            Ok(fd) => fd as jint,
+            Err(e) => {
+                set_port_error!(handle, format!("Get readable event fd failed: {}", e));
+                -1
+            }
+        }
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = &mut wrapper;
+        set_port_error!(handle, "Get readable event fd failed: only supported on Linux");
+        -1
+    }
+}
+
+/// Reset the eventfd from `getReadableEventFd`, so it is signalled again on new input
+/// Returns: 1 if it was signalled, 0 if not, if it was never requested, or if not on Linux
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_resetReadableEvent(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jboolean {
+    if handle == 0 {
+        return 0;
+    }
+
+    let Some(wrapper) = lock_port(handle) else {
+        return 0;
+    };
+    #[cfg(target_os = "linux")]
+    {
+        wrapper.reset_readable_event() as jboolean
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = wrapper;
+        0
+    }
+}
+
+/// Wait until any of the given ports has data to read, or the timeout expires
+/// readable_out[i] is set to true for each handle that has data available.
+/// On Linux this is a single poll() over all ports; elsewhere it falls back to
+/// checking bytes_to_read on each port in turn.
+/// Returns: number of readable ports (0 on timeout), or -1 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_pollReadable(
+    env: JNIEnv,
+    _class: JClass,
+    handles: JLongArray,
+    readable_out: JBooleanArray,
+    timeout_ms: jint,
+) -> jint {
+    let length = match env.get_array_length(&handles) {
+        Ok(n) => n as usize,
+        Err(e) => {
+            set_error!(format!("Poll failed: could not get handles length: {}", e));
+            return -1;
+        }
+    };
+    let mut raw_handles = vec![0 as jlong; length];
+    if let Err(e) = env.get_long_array_region(&handles, 0, &mut raw_handles) {
+        set_error!(format!("Poll failed: could not read handles: {}", e));
+        return -1;
+    }
+    if raw_handles.contains(&0) {
+        set_error!("Poll failed: port handle is null");
+        return -1;
+    }
+
+    // Take each port's read turn once, in handle order, so that concurrent polls cannot deadlock.
+    // Each port is unlocked before the next turn is taken: a read in progress on a later port
+    // must not keep the earlier ones locked.
+    let mut unique = raw_handles.clone();
+    unique.sort_unstable();
+    unique.dedup();
+    let mut locked = Vec::with_capacity(unique.len());
+    for &handle in &unique {
+        match lock_port_with_turn(handle, Some(IoTurn::Read)) {
+            Some(mut port) => {
+                port.unlock();
+                locked.push(port);
+            }
+            None => {
+                set_error!("Poll failed: port is not open");
+                return -1;
+            }
+        };
+    }
+
+    let timeout = Duration::from_millis(timeout_ms.max(0) as u64);
+    let readable_unique = match wait_any_readable(&mut locked, timeout) {
+        Ok(readable) => readable,
+        Err(e) => {
+            set_error!(format!("Poll failed: {}", e));
+            return -1;
+        }
+    };
+    let readable: Vec<bool> = raw_handles
+        .iter()
+        .map(|handle| unique.binary_search(handle).is_ok_and(|i| readable_unique[i]))
+        .collect();
+
+    let flags: Vec<jboolean> = readable.iter().map(|&r| r as jboolean).collect();
+    if let Err(e) = env.set_boolean_array_region(&readable_out, 0, &flags) {
+        set_error!(format!("Poll failed: could not write results: {}", e));
+        return -1;
+    }
+
+    readable.iter().filter(|&&r| r).count() as jint
+}
+
+/// Block until at least `count` bytes are available to read or `timeout_ms` elapses
+/// Returns: number of bytes available (possibly less than count on timeout), -1 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_waitForBytes(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    count: jint,
+    timeout_ms: jint,
+) -> jint {
+    if handle == 0 {
+        set_error!("Wait for bytes failed: port handle is null");
+        return -1;
+    }
+
+    let mut wrapper = lock_port!(handle, Read, "Wait for bytes failed", -1);
+    let timeout = Duration::from_millis(timeout_ms.max(0) as u64);
+    match wrapper.wait_for_bytes(count.max(0) as u32, timeout) {
+        Ok(n) => n.min(jint::MAX as u32) as jint,
+        Err(e) => {
+            set_port_error!(handle, format!("Wait for bytes failed: {}", e));
+            -1
+        }
+    }
+}
+
+/// Block until all output queued so far has been transmitted or `timeout_ms` elapses
+/// Returns: 1 if the output emptied, 0 on timeout, -1 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_waitOutputEmpty(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    timeout_ms: jint,
+) -> jint {
+    if handle == 0 {
+        set_error!("Wait output empty failed: port handle is null");
+        return -1;
+    }
+    if timeout_ms < 0 {
+        set_port_error!(
+            handle,
+            format!("Wait output empty failed: invalid timeout_ms={}", timeout_ms)
+        );
+        return -1;
+    }
+
+    let mut wrapper = lock_port!(handle, Write, "Wait output empty failed", -1);
+    let deadline = Instant::now() + Duration::from_millis(timeout_ms as u64);
+    match wrapper.wait_output_empty(deadline) {
+        Ok(emptied) => emptied as jint,
+        Err(e) => {
+            set_port_error!(handle, format!("Wait output empty failed: {}", e));
+            -1
+        }
+    }
+}
+
+/// Get the number of bytes available to read
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_bytesAvailable(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jint {
+    if handle == 0 {
+        return 0;
+    }
+
+    let Some(mut wrapper) = lock_port(handle) else {
+        return 0;
+    };
+    match wrapper.port.bytes_to_read() {
+        Ok(n) => {
+            wrapper.observe_input_depth(n);
+            (n as usize + wrapper.read_residual.len()) as jint
+        }
+        Err(e) => {
+            set_port_error!(handle, format!("Failed to get bytes available: {}", e));
+            0
+        }
+    }
+}
+
+/// Get a snapshot of the port status in one call.
+/// Returns a tab-separated line: input_bytes\toutput_bytes\tmodem_bits\tkernel_rs485
+/// where modem_bits is a bitmask (1 = CTS, 2 = DSR, 4 = RI, 8 = CD), or -1 if the
+/// modem lines cannot be read (e.g. on a PTY), and kernel_rs485 is "1" or "0".
+/// Returns null on failure.
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_getPortStatus(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jstring {
+    if handle == 0 {
+        set_error!("Get port status failed: port handle is null");
+        return std::ptr::null_mut();
+    }
+
+    let mut port = lock_port!(handle, "Get port status failed", std::ptr::null_mut());
+    let wrapper = &mut *port;
+
+    let input_bytes = match wrapper.port.bytes_to_read() {
+        Ok(n) => n,
+        Err(e) => {
+            set_port_error!(handle, format!("Get port status failed: {}", e));
+            return std::ptr::null_mut();
+        }
+    };
+    let output_bytes = match wrapper.port.bytes_to_write() {
+        Ok(n) => n,
+        Err(e) => {
+            set_port_error!(handle, format!("Get port status failed: {}", e));
+            return std::ptr::null_mut();
+        }
+    };
+    wrapper.observe_input_depth(input_bytes);
+    wrapper.queue_high_water.observe_output(output_bytes);
+    let modem_bits = wrapper
+        .modem_status()
+        .map(|bits| bits as i64)
+        .unwrap_or(-1);
+
+    #[cfg(target_os = "linux")]
+    let kernel_rs485 = wrapper.is_kernel_rs485_active();
+    #[cfg(not(target_os = "linux"))]
+    let kernel_rs485 = false;
+
+    let status = format!(
+        "{}\t{}\t{}\t{}",
+        input_bytes,
+        output_bytes,
+        modem_bits,
+        if kernel_rs485 { "1" } else { "0" }
+    );
+    string_to_jstring(&mut env, &status)
+}
+
+/// Get the deepest input and output queues observed since open or the last reset.
+/// Returns a tab-separated line: input_bytes\toutput_bytes, or null on failure.
+///
+/// Depths are sampled on each read, write and port status query, so short peaks
+/// between calls are not seen.
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_getQueueHighWater(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jstring {
+    if handle == 0 {
+        set_error!("Get queue high-water marks failed: port handle is null");
+        return std::ptr::null_mut();
+    }
+
+    let wrapper = lock_port!(handle, "Get queue high-water marks failed", std::ptr::null_mut());
+    let high_water = wrapper.queue_high_water;
+    string_to_jstring(
+        &mut env,
+        &format!("{}\t{}", high_water.input, high_water.output),
+    )
+}
+
+/// Reset the queue high-water marks to zero.
+/// Returns 0 on success, -1 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_resetQueueHighWater(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jint {
+    if handle == 0 {
+        set_error!("Reset queue high-water marks failed: port handle is null");
+        return -1;
+    }
+
+    let mut wrapper = lock_port!(handle, "Reset queue high-water marks failed", -1);
+    wrapper.queue_high_water = QueueHighWater::default();
+    0
+}
+
+/// Flush the output buffer
+/// A manual RS-485 direction pin left asserted by a write with auto-flush off is released.
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_flush(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Flush failed: port handle is null");
+        return 0;
+    }
+
+    let mut wrapper = lock_port!(handle, "Flush failed", 0);
+    match wrapper.flush_output() {
+        Ok(_) => 1,
+        Err(e) => {
+            set_port_error!(handle, format!("Flush failed: {}", e));
+            0
+        }
+    }
+}
+
+/// Read into `buffer` until it is full or `timeout` elapses.
+///
+/// Each read waits for input with the port unlocked until the exact deadline; input already
+/// queued is read even once it has passed, so a zero timeout reads without waiting. Returns
+/// the number of bytes read, which is 0 if nothing arrived.
+fn read_with_deadline(
+    wrapper: &mut LockedPort,
+    buffer: &mut [u8],
+    timeout: Duration,
+) -> std::io::Result<usize> {
+    let deadline = Instant::now() + timeout;
+    let mut total = 0;
+
+    loop {
+        if total == buffer.len() {
+            return Ok(total);
+        }
+        // Past the deadline this still takes input that is already queued
+        match wrapper.read_data_before(&mut buffer[total..], deadline) {
+            // Input was signalled but none was read; back off before waiting again
+            Ok(0) => {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                if remaining.is_zero() {
+                    return Ok(total);
+                }
+                wrapper.unlocked(|| std::thread::sleep(READ_RETRY_BACKOFF.min(remaining)))?;
+            }
+            Ok(n) => total += n,
+            Err(e) if e.kind() == std::io::ErrorKind::TimedOut => return Ok(total),
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Write a whole request (with RS-485 control) after the input has been cleared
+fn write_request(wrapper: &mut PortWrapper, request: &[u8]) -> Result<(), String> {
+    // Input was just cleared; don't let a chunked write clear it again mid-request
+    let clear_input_before_write = wrapper.clear_input_before_write;
+    wrapper.clear_input_before_write = false;
+    let mut written = 0;
+    let result = loop {
+        if written == request.len() {
+            break Ok(());
+        }
+        match wrapper.write_rs485(&request[written..]) {
+            Ok(0) => {
+                break Err(format!(
+                    "write stalled after {} of {} bytes",
+                    written,
+                    request.len()
+                ))
+            }
+            Ok(n) => written += n,
+            Err(e) => break Err(format!("write error: {}", e)),
+        }
+    };
+    wrapper.clear_input_before_write = clear_input_before_write;
+    result
+}
+
+/// Perform a request/response exchange as one operation:
+/// clear input, write the request (with RS-485 control), drain, then read the
+/// response until `max_len` bytes arrive or `timeout_ms` elapses.
+/// Returns: bytes read, 0 on response timeout, -1 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_transact(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    request: JByteArray,
+    response: JByteArray,
+    max_len: jint,
+    timeout_ms: jint,
+) -> jint {
+    if handle == 0 {
+        set_error!("Transact failed: port handle is null");
+        return -1;
+    }
+
+    let request = match env.convert_byte_array(&request) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            set_port_error!(handle, format!("Transact failed: could not read request: {}", e));
+            return -1;
+        }
+    };
+
+    let mut wrapper = lock_port!(handle, Read + Write, "Transact failed", -1);
+
+    if let Err(e) = wrapper.clear_input() {
+        set_port_error!(handle, format!("Transact failed: could not clear input: {}", e));
+        return -1;
+    }
+
+    if let Err(e) = write_request(&mut wrapper, &request) {
+        set_port_error!(handle, format!("Transact failed: {}", e));
+        return -1;
+    }
+
+    if let Err(e) = wrapper.port.flush() {
+        set_port_error!(handle, format!("Transact failed: could not drain output: {}", e));
+        return -1;
+    }
+
+    let mut read_buffer = vec![0u8; max_len.max(0) as usize];
+    let timeout = Duration::from_millis(timeout_ms.max(0) as u64);
+    let bytes_read = match read_with_deadline(&mut wrapper, &mut read_buffer, timeout) {
+        Ok(n) => n,
+        Err(e) => {
+            set_port_error!(handle, format!("Transact failed: read error: {}", e));
+            return -1;
+        }
+    };
+
+    if bytes_read > 0 {
+        let i8_buffer: Vec<i8> = read_buffer[..bytes_read].iter().map(|&b| b as i8).collect();
+        if let Err(e) = env.set_byte_array_region(&response, 0, &i8_buffer) {
+            set_port_error!(handle, format!("Transact failed: could not write to buffer: {}", e));
+            return -1;
+        }
+    }
+
+    bytes_read as jint
+}
+
+/// Measure the round-trip latency to an echo device or loopback: `iterations` times, clear
+/// input, write `probe` and wait for `response_len` bytes to come back within `timeout_ms`.
+/// Each round trip is timed natively on the monotonic clock, from the start of the write to
+/// the arrival of the last response byte.
+/// Returns: tab-separated min\tavg\tmax latency in microseconds, or null on failure
+/// (including a response that does not arrive in time)
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_measureRoundTrip(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    probe: JByteArray,
+    response_len: jint,
+    timeout_ms: jint,
+    iterations: jint,
+) -> jstring {
+    if handle == 0 {
+        set_error!("Measure round trip failed: port handle is null");
+        return std::ptr::null_mut();
+    }
+    if response_len <= 0 || timeout_ms <= 0 || iterations <= 0 {
+        set_port_error!(handle, format!(
+            "Measure round trip failed: invalid response_len={}, timeout_ms={} or iterations={}",
+            response_len, timeout_ms, iterations
+        ));
+        return std::ptr::null_mut();
+    }
+
+    let probe = match env.convert_byte_array(&probe) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            set_port_error!(
+                handle,
+                format!("Measure round trip failed: could not read probe: {}", e)
+            );
+            return std::ptr::null_mut();
+        }
+    };
+
+    let mut wrapper =
+        lock_port!(handle, Read + Write, "Measure round trip failed", std::ptr::null_mut());
+    let timeout = Duration::from_millis(timeout_ms as u64);
+    let mut response = vec![0u8; response_len as usize];
+    let mut latencies = Vec::with_capacity(iterations as usize);
+
+    for iteration in 1..=iterations {
+        if let Err(e) = wrapper.clear_input() {
+            set_port_error!(
+                handle,
+                format!("Measure round trip failed: could not clear input: {}", e)
+            );
+            return std::ptr::null_mut();
+        }
+
+        let start = Instant::now();
+        if let Err(e) = write_request(&mut wrapper, &probe) {
+            set_port_error!(handle, format!("Measure round trip failed: {}", e));
+            return std::ptr::null_mut();
+        }
+        let received = match read_with_deadline(&mut wrapper, &mut response, timeout) {
+            Ok(n) => n,
+            Err(e) => {
+                set_port_error!(handle, format!("Measure round trip failed: read error: {}", e));
+                return std::ptr::null_mut();
+            }
+        };
+        let elapsed = start.elapsed();
+
+        if received < response.len() {
+            set_port_error!(handle, format!(
+                "Measure round trip failed: iteration {} received {} of {} bytes within {} ms",
+                iteration,
+                received,
+                response.len(),
+                timeout_ms
+            ));
+            return std::ptr::null_mut();
+        }
+        latencies.push(elapsed.as_micros() as u64);
+    }
+
+    let min = latencies.iter().min().copied().unwrap_or(0);
+    let max = latencies.iter().max().copied().unwrap_or(0);
+    let avg = latencies.iter().sum::<u64>() / latencies.len() as u64;
+    string_to_jstring(&mut env, &format!("{}\t{}\t{}", min, avg, max))
+}
+
+/// Read until `length` bytes arrive or `timeout_ms` elapses
+/// Sets the port's last_read_timed_out flag if the deadline cut the read short. A timeout_ms
+/// of 0 only takes the bytes already received and never counts as timed out.
+/// Returns: bytes read (less than length on timeout), -1 on failure, -2 if cancelled
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_readFully(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    buffer: JByteArray,
+    offset: jint,
+    length: jint,
+    timeout_ms: jint,
+) -> jint {
+    if handle == 0 {
+        set_error!("Read fully failed: port handle is null");
+        return -1;
+    }
+
+    let mut wrapper = lock_port!(handle, Read, "Read fully failed", -1);
+    wrapper.last_read_timed_out = false;
+
+    let mut read_buffer = vec![0u8; length.max(0) as usize];
+    let timeout = Duration::from_millis(timeout_ms.max(0) as u64);
+    let bytes_read = match read_with_deadline(&mut wrapper, &mut read_buffer, timeout) {
+        Ok(n) => n,
+        Err(e) => {
+            set_port_error!(handle, format!("Read fully failed: {}", e));
+            return read_error_code(&e);
+        }
+    };
+    wrapper.last_read_timed_out = bytes_read < read_buffer.len() && !timeout.is_zero();
+
+    if bytes_read > 0 {
+        let i8_buffer: Vec<i8> = read_buffer[..bytes_read].iter().map(|&b| b as i8).collect();
+        if let Err(e) = env.set_byte_array_region(&buffer, offset, &i8_buffer) {
+            set_port_error!(handle, format!("Read fully failed: could not write to buffer: {}", e));
+            return -1;
+        }
+    }
+
+    bytes_read as jint
+}
+
+/// Read until the line stays idle for `idle_gap` after data has arrived, `buffer` is full,
+/// or `timeout` elapses.
+///
+/// The idle timer starts with the first byte, so a frame that has not begun yet is waited for
+/// until the overall timeout. Input is waited for with the port unlocked, until the idle gap or
+/// the overall timeout ends, and queued bytes are then read without blocking. Returns the
+/// number of bytes read, which is 0 if nothing arrived.
+fn read_until_idle(
+    wrapper: &mut LockedPort,
+    buffer: &mut [u8],
+    idle_gap: Duration,
+    timeout: Duration,
+) -> std::io::Result<usize> {
+    let start = Instant::now();
+    let mut last_received: Option<Instant> = None;
+    let mut total = 0;
+
+    while total < buffer.len() {
+        let now = Instant::now();
+        if now.duration_since(start) >= timeout {
+            break;
+        }
+        if last_received.is_some_and(|last| now.duration_since(last) >= idle_gap) {
+            break;
+        }
+        let available = wrapper.port.bytes_to_read()? as usize + wrapper.read_residual.len();
+        if available == 0 {
+            let deadline = match last_received {
+                Some(last) => (start + timeout).min(last + idle_gap),
+                None => start + timeout,
+            };
+            wrapper.wait_for_input(Some(deadline))?;
+            continue;
+        }
+        let end = total + available.min(buffer.len() - total);
+        let n = wrapper.read_data(&mut buffer[total..end])?;
+        if n > 0 {
+            total += n;
+            last_received = Some(Instant::now());
+        }
+    }
+    Ok(total)
+}
+
+/// Read a frame delimited by an idle line: accumulate bytes until none arrive for
+/// `idle_gap_ms` after the first one, `max_len` bytes arrive, or `timeout_ms` elapses.
+/// Returns: bytes read (0 if nothing arrived before the timeout), -1 on failure, -2 if cancelled
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_readUntilIdle(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    buffer: JByteArray,
+    offset: jint,
+    max_len: jint,
+    idle_gap_ms: jint,
+    timeout_ms: jint,
+) -> jint {
+    if handle == 0 {
+        set_error!("Read until idle failed: port handle is null");
+        return -1;
+    }
+
+    let mut wrapper = lock_port!(handle, Read, "Read until idle failed", -1);
+
+    let mut read_buffer = vec![0u8; max_len.max(0) as usize];
+    let idle_gap = Duration::from_millis(idle_gap_ms.max(0) as u64);
+    let timeout = Duration::from_millis(timeout_ms.max(0) as u64);
+    let bytes_read = match read_until_idle(&mut wrapper, &mut read_buffer, idle_gap, timeout) {
+        Ok(n) => n,
+        Err(e) => {
+            set_port_error!(handle, format!("Read until idle failed: {}", e));
+            return read_error_code(&e);
+        }
+    };
+
+    if bytes_read > 0 {
+        let i8_buffer: Vec<i8> = read_buffer[..bytes_read].iter().map(|&b| b as i8).collect();
+        if let Err(e) = env.set_byte_array_region(&buffer, offset, &i8_buffer) {
+            set_port_error!(
+                handle,
+                format!("Read until idle failed: could not write to buffer: {}", e)
+            );
+            return -1;
+        }
+    }
+
+    bytes_read as jint
+}
+
+/// Check the CRC at the end of `frame`. Returns the payload length, or a description of why
+/// the frame is invalid.
+fn verify_crc(frame: &[u8], crc_type: CrcType, little_endian: bool) -> Result<usize, String> {
+    let Some(payload_len) = frame.len().checked_sub(crc_type.len()) else {
+        return Err(format!("frame of {} bytes is shorter than its CRC", frame.len()));
+    };
+    let (payload, received) = frame.split_at(payload_len);
+    let expected = crc_type.to_bytes(payload, little_endian);
+    if received != expected.as_slice() {
+        return Err(format!(
+            "CRC mismatch in frame of {} bytes: received {}, computed {}",
+            frame.len(),
+            hex_string(received),
+            hex_string(&expected)
+        ));
+    }
+    Ok(payload_len)
+}
+
+/// Format bytes as contiguous lowercase hex, e.g. `1d0f`
+fn hex_string(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Read a frame delimited by an idle line, as readUntilIdle does, and check the CRC at its
+/// end. The frame, CRC included, is copied to the buffer even if the CRC does not match.
+/// crc_type: 0 = CRC-16/CCITT-FALSE, 1 = CRC-32
+/// Returns: the payload length (the frame without its CRC), 0 if nothing arrived before the
+/// timeout, -1 on failure or CRC mismatch, -2 if cancelled
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_readAndVerifyCrc(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    buffer: JByteArray,
+    offset: jint,
+    max_len: jint,
+    crc_type: jint,
+    little_endian: jboolean,
+    idle_gap_ms: jint,
+    timeout_ms: jint,
+) -> jint {
+    if handle == 0 {
+        set_error!("Read and verify CRC failed: port handle is null");
+        return -1;
+    }
+    let Some(crc_type) = CrcType::from_jint(crc_type) else {
+        set_port_error!(
+            handle,
+            format!("Read and verify CRC failed: invalid CRC type {}", crc_type)
+        );
+        return -1;
+    };
+
+    let mut wrapper = lock_port!(handle, Read, "Read and verify CRC failed", -1);
+
+    let mut read_buffer = vec![0u8; max_len.max(0) as usize];
+    let idle_gap = Duration::from_millis(idle_gap_ms.max(0) as u64);
+    let timeout = Duration::from_millis(timeout_ms.max(0) as u64);
+    let bytes_read = match read_until_idle(&mut wrapper, &mut read_buffer, idle_gap, timeout) {
+        Ok(0) => return 0,
+        Ok(n) => n,
+        Err(e) => {
+            set_port_error!(handle, format!("Read and verify CRC failed: {}", e));
+            return read_error_code(&e);
+        }
+    };
+
+    let frame = &read_buffer[..bytes_read];
+    let i8_buffer: Vec<i8> = frame.iter().map(|&b| b as i8).collect();
+    if let Err(e) = env.set_byte_array_region(&buffer, offset, &i8_buffer) {
+        set_port_error!(
+            handle,
+            format!("Read and verify CRC failed: could not write to buffer: {}", e)
+        );
+        return -1;
+    }
+
+    match verify_crc(frame, crc_type, little_endian != 0) {
+        Ok(payload_len) => payload_len as jint,
+        Err(e) => {
+            set_port_error!(handle, format!("Read and verify CRC failed: {}", e));
+            -1
+        }
+    }
+}
+
+/// Find the first occurrence of `pattern` in `data` that ends after `scanned`, the length of
+/// a prefix already searched. Only the last `pattern.len() - 1` bytes of that prefix are looked
+/// at again, for a match straddling it. Returns the index just past the match.
+fn find_pattern_end(data: &[u8], pattern: &[u8], scanned: usize) -> Option<usize> {
+    let start = scanned.saturating_sub(pattern.len().saturating_sub(1));
+    data.get(start..)?
+        .windows(pattern.len())
+        .position(|window| window == pattern)
+        .map(|index| start + index + pattern.len())
+}
+
+/// Read into `buffer` until the data read ends with `pattern`, `buffer` is full, or `timeout`
+/// elapses.
+///
+/// Each read takes whatever is queued, so it can run past the pattern; the bytes after the
+/// match are kept in the port's read residual and returned first by the next reads. Input is
+/// waited for as in read_with_deadline. Returns the number of bytes up to and including the
+/// match and whether it was found; without a match, everything read is returned.
+fn read_until_pattern(
+    wrapper: &mut LockedPort,
+    pattern: &[u8],
+    buffer: &mut [u8],
+    timeout: Duration,
+) -> std::io::Result<(usize, bool)> {
+    let deadline = Instant::now() + timeout;
+    let mut total = 0;
+
+    loop {
+        if total == buffer.len() {
+            return Ok((total, false));
+        }
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Ok((total, false));
+        }
+        match wrapper.read_data_before(&mut buffer[total..], deadline) {
+            Ok(n) => {
+                let scanned = total;
+                total += n;
+                if let Some(end) = find_pattern_end(&buffer[..total], pattern, scanned) {
+                    let past_match = buffer[end..total].iter().copied();
+                    wrapper.read_residual.splice(0..0, past_match);
+                    #[cfg(target_os = "linux")]
+                    wrapper.signal_residual_input();
+                    return Ok((end, true));
+                }
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::TimedOut => return Ok((total, false)),
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Read until the incoming data ends with a multi-byte pattern, `max_len` bytes arrive, or
+/// `timeout_ms` elapses. Bytes read past the pattern are returned by the next reads.
+/// Sets the port's last_read_timed_out flag if the deadline passed before the pattern arrived.
+/// Returns: bytes read, including the pattern if found, -1 on failure, -2 if cancelled
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_readUntilPattern(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    pattern: JByteArray,
+    buffer: JByteArray,
+    offset: jint,
+    max_len: jint,
+    timeout_ms: jint,
+) -> jint {
+    if handle == 0 {
+        set_error!("Read until pattern failed: port handle is null");
+        return -1;
+    }
+
+    let pattern = match env.convert_byte_array(&pattern) {
+        Ok(pattern) if !pattern.is_empty() => pattern,
+        Ok(_) => {
+            set_port_error!(handle, "Read until pattern failed: pattern is empty");
+            return -1;
+        }
+        Err(e) => {
+            set_port_error!(
+                handle,
+                format!("Read until pattern failed: could not read pattern: {}", e)
+            );
+            return -1;
+        }
+    };
+
+    let mut wrapper = lock_port!(handle, Read, "Read until pattern failed", -1);
+    wrapper.last_read_timed_out = false;
+
+    let mut read_buffer = vec![0u8; max_len.max(0) as usize];
+    let timeout = Duration::from_millis(timeout_ms.max(0) as u64);
+    let (bytes_read, found) =
+        match read_until_pattern(&mut wrapper, &pattern, &mut read_buffer, timeout) {
+            Ok(result) => result,
+            Err(e) => {
+                set_port_error!(handle, format!("Read until pattern failed: {}", e));
+                return read_error_code(&e);
+            }
+        };
+    wrapper.last_read_timed_out = !found && bytes_read < read_buffer.len();
+
+    if bytes_read > 0 {
+        let i8_buffer: Vec<i8> = read_buffer[..bytes_read].iter().map(|&b| b as i8).collect();
+        if let Err(e) = env.set_byte_array_region(&buffer, offset, &i8_buffer) {
+            set_port_error!(
+                handle,
+                format!("Read until pattern failed: could not write to buffer: {}", e)
+            );
+            return -1;
+        }
+    }
+
+    bytes_read as jint
+}
+
+/// Layout of a frame that starts with a fixed-size header holding the frame length
+#[derive(Debug, Clone, Copy)]
+struct LengthPrefix {
+    header_len: usize,
+    field_offset: usize,
+    field_size: usize,
+    big_endian: bool,
+    /// Whether the length field counts the header as well as the payload
+    includes_header: bool,
+}
+
+impl LengthPrefix {
+    /// Check that the length field is 1-4 bytes and lies within the header
+    fn validate(&self) -> Result<(), String> {
+        if !(1..=4).contains(&self.field_size) {
+            return Err(format!("length field size {} is not 1-4 bytes", self.field_size));
+        }
+        if self.field_offset + self.field_size > self.header_len {
+            return Err(format!(
+                "length field at offset {} of {} bytes does not fit in a {}-byte header",
+                self.field_offset, self.field_size, self.header_len
+            ));
+        }
+        Ok(())
+    }
+
+    /// Decode the total frame length, header included, from a complete header
+    fn frame_len(&self, header: &[u8]) -> Result<usize, String> {
+        let field = &header[self.field_offset..self.field_offset + self.field_size];
+        let fold = |value: usize, &byte: &u8| (value << 8) | usize::from(byte);
+        let length = if self.big_endian {
+            field.iter().fold(0, fold)
+        } else {
+            field.iter().rev().fold(0, fold)
+        };
+        if !self.includes_header {
+            return Ok(self.header_len + length);
+        }
+        if length < self.header_len {
+            return Err(format!(
+                "frame length {} is shorter than the {}-byte header",
+                length, self.header_len
+            ));
+        }
+        Ok(length)
+    }
+}
+
+/// Read one length-prefixed frame into `buffer`, which must hold at least the header: the
+/// header first, then as many bytes as its length field announces, all within `timeout`.
+///
+/// A frame longer than `buffer` is rejected with `InvalidData` before its payload is read, and
+/// its header is put back into the port's read residual so the caller can resynchronize.
+/// Returns the number of bytes read and whether the whole frame arrived before the deadline.
+fn read_length_prefixed_frame(
+    wrapper: &mut LockedPort,
+    prefix: &LengthPrefix,
+    buffer: &mut [u8],
+    timeout: Duration,
+) -> std::io::Result<(usize, bool)> {
+    let deadline = Instant::now() + timeout;
+    let header_len = prefix.header_len;
+    let header_read = read_with_deadline(wrapper, &mut buffer[..header_len], timeout)?;
+    if header_read < header_len {
+        return Ok((header_read, false));
+    }
+
+    let frame_len = prefix
+        .frame_len(&buffer[..header_len])
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    if frame_len > buffer.len() {
+        wrapper.read_residual.splice(0..0, buffer[..header_len].iter().copied());
+        #[cfg(target_os = "linux")]
+        wrapper.signal_residual_input();
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("frame of {} bytes exceeds the maximum of {}", frame_len, buffer.len()),
+        ));
+    }
+
+    let remaining = deadline.saturating_duration_since(Instant::now());
+    let body_read = read_with_deadline(wrapper, &mut buffer[header_len..frame_len], remaining)?;
+    let total = header_len + body_read;
+    Ok((total, total == frame_len))
+}
+
+/// Read a frame whose header carries its length: `header_len` bytes are read first, the
+/// `length_field_size`-byte unsigned length at `length_field_offset` is decoded, then exactly
+/// the rest of the frame is read. `includes_header` tells whether the length counts the header.
+/// A frame longer than `max_len` fails without its payload being read; the header is left
+/// unread. Sets the port's last_read_timed_out flag if the deadline cut the frame short.
+/// Returns: bytes read (less than the frame length on timeout), -1 on failure, -2 if cancelled
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_readLengthPrefixedFrame(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    buffer: JByteArray,
+    offset: jint,
+    header_len: jint,
+    length_field_offset: jint,
+    length_field_size: jint,
+    length_is_big_endian: jboolean,
+    includes_header: jboolean,
+    max_len: jint,
+    timeout_ms: jint,
+) -> jint {
+    if handle == 0 {
+        set_error!("Read length-prefixed frame failed: port handle is null");
+        return -1;
+    }
+    if header_len <= 0 || length_field_offset < 0 || max_len < header_len {
+        set_port_error!(handle, format!(
+            "Read length-prefixed frame failed: invalid header_len={}, length_field_offset={} \
+             or max_len={}",
+            header_len, length_field_offset, max_len
+        ));
+        return -1;
+    }
+    let prefix = LengthPrefix {
+        header_len: header_len as usize,
+        field_offset: length_field_offset as usize,
+        field_size: length_field_size.max(0) as usize,
+        big_endian: length_is_big_endian != 0,
+        includes_header: includes_header != 0,
+    };
+    if let Err(e) = prefix.validate() {
+        set_port_error!(handle, format!("Read length-prefixed frame failed: {}", e));
+        return -1;
+    }
+
+    let mut wrapper = lock_port!(handle, Read, "Read length-prefixed frame failed", -1);
+    wrapper.last_read_timed_out = false;
+
+    let mut read_buffer = vec![0u8; max_len as usize];
+    let timeout = Duration::from_millis(timeout_ms.max(0) as u64);
+    let (bytes_read, complete) =
+        match read_length_prefixed_frame(&mut wrapper, &prefix, &mut read_buffer, timeout) {
+            Ok(result) => result,
+            Err(e) => {
+                set_port_error!(handle, format!("Read length-prefixed frame failed: {}", e));
+                return read_error_code(&e);
+            }
+        };
+    wrapper.last_read_timed_out = !complete;
+
+    if bytes_read > 0 {
+        let i8_buffer: Vec<i8> = read_buffer[..bytes_read].iter().map(|&b| b as i8).collect();
+        if let Err(e) = env.set_byte_array_region(&buffer, offset, &i8_buffer) {
+            set_port_error!(
+                handle,
+                format!("Read length-prefixed frame failed: could not write to buffer: {}", e)
+            );
+            return -1;
+        }
+    }
+
+    bytes_read as jint
+}
+
+/// Most complete frames a frame reader holds before dropping the oldest
+const FRAME_QUEUE_CAPACITY: usize = 64;
+
+/// Longest frame a frame reader assembles; a longer burst is split into several frames
+const MAX_FRAME_LEN: usize = 4096;
+
+/// How often an idle frame reader checks whether it should stop
+const FRAME_READER_STOP_POLL: Duration = Duration::from_millis(100);
+
+/// Frames completed by a frame reader thread, waiting for `nextFrame`
+#[derive(Default)]
+struct FrameQueue {
+    frames: VecDeque<Vec<u8>>,
+    /// Frames discarded because the queue was full
+    dropped: u64,
+    /// Read error that stopped the thread
+    error: Option<String>,
+    /// Leading bytes of the queued frames that were captured before the reader took them
+    /// over, as they were read by other means first
+    captured: usize,
+}
+
+/// Background thread that splits the byte stream into frames at idle gaps.
+///
+/// The thread reads from its own clone of the port, so it never touches the wrapper.
+/// A frame ends when no byte arrives for the idle gap after the last one. Dropping the
+/// reader stops the thread and waits for it to exit.
+struct FrameReader {
+    stop: Arc<AtomicBool>,
+    queue: Arc<(Mutex<FrameQueue>, Condvar)>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl FrameReader {
+    /// Start reading frames from `port`. `pending` is input already taken from the port, which
+    /// starts the first frame.
+    fn spawn(
+        mut port: Box<dyn serialport::SerialPort>,
+        idle_gap: Duration,
+        pending: &[u8],
+    ) -> std::io::Result<Self> {
+        let pending = pending.to_vec();
+        let stop = Arc::new(AtomicBool::new(false));
+        let queue = FrameQueue {
+            captured: pending.len(),
+            ..FrameQueue::default()
+        };
+        let queue = Arc::new((Mutex::new(queue), Condvar::new()));
+        let thread_stop = Arc::clone(&stop);
+        let thread_queue = Arc::clone(&queue);
+        let thread = std::thread::Builder::new()
+            .name("jrserial-frame-reader".to_string())
+            .spawn(move || {
+                let (lock, ready) = &*thread_queue;
+                let push = |frame: Vec<u8>| {
+                    let mut queue = lock.lock().unwrap_or_else(|e| e.into_inner());
+                    if queue.frames.len() == FRAME_QUEUE_CAPACITY {
+                        if let Some(oldest) = queue.frames.pop_front() {
+                            queue.captured = queue.captured.saturating_sub(oldest.len());
+                        }
+                        queue.dropped += 1;
+                    }
+                    queue.frames.push_back(frame);
+                    ready.notify_all();
+                };
+
+                let mut frame = Vec::new();
+                for chunk in pending.chunks(MAX_FRAME_LEN) {
+                    frame = chunk.to_vec();
+                    if frame.len() == MAX_FRAME_LEN {
+                        push(std::mem::take(&mut frame));
+                    }
+                }
+                let mut buffer = [0u8; MAX_FRAME_LEN];
+                while !thread_stop.load(Ordering::Acquire) {
+                    // Wait out the idle gap after data, otherwise wake up to check for stop
+                    let timeout = if frame.is_empty() {
+                        FRAME_READER_STOP_POLL
+                    } else {
+                        idle_gap
+                    };
+                    let room = MAX_FRAME_LEN - frame.len();
+                    let result = port
+                        .set_timeout(timeout)
+                        .map_err(std::io::Error::from)
+                        .and_then(|_| detect_eof(port.read(&mut buffer[..room]), room));
+                    match result {
+                        Ok(n) => {
+                            frame.extend_from_slice(&buffer[..n]);
+                            if frame.len() == MAX_FRAME_LEN {
+                                push(std::mem::take(&mut frame));
+                            }
+                        }
+                        Err(e) if e.kind() == std::io::ErrorKind::TimedOut => {
+                            if !frame.is_empty() {
+                                push(std::mem::take(&mut frame));
+                            }
+                        }
+                        Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+                        Err(e) => {
+                            if !frame.is_empty() {
+                                push(frame);
+                            }
+                            let mut queue = lock.lock().unwrap_or_else(|e| e.into_inner());
+                            queue.error = Some(e.to_string());
+                            ready.notify_all();
+                            return;
+                        }
+                    }
+                }
+            })?;
+        Ok(Self {
+            stop,
+            queue,
+            thread: Some(thread),
+        })
+    }
+
+    /// The queue of complete frames, which outlives the reader for a caller waiting on it
+    fn queue(&self) -> Arc<(Mutex<FrameQueue>, Condvar)> {
+        Arc::clone(&self.queue)
+    }
+
+    /// Take the oldest complete frame from a reader's queue, waiting up to `timeout` for one,
+    /// with the number of its leading bytes that were captured already.
+    /// Returns None on timeout, or the error that stopped the thread once the queue is empty.
+    fn next_frame(
+        queue: &(Mutex<FrameQueue>, Condvar),
+        timeout: Duration,
+    ) -> Result<Option<(Vec<u8>, usize)>, String> {
+        let (lock, ready) = queue;
+        let queue = lock.lock().unwrap_or_else(|e| e.into_inner());
+        let (mut queue, _) = ready
+            .wait_timeout_while(queue, timeout, |q| q.frames.is_empty() && q.error.is_none())
+            .unwrap_or_else(|e| e.into_inner());
+        match queue.frames.pop_front() {
+            Some(frame) => {
+                let captured = queue.captured.min(frame.len());
+                queue.captured -= captured;
+                Ok(Some((frame, captured)))
+            }
+            None => queue.error.clone().map_or(Ok(None), Err),
+        }
+    }
+
+    /// Put a frame taken with `next_frame` back at the head of a reader's queue
+    fn unread_frame(queue: &(Mutex<FrameQueue>, Condvar), frame: Vec<u8>, captured: usize) {
+        let (lock, _) = queue;
+        let mut queue = lock.lock().unwrap_or_else(|e| e.into_inner());
+        queue.frames.push_front(frame);
+        queue.captured += captured;
+    }
+
+    fn dropped(&self) -> u64 {
+        let (lock, _) = &*self.queue;
+        lock.lock().unwrap_or_else(|e| e.into_inner()).dropped
+    }
+}
+
+impl Drop for FrameReader {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Release);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+        // Wake up a nextFrame still waiting on the queue
+        let (lock, ready) = &*self.queue;
+        let mut queue = lock.lock().unwrap_or_else(|e| e.into_inner());
+        queue.error.get_or_insert_with(|| "frame reader was stopped".to_string());
+        ready.notify_all();
+    }
+}
+
+/// Start a background thread that splits incoming data into frames at idle gaps of
+/// `idle_gap_micros`, for retrieval with nextFrame
+/// Returns: 1 on success, 0 on failure or if a frame reader is already running
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_startFrameReader(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    idle_gap_micros: jint,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Start frame reader failed: port handle is null");
+        return 0;
+    }
+    if idle_gap_micros <= 0 {
+        set_port_error!(handle, format!(
+            "Start frame reader failed: invalid idle_gap_micros={}",
+            idle_gap_micros
+        ));
+        return 0;
+    }
+
+    let mut wrapper = lock_port!(handle, "Start frame reader failed", 0);
+    if wrapper.frame_reader.is_some() {
+        set_port_error!(handle, "Start frame reader failed: frame reader is already running");
+        return 0;
+    }
+    let idle_gap = Duration::from_micros(idle_gap_micros as u64);
+    // Bytes kept from a pattern match go to the first frame; frames are queued as received,
+    // before bit reversal
+    let mut pending = wrapper.read_residual.clone();
+    if wrapper.bit_reversal {
+        reverse_bits_in_place(&mut pending);
+    }
+    let reader = wrapper
+        .port
+        .try_clone()
+        .map_err(std::io::Error::from)
+        .and_then(|port| FrameReader::spawn(port, idle_gap, &pending));
+    match reader {
+        Ok(reader) => {
+            wrapper.read_residual.clear();
+            wrapper.frame_reader = Some(reader);
+            1
+        }
+        Err(e) => {
+            set_port_error!(handle, format!("Start frame reader failed: {}", e));
+            0
+        }
+    }
+}
+
+/// Stop the frame reader thread and discard any frames it has queued
+/// Returns: 1 on success (including when no frame reader is running), 0 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_stopFrameReader(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Stop frame reader failed: port handle is null");
+        return 0;
+    }
+
+    let mut wrapper = lock_port!(handle, "Stop frame reader failed", 0);
+    wrapper.stop_frame_reader();
+    1
+}
+
+/// Take the next frame from the frame reader, waiting up to `timeout_ms` for one
+/// A frame longer than `max_len` is left queued and reported as a failure.
+/// Returns: the frame length, 0 on timeout, -1 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_nextFrame(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    buffer: JByteArray,
+    max_len: jint,
+    timeout_ms: jint,
+) -> jint {
+    if handle == 0 {
+        set_error!("Next frame failed: port handle is null");
+        return -1;
+    }
+
+    let mut wrapper = lock_port!(handle, Read, "Next frame failed", -1);
+    let Some(queue) = wrapper.frame_reader.as_ref().map(FrameReader::queue) else {
+        set_port_error!(handle, "Next frame failed: frame reader is not running");
+        return -1;
+    };
+    let timeout = Duration::from_millis(timeout_ms.max(0) as u64);
+    let (frame, captured) = match wrapper.unlocked(|| FrameReader::next_frame(&queue, timeout)) {
+        Ok(Ok(Some(taken))) => taken,
+        Ok(Ok(None)) => return 0,
+        Ok(Err(e)) => {
+            set_port_error!(handle, format!("Next frame failed: frame reader stopped: {}", e));
+            return -1;
+        }
+        Err(e) => {
+            set_port_error!(handle, format!("Next frame failed: {}", e));
+            return -1;
+        }
+    };
+    if frame.len() > max_len.max(0) as usize {
+        set_port_error!(handle, format!(
+            "Next frame failed: frame of {} bytes does not fit in {} bytes",
+            frame.len(),
+            max_len
+        ));
+        FrameReader::unread_frame(&queue, frame, captured);
+        return -1;
+    }
+
+    // The reader thread has no access to the capture, so frames are recorded as they are
+    // taken, as received on the line
+    let mut frame = frame;
+    wrapper.capture_received(&frame[captured..]);
+    if wrapper.bit_reversal {
+        reverse_bits_in_place(&mut frame);
+    }
+    let i8_buffer: Vec<i8> = frame.iter().map(|&b| b as i8).collect();
+    if let Err(e) = env.set_byte_array_region(&buffer, 0, &i8_buffer) {
+        set_port_error!(handle, format!("Next frame failed: could not write to buffer: {}", e));
+        return -1;
+    }
+    frame.len() as jint
+}
+
+/// Get the number of frames the frame reader dropped because its queue was full
+/// Returns: the count (0 if no frame reader is running), or -1 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_getDroppedFrameCount(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jlong {
+    if handle == 0 {
+        set_error!("Get dropped frame count failed: port handle is null");
+        return -1;
+    }
+
+    let wrapper = lock_port!(handle, "Get dropped frame count failed", -1);
+    wrapper.frame_reader.as_ref().map_or(0, |reader| reader.dropped() as jlong)
+}
+
+/// Read a single byte, waiting at most `timeout_ms` (0 = block until a byte arrives)
+/// Sets the port's last_read_timed_out flag on timeout.
+/// Returns: the byte (0-255), -1 on timeout or failure, -2 if cancelled
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_readOne(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    timeout_ms: jint,
+) -> jint {
+    if handle == 0 {
+        set_error!("Read one failed: port handle is null");
+        return -1;
+    }
+
+    let mut wrapper = lock_port!(handle, Read, "Read one failed", -1);
+    wrapper.last_read_timed_out = false;
+
+    let timeout = (timeout_ms > 0).then(|| Duration::from_millis(timeout_ms as u64));
+    match wrapper.read_one(timeout) {
+        Ok(Some(byte)) => byte as jint,
+        Ok(None) => {
+            wrapper.last_read_timed_out = true;
+            -1
+        }
+        Err(e) => {
+            set_port_error!(handle, format!("Read one failed: {}", e));
+            read_error_code(&e)
+        }
+    }
+}
+
+/// Check whether the last readFully or readOne returned early because its deadline elapsed
+/// Returns: 1 if it timed out, 0 otherwise
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_lastReadTimedOut(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Last read timed out failed: port handle is null");
+        return 0;
+    }
+
+    let wrapper = lock_port!(handle, "Last read timed out failed", 0);
+    wrapper.last_read_timed_out as jboolean
+}
+
+/// Result codes of checkAccess
+const ACCESS_OK: jint = 0;
+const ACCESS_NO_SUCH_FILE: jint = 1;
+const ACCESS_NO_PERMISSION: jint = 2;
+
+/// Check whether the current user may open the device for reading and writing.
+#[cfg(target_os = "linux")]
+fn check_port_access(path: &str) -> std::io::Result<jint> {
+    if let Err(e) = std::fs::metadata(path) {
+        return match e.kind() {
+            std::io::ErrorKind::NotFound => Ok(ACCESS_NO_SUCH_FILE),
+            std::io::ErrorKind::PermissionDenied => Ok(ACCESS_NO_PERMISSION),
+            _ => Err(e),
+        };
+    }
+
+    let c_path = std::ffi::CString::new(path)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+    if unsafe { libc::access(c_path.as_ptr(), libc::R_OK | libc::W_OK) } == 0 {
+        return Ok(ACCESS_OK);
+    }
+    let e = std::io::Error::last_os_error();
+    match e.raw_os_error() {
+        Some(libc::EACCES) | Some(libc::EPERM) | Some(libc::EROFS) => Ok(ACCESS_NO_PERMISSION),
+        Some(libc::ENOENT) => Ok(ACCESS_NO_SUCH_FILE),
+        _ => Err(e),
+    }
+}
+
+/// Check whether the current user can open a port, without opening it (Linux only)
+/// Returns: 0 = accessible, 1 = no such file, 2 = no read/write permission,
+/// -1 on failure or if not on Linux
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_checkAccess(
+    mut env: JNIEnv,
+    _class: JClass,
+    port_name: JString,
+) -> jint {
+    let port_name = match jstring_to_string(&mut env, port_name) {
+        Ok(s) => s,
+        Err(e) => {
+            set_error!(format!("Invalid port name: {}", e));
+            return -1;
+        }
+    };
+
+    #[cfg(target_os = "linux")]
+    {
+        match check_port_access(&port_name) {
+            Ok(code) => code,
+            Err(e) => {
+                set_error!(format!("Check access failed for {}: {}", port_name, e));
+                -1
+            }
+        }
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = (port_name, ACCESS_OK, ACCESS_NO_SUCH_FILE, ACCESS_NO_PERMISSION);
+        set_error!("Check access failed: only supported on Linux");
+        -1
+    }
+}
+
+// ============================================================================
+// Port Enumeration with Symlink/PTY/Bluetooth Detection
+// ============================================================================
+
+/// Information about a serial port's device type
+struct PortTypeInfo {
+    is_symlink: bool,
+    is_pseudo_terminal: bool,
+    is_bluetooth: bool,
+    /// True for PTYs, null-modem emulators and virtual COM ports, which have no UART behind
+    /// them and so ignore line timing and RS-485 direction control
+    is_virtual: bool,
+    /// Remote device an RFCOMM port is bound to, if known
+    rfcomm_binding: Option<RfcommBinding>,
+}
+
+/// Port name prefixes of virtual serial port drivers: com0com pairs on Windows (CNCA0/CNCB0)
+/// and tty0tty pairs on Linux (/dev/tnt0)
+const VIRTUAL_PORT_PREFIXES: &[&str] = &["CNCA", "CNCB", "/dev/tnt"];
+
+/// Virtual serial port drivers, as named by the `device/driver` link of a sysfs tty
+#[cfg(any(target_os = "linux", test))]
+const VIRTUAL_TTY_DRIVERS: &[&str] = &["tty0tty"];
+
+/// Check whether a tty is virtual from its sysfs directory (e.g. /sys/class/tty/tnt0).
+/// A tty backed by hardware has a `device` link to the device on its bus; one without
+/// it, or bound to a known virtual driver, is virtual. Unknown ttys are not reported.
+#[cfg(any(target_os = "linux", test))]
+fn is_virtual_tty(sysfs_dir: &std::path::Path) -> bool {
+    if !sysfs_dir.is_dir() {
+        return false;
+    }
+    match std::fs::read_link(sysfs_dir.join("device").join("driver")) {
+        Ok(driver) => driver
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| VIRTUAL_TTY_DRIVERS.contains(&name)),
+        Err(_) => !sysfs_dir.join("device").exists(),
+    }
+}
+
+/// Check whether a port name matches a known virtual serial port driver
+fn has_virtual_port_name(path: &str) -> bool {
+    VIRTUAL_PORT_PREFIXES
+        .iter()
+        .any(|prefix| path.starts_with(prefix))
+}
+
+/// USB-to-serial chip families told apart by getChipset: name, USB vendor ID and Linux driver
+const CHIPSETS: &[(&str, u16, &str)] = &[
+    ("ftdi", 0x0403, "ftdi_sio"),
+    ("cp210x", 0x10c4, "cp210x"),
+    ("ch340", 0x1a86, "ch341"),
+    ("pl2303", 0x067b, "pl2303"),
+];
+
+/// Classify a USB-to-serial adapter by its kernel driver or, failing that, its USB vendor ID.
+/// Returns one of the CHIPSETS names, or "unknown".
+fn classify_chipset(driver: Option<&str>, vid: Option<u16>) -> &'static str {
+    CHIPSETS
+        .iter()
+        .find(|(_, _, chip_driver)| driver == Some(*chip_driver))
+        .or_else(|| CHIPSETS.iter().find(|(_, chip_vid, _)| vid == Some(*chip_vid)))
+        .map_or("unknown", |(name, _, _)| name)
+}
+
+/// Read the driver name and USB vendor ID of the device behind a sysfs tty
+/// (e.g. /sys/class/tty/ttyUSB0). The `device` link points at the USB interface (ttyACM) or
+/// a port below it (ttyUSB); the vendor ID is in `idVendor` of the USB device above them.
+#[cfg(any(target_os = "linux", test))]
+fn read_usb_serial_ids(sysfs_dir: &std::path::Path) -> (Option<String>, Option<u16>) {
+    let device = sysfs_dir.join("device");
+    let driver = std::fs::read_link(device.join("driver"))
+        .ok()
+        .and_then(|driver| driver.file_name()?.to_str().map(str::to_string));
+    let vid = std::fs::canonicalize(&device).ok().and_then(|device| {
+        device
+            .ancestors()
+            .take(3)
+            .find_map(|dir| std::fs::read_to_string(dir.join("idVendor")).ok())
+            .and_then(|vid| u16::from_str_radix(vid.trim(), 16).ok())
+    });
+    (driver, vid)
+}
+
+/// Detect the chipset of the adapter behind a port. On Linux the driver and vendor ID are read
+/// from sysfs, following symlinks such as /dev/serial/by-id; elsewhere only `usb_vid`, the
+/// vendor ID reported by port enumeration, is used.
+fn detect_chipset(port_name: &str, usb_vid: Option<u16>) -> &'static str {
+    #[cfg(target_os = "linux")]
+    {
+        let (driver, vid) = std::fs::canonicalize(port_name)
+            .ok()
+            .and_then(|path| {
+                let name = path.file_name()?.to_owned();
+                Some(read_usb_serial_ids(&std::path::Path::new("/sys/class/tty").join(name)))
+            })
+            .unwrap_or_default();
+        classify_chipset(driver.as_deref(), vid.or(usb_vid))
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = port_name;
+        classify_chipset(None, usb_vid)
+    }
+}
+
+/// Remote Bluetooth device and channel an RFCOMM tty is bound to
+struct RfcommBinding {
+    /// Remote MAC address, e.g. "00:11:22:33:44:55"
+    address: String,
+    channel: u8,
+}
+
+/// Read the binding of an RFCOMM tty from its sysfs directory (e.g. /sys/class/tty/rfcomm0).
+/// The kernel's RFCOMM driver exposes the remote address and channel as `address` and `channel`.
+#[cfg(any(target_os = "linux", test))]
+fn read_rfcomm_binding(sysfs_dir: &std::path::Path) -> Option<RfcommBinding> {
+    let address = std::fs::read_to_string(sysfs_dir.join("address")).ok()?;
+    let channel = std::fs::read_to_string(sysfs_dir.join("channel")).ok()?;
+    let address = address.trim();
+    if address.is_empty() {
+        return None;
+    }
+    Some(RfcommBinding {
+        address: address.to_uppercase(),
+        channel: channel.trim().parse().ok()?,
+    })
+}
+
+/// Check if a path is a symlink and/or resolves to a pseudo-terminal.
+/// Metadata that cannot be read is reported in `warnings` and treated as absent.
+#[cfg(unix)]
+fn get_port_type_info(path: &str, warnings: &mut Vec<String>) -> PortTypeInfo {
+    use std::fs;
+    use std::path::Path;
+
+    let path = Path::new(path);
+
+    // Check if it's a symlink
+    let is_symlink = match fs::symlink_metadata(path) {
+        Ok(m) => m.file_type().is_symlink(),
+        Err(e) => {
+            warnings.push(format!("{}: could not read metadata: {}", path.display(), e));
+            false
+        }
+    };
+
+    // Get the resolved path (follows symlinks)
+    let resolved_path = if is_symlink {
+        match fs::read_link(path) {
+            Ok(p) => p.to_string_lossy().to_string(),
+            Err(e) => {
+                warnings.push(format!("{}: could not resolve symlink: {}", path.display(), e));
+                String::new()
+            }
+        }
+    } else {
+        path.to_string_lossy().to_string()
+    };
+
+    // Check if the path (or resolved target) is a pseudo-terminal
+    // PTYs are typically /dev/pts/N or /dev/pty*
+    let path_str = path.to_string_lossy();
+    let is_pseudo_terminal = resolved_path.contains("/dev/pts/")
+        || resolved_path.contains("/dev/pt")
+        || path_str.contains("/dev/pts/")
+        || path_str.contains("/dev/pt");
+
+    // Check if this is a Bluetooth serial port
+    // macOS: /dev/cu.Bluetooth-*, /dev/tty.Bluetooth-*
+    // Linux: /dev/rfcomm*
+    let path_lower = path_str.to_lowercase();
+    let is_bluetooth = path_lower.contains("bluetooth")
+        || path_str.starts_with("/dev/rfcomm");
+
+    // For RFCOMM ports, look up the bound remote device (also through symlinks)
+    #[cfg(target_os = "linux")]
+    let rfcomm_binding = Path::new(&resolved_path)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .filter(|name| name.starts_with("rfcomm"))
+        .and_then(|name| read_rfcomm_binding(&Path::new("/sys/class/tty").join(name)));
+    #[cfg(not(target_os = "linux"))]
+    let rfcomm_binding = None;
+
+    // Virtual devices that are not PTYs are recognized by name or, on Linux, by sysfs
+    let is_virtual = is_pseudo_terminal
+        || has_virtual_port_name(&path_str)
+        || has_virtual_port_name(&resolved_path);
+    #[cfg(target_os = "linux")]
+    let is_virtual = is_virtual
+        || Path::new(&resolved_path)
+            .file_name()
+            .is_some_and(|name| is_virtual_tty(&Path::new("/sys/class/tty").join(name)));
+
+    PortTypeInfo {
+        is_symlink,
+        is_pseudo_terminal,
+        is_bluetooth,
+        is_virtual,
+        rfcomm_binding,
+    }
+}
+
+/// On non-Unix platforms, symlink/PTY detection is not applicable
+/// but Bluetooth detection still works via pattern matching
+#[cfg(not(unix))]
+fn get_port_type_info(path: &str, _warnings: &mut Vec<String>) -> PortTypeInfo {
+    let path_lower = path.to_lowercase();
+    let is_bluetooth = path_lower.contains("bluetooth");
+
+    PortTypeInfo {
+        is_symlink: false,
+        is_pseudo_terminal: false,
+        is_bluetooth,
+        is_virtual: has_virtual_port_name(path),
+        rfcomm_binding: None,
+    }
+}
+
+/// Check whether a port is a virtual device rather than a real UART
+/// Detects PTYs (socat pairs), Linux ttys with no hardware device or a virtual driver
+/// (tty0tty), and known virtual COM port names (com0com). Ports that cannot be inspected
+/// are reported as not virtual.
+/// Returns: 1 if the port is virtual, 0 otherwise
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_isVirtualPort(
+    mut env: JNIEnv,
+    _class: JClass,
+    port_name: JString,
+) -> jboolean {
+    let port_name = match jstring_to_string(&mut env, port_name) {
+        Ok(s) => s,
+        Err(e) => {
+            set_error!(format!("Invalid port name: {}", e));
+            return 0;
+        }
+    };
+    let info = get_port_type_info(&port_name, &mut Vec::new());
+    info.is_virtual as jboolean
+}
+
+/// Detect the USB-to-serial chipset of the adapter behind a port
+/// On Linux the kernel driver and USB vendor ID are read from sysfs; on other platforms the
+/// vendor ID reported by port enumeration is used (on Windows it is taken from the device
+/// instance ID, e.g. USB\VID_0403&PID_6001\...).
+/// Returns: "ftdi", "cp210x", "ch340", "pl2303" or "unknown", or null on error
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_getChipset(
+    mut env: JNIEnv,
+    _class: JClass,
+    port_name: JString,
+) -> jstring {
+    let port_name = match jstring_to_string(&mut env, port_name) {
+        Ok(s) => s,
+        Err(e) => {
+            set_error!(format!("Invalid port name: {}", e));
+            return std::ptr::null_mut();
+        }
+    };
+    let usb_vid = serialport::available_ports()
+        .ok()
+        .and_then(|ports| ports.into_iter().find(|p| p.port_name == port_name))
+        .and_then(|p| match p.port_type {
+            SerialPortType::UsbPort(usb) => Some(usb.vid),
+            _ => None,
+        });
+    string_to_jstring(&mut env, detect_chipset(&port_name, usb_vid))
+}
+
+/// List available serial ports with detailed info.
+/// Returns tab-separated lines: name\tsymlink\tpty\tbluetooth\tbt_address\tbt_channel\n
+/// where each flag is "1" or "0", and bt_address/bt_channel are the remote device an
+/// RFCOMM port is bound to (empty when not applicable or unknown)
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_listPorts(
+    mut env: JNIEnv,
+    _class: JClass,
+) -> jstring {
+    set_enumeration_warnings(Vec::new());
+    let ports = match serialport::available_ports() {
+        Ok(ports) => ports,
+        Err(e) => {
+            set_error!(format!("Failed to list ports: {}", e));
+            return std::ptr::null_mut();
+        }
+    };
+
+    let mut warnings = Vec::new();
+    let result: String = ports
+        .iter()
+        .map(|p| {
+            let info = get_port_type_info(&p.port_name, &mut warnings);
+            // Use native Bluetooth detection from serialport-rs, with pattern fallback
+            let is_bluetooth = matches!(p.port_type, SerialPortType::BluetoothPort)
+                || info.is_bluetooth;
+            let (bt_address, bt_channel) = match &info.rfcomm_binding {
+                Some(binding) => (binding.address.clone(), binding.channel.to_string()),
+                None => (String::new(), String::new()),
+            };
+            format!(
+                "{}\t{}\t{}\t{}\t{}\t{}",
+                p.port_name,
+                if info.is_symlink { "1" } else { "0" },
+                if info.is_pseudo_terminal { "1" } else { "0" },
+                if is_bluetooth { "1" } else { "0" },
+                bt_address,
+                bt_channel
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    set_enumeration_warnings(warnings);
+    string_to_jstring(&mut env, &result)
+}
+
+/// Replace the enumeration warnings reported for the current thread
+fn set_enumeration_warnings(warnings: Vec<String>) {
+    ENUMERATION_WARNINGS.with(|w| *w.borrow_mut() = warnings);
+}
+
+/// Get the per-port problems noticed by the last listPorts or listPortsJson on this thread
+/// Ports affected by a warning are still listed, with the unreadable details left out.
+/// Returns: newline-separated warnings, or null if there are none
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_getEnumerationWarnings(
+    mut env: JNIEnv,
+    _class: JClass,
+) -> jstring {
+    let warnings = ENUMERATION_WARNINGS.with(|w| w.borrow().join("\n"));
+    if warnings.is_empty() {
+        return std::ptr::null_mut();
+    }
+    string_to_jstring(&mut env, &warnings)
+}
+
+/// Escape a string for inclusion in a JSON document, including the quotes
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Format an optional string as a JSON value (string or null)
+fn json_optional_string(s: Option<&str>) -> String {
+    s.map(json_string).unwrap_or_else(|| "null".to_string())
+}
+
+/// List available serial ports as a JSON array of objects.
+/// Each object has: name, is_symlink, is_pty, is_bluetooth, is_virtual, type ("usb", "pci",
+/// "bluetooth" or "unknown"), chipset (see getChipset), for USB ports, vid, pid, serial_number,
+/// manufacturer and product (null for other types), and for bound RFCOMM ports,
+/// bluetooth_address and bluetooth_channel (null otherwise).
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_listPortsJson(
+    mut env: JNIEnv,
+    _class: JClass,
+) -> jstring {
+    set_enumeration_warnings(Vec::new());
+    let ports = match serialport::available_ports() {
+        Ok(ports) => ports,
+        Err(e) => {
+            set_error!(format!("Failed to list ports: {}", e));
+            return std::ptr::null_mut();
+        }
+    };
+
+    let mut warnings = Vec::new();
+    let entries: Vec<String> = ports
+        .iter()
+        .map(|p| {
+            let info = get_port_type_info(&p.port_name, &mut warnings);
+            let is_bluetooth = matches!(p.port_type, SerialPortType::BluetoothPort)
+                || info.is_bluetooth;
+            let port_type = match p.port_type {
+                SerialPortType::UsbPort(_) => "usb",
+                SerialPortType::PciPort => "pci",
+                SerialPortType::BluetoothPort => "bluetooth",
+                SerialPortType::Unknown => "unknown",
+            };
+            let usb_vid = match &p.port_type {
+                SerialPortType::UsbPort(usb) => Some(usb.vid),
+                _ => None,
+            };
+            let chipset = detect_chipset(&p.port_name, usb_vid);
+            let usb_fields = match &p.port_type {
+                SerialPortType::UsbPort(usb) => format!(
+                    "\"vid\":{},\"pid\":{},\"serial_number\":{},\"manufacturer\":{},\"product\":{}",
+                    usb.vid,
+                    usb.pid,
+                    json_optional_string(usb.serial_number.as_deref()),
+                    json_optional_string(usb.manufacturer.as_deref()),
+                    json_optional_string(usb.product.as_deref())
+                ),
+                _ => "\"vid\":null,\"pid\":null,\"serial_number\":null,\"manufacturer\":null,\"product\":null"
+                    .to_string(),
+            };
+            let bluetooth_fields = match &info.rfcomm_binding {
+                Some(binding) => format!(
+                    "\"bluetooth_address\":{},\"bluetooth_channel\":{}",
+                    json_string(&binding.address),
+                    binding.channel
+                ),
+                None => "\"bluetooth_address\":null,\"bluetooth_channel\":null".to_string(),
+            };
+            format!(
+                "{{\"name\":{},\"is_symlink\":{},\"is_pty\":{},\"is_bluetooth\":{},\
+                 \"is_virtual\":{},\"type\":{},\"chipset\":{},{},{}}}",
+                json_string(&p.port_name),
+                info.is_symlink,
+                info.is_pseudo_terminal,
+                is_bluetooth,
+                info.is_virtual,
+                json_string(port_type),
+                json_string(chipset),
+                usb_fields,
+                bluetooth_fields
+            )
+        })
+        .collect();
+
+    set_enumeration_warnings(warnings);
+    string_to_jstring(&mut env, &format!("[{}]", entries.join(",")))
+}
+
+/// Find the device path of the USB serial port with the given USB serial number.
+/// Returns null (and sets the last error) if no port or more than one port matches.
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_findPortByUsbSerial(
+    mut env: JNIEnv,
+    _class: JClass,
+    serial_number: JString,
+) -> jstring {
+    let serial_number = match jstring_to_string(&mut env, serial_number) {
+        Ok(s) => s,
+        Err(e) => {
+            set_error!(format!("Invalid USB serial number: {}", e));
+            return std::ptr::null_mut();
+        }
+    };
+
+    let ports = match serialport::available_ports() {
+        Ok(ports) => ports,
+        Err(e) => {
+            set_error!(format!("Failed to list ports: {}", e));
+            return std::ptr::null_mut();
+        }
+    };
+
+    let candidates: Vec<String> = ports
+        .into_iter()
+        .filter(|p| match &p.port_type {
+            SerialPortType::UsbPort(usb) => {
+                usb.serial_number.as_deref() == Some(serial_number.as_str())
+            }
+            _ => false,
+        })
+        .map(|p| p.port_name)
+        .collect();
+
+    match candidates.as_slice() {
+        [port_name] => string_to_jstring(&mut env, port_name),
+        [] => {
+            set_error!(format!(
+                "No USB serial port found with serial number '{}'",
+                serial_number
+            ));
+            std::ptr::null_mut()
+        }
+        _ => {
+            set_error!(format!(
+                "Multiple USB serial ports found with serial number '{}': {}",
+                serial_number,
+                candidates.join(", ")
+            ));
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// Set timeout
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_setTimeout(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    timeout_ms: jint,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Set timeout failed: port handle is null");
+        return 0;
+    }
+
+    let timeout = normalize_timeout_ms(timeout_ms as u64);
+
+    let mut wrapper = lock_port!(handle, "Set timeout failed", 0);
+    match wrapper.port.set_timeout(timeout) {
+        Ok(_) => 1,
+        Err(e) => {
+            set_port_error!(handle, format!("Set timeout failed: {}", e));
+            0
+        }
+    }
+}
+
+/// Clear input buffer
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_clearInput(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Clear input failed: port handle is null");
+        return 0;
+    }
+
+    let mut wrapper = lock_port!(handle, "Clear input failed", 0);
+    match wrapper.clear_input() {
+        Ok(_) => 1,
+        Err(e) => {
+            set_port_error!(handle, format!("Clear input failed: {}", e));
+            0
+        }
+    }
+}
+
+/// Clear output buffer
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_clearOutput(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Clear output failed: port handle is null");
+        return 0;
+    }
+
+    let wrapper = lock_port!(handle, "Clear output failed", 0);
+    match wrapper.port.clear(serialport::ClearBuffer::Output) {
+        Ok(_) => 1,
+        Err(e) => {
+            set_port_error!(handle, format!("Clear output failed: {}", e));
+            0
+        }
+    }
+}
+
+/// Clear both input and output buffers
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_clearAll(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Clear all failed: port handle is null");
+        return 0;
+    }
+
+    let mut wrapper = lock_port!(handle, "Clear all failed", 0);
+    wrapper.read_residual.clear();
+    match wrapper.port.clear(serialport::ClearBuffer::All) {
+        Ok(_) => 1,
+        Err(e) => {
+            set_port_error!(handle, format!("Clear all failed: {}", e));
+            0
+        }
+    }
+}
+
+/// Discard the given buffers, returning how many bytes they held just before.
+/// Bytes that arrive or are written between sampling and clearing are discarded uncounted.
+fn clear_counted(
+    wrapper: &mut PortWrapper,
+    buffer: serialport::ClearBuffer,
+) -> std::io::Result<u64> {
+    let input = match buffer {
+        serialport::ClearBuffer::Output => 0,
+        _ => wrapper.port.bytes_to_read()? + wrapper.read_residual.len() as u32,
+    };
+    let output = match buffer {
+        serialport::ClearBuffer::Input => 0,
+        _ => wrapper.port.bytes_to_write()?,
+    };
+    if !matches!(buffer, serialport::ClearBuffer::Output) {
+        wrapper.read_residual.clear();
+    }
+    wrapper.port.clear(buffer)?;
+    Ok(u64::from(input) + u64::from(output))
+}
+
+/// Clear the input buffer, counting the bytes discarded
+/// Returns: the number of bytes discarded, or -1 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_clearInputCounted(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jlong {
+    if handle == 0 {
+        set_error!("Clear input failed: port handle is null");
+        return -1;
+    }
+
+    let mut wrapper = lock_port!(handle, "Clear input failed", -1);
+    match clear_counted(&mut wrapper, serialport::ClearBuffer::Input) {
+        Ok(discarded) => discarded as jlong,
+        Err(e) => {
+            set_port_error!(handle, format!("Clear input failed: {}", e));
+            -1
+        }
+    }
+}
+
+/// Clear the output buffer, counting the bytes discarded
+/// Returns: the number of bytes discarded, or -1 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_clearOutputCounted(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jlong {
+    if handle == 0 {
+        set_error!("Clear output failed: port handle is null");
+        return -1;
+    }
+
+    let mut wrapper = lock_port!(handle, "Clear output failed", -1);
+    match clear_counted(&mut wrapper, serialport::ClearBuffer::Output) {
+        Ok(discarded) => discarded as jlong,
+        Err(e) => {
+            set_port_error!(handle, format!("Clear output failed: {}", e));
+            -1
+        }
+    }
+}
+
+/// Clear both input and output buffers, counting the bytes discarded
+/// Returns: the number of bytes discarded, or -1 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_clearAllCounted(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jlong {
+    if handle == 0 {
+        set_error!("Clear all failed: port handle is null");
+        return -1;
+    }
+
+    let mut wrapper = lock_port!(handle, "Clear all failed", -1);
+    match clear_counted(&mut wrapper, serialport::ClearBuffer::All) {
+        Ok(discarded) => discarded as jlong,
+        Err(e) => {
+            set_port_error!(handle, format!("Clear all failed: {}", e));
+            -1
+        }
+    }
+}
+
+/// Set RTS (Request To Send) pin state - for manual RS-485 control
+/// Note: This is only needed if you're NOT using automatic RS-485 control
+/// Set to true before transmitting, false after transmitting
+/// Returns: 1 on success, 0 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_setRTS(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    level: jboolean,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Set RTS failed: port handle is null");
+        return 0;
+    }
+
+    let mut wrapper = lock_port!(handle, "Set RTS failed", 0);
+    match wrapper.set_rts(level != 0) {
+        Ok(_) => 1,
+        Err(e) => {
+            set_port_error!(handle, format!("Set RTS failed: {}", e));
+            0
+        }
+    }
+}
+
+/// Set DTR (Data Terminal Ready) pin state - alternative for manual RS-485 control
+/// Note: This is only needed if you're NOT using automatic RS-485 control
+/// Returns: 1 on success, 0 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_setDTR(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    level: jboolean,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Set DTR failed: port handle is null");
+        return 0;
+    }
+
+    let mut wrapper = lock_port!(handle, "Set DTR failed", 0);
+    match wrapper.set_dtr(level != 0) {
+        Ok(_) => 1,
+        Err(e) => {
+            set_port_error!(handle, format!("Set DTR failed: {}", e));
+            0
+        }
+    }
+}
+
+/// Get the last level set on RTS through this library
+/// On Linux, a mismatch with the line state read back from the driver is recorded as a
+/// warning, and a line never set since open reports the driver's state.
+/// Returns: 1 if high, 0 if low, -1 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_getRTS(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jint {
+    if handle == 0 {
+        set_error!("Get RTS failed: port handle is null");
+        return -1;
+    }
+
+    let mut wrapper = lock_port!(handle, "Get RTS failed", -1);
+    match wrapper.output_line_state(false) {
+        Ok(level) => level as jint,
+        Err(e) => {
+            set_port_error!(handle, format!("Get RTS failed: {}", e));
+            -1
+        }
+    }
+}
+
+/// Get the last level set on DTR through this library
+/// On Linux, a mismatch with the line state read back from the driver is recorded as a
+/// warning, and a line never set since open reports the driver's state.
+/// Returns: 1 if high, 0 if low, -1 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_getDTR(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jint {
+    if handle == 0 {
+        set_error!("Get DTR failed: port handle is null");
+        return -1;
+    }
+
+    let mut wrapper = lock_port!(handle, "Get DTR failed", -1);
+    match wrapper.output_line_state(true) {
+        Ok(level) => level as jint,
+        Err(e) => {
+            set_port_error!(handle, format!("Get DTR failed: {}", e));
+            -1
+        }
+    }
+}
+
+/// Pulse RTS or DTR: set it to `assert_level`, hold for `duration_ms`, then restore the
+/// opposite level. Timing is done natively to avoid JNI round trips between the edges.
+/// line: 0 = RTS, 1 = DTR
+/// Returns: 1 on success, 0 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_pulseLine(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    line: jint,
+    assert_level: jboolean,
+    duration_ms: jint,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Pulse line failed: port handle is null");
+        return 0;
+    }
+
+    let mut wrapper = lock_port!(handle, "Pulse line failed", 0);
+    let assert_level = assert_level != 0;
+    let mut set_line = |level: bool| match line {
+        0 => wrapper.set_rts(level),
+        1 => wrapper.set_dtr(level),
+        _ => Err(serialport::Error::new(
+            serialport::ErrorKind::InvalidInput,
+            format!("invalid line {}", line),
+        )),
+    };
+
+    if let Err(e) = set_line(assert_level) {
+        set_port_error!(handle, format!("Pulse line failed: {}", e));
+        return 0;
+    }
+    std::thread::sleep(Duration::from_millis(duration_ms.max(0) as u64));
+    if let Err(e) = set_line(!assert_level) {
+        set_port_error!(handle, format!("Pulse line failed: could not release line: {}", e));
+        return 0;
+    }
+    1
+}
+
+/// Report why writes may be stalled
+/// Returns: 0 = idle, 1 = output buffer not empty, 2 = blocked by hardware flow control (CTS
+/// deasserted), -1 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_getWriteStallReason(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jint {
+    if handle == 0 {
+        set_error!("Get write stall reason failed: port handle is null");
+        return -1;
+    }
+
+    let mut wrapper = lock_port!(handle, "Get write stall reason failed", -1);
+    match write_stall_reason(&mut wrapper) {
+        Ok(reason) => reason,
+        Err(e) => {
+            set_port_error!(handle, format!("Get write stall reason failed: {}", e));
+            -1
+        }
+    }
+}
+
+/// Transmit the XON (START) flow control character with tcflow(TCION) (Linux only)
+/// Returns: 1 on success, 0 on failure or if not on Linux
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_sendXon(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Send XON failed: port handle is null");
+        return 0;
+    }
+
+    let mut port = lock_port!(handle, "Send XON failed", 0);
+    let wrapper = &mut *port;
+    #[cfg(target_os = "linux")]
+    {
+        match wrapper.send_flow_char(true) {
+            Ok(_) => 1,
+            Err(e) => {
+                set_port_error!(handle, format!("Send XON failed: {}", e));
+                0
+            }
+        }
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = wrapper;
+        set_port_error!(handle, "Send XON failed: only supported on Linux");
+        0
+    }
+}
+
+/// Transmit the XOFF (STOP) flow control character with tcflow(TCIOFF) (Linux only)
+/// Returns: 1 on success, 0 on failure or if not on Linux
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_sendXoff(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Send XOFF failed: port handle is null");
+        return 0;
+    }
+
+    let mut port = lock_port!(handle, "Send XOFF failed", 0);
+    let wrapper = &mut *port;
+    #[cfg(target_os = "linux")]
+    {
+        match wrapper.send_flow_char(false) {
+            Ok(_) => 1,
+            Err(e) => {
+                set_port_error!(handle, format!("Send XOFF failed: {}", e));
+                0
+            }
+        }
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = wrapper;
+        set_port_error!(handle, "Send XOFF failed: only supported on Linux");
+        0
+    }
+}
+
+/// Check whether the last read found the port closed by its peer (end of file)
+/// Returns: 1 if it did, 0 if not or if the handle is null
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_isEof(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jboolean {
+    if handle == 0 {
+        return 0;
+    }
+
+    let Some(wrapper) = lock_port(handle) else {
+        return 0;
+    };
+    wrapper.eof as jboolean
+}
+
+/// Check whether output is held back by XON/XOFF flow control (Linux only)
+/// The output queue is sampled over a few character times, so this blocks briefly.
+/// Returns: 1 if output is stopped, 0 if not, -1 on failure or if not on Linux
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_isOutputStopped(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jint {
+    if handle == 0 {
+        set_error!("Is output stopped failed: port handle is null");
+        return -1;
+    }
+
+    let mut port = lock_port!(handle, "Is output stopped failed", -1);
+    let wrapper = &mut *port;
+    #[cfg(target_os = "linux")]
+    {
+        match wrapper.output_stopped() {
+            Ok(stopped) => stopped as jint,
+            Err(e) => {
+                set_port_error!(handle, format!("Is output stopped failed: {}", e));
+                -1
+            }
+        }
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = wrapper;
+        set_port_error!(handle, "Is output stopped failed: only supported on Linux");
+        -1
+    }
+}
+
+/// Read CTS (Clear To Send) input pin state
+/// Returns: 1 if asserted, 0 if not asserted, -1 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_readCTS(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jint {
+    if handle == 0 {
+        set_error!("Read CTS failed: port handle is null");
+        return -1;
+    }
+
+    let mut wrapper = lock_port!(handle, "Read CTS failed", -1);
+    match wrapper.port.read_clear_to_send() {
+        Ok(level) => level as jint,
+        Err(e) => {
+            set_port_error!(handle, format!("Read CTS failed: {}", e));
+            -1
+        }
+    }
+}
+
+/// Time allowed for a toggled RTS level to propagate to CTS
+const FLOW_CONTROL_SETTLE: Duration = Duration::from_millis(10);
+
+/// Verify RTS/CTS wiring by toggling RTS and checking that CTS follows
+/// Requires RTS looped back to CTS, or a peer that mirrors RTS on its CTS output.
+/// RTS is left asserted afterwards.
+/// Returns: 1 if CTS tracks RTS, 0 if it does not, -1 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_verifyHardwareFlowControl(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jint {
+    if handle == 0 {
+        set_error!("Verify hardware flow control failed: port handle is null");
+        return -1;
+    }
+
+    let mut wrapper = lock_port!(handle, "Verify hardware flow control failed", -1);
+
+    let mut tracks = true;
+    for level in [false, true, false, true] {
+        if let Err(e) = wrapper.set_rts(level) {
+            set_port_error!(handle, format!("Verify hardware flow control failed: could not set RTS: {}", e));
+            return -1;
+        }
+        std::thread::sleep(FLOW_CONTROL_SETTLE);
+        match wrapper.port.read_clear_to_send() {
+            Ok(cts) => tracks &= cts == level,
+            Err(e) => {
+                set_port_error!(handle, format!("Verify hardware flow control failed: could not read CTS: {}", e));
+                return -1;
+            }
+        }
+    }
+
+    tracks as jint
+}
+
+/// Get warnings about kernel RS-485 settings the driver did not apply as requested (Linux only)
+/// Returns: newline-separated warnings, or null if there are none
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_getRs485Warnings(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jstring {
+    if handle == 0 {
+        return std::ptr::null_mut();
+    }
+
+    let Some(wrapper) = lock_port(handle) else {
+        return std::ptr::null_mut();
+    };
+    #[cfg(target_os = "linux")]
+    {
+        let warnings = wrapper.rs485_warnings();
+        if warnings.is_empty() {
+            return std::ptr::null_mut();
+        }
+        string_to_jstring(&mut env, &warnings.join("\n"))
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = (&mut env, wrapper);
+        std::ptr::null_mut()
+    }
+}
+
+/// Get the line settings the driver applied differently than requested when the port was opened
+/// Returns: newline-separated "requested vs applied" descriptions, or null if there are none
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_getConfigDiscrepancies(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jstring {
+    if handle == 0 {
+        return std::ptr::null_mut();
+    }
+
+    let Some(wrapper) = lock_port(handle) else {
+        return std::ptr::null_mut();
+    };
+    if wrapper.config_discrepancies.is_empty() {
+        return std::ptr::null_mut();
+    }
+    string_to_jstring(&mut env, &wrapper.config_discrepancies.join("\n"))
+}
+
+/// Get the baud rate the port was opened at. With a baud tolerance, this may be a standard
+/// rate near the requested one.
+/// Returns: the baud rate, or 0 if the port was opened without the tolerance or the handle is null
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_getOpenedBaudRate(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jint {
+    if handle == 0 {
+        return 0;
+    }
+
+    let Some(wrapper) = lock_port(handle) else {
+        return 0;
+    };
+    wrapper.opened_baud_rate as jint
+}
+
+/// Enable or disable listen-only mode: writes are rejected and the RS-485 direction
+/// pin is kept in receive mode (in kernel mode, SER_RS485_RTS_ON_SEND is cleared)
+/// Returns: 1 on success, 0 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_setListenOnly(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    enabled: jboolean,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Set listen-only failed: port handle is null");
+        return 0;
+    }
+
+    let mut wrapper = lock_port!(handle, "Set listen-only failed", 0);
+    match wrapper.set_listen_only(enabled != 0) {
+        Ok(_) => 1,
+        Err(e) => {
+            set_port_error!(handle, format!("Set listen-only failed: {}", e));
+            0
+        }
+    }
+}
+
+/// Keep the manual RS-485 direction pin asserted between writes, or drain and release it
+/// Rejected in kernel RS-485 mode, where the driver controls RTS.
+/// Returns: 1 on success, 0 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_holdTransmitEnable(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    hold: jboolean,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Hold transmit enable failed: port handle is null");
+        return 0;
+    }
+
+    let mut wrapper = lock_port!(handle, "Hold transmit enable failed", 0);
+    match wrapper.set_hold_transmit_enable(hold != 0) {
+        Ok(_) => 1,
+        Err(e) => {
+            set_port_error!(handle, format!("Hold transmit enable failed: {}", e));
+            0
+        }
+    }
+}
+
+/// Check if the handle still refers to a usable port
+/// Returns: 1 if the handle is non-null and the device is still open and connected, 0 otherwise
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_isValid(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jboolean {
+    if handle == 0 {
+        return 0;
+    }
+
+    let Some(wrapper) = lock_port(handle) else {
+        return 0;
+    };
+    if wrapper.is_valid() { 1 } else { 0 }
+}
+
+/// Start a thread that probes the port every `probe_interval_ms` and reopens it with the
+/// same settings after a disconnect (Linux only). The thread is stopped and joined on close.
+/// Returns: 1 on success, 0 on failure or if not on Linux
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_enableAutoReconnect(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    probe_interval_ms: jint,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Enable auto reconnect failed: port handle is null");
+        return 0;
+    }
+    if probe_interval_ms <= 0 {
+        set_port_error!(handle, "Enable auto reconnect failed: probe interval must be positive");
+        return 0;
+    }
+
+    let mut port = lock_port!(handle, "Enable auto reconnect failed", 0);
+    #[cfg(target_os = "linux")]
+    let lock = port_locker(&port.handle);
+    let wrapper = &mut *port;
+    #[cfg(target_os = "linux")]
+    {
+        let interval = Duration::from_millis(probe_interval_ms as u64);
+        match wrapper.enable_auto_reconnect(interval, lock) {
+            Ok(_) => 1,
+            Err(e) => {
+                set_port_error!(handle, format!("Enable auto reconnect failed: {}", e));
+                0
+            }
+        }
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = wrapper;
+        set_port_error!(handle, "Enable auto reconnect failed: only supported on Linux");
+        0
+    }
+}
+
+/// Stop the automatic reconnect thread, waiting for it to exit (Linux only)
+/// Returns: 1 on success (also if no thread was running), 0 on failure or if not on Linux
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_disableAutoReconnect(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Disable auto reconnect failed: port handle is null");
+        return 0;
+    }
+
+    let mut port = lock_port!(handle, "Disable auto reconnect failed", 0);
+    let wrapper = &mut *port;
+    #[cfg(target_os = "linux")]
+    {
+        wrapper.disable_auto_reconnect();
+        1
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = wrapper;
+        set_port_error!(handle, "Disable auto reconnect failed: only supported on Linux");
+        0
+    }
+}
+
+/// Get the connection state of the port
+/// Returns: 0 = connected, 1 = reconnecting, 2 = failed (disconnected and not being
+/// reconnected), -1 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_getConnectionState(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jint {
+    if handle == 0 {
+        set_error!("Get connection state failed: port handle is null");
+        return -1;
+    }
+
+    let wrapper = lock_port!(handle, "Get connection state failed", -1);
+    wrapper.connection_state() as jint
+}
+
+/// Get the number of ring indicator (RI) edges counted by the driver since the port was
+/// opened (Linux only)
+/// Returns: the count, or -1 on failure, if the driver does not count RI edges, or if not on Linux
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_getRingCount(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jlong {
+    if handle == 0 {
+        set_error!("Get ring count failed: port handle is null");
+        return -1;
+    }
+
+    let wrapper = lock_port!(handle, "Get ring count failed", -1);
+    #[cfg(target_os = "linux")]
+    {
+        match wrapper.ring_count() {
+            Ok(count) => count as jlong,
+            Err(e) => {
+                set_port_error!(handle, format!("Get ring count failed: {}", e));
+                -1
+            }
+        }
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = wrapper;
+        set_port_error!(handle, "Get ring count failed: only supported on Linux");
+        -1
+    }
+}
+
+/// Check if kernel RS-485 mode is active (Linux only)
+/// Returns: 1 if kernel mode is active, 0 otherwise
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_isKernelRs485Active(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jboolean {
+    if handle == 0 {
+        return 0;
+    }
+
+    let Some(wrapper) = lock_port(handle) else {
+        return 0;
+    };
+    #[cfg(target_os = "linux")]
+    {
+        if wrapper.is_kernel_rs485_active() { 1 } else { 0 }
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = wrapper; // Suppress unused warning
+        0 // Kernel RS-485 is only available on Linux
+    }
+}
+
+/// Get how RS-485 direction control is actually performed, which tells why an Auto request
+/// ended up in manual mode
+/// Returns: 0 = None, 1 = KernelAuto, 2 = ManualByFallback (the kernel rejected RS-485 mode or
+/// the platform has none), 3 = ManualByRequest, 4 = ManualByPin (DTR, which the kernel cannot
+/// drive), or -1 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_getRs485ActualMode(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jint {
+    if handle == 0 {
+        set_error!("Get RS-485 actual mode failed: port handle is null");
+        return -1;
+    }
+
+    let wrapper = lock_port!(handle, "Get RS-485 actual mode failed", -1);
+    jint::from(wrapper.rs485_actual_mode())
+}
+
+/// Set RS-485 timing delays (Linux kernel mode only)
+/// delay_before_send_micros: Delay in microseconds before sending (RTS assertion to data)
+/// delay_after_send_micros: Delay in microseconds after sending (data to RTS de-assertion)
+/// Returns: 1 on success, 0 on failure or if not on Linux
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_setRs485Delays(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    delay_before_send_micros: jint,
+    delay_after_send_micros: jint,
+) -> jboolean {
+    if handle == 0 {
+        return 0;
+    }
+
+    let Some(mut port) = lock_port(handle) else {
+        return 0;
+    };
+    let wrapper = &mut *port;
+    #[cfg(target_os = "linux")]
+    {
+        wrapper.set_rs485_delays(delay_before_send_micros as u32, delay_after_send_micros as u32);
+        1
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = (wrapper, delay_before_send_micros, delay_after_send_micros);
+        0 // RS-485 delays only available on Linux with kernel mode
+    }
+}
+
+/// Settings for opening a port with the full configuration, decoded from JNI arguments
+struct OpenSettings {
+    baud_rate: u32,
+    data_bits: DataBits,
+    stop_bits: StopBits,
+    parity: Parity,
+    flow_control: FlowControl,
+    dtr_on_open: bool,
+    timeout: Duration,
+    control_mode: Rs485ControlMode,
+    control_pin: Rs485ControlPin,
+    rts_active_high: bool,
+    rx_during_tx: bool,
+    termination_enabled: bool,
+    delay_before_micros: u32,
+    delay_after_micros: u32,
+    access_mode: AccessMode,
+    /// How far, in percent, a standard rate used when the exact baud rate fails may be off
+    baud_tolerance_percent: u32,
+}
+
+impl OpenSettings {
+    #[allow(clippy::too_many_arguments)]
+    fn from_jni(
+        baud_rate: jint,
+        data_bits: jint,
+        stop_bits: jint,
+        parity: jint,
+        flow_control: jint,
+        dtr_on_open: jboolean,
+        timeout_ms: jint,
+        rs485_mode: jint,
+        rs485_pin: jint,
+        rts_active_high: jboolean,
+        rx_during_tx: jboolean,
+        termination_enabled: jboolean,
+        delay_before_micros: jint,
+        delay_after_micros: jint,
+        access_mode: jint,
+        baud_tolerance_percent: jint,
+    ) -> Result<Self, String> {
+        check_line_settings(data_bits, stop_bits, parity, Some(flow_control))?;
+        if baud_tolerance_percent < 0 {
+            return Err(format!("invalid baud tolerance {}%", baud_tolerance_percent));
+        }
+        Ok(Self {
+            baud_rate: baud_rate as u32,
+            data_bits: data_bits_from_jint(data_bits),
+            stop_bits: stop_bits_from_jint(stop_bits),
+            parity: parity_from_jint(parity),
+            flow_control: flow_control_from_jint(flow_control),
+            dtr_on_open: dtr_on_open != 0,
+            timeout: normalize_timeout_ms(timeout_ms as u64),
+            control_mode: rs485_mode_from_jint(rs485_mode),
+            control_pin: rs485_pin_from_jint(rs485_pin),
+            rts_active_high: rts_active_high != 0,
+            rx_during_tx: rx_during_tx != 0,
+            termination_enabled: termination_enabled != 0,
+            delay_before_micros: delay_before_micros as u32,
+            delay_after_micros: delay_after_micros as u32,
+            access_mode: access_mode_from_jint(access_mode),
+            baud_tolerance_percent: baud_tolerance_percent as u32,
+        })
+    }
+}
+
+/// Standard rates from `rates` within `tolerance_percent` of `requested`, nearest first.
+/// The requested rate itself is left out.
+fn baud_rates_within_tolerance(rates: &[u32], requested: u32, tolerance_percent: u32) -> Vec<u32> {
+    let max_offset = u64::from(requested) * u64::from(tolerance_percent) / 100;
+    let offset = |rate: u32| u64::from(rate.abs_diff(requested));
+    let mut candidates: Vec<u32> = rates
+        .iter()
+        .copied()
+        .filter(|&rate| rate != requested && offset(rate) <= max_offset)
+        .collect();
+    candidates.sort_by_key(|&rate| offset(rate));
+    candidates
+}
+
+/// Open and configure a port, without settling. Records the open failure reason
+/// of the current thread and returns a description of any failure.
+fn open_with_settings(port_name: String, settings: &OpenSettings) -> Result<PortWrapper, String> {
+    if settings.access_mode == AccessMode::ReadOnly
+        && settings.control_mode != Rs485ControlMode::None
+    {
+        return Err("Failed to open port: RS-485 control requires write access".to_string());
+    }
+
+    let port_name = normalize_port_name(port_name);
+    let builder = serialport::new(port_name.as_str(), settings.baud_rate)
+        .data_bits(settings.data_bits)
+        .stop_bits(settings.stop_bits)
+        .parity(settings.parity)
+        .flow_control(settings.flow_control)
+        .timeout(settings.timeout);
+
+    let open = |baud_rate: u32| -> serialport::Result<PortWrapper> {
+        let builder = builder.clone().baud_rate(baud_rate);
+        // Platform-specific port opening; only Linux restricts the access mode at the OS level
+        #[cfg(target_os = "linux")]
+        {
+            let (port, original_termios) = platform::open_capturing_termios(&port_name, || {
+                platform::open_with_access(builder, settings.access_mode)
+            });
+            let mut wrapper = PortWrapper::new(port?);
+            wrapper.original_termios = original_termios;
+            Ok(wrapper)
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            builder.open().map(PortWrapper::new)
+        }
+    };
+
+    // A driver that rejects the exact rate may accept a nearby standard one
+    let mut baud_rate = settings.baud_rate;
+    let mut result = open(baud_rate);
+    if let Err(e) = &result {
+        if settings.baud_tolerance_percent > 0 && open_failure_reason(e) == OPEN_FAILURE_OTHER {
+            let candidates = baud_rates_within_tolerance(
+                &platform::standard_baud_rates(),
+                settings.baud_rate,
+                settings.baud_tolerance_percent,
+            );
+            for candidate in candidates {
+                if let Ok(wrapper) = open(candidate) {
+                    baud_rate = candidate;
+                    result = Ok(wrapper);
+                    break;
+                }
+            }
+        }
+    }
+
+    let mut wrapper = result.map_err(|e| {
+        LAST_OPEN_FAILURE.set(open_failure_reason(&e));
+        format!("Failed to open port: {}", e)
+    })?;
+    if baud_rate != settings.baud_rate {
+        if let Ok(mut warning) = wrapper.last_warning.lock() {
+            *warning = Some(format!(
+                "baud rate {} could not be set, opened at {} instead ({:+.2}%, within the \
+                 tolerance of {}%)",
+                settings.baud_rate,
+                baud_rate,
+                (baud_rate as f64 / settings.baud_rate as f64 - 1.0) * 100.0,
+                settings.baud_tolerance_percent
+            ));
+        }
+    }
+    wrapper.opened_baud_rate = baud_rate;
+    wrapper.access_mode = settings.access_mode;
+    wrapper.config_discrepancies = line_setting_discrepancies(
+        &wrapper,
+        baud_rate,
+        settings.data_bits,
+        settings.stop_bits,
+        settings.parity,
+        settings.flow_control,
+    );
+
+    // Suppress DTR if requested (prevents Arduino reset)
+    if !settings.dtr_on_open {
+        wrapper.set_dtr(false).map_err(|e| format!("Failed to suppress DTR: {}", e))?;
+    }
+
+    // Configure extended RS-485 mode if requested
+    if settings.control_mode != Rs485ControlMode::None {
+        wrapper
+            .configure_rs485_extended(
+                settings.control_mode,
+                settings.control_pin,
+                settings.rts_active_high,
+                settings.rx_during_tx,
+                settings.termination_enabled,
+                settings.delay_before_micros,
+                settings.delay_after_micros,
+            )
+            .map_err(|e| format!("Failed to configure RS-485: {}", e))?;
+    }
+
+    Ok(wrapper)
+}
+
+/// Open a serial port with extended RS-485 configuration
+/// flow_control: 0 = None, 1 = Software (XON/XOFF), 2 = Hardware (RTS/CTS)
+/// dtr_on_open: true to assert DTR on open, false to suppress (for Arduino)
+/// rs485_mode: 0 = None, 1 = Auto, 2 = Manual
+/// rs485_pin: 0 = RTS, 1 = DTR
+/// rts_active_high: true if RTS is active high during transmission
+/// rx_during_tx: true to enable receiving during transmission
+/// termination_enabled: true to enable bus termination
+/// delay_before_micros: delay in microseconds before sending
+/// delay_after_micros: delay in microseconds after sending
+/// access_mode: 0 = read/write, 1 = read-only, 2 = write-only (RS-485 control needs write access)
+/// baud_tolerance_percent: if the baud rate cannot be set, how far off a standard rate opened
+///   instead may be, in percent; 0 to fail instead
+/// settle_ms: time to wait after the port is configured before returning, 0 for none
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_openWithRs485Config(
+    mut env: JNIEnv,
+    _class: JClass,
+    port_name: JString,
+    baud_rate: jint,
+    data_bits: jint,
+    stop_bits: jint,
+    parity: jint,
+    flow_control: jint,
+    dtr_on_open: jboolean,
+    timeout_ms: jint,
+    rs485_mode: jint,
+    rs485_pin: jint,
+    rts_active_high: jboolean,
+    rx_during_tx: jboolean,
+    termination_enabled: jboolean,
+    delay_before_micros: jint,
+    delay_after_micros: jint,
+    access_mode: jint,
+    baud_tolerance_percent: jint,
+    settle_ms: jint,
+) -> jlong {
+    // A failure of this open must not be attributed to an earlier one
+    LAST_OPEN_FAILURE.set(OPEN_FAILURE_OTHER);
+
+    let port_name = match jstring_to_string(&mut env, port_name) {
+        Ok(s) => s,
+        Err(e) => {
+            set_error!(format!("Invalid port name: {}", e));
+            return 0;
+        }
+    };
+
+    let settings = OpenSettings::from_jni(
+        baud_rate,
+        data_bits,
+        stop_bits,
+        parity,
+        flow_control,
+        dtr_on_open,
+        timeout_ms,
+        rs485_mode,
+        rs485_pin,
+        rts_active_high,
+        rx_during_tx,
+        termination_enabled,
+        delay_before_micros,
+        delay_after_micros,
+        access_mode,
+        baud_tolerance_percent,
+    );
+    let settings = match settings {
+        Ok(settings) => settings,
+        Err(e) => {
+            set_error!(format!("Failed to open port: {}", e));
+            return 0;
+        }
+    };
+
+    match open_with_settings(port_name, &settings) {
+        Ok(wrapper) => {
+            settle_after_open(settle_ms);
+            register_port(wrapper)
+        }
+        Err(e) => {
+            set_error!(e);
+            0
+        }
+    }
+}
+
+/// Open several ports with identical settings in one call, as openWithRs485Config does
+/// for one port. A failure does not stop the remaining ports from being opened, and the
+/// ports that did open are left open for the caller to close. The settle time is waited
+/// once, after all ports are open. If any port fails, a single error lists every failure.
+/// handles_out: receives one handle per port name, 0 for each port that failed
+/// Returns: the number of ports opened, or -1 if the arguments could not be read
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_openMany(
+    mut env: JNIEnv,
+    _class: JClass,
+    port_names: JObjectArray,
+    baud_rate: jint,
+    data_bits: jint,
+    stop_bits: jint,
+    parity: jint,
+    flow_control: jint,
+    dtr_on_open: jboolean,
+    timeout_ms: jint,
+    rs485_mode: jint,
+    rs485_pin: jint,
+    rts_active_high: jboolean,
+    rx_during_tx: jboolean,
+    termination_enabled: jboolean,
+    delay_before_micros: jint,
+    delay_after_micros: jint,
+    access_mode: jint,
+    baud_tolerance_percent: jint,
+    settle_ms: jint,
+    handles_out: JLongArray,
+) -> jint {
+    LAST_OPEN_FAILURE.set(OPEN_FAILURE_OTHER);
+
+    let count = match env.get_array_length(&port_names) {
+        Ok(n) => n,
+        Err(e) => {
+            set_error!(format!("Open many failed: could not read port names: {}", e));
+            return -1;
+        }
+    };
+    match env.get_array_length(&handles_out) {
+        Ok(n) if n >= count => {}
+        Ok(n) => {
+            set_error!(format!(
+                "Open many failed: handle array has {} entries for {} ports",
+                n, count
+            ));
+            return -1;
+        }
+        Err(e) => {
+            set_error!(format!("Open many failed: could not read handle array: {}", e));
+            return -1;
+        }
+    }
+
+    let settings = OpenSettings::from_jni(
+        baud_rate,
+        data_bits,
+        stop_bits,
+        parity,
+        flow_control,
+        dtr_on_open,
+        timeout_ms,
+        rs485_mode,
+        rs485_pin,
+        rts_active_high,
+        rx_during_tx,
+        termination_enabled,
+        delay_before_micros,
+        delay_after_micros,
+        access_mode,
+        baud_tolerance_percent,
+    );
+    let settings = match settings {
+        Ok(settings) => settings,
+        Err(e) => {
+            set_error!(format!("Open many failed: {}", e));
+            return -1;
+        }
+    };
+
+    let mut handles: Vec<jlong> = Vec::with_capacity(count as usize);
+    let mut failures = Vec::new();
+    for i in 0..count {
+        let name = env
+            .get_object_array_element(&port_names, i)
+            .map_err(|e| e.to_string())
+            .and_then(|obj| jstring_to_string(&mut env, JString::from(obj)));
+        let (label, result) = match name {
+            Ok(name) => (name.clone(), open_with_settings(name, &settings)),
+            Err(e) => (format!("#{}", i), Err(format!("Invalid port name: {}", e))),
+        };
+        match result {
+            Ok(wrapper) => handles.push(register_port(wrapper)),
+            Err(e) => {
+                handles.push(0);
+                failures.push(format!("{}: {}", label, e));
+            }
+        }
+    }
+
+    if handles.iter().any(|&handle| handle != 0) {
+        settle_after_open(settle_ms);
+    }
+    if !failures.is_empty() {
+        set_error!(format!(
+            "Failed to open {} of {} ports: {}",
+            failures.len(),
+            count,
+            failures.join("; ")
+        ));
+    }
+
+    if let Err(e) = env.set_long_array_region(&handles_out, 0, &handles) {
+        // Without the handles, the caller could never close the ports that opened
+        for &handle in &handles {
+            release_port(handle);
+        }
+        set_error!(format!("Open many failed: could not write handles: {}", e));
+        return -1;
+    }
+    handles.iter().filter(|&&handle| handle != 0).count() as jint
+}
+
+/// Probe which baud rates a port's driver accepts. The port is opened briefly without
+/// touching DTR/RTS (as openQuiet does), and its original settings are restored.
+/// Returns: the accepted rates comma-separated, a tab, then 1 if custom rates are
+/// available or 0 if not; null on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_getSupportedBaudRates(
+    mut env: JNIEnv,
+    _class: JClass,
+    port_name: JString,
+) -> jstring {
+    let port_name = match jstring_to_string(&mut env, port_name) {
+        Ok(s) => s,
+        Err(e) => {
+            set_error!(format!("Invalid port name: {}", e));
+            return std::ptr::null_mut();
+        }
+    };
+
+    let builder = serialport::new(normalize_port_name(port_name), 9600).preserve_dtr_on_open();
+
+    #[cfg(target_os = "linux")]
+    let port_result = builder.open_native();
+
+    #[cfg(not(target_os = "linux"))]
+    let port_result = builder.open();
+
+    let mut wrapper = match port_result {
+        Ok(port) => PortWrapper::new(port),
+        Err(e) => {
+            set_error!(format!("Failed to open port: {}", e));
+            return std::ptr::null_mut();
+        }
+    };
+
+    // Closing the port must not drop DTR/RTS either
+    #[cfg(target_os = "linux")]
+    if let Err(e) = wrapper.set_hangup_on_close(false) {
+        set_error!(format!("Failed to clear HUPCL: {}", e));
+        return std::ptr::null_mut();
+    }
+
+    match wrapper.probe_baud_rates() {
+        Ok((rates, custom)) => {
+            let rates: Vec<String> = rates.iter().map(|rate| rate.to_string()).collect();
+            string_to_jstring(&mut env, &format!("{}\t{}", rates.join(","), custom as u8))
+        }
+        Err(e) => {
+            set_error!(format!("Failed to probe baud rates: {}", e));
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// Open a serial port without driving the DTR/RTS control lines
+/// flow_control: 0 = None, 1 = Software (XON/XOFF), 2 = Hardware (RTS/CTS)
+/// settle_ms: time to wait after the port is configured before returning, 0 for none
+///
+/// No RS-485 control is configured and DTR is left in whatever state the
+/// operating system applies on open; that state is not checked. On Linux, HUPCL
+/// is also cleared so that closing the port does not drop DTR/RTS, which keeps
+/// repeated open/close cycles from pulsing the lines.
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_openQuiet(
+    mut env: JNIEnv,
+    _class: JClass,
+    port_name: JString,
+    baud_rate: jint,
+    data_bits: jint,
+    stop_bits: jint,
+    parity: jint,
+    flow_control: jint,
+    timeout_ms: jint,
+    settle_ms: jint,
+) -> jlong {
+    // A failure of this open must not be attributed to an earlier one
+    LAST_OPEN_FAILURE.set(OPEN_FAILURE_OTHER);
+
+    let port_name = match jstring_to_string(&mut env, port_name) {
+        Ok(s) => s,
+        Err(e) => {
+            set_error!(format!("Invalid port name: {}", e));
+            return 0;
+        }
+    };
+
+    if let Err(e) = check_line_settings(data_bits, stop_bits, parity, Some(flow_control)) {
+        set_error!(format!("Failed to open port: {}", e));
+        return 0;
+    }
+
+    let data_bits = data_bits_from_jint(data_bits);
+    let stop_bits = stop_bits_from_jint(stop_bits);
+    let parity = parity_from_jint(parity);
+    let flow_control = flow_control_from_jint(flow_control);
+    let timeout = normalize_timeout_ms(timeout_ms as u64);
+
+    let port_name = normalize_port_name(port_name);
+    let builder = serialport::new(port_name.as_str(), baud_rate as u32)
+        .data_bits(data_bits)
+        .stop_bits(stop_bits)
+        .parity(parity)
+        .flow_control(flow_control)
+        .preserve_dtr_on_open()
+        .timeout(timeout);
+
+    // Platform-specific port opening
+    #[cfg(target_os = "linux")]
+    let (port_result, original_termios) =
+        platform::open_capturing_termios(&port_name, || builder.open_native());
+
+    #[cfg(not(target_os = "linux"))]
+    let port_result = builder.open();
+
+    match port_result {
+        Ok(port) => {
+            let mut wrapper = PortWrapper::new(port);
+            #[cfg(target_os = "linux")]
+            {
+                wrapper.original_termios = original_termios;
+            }
+            wrapper.config_discrepancies = line_setting_discrepancies(
+                &wrapper,
+                baud_rate as u32,
+                data_bits,
+                stop_bits,
+                parity,
+                flow_control,
+            );
+
+            #[cfg(target_os = "linux")]
+            if let Err(e) = wrapper.set_hangup_on_close(false) {
+                set_error!(format!("Failed to clear HUPCL: {}", e));
+                return 0;
+            }
+
+            settle_after_open(settle_ms);
+
+            register_port(wrapper)
+        }
+        Err(e) => {
+            LAST_OPEN_FAILURE.set(open_failure_reason(&e));
+            set_error!(format!("Failed to open port: {}", e));
+            0
+        }
+    }
+}
+
+/// Set RS-485 configuration at runtime
+/// enabled: true to enable RS-485 mode
+/// rs485_pin: 0 = RTS, 1 = DTR
+/// rts_active_high: true if RTS is active high during transmission
+/// rx_during_tx: true to enable receiving during transmission
+/// termination_enabled: true to enable bus termination
+/// delay_before_micros: delay in microseconds before sending
+/// delay_after_micros: delay in microseconds after sending
+/// Returns: 1 on success, 0 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_setRs485Config(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    enabled: jboolean,
+    rs485_pin: jint,
+    rts_active_high: jboolean,
+    rx_during_tx: jboolean,
+    termination_enabled: jboolean,
+    delay_before_micros: jint,
+    delay_after_micros: jint,
+) -> jboolean {
+    if handle == 0 {
+        return 0;
+    }
+
+    let control_mode = if enabled != 0 {
+        Rs485ControlMode::Auto
+    } else {
+        Rs485ControlMode::None
+    };
+
+    let control_pin = rs485_pin_from_jint(rs485_pin);
+
+    let Some(mut wrapper) = lock_port(handle) else {
+        return 0;
+    };
+    match wrapper.configure_rs485_extended(
+        control_mode,
+        control_pin,
+        rts_active_high != 0,
+        rx_during_tx != 0,
+        termination_enabled != 0,
+        delay_before_micros as u32,
+        delay_after_micros as u32,
+    ) {
+        Ok(_) => 1,
+        Err(e) => {
+            set_port_error!(handle, format!("Failed to set RS-485 config: {}", e));
+            0
+        }
+    }
+}
+
+/// Set how writes react when the port would block
+/// policy: 0 = Block, 1 = Error, 2 = Partial
+/// Returns: 1 on success, 0 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_setWriteBlockingPolicy(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    policy: jint,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Set write blocking policy failed: port handle is null");
+        return 0;
+    }
+
+    let policy = match policy {
+        0 => WriteBlockingPolicy::Block,
+        1 => WriteBlockingPolicy::Error,
+        2 => WriteBlockingPolicy::Partial,
+        _ => {
+            set_port_error!(handle, format!("Set write blocking policy failed: invalid policy {}", policy));
+            return 0;
+        }
+    };
+
+    let mut wrapper = lock_port!(handle, "Set write blocking policy failed", 0);
+    wrapper.write_blocking_policy = policy;
+
+    1
+}
+
+/// Cap how many bytes a single underlying read requests, so reads with a large length
+/// return as soon as some data has arrived
+/// max_chunk: maximum bytes per read, or 0 for no limit
+/// Returns: 1 on success, 0 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_setMaxReadChunk(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    max_chunk: jint,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Set max read chunk failed: port handle is null");
+        return 0;
+    }
+    if max_chunk < 0 {
+        set_port_error!(handle, format!("Set max read chunk failed: invalid size {}", max_chunk));
+        return 0;
+    }
+
+    let mut wrapper = lock_port!(handle, "Set max read chunk failed", 0);
+    let max_chunk = max_chunk as usize;
+    wrapper.max_read_chunk = max_chunk;
+    if max_chunk > 0 && wrapper.read_scratch.len() > max_chunk {
+        // Reads never use more than one chunk of scratch space now
+        wrapper.read_scratch.truncate(max_chunk);
+        wrapper.read_scratch.shrink_to_fit();
+    }
+
+    1
+}
+
+/// Warn when operations that query the input queue find more than `bytes` waiting
+/// The warning is reported through `getLastWarning`.
+/// bytes: the threshold in bytes, or 0 to disable the warning
+/// Returns: 1 on success, 0 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_setOverflowWarnThreshold(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    bytes: jint,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Set overflow warning threshold failed: port handle is null");
+        return 0;
+    }
+    if bytes < 0 {
+        set_port_error!(
+            handle,
+            format!("Set overflow warning threshold failed: invalid threshold {}", bytes)
+        );
+        return 0;
+    }
+
+    let mut wrapper = lock_port!(handle, "Set overflow warning threshold failed", 0);
+    wrapper.overflow_warn_threshold = bytes as u32;
+
+    1
+}
+
+/// Limit how long a write waits for its output to drain before releasing the RS-485 bus.
+/// Output still queued when the timeout passes is discarded and a warning is recorded.
+/// timeout_ms: the drain timeout in milliseconds, or 0 to wait indefinitely
+/// Returns: 1 on success, 0 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_setDrainTimeout(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    timeout_ms: jint,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Set drain timeout failed: port handle is null");
+        return 0;
+    }
+    if timeout_ms < 0 {
+        set_port_error!(
+            handle,
+            format!("Set drain timeout failed: invalid timeout {}", timeout_ms)
+        );
+        return 0;
+    }
+
+    let mut wrapper = lock_port!(handle, "Set drain timeout failed", 0);
+    wrapper.drain_timeout = match timeout_ms {
+        0 => None,
+        ms => Some(Duration::from_millis(ms as u64)),
+    };
+
+    1
+}
+
+/// Pace writes for slow receivers: split writes into chunks of `chunk_bytes`
+/// separated by `gap_micros`. RS-485 direction stays asserted across the chunks.
+/// chunk_bytes: maximum bytes per chunk, or 0 to disable pacing
+/// Returns: 1 on success, 0 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_setWritePacing(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    chunk_bytes: jint,
+    gap_micros: jint,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Set write pacing failed: port handle is null");
+        return 0;
+    }
+    if chunk_bytes < 0 || gap_micros < 0 {
+        set_port_error!(handle, format!(
+            "Set write pacing failed: invalid chunk_bytes={} or gap_micros={}",
+            chunk_bytes, gap_micros
+        ));
+        return 0;
+    }
+
+    let mut wrapper = lock_port!(handle, "Set write pacing failed", 0);
+    wrapper.write_pacing = WritePacing {
+        chunk_bytes: chunk_bytes as usize,
+        gap: Duration::from_micros(gap_micros as u64),
+    };
+
+    1
+}
+
+/// Limit the sustained write rate to `bytes_per_sec`, 0 for unlimited. Writes are delayed as
+/// a whole, before the RS-485 direction pin is asserted, so a frame is never split.
+/// Returns: 1 on success, 0 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_setMaxThroughput(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    bytes_per_sec: jint,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Set max throughput failed: port handle is null");
+        return 0;
+    }
+    if bytes_per_sec < 0 {
+        set_port_error!(
+            handle,
+            format!("Set max throughput failed: invalid bytes_per_sec={}", bytes_per_sec)
+        );
+        return 0;
+    }
+
+    let mut wrapper = lock_port!(handle, "Set max throughput failed", 0);
+    wrapper.throughput_limit = ThroughputLimit::new(bytes_per_sec as u32);
+
+    1
+}
+
+/// Release the RS-485 direction pin (drive it to receive) and record a warning if no write
+/// succeeds within `timeout_ms`; 0 disables the failsafe. Requires manual RS-485 control.
+/// Returns: 1 on success, 0 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_setFailsafe(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    timeout_ms: jint,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Set failsafe failed: port handle is null");
+        return 0;
+    }
+    if timeout_ms < 0 {
+        set_port_error!(handle, format!("Set failsafe failed: invalid timeout_ms={}", timeout_ms));
+        return 0;
+    }
+
+    let mut wrapper = lock_port!(handle, "Set failsafe failed", 0);
+    let timeout = (timeout_ms > 0).then(|| Duration::from_millis(timeout_ms as u64));
+    match wrapper.set_failsafe(timeout) {
+        Ok(()) => 1,
+        Err(e) => {
+            set_port_error!(handle, format!("Set failsafe failed: {}", e));
+            0
+        }
+    }
+}
+
+/// Hold the line idle for `bits` bit periods before each write, 0 to disable. The time is
+/// derived from the current baud rate; with manual RS-485 control the direction pin is
+/// already asserted, so the driver sends the idle (mark) state onto the bus.
+/// Returns: 1 on success, 0 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_setPreambleIdleBits(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    bits: jint,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Set preamble idle bits failed: port handle is null");
+        return 0;
+    }
+    if bits < 0 {
+        set_port_error!(handle, format!("Set preamble idle bits failed: invalid bits={}", bits));
+        return 0;
+    }
+
+    let mut wrapper = lock_port!(handle, "Set preamble idle bits failed", 0);
+    wrapper.preamble_idle_bits = bits as u32;
+
+    1
+}
+
+/// Start recording every read and write to a libpcap file (link type DLT_USER0; each packet
+/// is a direction byte, 0 = received and 1 = transmitted, followed by the data). Replaces
+/// any capture already running.
+/// Returns: 1 on success, 0 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_startCapture(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    file_path: JString,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Start capture failed: port handle is null");
+        return 0;
+    }
+    let file_path = match jstring_to_string(&mut env, file_path) {
+        Ok(s) => s,
+        Err(e) => {
+            set_port_error!(handle, format!("Start capture failed: invalid file path: {}", e));
+            return 0;
+        }
+    };
+
+    let mut wrapper = lock_port!(handle, "Start capture failed", 0);
+    match wrapper.start_capture(&file_path) {
+        Ok(()) => 1,
+        Err(e) => {
+            set_port_error!(handle, format!("Start capture failed: {}: {}", file_path, e));
+            0
+        }
+    }
+}
+
+/// Stop recording and flush the capture file; succeeds if no capture is running
+/// Returns: 1 on success, 0 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_stopCapture(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Stop capture failed: port handle is null");
+        return 0;
+    }
+
+    let mut wrapper = lock_port!(handle, "Stop capture failed", 0);
+    match wrapper.stop_capture() {
+        Ok(()) => 1,
+        Err(e) => {
+            set_port_error!(handle, format!("Stop capture failed: {}", e));
+            0
+        }
+    }
+}
+
+/// Reverse the bit order of every byte written and read (software bit reversal), or stop
+/// doing so. Capture files record the bytes as they are on the wire.
+/// Returns: 1 on success, 0 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_setBitReversal(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    enabled: jboolean,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Set bit reversal failed: port handle is null");
+        return 0;
+    }
+
+    let mut wrapper = lock_port!(handle, "Set bit reversal failed", 0);
+    wrapper.bit_reversal = enabled != 0;
+
+    1
+}
+
+/// Set how many times a read is retried after a transient error (such as a sporadic EIO
+/// from a USB driver) before it fails; 0 disables retrying
+/// Returns: 1 on success, 0 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_setReadRetries(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    count: jint,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Set read retries failed: port handle is null");
+        return 0;
+    }
+    if count < 0 {
+        set_port_error!(handle, format!("Set read retries failed: invalid count={}", count));
+        return 0;
+    }
+
+    let mut wrapper = lock_port!(handle, "Set read retries failed", 0);
+    wrapper.read_retries = count as u32;
+    1
+}
+
+/// Get the number of retries reads have made after transient errors since the port was opened
+/// Returns: the count, or -1 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_getReadRetryCount(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jlong {
+    if handle == 0 {
+        set_error!("Get read retry count failed: port handle is null");
+        return -1;
+    }
+
+    let wrapper = lock_port!(handle, "Get read retry count failed", -1);
+    wrapper.read_retry_count as jlong
+}
+
+/// Enable or disable DTR/DSR handshaking, independently of RTS/CTS flow control (Linux only)
+/// While enabled, DTR is held asserted and each write waits for DSR before sending.
+/// Returns: 1 on success, 0 on failure or if not on Linux
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_setDtrFlowControl(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    enabled: jboolean,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Set DTR flow control failed: port handle is null");
+        return 0;
+    }
+
+    let mut port = lock_port!(handle, "Set DTR flow control failed", 0);
+    let wrapper = &mut *port;
+    #[cfg(target_os = "linux")]
+    {
+        match wrapper.set_dtr_flow_control(enabled != 0) {
+            Ok(_) => 1,
+            Err(e) => {
+                set_port_error!(handle, format!("Set DTR flow control failed: {}", e));
+                0
+            }
+        }
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = (wrapper, enabled);
+        set_port_error!(handle, "Set DTR flow control failed: only supported on Linux");
+        0
+    }
+}
+
+/// Set the direction pin timing for manual RS-485 control, in microseconds.
+/// pre_micros: delay after asserting the direction pin, before sending data
+/// post_micros: delay after the data has been sent, before releasing the direction pin
+/// Unlike the kernel RS-485 delays, these are not rounded to whole milliseconds.
+/// Returns: 1 on success, 0 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_setManualRs485Timing(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    pre_micros: jint,
+    post_micros: jint,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Set manual RS-485 timing failed: port handle is null");
+        return 0;
+    }
+    if pre_micros < 0 || post_micros < 0 {
+        set_port_error!(handle, format!(
+            "Set manual RS-485 timing failed: invalid pre_micros={} or post_micros={}",
+            pre_micros, post_micros
+        ));
+        return 0;
+    }
+
+    let mut wrapper = lock_port!(handle, "Set manual RS-485 timing failed", 0);
+    wrapper.rs485_pre_delay = Duration::from_micros(pre_micros as u64);
+    wrapper.rs485_post_delay = Duration::from_micros(post_micros as u64);
+
+    1
+}
+
+/// Enable or disable clearing the input buffer at the start of each write
+/// Returns: 1 on success, 0 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_setClearInputBeforeWrite(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    enabled: jboolean,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Set clear input before write failed: port handle is null");
+        return 0;
+    }
+
+    let mut wrapper = lock_port!(handle, "Set clear input before write failed", 0);
+    wrapper.clear_input_before_write = enabled != 0;
+
+    1
+}
+
+/// Enable or disable draining the output at the end of each write
+/// With auto-flush off, a manual RS-485 direction pin stays asserted until `flush`.
+/// Returns: 1 on success, 0 on failure
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_setAutoFlush(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    enabled: jboolean,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Set auto flush failed: port handle is null");
+        return 0;
+    }
+
+    let mut wrapper = lock_port!(handle, "Set auto flush failed", 0);
+    match wrapper.set_auto_flush(enabled != 0) {
+        Ok(()) => 1,
+        Err(e) => {
+            set_port_error!(handle, format!("Set auto flush failed: {}", e));
+            0
+        }
+    }
+}
+
+/// Set whether closing the port hangs up (drops DTR/RTS) via the HUPCL termios flag
+/// Returns: 1 on success, 0 on failure or if not on Linux
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_setHangupOnClose(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    enabled: jboolean,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Set hangup on close failed: port handle is null");
+        return 0;
+    }
+
+    let mut port = lock_port!(handle, "Set hangup on close failed", 0);
+    let wrapper = &mut *port;
+    #[cfg(target_os = "linux")]
+    {
+        match wrapper.set_hangup_on_close(enabled != 0) {
+            Ok(_) => 1,
+            Err(e) => {
+                set_port_error!(handle, format!("Set hangup on close failed: {}", e));
+                0
+            }
+        }
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = (wrapper, enabled);
+        set_port_error!(handle, "Set hangup on close failed: only supported on Linux");
+        0
+    }
+}
+
+/// Set whether closing the port restores the termios settings the tty had before it was
+/// opened (Linux only; elsewhere this is accepted and has no effect)
+/// Returns: 1 on success, 0 on failure or if the settings were not captured on open
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_setRestoreOnClose(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    enabled: jboolean,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Set restore on close failed: port handle is null");
+        return 0;
+    }
+
+    let mut port = lock_port!(handle, "Set restore on close failed", 0);
+    let wrapper = &mut *port;
+    #[cfg(target_os = "linux")]
+    {
+        match wrapper.set_restore_on_close(enabled != 0) {
+            Ok(_) => 1,
+            Err(e) => {
+                set_port_error!(handle, format!("Set restore on close failed: {}", e));
+                0
+            }
+        }
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = (wrapper, enabled);
+        1
+    }
+}
+
+/// Set whether the port ignores the modem control lines via the CLOCAL termios flag
+/// CLOCAL is set on open, so 3-wire connections without carrier detect do not block.
+/// Returns: 1 on success, 0 on failure or if not on Linux
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_setIgnoreModemLines(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    ignore: jboolean,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Set ignore modem lines failed: port handle is null");
+        return 0;
+    }
+
+    let mut port = lock_port!(handle, "Set ignore modem lines failed", 0);
+    let wrapper = &mut *port;
+    #[cfg(target_os = "linux")]
+    {
+        match wrapper.set_ignore_modem_lines(ignore != 0) {
+            Ok(_) => 1,
+            Err(e) => {
+                set_port_error!(handle, format!("Set ignore modem lines failed: {}", e));
+                0
+            }
+        }
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = (wrapper, ignore);
+        set_port_error!(handle, "Set ignore modem lines failed: only supported on Linux");
+        0
+    }
+}
+
+/// Move the calling thread to real-time SCHED_FIFO scheduling, or back to normal scheduling
+/// (Linux only). Meant for the thread that reads the port, to reduce scheduling latency.
+/// Returns: 1 on success, 0 on failure (e.g. missing privileges) or if not on Linux
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_setReadThreadRealtime(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    enabled: jboolean,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Set read thread realtime failed: port handle is null");
+        return 0;
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        match platform::set_current_thread_realtime(enabled != 0) {
+            Ok(_) => 1,
+            Err(e) => {
+                set_port_error!(handle, format!("Set read thread realtime failed: {}", e));
+                0
+            }
+        }
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = enabled;
+        set_port_error!(handle, "Set read thread realtime failed: only supported on Linux");
+        0
+    }
+}
+
+/// Set whether the port's input is line-buffered via the ICANON termios flag (Linux only)
+/// Ports are opened with ICANON cleared, so reads return bytes as they arrive.
+/// Returns: 1 on success, 0 on failure or if not on Linux
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_setCanonicalMode(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    canonical: jboolean,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Set canonical mode failed: port handle is null");
+        return 0;
+    }
+
+    let mut port = lock_port!(handle, "Set canonical mode failed", 0);
+    let wrapper = &mut *port;
+    #[cfg(target_os = "linux")]
+    {
+        match wrapper.set_canonical_mode(canonical != 0) {
+            Ok(_) => 1,
+            Err(e) => {
+                set_port_error!(handle, format!("Set canonical mode failed: {}", e));
+                0
+            }
+        }
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = (wrapper, canonical);
+        set_port_error!(handle, "Set canonical mode failed: only supported on Linux");
+        0
+    }
+}
+
+/// Set different input (receive) and output (transmit) baud rates (Linux only)
+/// Returns: 1 on success, 0 on failure, if the driver cannot split the speeds, or if not on
+/// Linux
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_setSplitBaud(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    input_baud: jint,
+    output_baud: jint,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Set split baud failed: port handle is null");
+        return 0;
+    }
+    if input_baud <= 0 || output_baud <= 0 {
+        set_port_error!(handle, format!(
+            "Set split baud failed: invalid input_baud={} or output_baud={}",
+            input_baud, output_baud
+        ));
+        return 0;
+    }
+
+    let mut port = lock_port!(handle, "Set split baud failed", 0);
+    let wrapper = &mut *port;
+    #[cfg(target_os = "linux")]
+    {
+        match wrapper.set_split_baud(input_baud as u32, output_baud as u32) {
+            Ok(_) => 1,
+            Err(e) => {
+                set_port_error!(handle, format!("Set split baud failed: {}", e));
+                0
+            }
+        }
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = wrapper;
+        set_port_error!(handle, "Set split baud failed: only supported on Linux");
+        0
+    }
+}
+
+/// Get the line speeds the driver reports as applied, which can differ from the requested
+/// baud rate when a custom rate is rounded to the nearest clock divisor.
+/// Returns a tab-separated line: input_baud\toutput_baud, or null on failure.
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_getActualBaudRate(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jstring {
+    if handle == 0 {
+        set_error!("Get actual baud rate failed: port handle is null");
+        return std::ptr::null_mut();
+    }
+
+    let wrapper = lock_port!(handle, "Get actual baud rate failed", std::ptr::null_mut());
+    match wrapper.actual_baud_rates() {
+        Ok((input, output)) => string_to_jstring(&mut env, &format!("{}\t{}", input, output)),
+        Err(e) => {
+            set_port_error!(handle, format!("Get actual baud rate failed: {}", e));
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// Change the baud rate, data bits, stop bits and parity of an open port without corrupting
+/// a character in flight: pending output is drained and stale input cleared first. On Linux
+/// the settings are applied in one termios update, and rolled back together if the driver
+/// rejects any of them.
+/// Returns: 1 on success, 0 on failure (the error names the setting that failed)
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_reconfigure(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    baud_rate: jint,
+    data_bits: jint,
+    stop_bits: jint,
+    parity: jint,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Reconfigure failed: port handle is null");
+        return 0;
+    }
+    if baud_rate <= 0 {
+        set_port_error!(handle, format!("Reconfigure failed: invalid baud rate {}", baud_rate));
+        return 0;
+    }
+    if let Err(e) = check_line_settings(data_bits, stop_bits, parity, None) {
+        set_port_error!(handle, format!("Reconfigure failed: {}", e));
+        return 0;
+    }
+    let baud_rate = baud_rate as u32;
+    let data_bits = data_bits_from_jint(data_bits);
+    let stop_bits = stop_bits_from_jint(stop_bits);
+    let parity = parity_from_jint(parity);
+
+    let mut port = lock_port!(handle, "Reconfigure failed", 0);
+    let wrapper = &mut *port;
+    match wrapper.reconfigure(baud_rate, data_bits, stop_bits, parity) {
+        Ok(()) => {
+            let flow_control = wrapper.port.flow_control().unwrap_or(FlowControl::None);
+            wrapper.config_discrepancies = line_setting_discrepancies(
+                wrapper,
+                baud_rate,
+                data_bits,
+                stop_bits,
+                parity,
+                flow_control,
+            );
+            1
+        }
+        Err(e) => {
+            set_port_error!(handle, format!("Reconfigure failed: {}", e));
+            0
+        }
+    }
+}
+
+/// Change the data bits (5-8) of an open port, draining pending output first so a character
+/// in flight is not corrupted. Received input is kept.
+/// Returns: 1 on success, 0 on failure (including if the driver did not apply the setting)
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_setDataBits(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    data_bits: jint,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Set data bits failed: port handle is null");
+        return 0;
+    }
+    if !(5..=8).contains(&data_bits) {
+        set_port_error!(
+            handle,
+            format!("Set data bits failed: invalid data bits {} (expected 5-8)", data_bits)
+        );
+        return 0;
+    }
+    let data_bits = data_bits_from_jint(data_bits);
+
+    let mut wrapper = lock_port!(handle, "Set data bits failed", 0);
+    let description = format!("data bits {}", u8::from(data_bits));
+    let result = wrapper.set_line_setting(
+        &description,
+        data_bits,
+        |port| port.data_bits(),
+        |port, value| port.set_data_bits(value),
+    );
+    match result {
+        Ok(()) => {
+            wrapper.config_discrepancies.retain(|d| !d.starts_with("data bits:"));
+            1
+        }
+        Err(e) => {
+            set_port_error!(handle, format!("Set data bits failed: {}", e));
+            0
+        }
+    }
+}
+
+/// Change the stop bits (1 or 2) of an open port, draining pending output first so a
+/// character in flight is not corrupted. Received input is kept.
+/// Returns: 1 on success, 0 on failure (including if the driver did not apply the setting)
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_setStopBits(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    stop_bits: jint,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Set stop bits failed: port handle is null");
+        return 0;
+    }
+    if !(1..=2).contains(&stop_bits) {
+        set_port_error!(
+            handle,
+            format!("Set stop bits failed: invalid stop bits {} (expected 1 or 2)", stop_bits)
+        );
+        return 0;
+    }
+    let stop_bits = stop_bits_from_jint(stop_bits);
+
+    let mut wrapper = lock_port!(handle, "Set stop bits failed", 0);
+    let description = format!("stop bits {}", u8::from(stop_bits));
+    let result = wrapper.set_line_setting(
+        &description,
+        stop_bits,
+        |port| port.stop_bits(),
+        |port, value| port.set_stop_bits(value),
+    );
+    match result {
+        Ok(()) => {
+            wrapper.config_discrepancies.retain(|d| !d.starts_with("stop bits:"));
+            1
+        }
+        Err(e) => {
+            set_port_error!(handle, format!("Set stop bits failed: {}", e));
+            0
+        }
+    }
+}
+
+/// Change the parity (0 = None, 1 = Odd, 2 = Even) of an open port, draining pending output
+/// first so a character in flight is not corrupted. Received input is kept.
+/// Returns: 1 on success, 0 on failure (including if the driver did not apply the setting)
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_setParity(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    parity: jint,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Set parity failed: port handle is null");
+        return 0;
+    }
+    if !(0..=2).contains(&parity) {
+        set_port_error!(
+            handle,
+            format!("Set parity failed: invalid parity {} (expected 0, 1 or 2)", parity)
+        );
+        return 0;
+    }
+    let parity = parity_from_jint(parity);
+
+    let mut wrapper = lock_port!(handle, "Set parity failed", 0);
+    let description = format!("parity {}", parity);
+    let result = wrapper.set_line_setting(
+        &description,
+        parity,
+        |port| port.parity(),
+        |port, value| port.set_parity(value),
+    );
+    match result {
+        Ok(()) => {
+            wrapper.config_discrepancies.retain(|d| !d.starts_with("parity:"));
+            1
+        }
+        Err(e) => {
+            set_port_error!(handle, format!("Set parity failed: {}", e));
+            0
+        }
+    }
+}
+
+/// Set the raw termios VMIN (minimum bytes per read) and VTIME (timer in tenths of a
+/// second) values (Linux only)
+/// Returns: 1 on success, 0 on failure, out-of-range values, or if not on Linux
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_setVminVtime(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    vmin: jint,
+    vtime: jint,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Set VMIN/VTIME failed: port handle is null");
+        return 0;
+    }
+    let (Ok(vmin), Ok(vtime)) = (u8::try_from(vmin), u8::try_from(vtime)) else {
+        set_port_error!(handle, "Set VMIN/VTIME failed: values must be in the range 0-255");
+        return 0;
+    };
+
+    let mut port = lock_port!(handle, "Set VMIN/VTIME failed", 0);
+    let wrapper = &mut *port;
+    #[cfg(target_os = "linux")]
+    {
+        match wrapper.set_vmin_vtime(vmin, vtime) {
+            Ok(_) => 1,
+            Err(e) => {
+                set_port_error!(handle, format!("Set VMIN/VTIME failed: {}", e));
+                0
+            }
+        }
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = (wrapper, vmin, vtime);
+        set_port_error!(handle, "Set VMIN/VTIME failed: only supported on Linux");
+        0
+    }
+}
+
+/// Switch between raw mode and text output processing (\n to \r\n translation)
+/// The port is in raw mode after open.
+/// Returns: 1 on success, 0 on failure or if not on Linux
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_setRawMode(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    raw: jboolean,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Set raw mode failed: port handle is null");
+        return 0;
+    }
+
+    let mut port = lock_port!(handle, "Set raw mode failed", 0);
+    let wrapper = &mut *port;
+    #[cfg(target_os = "linux")]
+    {
+        match wrapper.set_raw_mode(raw != 0) {
+            Ok(_) => 1,
+            Err(e) => {
+                set_port_error!(handle, format!("Set raw mode failed: {}", e));
+                0
+            }
+        }
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = (wrapper, raw);
+        set_port_error!(handle, "Set raw mode failed: only supported on Linux");
+        0
+    }
+}
+
+/// Configure a custom baud divisor so the line rate is base_baud / divisor (Linux only)
+/// base_baud: 0 keeps the driver's base baud
+/// divisor: 0 clears the custom divisor
+/// Returns: 1 on success, 0 on failure or if not on Linux
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_setCustomDivisor(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    base_baud: jint,
+    divisor: jint,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Set custom divisor failed: port handle is null");
+        return 0;
+    }
+    if base_baud < 0 || divisor < 0 {
+        set_port_error!(handle, format!(
+            "Set custom divisor failed: invalid base_baud={} or divisor={}",
+            base_baud, divisor
+        ));
+        return 0;
+    }
+
+    let mut port = lock_port!(handle, "Set custom divisor failed", 0);
+    let wrapper = &mut *port;
+    #[cfg(target_os = "linux")]
+    {
+        match wrapper.set_custom_divisor(base_baud as u32, divisor as u32) {
+            Ok(_) => 1,
+            Err(e) => {
+                set_port_error!(handle, format!("Set custom divisor failed: {}", e));
+                0
+            }
+        }
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = wrapper;
+        set_port_error!(handle, "Set custom divisor failed: only supported on Linux");
+        0
+    }
+}
+
+/// Get the UART's receive FIFO trigger level in bytes (Linux only, 8250-family UARTs)
+/// Returns: the level, or -1 on failure, if the driver does not expose it, or if not on Linux
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_getFifoTriggerLevel(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jint {
+    if handle == 0 {
+        set_error!("Get FIFO trigger level failed: port handle is null");
+        return -1;
+    }
+
+    let wrapper = lock_port!(handle, "Get FIFO trigger level failed", -1);
+    #[cfg(target_os = "linux")]
+    {
+        match wrapper.fifo_trigger_level() {
+            Ok(level) => level as jint,
+            Err(e) => {
+                set_port_error!(handle, format!("Get FIFO trigger level failed: {}", e));
+                -1
+            }
+        }
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = wrapper;
+        set_port_error!(handle, "Get FIFO trigger level failed: only supported on Linux");
+        -1
+    }
+}
+
+/// Set the UART's receive FIFO trigger level in bytes (Linux only, 8250-family UARTs)
+/// The driver rounds the level down to one the UART supports.
+/// Returns: the applied level, or -1 on failure, if the driver does not expose it, or if not
+/// on Linux
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_setFifoTriggerLevel(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    level: jint,
+) -> jint {
+    if handle == 0 {
+        set_error!("Set FIFO trigger level failed: port handle is null");
+        return -1;
+    }
+    if level <= 0 {
+        set_port_error!(handle, format!("Set FIFO trigger level failed: invalid level={}", level));
+        return -1;
+    }
+
+    let mut port = lock_port!(handle, "Set FIFO trigger level failed", -1);
+    let wrapper = &mut *port;
+    #[cfg(target_os = "linux")]
+    {
+        match wrapper.set_fifo_trigger_level(level as u32) {
+            Ok(applied) => applied as jint,
+            Err(e) => {
+                set_port_error!(handle, format!("Set FIFO trigger level failed: {}", e));
+                -1
+            }
+        }
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = wrapper;
+        set_port_error!(handle, "Set FIFO trigger level failed: only supported on Linux");
+        -1
+    }
+}
+
+/// Set the kernel close timing in hundredths of a second (Linux only)
+/// close_delay: how long DTR/RTS stay low after close before the port can be reopened
+/// closing_wait: how long close waits for output to drain, 0 = forever, 65535 = no wait
+/// Returns: 1 on success, 0 on failure or if not on Linux
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_setCloseTiming(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    close_delay: jint,
+    closing_wait: jint,
+) -> jboolean {
+    if handle == 0 {
+        set_error!("Set close timing failed: port handle is null");
+        return 0;
+    }
+    let (Ok(close_delay), Ok(closing_wait)) = (u16::try_from(close_delay), u16::try_from(closing_wait))
+    else {
+        set_port_error!(handle, format!(
+            "Set close timing failed: invalid close_delay={} or closing_wait={}",
+            close_delay, closing_wait
+        ));
+        return 0;
+    };
+
+    let mut port = lock_port!(handle, "Set close timing failed", 0);
+    let wrapper = &mut *port;
+    #[cfg(target_os = "linux")]
+    {
+        match wrapper.set_close_timing(close_delay, closing_wait) {
+            Ok(_) => 1,
+            Err(e) => {
+                set_port_error!(handle, format!("Set close timing failed: {}", e));
+                0
+            }
+        }
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = (wrapper, close_delay, closing_wait);
+        set_port_error!(handle, "Set close timing failed: only supported on Linux");
+        0
+    }
+}
+
+/// Get the features this native build supports on the current platform, as a bitmask of
+/// `CAPABILITY_*` flags. Features that depend on the system are probed at call time.
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_getCapabilities(
+    _env: JNIEnv,
+    _class: JClass,
+) -> jint {
+    let mut capabilities = 0;
+    // serialport passes any rate through to the driver on these platforms
+    if cfg!(any(target_os = "linux", target_os = "macos", target_os = "windows")) {
+        capabilities |= CAPABILITY_CUSTOM_BAUD_RATE;
+    }
+    if cfg!(target_os = "linux") {
+        capabilities |= CAPABILITY_KERNEL_RS485
+            | CAPABILITY_CUSTOM_DIVISOR
+            | CAPABILITY_AUTO_RECONNECT
+            | CAPABILITY_NINE_BIT
+            | CAPABILITY_MODEM_EVENT_COUNTERS
+            | CAPABILITY_TERMIOS_CONTROL
+            | CAPABILITY_SOFTWARE_FLOW_CONTROL
+            | CAPABILITY_ACCESS_CHECK
+            | CAPABILITY_DTR_FLOW_CONTROL;
+    }
+    #[cfg(target_os = "linux")]
+    {
+        if platform::read_cancellation_available() {
+            capabilities |= CAPABILITY_READ_CANCELLATION;
+        }
+        if platform::realtime_scheduling_allowed() {
+            capabilities |= CAPABILITY_REALTIME_SCHEDULING;
+        }
+    }
+    capabilities
+}
+
+/// Get the native library version and build information
+/// Format: "jrserial <version> (serialport <version>, <target>, git <hash>)"
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_getNativeVersion(
+    mut env: JNIEnv,
+    _class: JClass,
+) -> jstring {
+    let version = format!(
+        "jrserial {} (serialport {}, {}, git {})",
+        env!("CARGO_PKG_VERSION"),
+        env!("JRSERIAL_SERIALPORT_VERSION"),
+        env!("JRSERIAL_TARGET"),
+        env!("JRSERIAL_GIT_HASH")
+    );
+    string_to_jstring(&mut env, &version)
+}
+
+/// Get why the last open on the current thread failed
+/// Returns: 0 = other reason, 1 = port busy, 2 = no such device, 3 = permission denied
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_getLastOpenFailure(
+    _env: JNIEnv,
+    _class: JClass,
+) -> jint {
+    LAST_OPEN_FAILURE.get()
+}
+
 /// Get the last error message from native code.
 /// Returns null if no error has occurred.
 /// The error includes the message and source location (file:line).
 #[no_mangle]
-pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_getLastError(
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_getLastError(
+    mut env: JNIEnv,
+    _class: JClass,
+) -> jstring {
+    match get_last_error_string() {
+        Some(msg) => string_to_jstring(&mut env, &msg),
+        None => std::ptr::null_mut(),
+    }
+}
+
+/// Get the last error recorded for a specific port, regardless of the calling thread.
+/// Returns null if the port has not recorded an error.
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_getLastErrorForHandle(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jstring {
+    if handle == 0 {
+        return std::ptr::null_mut();
+    }
+
+    // Read without the port lock, which a blocked call on another thread may hold
+    let Some(port) = port_handle(handle) else {
+        return std::ptr::null_mut();
+    };
+    let message = match port.last_error.lock() {
+        Ok(last_error) => last_error.as_ref().map(ErrorContext::format),
+        Err(_) => None,
+    };
+    match message {
+        Some(msg) => string_to_jstring(&mut env, &msg),
+        None => std::ptr::null_mut(),
+    }
+}
+
+/// Get the newest error retained for the current thread as separate fields, even if it was
+/// already reported through an exception.
+/// Returns: `message\tfile\tline`, or null if there is none
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_getLastErrorDetails(
+    mut env: JNIEnv,
+    _class: JClass,
+) -> jstring {
+    match get_newest_error_details() {
+        Some(details) => string_to_jstring(&mut env, &details),
+        None => std::ptr::null_mut(),
+    }
+}
+
+/// Get the last error recorded for a specific port as separate fields, from any thread.
+/// Returns: `message\tfile\tline`, or null if the port has not recorded an error
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_getLastErrorDetailsForHandle(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jstring {
+    if handle == 0 {
+        return std::ptr::null_mut();
+    }
+
+    // Read without the port lock, which a blocked call on another thread may hold
+    let Some(port) = port_handle(handle) else {
+        return std::ptr::null_mut();
+    };
+    let details = match port.last_error.lock() {
+        Ok(last_error) => last_error.as_ref().map(ErrorContext::details),
+        Err(_) => None,
+    };
+    match details {
+        Some(details) => string_to_jstring(&mut env, &details),
+        None => std::ptr::null_mut(),
+    }
+}
+
+/// Get and clear the last non-fatal warning recorded for a port, such as lost input
+/// after an overrun (Linux only), an input queue above the overflow warning threshold,
+/// or output discarded by a drain timeout.
+/// Returns null if there is no warning.
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_getLastWarning(
     mut env: JNIEnv,
     _class: JClass,
+    handle: jlong,
 ) -> jstring {
-    match get_last_error_string() {
+    if handle == 0 {
+        return std::ptr::null_mut();
+    }
+
+    let Some(wrapper) = lock_port(handle) else {
+        return std::ptr::null_mut();
+    };
+    let message = match wrapper.last_warning.lock() {
+        Ok(mut last_warning) => last_warning.take(),
+        Err(_) => None,
+    };
+    match message {
         Some(msg) => string_to_jstring(&mut env, &msg),
         None => std::ptr::null_mut(),
     }
 }
 
-/// Clear the last error.
+/// Clear the error history, including the last error.
 #[no_mangle]
 pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_clearLastError(
     _env: JNIEnv,
@@ -912,3 +7928,1028 @@ pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_clearLastError
 ) {
     clear_error();
 }
+
+/// Mark the last error as reported while keeping it in the history.
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_acknowledgeLastError(
+    _env: JNIEnv,
+    _class: JClass,
+) {
+    acknowledge_error();
+}
+
+/// Get all retained errors of the current thread, oldest first, newline-joined.
+/// Returns null if there are none.
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_getErrorHistory(
+    mut env: JNIEnv,
+    _class: JClass,
+) -> jstring {
+    match get_error_history_string() {
+        Some(history) => string_to_jstring(&mut env, &history),
+        None => std::ptr::null_mut(),
+    }
+}
+
+/// Set how many errors are retained per thread (minimum 1)
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_setErrorHistorySize(
+    _env: JNIEnv,
+    _class: JClass,
+    size: jint,
+) {
+    ERROR_HISTORY_SIZE.store(size.max(1) as usize, Ordering::Relaxed);
+}
+
+/// Enable or disable strict validation of the line setting codes passed to the open calls
+#[no_mangle]
+pub extern "system" fn Java_dev_nemecec_jrserial_NativeSerialPort_setStrictConfigValidation(
+    _env: JNIEnv,
+    _class: JClass,
+    enabled: jboolean,
+) {
+    STRICT_CONFIG_VALIDATION.store(enabled != 0, Ordering::Relaxed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_history_keeps_most_recent_errors() {
+        clear_error();
+        for i in 0..DEFAULT_ERROR_HISTORY_SIZE + 2 {
+            set_error!(format!("error {}", i));
+        }
+        let history = get_error_history_string().unwrap();
+        let lines: Vec<&str> = history.lines().collect();
+        assert_eq!(lines.len(), DEFAULT_ERROR_HISTORY_SIZE);
+        assert!(lines[0].starts_with("error 2 "));
+        assert!(get_last_error_string().unwrap().starts_with("error 9 "));
+
+        acknowledge_error();
+        assert!(get_last_error_string().is_none());
+        assert!(get_error_history_string().is_some());
+
+        clear_error();
+        assert!(get_error_history_string().is_none());
+    }
+
+    #[test]
+    fn windows_com_port_name_prefixes_only_high_com_ports() {
+        assert_eq!(windows_com_port_name("COM1"), "COM1");
+        assert_eq!(windows_com_port_name("COM9"), "COM9");
+        assert_eq!(windows_com_port_name("COM10"), r"\\.\COM10");
+        assert_eq!(windows_com_port_name("com256"), r"\\.\com256");
+        assert_eq!(windows_com_port_name(r"\\.\COM10"), r"\\.\COM10");
+        assert_eq!(windows_com_port_name(r"\\.\COM3"), r"\\.\COM3");
+        assert_eq!(windows_com_port_name("/dev/ttyUSB10"), "/dev/ttyUSB10");
+    }
+
+    #[test]
+    fn windows_com_port_name_handles_special_characters() {
+        assert_eq!(windows_com_port_name("CÖM10"), "CÖM10");
+        assert_eq!(windows_com_port_name("CO€10"), "CO€10");
+        assert_eq!(windows_com_port_name("COM1€"), "COM1€");
+        assert_eq!(windows_com_port_name("COM"), "COM");
+        assert_eq!(windows_com_port_name(r"\\.\COM 10"), r"\\.\COM 10");
+        assert_eq!(
+            windows_com_port_name(r"\\?\USB#VID_0403"),
+            r"\\?\USB#VID_0403"
+        );
+        assert_eq!(
+            windows_com_port_name("/dev/serial/by-id/usb Gerät"),
+            "/dev/serial/by-id/usb Gerät"
+        );
+    }
+
+    #[test]
+    fn rfcomm_binding_is_read_from_sysfs() {
+        let dir = std::env::temp_dir().join(format!("jrserial-rfcomm-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        assert!(read_rfcomm_binding(&dir).is_none());
+
+        std::fs::write(dir.join("address"), "00:1a:7d:da:71:13\n").unwrap();
+        std::fs::write(dir.join("channel"), "3\n").unwrap();
+        let binding = read_rfcomm_binding(&dir).unwrap();
+        assert_eq!(binding.address, "00:1A:7D:DA:71:13");
+        assert_eq!(binding.channel, 3);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn virtual_ttys_are_detected_from_sysfs() {
+        use std::os::unix::fs::symlink;
+
+        let root = std::env::temp_dir().join(format!("jrserial-virtual-{}", std::process::id()));
+        let uart = root.join("ttyS0");
+        let null_modem = root.join("tnt0");
+        let bound = root.join("ttyVIRT0");
+        for dir in [&uart, &null_modem, &bound] {
+            std::fs::create_dir_all(dir).unwrap();
+        }
+        std::fs::create_dir_all(root.join("drivers").join("serial8250")).unwrap();
+        std::fs::create_dir_all(root.join("drivers").join("tty0tty")).unwrap();
+        std::fs::create_dir_all(root.join("dev0")).unwrap();
+        std::fs::create_dir_all(root.join("dev1")).unwrap();
+        symlink(root.join("dev0"), uart.join("device")).unwrap();
+        symlink(root.join("drivers/serial8250"), root.join("dev0/driver")).unwrap();
+        symlink(root.join("dev1"), bound.join("device")).unwrap();
+        symlink(root.join("drivers/tty0tty"), root.join("dev1/driver")).unwrap();
+
+        assert!(!is_virtual_tty(&uart));
+        assert!(is_virtual_tty(&null_modem));
+        assert!(is_virtual_tty(&bound));
+        assert!(!is_virtual_tty(&root.join("missing")));
+
+        assert!(has_virtual_port_name("CNCA0"));
+        assert!(has_virtual_port_name("/dev/tnt3"));
+        assert!(!has_virtual_port_name("COM3"));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn throughput_limit_delays_the_write_after_a_large_one() {
+        let start = Instant::now();
+        let mut unlimited = ThroughputLimit::default();
+        unlimited.consume(1_000_000);
+        assert_eq!(unlimited.delay(start), Duration::ZERO);
+
+        let mut limit = ThroughputLimit::new(1000);
+        // A burst of up to 100 ms worth goes out without delay
+        assert_eq!(limit.delay(start), Duration::ZERO);
+        limit.consume(100);
+        assert_eq!(limit.delay(start), Duration::ZERO);
+        // The whole frame is sent, and the next one waits until it is paid for
+        limit.consume(500);
+        assert_eq!(limit.delay(start), Duration::from_millis(500));
+        assert_eq!(limit.delay(start + Duration::from_millis(200)), Duration::from_millis(300));
+        assert_eq!(limit.delay(start + Duration::from_millis(500)), Duration::ZERO);
+        // Idle time only builds up the burst allowance
+        assert_eq!(limit.delay(start + Duration::from_secs(60)), Duration::ZERO);
+        limit.consume(200);
+        assert_eq!(limit.delay(start + Duration::from_secs(60)), Duration::from_millis(100));
+    }
+
+    #[test]
+    fn patterns_are_found_across_read_boundaries() {
+        let crlf: &[u8] = b"\r\n";
+        assert_eq!(find_pattern_end(b"OK\r\nnext", crlf, 0), Some(4));
+        // The first read ended between the two pattern bytes
+        assert_eq!(find_pattern_end(b"OK\r\n", crlf, 3), Some(4));
+        // A match entirely within the already searched prefix is not reported again
+        assert_eq!(find_pattern_end(b"a\r\nb", crlf, 4), None);
+        assert_eq!(find_pattern_end(b"OK\r", crlf, 0), None);
+        assert_eq!(find_pattern_end(b"\x03\x03\x7f", &[0x03, 0x7f], 1), Some(3));
+        assert_eq!(find_pattern_end(b"", crlf, 0), None);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn chipsets_are_classified_from_sysfs_driver_and_vendor_id() {
+        use std::os::unix::fs::symlink;
+
+        let root = std::env::temp_dir().join(format!("jrserial-chipset-{}", std::process::id()));
+        let usb_device = root.join("usb1").join("1-1");
+        let port = usb_device.join("1-1:1.0").join("ttyUSB0");
+        let tty = root.join("ttyUSB0");
+        std::fs::create_dir_all(&port).unwrap();
+        std::fs::create_dir_all(root.join("drivers").join("ch341")).unwrap();
+        std::fs::create_dir_all(&tty).unwrap();
+        std::fs::write(usb_device.join("idVendor"), "1a86\n").unwrap();
+        symlink(&port, tty.join("device")).unwrap();
+        symlink(root.join("drivers/ch341"), port.join("driver")).unwrap();
+
+        let (driver, vid) = read_usb_serial_ids(&tty);
+        assert_eq!(driver.as_deref(), Some("ch341"));
+        assert_eq!(vid, Some(0x1a86));
+        assert_eq!(classify_chipset(driver.as_deref(), vid), "ch340");
+        assert_eq!(read_usb_serial_ids(&root.join("missing")), (None, None));
+
+        assert_eq!(classify_chipset(Some("ftdi_sio"), None), "ftdi");
+        assert_eq!(classify_chipset(Some("cdc_acm"), Some(0x10c4)), "cp210x");
+        assert_eq!(classify_chipset(None, Some(0x067b)), "pl2303");
+        assert_eq!(classify_chipset(Some("cdc_acm"), Some(0x2341)), "unknown");
+        assert_eq!(classify_chipset(None, None), "unknown");
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn open_failures_are_classified() {
+        let io = |kind| serialport::Error::new(serialport::ErrorKind::Io(kind), "");
+        assert_eq!(
+            open_failure_reason(&io(std::io::ErrorKind::NotFound)),
+            OPEN_FAILURE_NOT_FOUND
+        );
+        assert_eq!(
+            open_failure_reason(&io(std::io::ErrorKind::PermissionDenied)),
+            OPEN_FAILURE_PERMISSION
+        );
+        assert_eq!(
+            open_failure_reason(&io(std::io::ErrorKind::ResourceBusy)),
+            OPEN_FAILURE_BUSY
+        );
+        assert_eq!(
+            open_failure_reason(&serialport::Error::new(serialport::ErrorKind::Unknown, "")),
+            OPEN_FAILURE_OTHER
+        );
+
+        #[cfg(unix)]
+        {
+            let e = serialport::new("/dev/jrserial-no-such-port", 9600).open().err().unwrap();
+            assert_eq!(open_failure_reason(&e), OPEN_FAILURE_NOT_FOUND);
+        }
+    }
+
+    #[test]
+    fn modified_utf8_port_names_decode_strictly() {
+        // U+1F50C encoded as a surrogate pair, the way JNI hands it over
+        let encoded = cesu8::to_java_cesu8("/tmp/port \u{1F50C} ä\\x");
+        assert_eq!(
+            cesu8::from_java_cesu8(&encoded).unwrap(),
+            "/tmp/port \u{1F50C} ä\\x"
+        );
+        // Unpaired high surrogate
+        assert!(cesu8::from_java_cesu8(&[0xED, 0xA0, 0x80]).is_err());
+    }
+
+    #[test]
+    fn strict_validation_rejects_unknown_line_settings() {
+        assert!(check_line_settings(9, 3, 7, Some(5)).is_ok());
+
+        STRICT_CONFIG_VALIDATION.store(true, Ordering::Relaxed);
+        let data_bits = check_line_settings(9, 1, 0, Some(0));
+        let stop_bits = check_line_settings(8, 3, 0, Some(0));
+        let parity = check_line_settings(8, 1, 7, None);
+        let flow_control = check_line_settings(8, 1, 0, Some(5));
+        let valid = check_line_settings(7, 2, 2, None);
+        STRICT_CONFIG_VALIDATION.store(false, Ordering::Relaxed);
+
+        assert!(data_bits.unwrap_err().contains("data bits: 9"));
+        assert!(stop_bits.unwrap_err().contains("stop bits: 3"));
+        assert!(parity.unwrap_err().contains("parity: 7"));
+        assert!(flow_control.unwrap_err().contains("flow control: 5"));
+        assert!(valid.is_ok());
+    }
+
+    #[test]
+    fn end_of_file_is_distinct_from_timeout() {
+        use std::io::{Error, ErrorKind};
+
+        assert!(is_eof(&detect_eof(Ok(0), 16)));
+        assert!(is_eof(&detect_eof(Err(ErrorKind::BrokenPipe.into()), 16)));
+        assert_eq!(detect_eof(Ok(0), 0).unwrap(), 0);
+        assert_eq!(detect_eof(Ok(5), 16).unwrap(), 5);
+        let timeout = detect_eof(Err(Error::new(ErrorKind::TimedOut, "timed out")), 16);
+        assert!(!is_eof(&timeout));
+        assert_eq!(timeout.unwrap_err().kind(), ErrorKind::TimedOut);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn unreadable_port_metadata_is_reported_as_warning() {
+        let mut warnings = Vec::new();
+        let info = get_port_type_info("/dev/jrserial-no-such-port", &mut warnings);
+        assert!(!info.is_symlink);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("/dev/jrserial-no-such-port: could not read metadata"));
+
+        warnings.clear();
+        get_port_type_info("/dev/null", &mut warnings);
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn transient_read_errors_are_retried() {
+        use std::io::{Error, ErrorKind};
+
+        // Timeouts and errors without an errno fail at once
+        let (_, retries) = read_with_retries(3, || -> std::io::Result<usize> {
+            Err(Error::from(ErrorKind::TimedOut))
+        });
+        assert_eq!(retries, 0);
+        let (_, retries) = read_with_retries(3, || -> std::io::Result<usize> {
+            Err(Error::from(ErrorKind::Other))
+        });
+        assert_eq!(retries, 0);
+
+        #[cfg(target_os = "linux")]
+        {
+            let mut failures = 2;
+            let (result, retries) = read_with_retries(3, || {
+                if failures > 0 {
+                    failures -= 1;
+                    Err(Error::from_raw_os_error(libc::EIO))
+                } else {
+                    Ok(7)
+                }
+            });
+            assert_eq!(result.unwrap(), 7);
+            assert_eq!(retries, 2);
+
+            // Retries run out
+            let (result, retries) = read_with_retries(1, || -> std::io::Result<usize> {
+                Err(Error::from_raw_os_error(libc::EIO))
+            });
+            assert!(result.is_err());
+            assert_eq!(retries, 1);
+
+            // Vanished devices fail at once
+            assert!(!is_transient_read_error(&Error::from_raw_os_error(libc::ENODEV)));
+        }
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn read_errors_keep_their_errno() {
+        use serialport::TTYPort;
+        use std::os::unix::io::{FromRawFd, IntoRawFd};
+
+        // Reading a directory fails with EISDIR: a real errno, and not a transient one
+        let dir = std::fs::File::open(std::env::temp_dir()).unwrap();
+        let port = unsafe { TTYPort::from_raw_fd(dir.into_raw_fd()) };
+        let mut wrapper = PortWrapper::new(port);
+        wrapper.read_retries = 2;
+
+        let e = wrapper.read_data(&mut [0u8; 8]).unwrap_err();
+        assert_eq!(e.raw_os_error(), Some(libc::EISDIR), "{:?}", e);
+        assert!(!is_transient_read_error(&e));
+        assert_eq!(wrapper.read_retry_count, 0);
+    }
+
+    #[test]
+    fn short_writes_are_continued_until_the_frame_is_complete() {
+        use std::io::{Error, ErrorKind};
+
+        // A driver that accepts at most 3 bytes per call
+        let data = b"0123456789";
+        let mut sent = Vec::new();
+        let written = write_fully(data, |rest| {
+            let n = rest.len().min(3);
+            sent.extend_from_slice(&rest[..n]);
+            Ok(n)
+        })
+        .unwrap();
+        assert_eq!(written, data.len());
+        assert_eq!(sent, data);
+
+        // A write that accepts nothing ends the frame early
+        let mut calls = 0;
+        let written = write_fully(data, |rest| {
+            calls += 1;
+            Ok(if calls == 1 { rest.len().min(4) } else { 0 })
+        })
+        .unwrap();
+        assert_eq!(written, 4);
+
+        // An error is reported only if nothing was written
+        let result = write_fully(data, |_| Err(Error::from(ErrorKind::BrokenPipe)));
+        assert!(result.is_err());
+        let mut calls = 0;
+        let written = write_fully(data, |_| {
+            calls += 1;
+            if calls == 1 {
+                Ok(5)
+            } else {
+                Err(Error::from(ErrorKind::BrokenPipe))
+            }
+        })
+        .unwrap();
+        assert_eq!(written, 5);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn frames_larger_than_the_driver_buffer_are_written_whole() {
+        use serialport::{SerialPort, TTYPort};
+        use std::io::Read;
+        use std::os::unix::io::AsRawFd;
+
+        // The write path of a manual RS-485 frame, without the direction pin a pty lacks
+        let (mut master, mut slave) = TTYPort::pair().unwrap();
+        // Non-blocking, so the driver accepts only what fits in its buffer per write
+        unsafe {
+            let fd = slave.as_raw_fd();
+            libc::fcntl(fd, libc::F_SETFL, libc::fcntl(fd, libc::F_GETFL) | libc::O_NONBLOCK);
+        }
+        let frame: Vec<u8> = (0..1024 * 1024).map(|i| b'A' + (i % 26) as u8).collect();
+        let len = frame.len();
+        let reader = std::thread::spawn(move || {
+            master.set_timeout(Duration::from_secs(2)).unwrap();
+            let mut received = vec![0u8; len];
+            master.read_exact(&mut received).map(|_| received)
+        });
+
+        let mut calls = 0;
+        let timeout = Duration::from_secs(2);
+        let written = write_fully(&frame, |rest| {
+            calls += 1;
+            write_paced(&mut slave, rest, WriteBlockingPolicy::Block, timeout, WritePacing::default())
+        })
+        .unwrap();
+        assert_eq!(written, frame.len());
+        assert!(calls > 1, "the pty took the whole frame in one write");
+        assert_eq!(reader.join().unwrap().unwrap(), frame);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn deadline_writes_stop_at_the_deadline() {
+        use serialport::TTYPort;
+        use std::os::unix::io::AsRawFd;
+
+        // Nobody reads the master, so the write blocks once the pty buffer is full. Non-blocking,
+        // so that a write the pty only has part room for returns rather than waiting in the kernel
+        let (_master, mut slave) = TTYPort::pair().unwrap();
+        unsafe {
+            let fd = slave.as_raw_fd();
+            libc::fcntl(fd, libc::F_SETFL, libc::fcntl(fd, libc::F_GETFL) | libc::O_NONBLOCK);
+        }
+        let data = vec![0x55u8; 1 << 20];
+        let start = Instant::now();
+        let deadline = start + Duration::from_millis(150);
+        let pacing = WritePacing { chunk_bytes: 4096, gap: Duration::ZERO };
+        let written =
+            write_until(&mut slave, &data, WriteBlockingPolicy::Block, pacing, deadline).unwrap();
+        assert!(written < data.len());
+        // Not rounded up to the 100 ms termios granularity
+        assert!(start.elapsed() < Duration::from_millis(200), "{:?}", start.elapsed());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn dmx_breaks_require_8n2_framing() {
+        use serialport::{SerialPort, TTYPort};
+
+        let (_master, mut slave) = TTYPort::pair().unwrap();
+        slave.set_baud_rate(DMX_BAUD_RATE).unwrap();
+        slave.set_data_bits(DataBits::Eight).unwrap();
+        slave.set_parity(Parity::None).unwrap();
+        slave.set_stop_bits(StopBits::One).unwrap();
+        let e = send_dmx_break(&mut slave).unwrap_err();
+        assert_eq!(e.kind(), std::io::ErrorKind::InvalidInput);
+        assert!(e.to_string().ends_with("port is at 250000 baud 8N1"), "{}", e);
+
+        // A pty forces 8 data bits and no parity, so only the stop bits can be varied here
+        slave.set_stop_bits(StopBits::Two).unwrap();
+        send_dmx_break(&mut slave).unwrap();
+    }
+
+    #[test]
+    fn dump_shows_hex_and_ascii_side_by_side() {
+        let dump = format_dump(b"Hello\r\nWorld!", 13, 8);
+        assert_eq!(
+            dump,
+            "00000000  48 65 6c 6c 6f 0d 0a 57  |Hello..W|\n\
+             00000008  6f 72 6c 64 21           |orld!|\n"
+        );
+
+        // Only the first MAX_DUMP_BYTES are rendered
+        let data = vec![0u8; MAX_DUMP_BYTES + 10];
+        let dump = format_dump(&data, data.len(), 16);
+        assert_eq!(dump.lines().count(), MAX_DUMP_BYTES / 16 + 1);
+        assert!(dump.ends_with("... 10 more bytes not shown\n"));
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn handle_survives_concurrent_use_and_close() {
+        use serialport::TTYPort;
+
+        let (master, _slave) = TTYPort::pair().unwrap();
+        let handle = register_port(PortWrapper::new(master));
+
+        let workers: Vec<_> = (0..8)
+            .map(|i| {
+                std::thread::spawn(move || {
+                    let mut calls = 0;
+                    while let Some(mut wrapper) = lock_port(handle) {
+                        if i % 2 == 0 {
+                            let _ = wrapper.port.bytes_to_read();
+                        } else {
+                            let _ = wrapper.port.set_timeout(Duration::from_millis(10));
+                        }
+                        drop(wrapper);
+                        record_port_error(handle, ErrorContext {
+                            message: "stress".to_string(),
+                            file: file!(),
+                            line: line!(),
+                        });
+                        calls += 1;
+                    }
+                    calls
+                })
+            })
+            .collect();
+
+        std::thread::sleep(Duration::from_millis(50));
+        assert!(release_port(handle));
+        for worker in workers {
+            assert!(worker.join().unwrap() > 0);
+        }
+
+        // A stale handle is rejected, however often it is used
+        assert!(lock_port(handle).is_none());
+        assert!(!release_port(handle));
+        record_port_error(handle, ErrorContext {
+            message: "late".to_string(),
+            file: file!(),
+            line: line!(),
+        });
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn reconfigure_keeps_mark_space_parity() {
+        use serialport::TTYPort;
+        use std::os::unix::io::AsRawFd;
+
+        let (_master, slave) = TTYPort::pair().unwrap();
+        let fd = slave.as_raw_fd();
+        let mut wrapper = PortWrapper::new(slave);
+        let cflag = || {
+            let mut termios = unsafe { std::mem::zeroed::<libc::termios>() };
+            assert_eq!(unsafe { libc::tcgetattr(fd, &mut termios) }, 0);
+            termios.c_cflag
+        };
+        let mut termios = unsafe { std::mem::zeroed::<libc::termios>() };
+        assert_eq!(unsafe { libc::tcgetattr(fd, &mut termios) }, 0);
+        termios.c_cflag |= libc::CMSPAR;
+        assert_eq!(unsafe { libc::tcsetattr(fd, libc::TCSANOW, &termios) }, 0);
+
+        wrapper.reconfigure(9600, DataBits::Eight, StopBits::Two, Parity::None).unwrap();
+        assert_ne!(cflag() & libc::CMSPAR, 0);
+        assert_ne!(cflag() & libc::CSTOPB, 0);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn blocked_read_does_not_hold_up_other_calls() {
+        use serialport::TTYPort;
+        use std::io::Read;
+
+        let (mut master, slave) = TTYPort::pair().unwrap();
+        let mut wrapper = PortWrapper::new(slave);
+        wrapper.port.set_timeout(Duration::from_secs(5)).unwrap();
+        let handle = register_port(wrapper);
+
+        let reader = std::thread::spawn(move || {
+            let mut port = lock_port_with_turn(handle, Some(IoTurn::Read)).unwrap();
+            let mut buf = [0u8; 8];
+            port.read_data(&mut buf).map(|n| buf[..n].to_vec())
+        });
+        std::thread::sleep(Duration::from_millis(50));
+
+        // A write goes ahead while the read waits for input
+        let start = Instant::now();
+        lock_port(handle).unwrap().write_rs485(b"out").unwrap();
+        assert!(start.elapsed() < Duration::from_secs(1));
+        let mut echoed = [0u8; 3];
+        master.set_timeout(Duration::from_secs(1)).unwrap();
+        master.read_exact(&mut echoed).unwrap();
+        assert_eq!(&echoed, b"out");
+
+        master.write_all(b"in").unwrap();
+        assert_eq!(reader.join().unwrap().unwrap(), b"in");
+
+        // Closing wakes up a read that is still waiting
+        let reader = std::thread::spawn(move || {
+            let mut port = lock_port_with_turn(handle, Some(IoTurn::Read)).unwrap();
+            port.read_data(&mut [0u8; 8])
+        });
+        std::thread::sleep(Duration::from_millis(50));
+        let start = Instant::now();
+        assert!(release_port(handle));
+        assert!(reader.join().unwrap().is_err());
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn blocked_write_does_not_hold_up_other_calls() {
+        use serialport::TTYPort;
+        use std::io::Read;
+
+        // Nobody reads the master at first, so the write waits for room once the pty is full
+        let (mut master, slave) = TTYPort::pair().unwrap();
+        let mut wrapper = PortWrapper::new(slave);
+        wrapper.port.set_timeout(Duration::from_secs(5)).unwrap();
+        let handle = register_port(wrapper);
+        let data = vec![0x55u8; 1 << 20];
+
+        let frame = data.clone();
+        let writer = std::thread::spawn(move || {
+            let mut port = lock_port_with_turn(handle, Some(IoTurn::Write)).unwrap();
+            port.write_rs485(&frame)
+        });
+        std::thread::sleep(Duration::from_millis(50));
+
+        // Other calls go ahead while the write waits for room
+        let start = Instant::now();
+        lock_port(handle).unwrap().port.bytes_to_read().unwrap();
+        assert!(start.elapsed() < Duration::from_millis(500), "{:?}", start.elapsed());
+        // The descriptor, whose flags the frame reader and failsafe share, stays blocking
+        let flags = unsafe { libc::fcntl(lock_port(handle).unwrap().raw_fd(), libc::F_GETFL) };
+        assert_eq!(flags & libc::O_NONBLOCK, 0);
+
+        let mut received = vec![0u8; data.len()];
+        master.set_timeout(Duration::from_secs(5)).unwrap();
+        master.read_exact(&mut received).unwrap();
+        assert_eq!(writer.join().unwrap().unwrap(), data.len());
+        assert_eq!(received, data);
+
+        // Closing ends a write that is still waiting
+        let frame = data.clone();
+        let writer = std::thread::spawn(move || {
+            let mut port = lock_port_with_turn(handle, Some(IoTurn::Write)).unwrap();
+            port.write_rs485(&frame)
+        });
+        std::thread::sleep(Duration::from_millis(50));
+        let start = Instant::now();
+        assert!(release_port(handle));
+        assert!(writer.join().unwrap().unwrap() < data.len());
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn deadline_reads_return_at_the_deadline() {
+        use serialport::TTYPort;
+        use std::io::Write;
+
+        let (mut master, slave) = TTYPort::pair().unwrap();
+        #[cfg(target_os = "linux")]
+        let wrapper = PortWrapper::new(slave);
+        #[cfg(not(target_os = "linux"))]
+        let wrapper = PortWrapper::new(Box::new(slave));
+        let handle = register_port(wrapper);
+        let mut port = lock_port(handle).unwrap();
+
+        // Not rounded up to the 100 ms termios granularity
+        let mut buf = [0u8; 4];
+        let start = Instant::now();
+        assert_eq!(read_with_deadline(&mut port, &mut buf, Duration::from_millis(130)).unwrap(), 0);
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(130), "{:?}", elapsed);
+        assert!(elapsed < Duration::from_millis(180), "{:?}", elapsed);
+
+        // Bytes arriving in pieces are collected until the buffer is full
+        master.write_all(b"ab").unwrap();
+        let writer = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(30));
+            master.write_all(b"cd").unwrap();
+            master
+        });
+        assert_eq!(read_with_deadline(&mut port, &mut buf, Duration::from_secs(1)).unwrap(), 4);
+        assert_eq!(&buf, b"abcd");
+        drop(port);
+        let _master = writer.join().unwrap();
+        assert!(release_port(handle));
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn only_the_cancel_eventfd_cancels_a_wait() {
+        use serialport::TTYPort;
+        use std::os::unix::io::AsRawFd;
+        use std::os::unix::thread::JoinHandleExt;
+
+        extern "C" fn ignore_signal(_: libc::c_int) {}
+
+        let (_master, slave) = TTYPort::pair().unwrap();
+        let port_fd = slave.as_raw_fd();
+        let cancel_fd = unsafe { libc::eventfd(0, libc::EFD_CLOEXEC | libc::EFD_NONBLOCK) };
+        assert!(cancel_fd >= 0);
+        unsafe {
+            // Without SA_RESTART, a signal fails the poll with EINTR
+            let mut action: libc::sigaction = std::mem::zeroed();
+            action.sa_sigaction = ignore_signal as extern "C" fn(libc::c_int) as usize;
+            libc::sigaction(libc::SIGUSR1, &action, std::ptr::null_mut());
+        }
+
+        // A signal during the wait neither cancels nor cuts the timeout short
+        let start = Instant::now();
+        let waiter = std::thread::spawn(move || platform::wait_readable(port_fd, cancel_fd, 300));
+        std::thread::sleep(Duration::from_millis(50));
+        unsafe {
+            libc::pthread_kill(waiter.as_pthread_t(), libc::SIGUSR1);
+        }
+        let e = waiter.join().unwrap().unwrap_err();
+        assert_eq!(e.kind(), std::io::ErrorKind::TimedOut);
+        assert_eq!(read_error_code(&e), -1);
+        assert!(start.elapsed() >= Duration::from_millis(300));
+
+        // A readable eventfd is reported as a cancellation
+        let one = 1u64;
+        unsafe {
+            libc::write(cancel_fd, &one as *const u64 as *const libc::c_void, 8);
+        }
+        let e = platform::wait_readable(port_fd, cancel_fd, 1000).unwrap_err();
+        assert_eq!(read_error_code(&e), READ_CANCELLED);
+        unsafe {
+            libc::close(cancel_fd);
+        }
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn reconnect_reopens_a_recreated_pty_under_its_old_name() {
+        use serialport::TTYPort;
+        use std::io::Write;
+
+        let (master, slave) = TTYPort::pair().unwrap();
+        let name = slave.name().unwrap();
+        let handle = register_port(PortWrapper::new(slave));
+        {
+            let mut port = lock_port(handle).unwrap();
+            let lock = port_locker(&port.handle);
+            port.enable_auto_reconnect(Duration::from_millis(10), lock).unwrap();
+        }
+        let wait_for_state = |state: u8| {
+            let start = Instant::now();
+            while lock_port(handle).unwrap().connection_state() != state {
+                assert!(start.elapsed() < Duration::from_secs(2), "state {} not reached", state);
+                std::thread::sleep(Duration::from_millis(5));
+            }
+        };
+
+        // Hanging up releases the old tty, so a new pair gets its name back
+        drop(master);
+        wait_for_state(CONNECTION_RECONNECTING);
+        // Tests running alongside may briefly take the name themselves
+        let start = Instant::now();
+        let mut master = loop {
+            let (master, slave) = TTYPort::pair().unwrap();
+            if slave.name().unwrap() == name {
+                break master;
+            }
+            assert!(start.elapsed() < Duration::from_secs(2), "{} was not freed", name);
+            std::thread::sleep(Duration::from_millis(5));
+        };
+        wait_for_state(CONNECTION_CONNECTED);
+
+        master.write_all(b"back").unwrap();
+        let mut port = lock_port_with_turn(handle, Some(IoTurn::Read)).unwrap();
+        port.port.set_timeout(Duration::from_secs(1)).unwrap();
+        let mut buf = [0u8; 4];
+        let n = port.read_data(&mut buf).unwrap();
+        assert_eq!(&buf[..n], &b"back"[..n]);
+        drop(port);
+        assert!(release_port(handle));
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn original_termios_is_restored_on_close_when_enabled() {
+        use serialport::TTYPort;
+
+        let (_master, mut slave) = TTYPort::pair().unwrap();
+        let path = slave.name().unwrap();
+        slave.set_baud_rate(1200).unwrap();
+        let open = || serialport::new(path.as_str(), 38400).open_native();
+
+        let (port, original_termios) = platform::open_capturing_termios(&path, open);
+        let mut wrapper = PortWrapper::new(port.unwrap());
+        assert_eq!(slave.baud_rate().unwrap(), 38400);
+        wrapper.original_termios = original_termios;
+        wrapper.set_restore_on_close(true).unwrap();
+        drop(wrapper);
+        assert_eq!(slave.baud_rate().unwrap(), 1200);
+
+        let (port, original_termios) = platform::open_capturing_termios(&path, open);
+        let mut wrapper = PortWrapper::new(port.unwrap());
+        wrapper.original_termios = original_termios;
+        drop(wrapper);
+        assert_eq!(slave.baud_rate().unwrap(), 38400);
+
+        let mut wrapper = PortWrapper::new(open().unwrap());
+        assert!(wrapper.set_restore_on_close(true).is_err());
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn readable_event_is_signalled_until_reset() {
+        use serialport::TTYPort;
+        use std::io::{Read, Write};
+
+        let (master, mut slave) = TTYPort::pair().unwrap();
+        let mut wrapper = PortWrapper::new(master);
+        let event_fd = wrapper.readable_event_fd().unwrap();
+        assert_eq!(wrapper.readable_event_fd().unwrap(), event_fd);
+        let signalled = |timeout_ms| {
+            let mut pfd = libc::pollfd {
+                fd: event_fd,
+                events: libc::POLLIN,
+                revents: 0,
+            };
+            unsafe { libc::poll(&mut pfd, 1, timeout_ms) == 1 }
+        };
+
+        assert!(!signalled(50));
+        slave.write_all(b"ping").unwrap();
+        assert!(signalled(2000));
+
+        // Unread input signals the event again after a reset
+        assert!(wrapper.reset_readable_event());
+        assert!(signalled(2000));
+
+        assert!(wrapper.reset_readable_event());
+        let mut buffer = [0u8; 4];
+        wrapper.port.set_timeout(Duration::from_secs(1)).unwrap();
+        wrapper.port.read_exact(&mut buffer).unwrap();
+        assert_eq!(&buffer, b"ping");
+        // The notifier may have signalled before the read consumed the input
+        wrapper.reset_readable_event();
+        assert!(!signalled(50));
+
+        // Bytes kept from a pattern match are signalled although the port has no input
+        wrapper.read_residual.extend_from_slice(b"left");
+        assert!(!wrapper.reset_readable_event());
+        assert!(signalled(0));
+        wrapper.read_residual.clear();
+        assert!(wrapper.reset_readable_event());
+        assert!(!signalled(50));
+        drop(wrapper);
+    }
+
+    #[test]
+    fn unsigned_values_are_encoded_in_the_requested_byte_order() {
+        assert_eq!(encode_unsigned(0x1234, 2, true).unwrap(), [0x12, 0x34]);
+        assert_eq!(encode_unsigned(0x1234, 2, false).unwrap(), [0x34, 0x12]);
+        assert_eq!(encode_unsigned(0xDEADBEEF, 4, true).unwrap(), [0xDE, 0xAD, 0xBE, 0xEF]);
+        assert_eq!(encode_unsigned(0xDEADBEEF, 4, false).unwrap(), [0xEF, 0xBE, 0xAD, 0xDE]);
+        assert_eq!(encode_unsigned(u64::MAX, 8, true).unwrap(), [0xFF; 8]);
+
+        assert!(encode_unsigned(0x10000, 2, true).is_none());
+        assert!(encode_unsigned(1, 0, true).is_none());
+        assert!(encode_unsigned(1, 9, false).is_none());
+    }
+
+    #[test]
+    fn preamble_is_rounded_up_to_whole_microseconds() {
+        assert_eq!(preamble_duration(0, 9600), Duration::ZERO);
+        assert_eq!(preamble_duration(10, 0), Duration::ZERO);
+        assert_eq!(preamble_duration(10, 1_000_000), Duration::from_micros(10));
+        // 11 bits at 9600 baud are 1145.83 µs
+        assert_eq!(preamble_duration(11, 9600), Duration::from_micros(1146));
+
+        let start = Instant::now();
+        precise_wait(Duration::from_micros(1500));
+        assert!(start.elapsed() >= Duration::from_micros(1500));
+    }
+
+    #[test]
+    fn length_prefixes_are_decoded() {
+        let prefix = LengthPrefix {
+            header_len: 4,
+            field_offset: 1,
+            field_size: 2,
+            big_endian: true,
+            includes_header: false,
+        };
+        assert!(prefix.validate().is_ok());
+        assert_eq!(prefix.frame_len(&[0xAA, 0x01, 0x02, 0x00]), Ok(4 + 0x0102));
+        let little = LengthPrefix { big_endian: false, ..prefix };
+        assert_eq!(little.frame_len(&[0xAA, 0x01, 0x02, 0x00]), Ok(4 + 0x0201));
+
+        let inclusive = LengthPrefix { includes_header: true, ..prefix };
+        assert_eq!(inclusive.frame_len(&[0xAA, 0x00, 0x09, 0x00]), Ok(9));
+        assert!(inclusive.frame_len(&[0xAA, 0x00, 0x03, 0x00]).is_err());
+
+        assert!(LengthPrefix { field_offset: 3, ..prefix }.validate().is_err());
+        assert!(LengthPrefix { field_size: 0, ..prefix }.validate().is_err());
+        assert!(LengthPrefix { field_size: 5, header_len: 8, ..prefix }.validate().is_err());
+    }
+
+    #[test]
+    fn failsafe_releases_the_pin_once_per_silence() {
+        let releases = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&releases);
+        let release = move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        };
+        let warning = Arc::new(Mutex::new(None));
+        let watchdog =
+            FailsafeWatchdog::spawn(Duration::from_millis(30), release, Arc::clone(&warning))
+                .unwrap();
+
+        // Nothing is released while no write holds the pin
+        std::thread::sleep(Duration::from_millis(100));
+        assert_eq!(releases.load(Ordering::SeqCst), 0);
+        assert!(warning.lock().unwrap().is_none());
+
+        // Writes keep the timer from running out
+        for _ in 0..10 {
+            watchdog.arm();
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(releases.load(Ordering::SeqCst), 0);
+        assert!(!watchdog.take_tripped());
+
+        // A write longer than the timeout is not cut short
+        watchdog.disarm();
+        std::thread::sleep(Duration::from_millis(100));
+        assert_eq!(releases.load(Ordering::SeqCst), 0);
+
+        // A long silence trips it only once
+        watchdog.arm();
+        std::thread::sleep(Duration::from_millis(150));
+        assert_eq!(releases.load(Ordering::SeqCst), 1);
+        assert!(watchdog.take_tripped());
+        assert!(!watchdog.take_tripped());
+        let message = warning.lock().unwrap().take().unwrap();
+        assert!(message.starts_with("failsafe: no write within 30 ms"), "{}", message);
+
+        // The next write rearms it
+        watchdog.arm();
+        std::thread::sleep(Duration::from_millis(150));
+        assert_eq!(releases.load(Ordering::SeqCst), 2);
+        drop(watchdog);
+    }
+
+    #[test]
+    fn frames_skip_bytes_captured_before_the_reader_took_over() {
+        let queue = FrameQueue {
+            frames: VecDeque::from([b"ab".to_vec(), b"cd".to_vec()]),
+            captured: 3,
+            ..FrameQueue::default()
+        };
+        let queue = (Mutex::new(queue), Condvar::new());
+
+        let (frame, captured) = FrameReader::next_frame(&queue, Duration::ZERO).unwrap().unwrap();
+        assert_eq!((&frame[..], captured), (&b"ab"[..], 2));
+        FrameReader::unread_frame(&queue, frame, captured);
+        assert_eq!(FrameReader::next_frame(&queue, Duration::ZERO), Ok(Some((b"ab".to_vec(), 2))));
+        assert_eq!(FrameReader::next_frame(&queue, Duration::ZERO), Ok(Some((b"cd".to_vec(), 1))));
+        assert_eq!(FrameReader::next_frame(&queue, Duration::ZERO), Ok(None));
+    }
+
+    #[test]
+    fn capture_packets_carry_the_direction_and_data() {
+        let path = std::env::temp_dir().join(format!("jrserial-{}.pcap", std::process::id()));
+        let path_str = path.to_str().unwrap();
+        let mut capture = TrafficCapture::create(path_str).unwrap();
+        capture.record(CAPTURE_TRANSMITTED, b"ping").unwrap();
+        capture.record(CAPTURE_RECEIVED, b"pong!").unwrap();
+        capture.finish().unwrap();
+
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(&bytes[..4], &0xa1b2_c3d4u32.to_le_bytes());
+        assert_eq!(&bytes[20..24], &147u32.to_le_bytes());
+
+        let first = &bytes[24..];
+        assert_eq!(&first[8..12], &5u32.to_le_bytes());
+        assert_eq!(&first[16..21], b"\x01ping");
+        let second = &first[21..];
+        assert_eq!(&second[8..12], &6u32.to_le_bytes());
+        assert_eq!(&second[16..], b"\x00pong!");
+    }
+
+    #[test]
+    fn bit_reversal_mirrors_each_byte() {
+        let mut data = [0x01, 0x80, 0x0f, 0xa5, 0x00];
+        reverse_bits_in_place(&mut data);
+        assert_eq!(data, [0x80, 0x01, 0xf0, 0xa5, 0x00]);
+        reverse_bits_in_place(&mut data);
+        assert_eq!(data, [0x01, 0x80, 0x0f, 0xa5, 0x00]);
+    }
+
+    #[test]
+    fn baud_fallbacks_are_nearest_first_within_tolerance() {
+        let rates = [9600, 19200, 38400, 57600, 115200, 230400];
+        assert_eq!(baud_rates_within_tolerance(&rates, 115000, 1), vec![115200]);
+        assert_eq!(baud_rates_within_tolerance(&rates, 48000, 20), vec![38400, 57600]);
+        assert_eq!(baud_rates_within_tolerance(&rates, 115200, 5), Vec::<u32>::new());
+        assert_eq!(baud_rates_within_tolerance(&rates, 100000, 0), Vec::<u32>::new());
+    }
+
+    #[test]
+    fn crcs_match_the_standard_check_values() {
+        assert_eq!(crc_ccitt(b"123456789"), 0x29B1);
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(crc_ccitt(b""), 0xFFFF);
+        assert_eq!(crc32(b""), 0);
+
+        assert_eq!(CrcType::Ccitt.to_bytes(b"123456789", false), vec![0x29, 0xB1]);
+        assert_eq!(CrcType::Crc32.to_bytes(b"123456789", true), vec![0x26, 0x39, 0xF4, 0xCB]);
+    }
+
+    #[test]
+    fn trailing_crcs_are_verified() {
+        let mut frame = b"123456789".to_vec();
+        frame.extend([0x26, 0x39, 0xF4, 0xCB]);
+        assert_eq!(verify_crc(&frame, CrcType::Crc32, true), Ok(9));
+        assert!(verify_crc(&frame, CrcType::Crc32, false).is_err());
+
+        frame[0] ^= 1;
+        let error = verify_crc(&frame, CrcType::Crc32, true).unwrap_err();
+        assert!(error.contains("received 2639f4cb"), "{}", error);
+        assert!(verify_crc(&[0x29], CrcType::Ccitt, false).is_err());
+    }
+}
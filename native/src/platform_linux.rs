@@ -14,16 +14,44 @@
 
 //! Linux-specific serial port wrapper with kernel RS-485 support.
 
-use crate::{Rs485ControlMode, Rs485ControlPin};
-use serialport::{SerialPort, TTYPort};
-use std::io::Write;
-use std::os::unix::io::AsRawFd;
+use crate::{
+    cap_read_len, capture_traffic, detect_eof, drain_until, is_eof, preamble_duration,
+    precise_wait, read_cancelled_error, read_with_retries, reverse_bits_in_place, send_dmx_break,
+    sleep_unless_zero, write_fully, write_paced, write_until, AccessMode, FailsafeWatchdog,
+    FrameReader, QueueHighWater, Rs485ControlMode, Rs485ControlPin, ThroughputLimit,
+    TrafficCapture, WriteBlockingPolicy, WritePacing, BIT_REVERSE, CAPTURE_RECEIVED,
+    CAPTURE_TRANSMITTED, CONNECTION_CONNECTED, CONNECTION_FAILED, CONNECTION_RECONNECTING,
+    DEFAULT_DRAIN_TIMEOUT, MODEM_CD, MODEM_CTS, MODEM_DSR, MODEM_RI, RS485_ACTUAL_KERNEL_AUTO,
+    RS485_ACTUAL_MANUAL_BY_FALLBACK, RS485_ACTUAL_MANUAL_BY_PIN, RS485_ACTUAL_MANUAL_BY_REQUEST,
+    RS485_ACTUAL_NONE,
+};
+use serialport::{DataBits, FlowControl, Parity, SerialPort, SerialPortBuilder, StopBits, TTYPort};
+use std::ffi::CString;
+use std::io::{Read, Write};
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
 
 // Linux kernel RS-485 ioctl constants
 // From linux/serial.h
 const TIOCGRS485: libc::c_ulong = 0x542E;
 const TIOCSRS485: libc::c_ulong = 0x542F;
 
+// Serial line info ioctl constants
+// From asm-generic/ioctls.h
+const TIOCGSERIAL: libc::c_ulong = 0x541E;
+const TIOCSSERIAL: libc::c_ulong = 0x541F;
+const TIOCMIWAIT: libc::c_ulong = 0x545C;
+const TIOCGICOUNT: libc::c_ulong = 0x545D;
+
+// Speed flags from linux/tty_flags.h
+const ASYNC_SPD_MASK: libc::c_int = 0x1030;
+const ASYNC_SPD_CUST: libc::c_int = 0x0030;
+
 // RS-485 flags from linux/serial.h
 const SER_RS485_ENABLED: u32 = 1 << 0;
 const SER_RS485_RTS_ON_SEND: u32 = 1 << 1;
@@ -42,12 +70,537 @@ struct SerialRs485 {
     padding: [u32; 5],
 }
 
+/// Linux kernel serial_struct structure
+/// Matches struct serial_struct from linux/serial.h
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+struct SerialStruct {
+    type_: libc::c_int,
+    line: libc::c_int,
+    port: libc::c_uint,
+    irq: libc::c_int,
+    flags: libc::c_int,
+    xmit_fifo_size: libc::c_int,
+    custom_divisor: libc::c_int,
+    baud_base: libc::c_int,
+    close_delay: libc::c_ushort,
+    io_type: libc::c_char,
+    reserved_char: [libc::c_char; 1],
+    hub6: libc::c_int,
+    closing_wait: libc::c_ushort,
+    closing_wait2: libc::c_ushort,
+    iomem_base: *mut libc::c_uchar,
+    iomem_reg_shift: libc::c_ushort,
+    port_high: libc::c_uint,
+    iomap_base: libc::c_ulong,
+}
+
+/// Linux kernel serial_icounter_struct structure
+/// Matches struct serial_icounter_struct from linux/serial.h
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+struct SerialIcounter {
+    cts: libc::c_int,
+    dsr: libc::c_int,
+    rng: libc::c_int,
+    dcd: libc::c_int,
+    rx: libc::c_int,
+    tx: libc::c_int,
+    frame: libc::c_int,
+    overrun: libc::c_int,
+    parity: libc::c_int,
+    brk: libc::c_int,
+    buf_overrun: libc::c_int,
+    reserved: [libc::c_int; 9],
+}
+
+/// Check that a descriptor is still open and its device has not hung up.
+///
+/// `F_GETFD` catches a descriptor that was closed underneath the port; a zero-timeout
+/// poll() catches a device that was unplugged while the descriptor stayed open.
+fn fd_is_valid(fd: RawFd) -> bool {
+    if unsafe { libc::fcntl(fd, libc::F_GETFD) } < 0 {
+        return false;
+    }
+    let mut pfd = libc::pollfd {
+        fd,
+        events: 0,
+        revents: 0,
+    };
+    let ready = unsafe { libc::poll(&mut pfd, 1, 0) };
+    ready >= 0 && pfd.revents & (libc::POLLHUP | libc::POLLNVAL) == 0
+}
+
+/// Settings captured from a connected port, reapplied when it is reopened
+#[derive(Clone, Copy)]
+struct PortSnapshot {
+    termios: libc::termios2,
+    /// Kernel RS-485 settings, if kernel RS-485 mode was enabled
+    rs485: Option<SerialRs485>,
+    exclusive: bool,
+}
+
+impl PortSnapshot {
+    fn capture(fd: RawFd) -> Result<Self, std::io::Error> {
+        let mut termios = unsafe { std::mem::zeroed::<libc::termios2>() };
+        if unsafe { libc::ioctl(fd, libc::TCGETS2, &mut termios as *mut libc::termios2) } != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        let mut rs485 = SerialRs485::default();
+        let rs485_ok = unsafe { libc::ioctl(fd, TIOCGRS485, &mut rs485 as *mut SerialRs485) } == 0;
+        let mut exclusive: libc::c_int = 0;
+        unsafe { libc::ioctl(fd, libc::TIOCGEXCL, &mut exclusive as *mut libc::c_int) };
+        Ok(Self {
+            termios,
+            rs485: (rs485_ok && rs485.flags & SER_RS485_ENABLED != 0).then_some(rs485),
+            exclusive: exclusive != 0,
+        })
+    }
+
+    /// Apply the snapshot to a freshly opened descriptor
+    fn apply(&self, fd: RawFd) -> Result<(), std::io::Error> {
+        let check = |result: libc::c_int| {
+            if result < 0 {
+                Err(std::io::Error::last_os_error())
+            } else {
+                Ok(())
+            }
+        };
+        unsafe {
+            if self.exclusive {
+                check(libc::ioctl(fd, libc::TIOCEXCL))?;
+                check(libc::flock(fd, libc::LOCK_EX | libc::LOCK_NB))?;
+            }
+            check(libc::ioctl(fd, libc::TCSETS2, &self.termios as *const libc::termios2))?;
+            if let Some(rs485) = self.rs485 {
+                check(libc::ioctl(fd, TIOCSRS485, &rs485 as *const SerialRs485))?;
+            }
+            // Blocking I/O with poll-based timeouts, like a port opened by serialport
+            check(libc::fcntl(fd, libc::F_SETFL, 0))
+        }
+    }
+}
+
+/// Runs its argument with the port's handle locked and returns true, or returns false without
+/// running it if the handle is busy or gone
+pub type PortLocker = Box<dyn Fn(&mut dyn FnMut()) -> bool + Send>;
+
+/// Background thread that reopens a port after its device disappears.
+///
+/// The thread works only with the port's descriptor number, never with the wrapper, so it
+/// can run alongside reads and writes on other threads. A reconnect opens the device path
+/// again, applies the settings last seen while connected and `dup3`s the new descriptor over
+/// the old one, so the port keeps its descriptor and Java keeps its handle. Descriptors are
+/// only swapped with the handle locked through `lock`; a busy handle is tried again at the
+/// next probe.
+struct ReconnectWatchdog {
+    /// Dropping or sending on this stops the thread
+    stop: mpsc::Sender<()>,
+    state: Arc<AtomicU8>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl ReconnectWatchdog {
+    fn spawn(
+        fd: RawFd,
+        path: CString,
+        open_flags: libc::c_int,
+        probe_interval: Duration,
+        lock: PortLocker,
+    ) -> Result<Self, std::io::Error> {
+        let (stop, stopped) = mpsc::channel();
+        let state = Arc::new(AtomicU8::new(CONNECTION_CONNECTED));
+        let thread_state = Arc::clone(&state);
+        // Taken before the thread starts, in case the device goes away before it runs
+        let mut snapshot = PortSnapshot::capture(fd).ok();
+        let thread = std::thread::Builder::new()
+            .name("jrserial-reconnect".to_string())
+            .spawn(move || {
+                while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(probe_interval) {
+                    if fd_is_valid(fd) {
+                        if let Ok(current) = PortSnapshot::capture(fd) {
+                            snapshot = Some(current);
+                        }
+                        thread_state.store(CONNECTION_CONNECTED, Ordering::Release);
+                        continue;
+                    }
+
+                    thread_state.store(CONNECTION_RECONNECTING, Ordering::Release);
+                    let Some(snapshot) = snapshot else {
+                        thread_state.store(CONNECTION_FAILED, Ordering::Release);
+                        return;
+                    };
+                    let mut result = None;
+                    if !lock(&mut || result = Some(reopen_onto(fd, &path, open_flags, &snapshot)))
+                    {
+                        continue;
+                    }
+                    match result.unwrap_or_else(|| Err(std::io::Error::other("not reopened"))) {
+                        Ok(()) => thread_state.store(CONNECTION_CONNECTED, Ordering::Release),
+                        // The device is back but rejects the port's settings
+                        Err(e) if matches!(e.raw_os_error(), Some(libc::EINVAL | libc::ENOTTY)) => {
+                            thread_state.store(CONNECTION_FAILED, Ordering::Release);
+                            return;
+                        }
+                        // Not plugged in again yet, or not accessible yet; retry next probe
+                        Err(_) => {}
+                    }
+                }
+            })?;
+        Ok(Self {
+            stop,
+            state,
+            thread: Some(thread),
+        })
+    }
+
+    fn state(&self) -> u8 {
+        self.state.load(Ordering::Acquire)
+    }
+}
+
+impl Drop for ReconnectWatchdog {
+    fn drop(&mut self) {
+        let _ = self.stop.send(());
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Background thread that signals an eventfd while the port has input, so the port can
+/// be watched by event loops (such as a Java NIO selector) that only accept descriptors.
+///
+/// After signalling, the thread sleeps until the event is reset, which wakes it through a
+/// second eventfd, before it polls the port again, so unread input does not make it spin.
+struct ReadableNotifier {
+    /// Readable while the port has input that the consumer has not acknowledged
+    event_fd: RawFd,
+    /// Written by `reset` to wake the thread up
+    rearm_fd: RawFd,
+    /// Written to stop the thread
+    stop_fd: RawFd,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl ReadableNotifier {
+    fn spawn(port_fd: RawFd) -> Result<Self, std::io::Error> {
+        let mut fds = Vec::with_capacity(3);
+        for _ in 0..3 {
+            let fd = unsafe { libc::eventfd(0, libc::EFD_CLOEXEC | libc::EFD_NONBLOCK) };
+            if fd < 0 {
+                let e = std::io::Error::last_os_error();
+                for fd in fds {
+                    unsafe {
+                        libc::close(fd);
+                    }
+                }
+                return Err(e);
+            }
+            fds.push(fd);
+        }
+        let (event_fd, rearm_fd, stop_fd) = (fds[0], fds[1], fds[2]);
+
+        let spawned = std::thread::Builder::new()
+            .name("jrserial-readable-event".to_string())
+            .spawn(move || loop {
+                let mut event = libc::pollfd {
+                    fd: event_fd,
+                    events: libc::POLLIN,
+                    revents: 0,
+                };
+                // Still signalled: wait for the consumer to reset it, otherwise for input
+                let watched = if unsafe { libc::poll(&mut event, 1, 0) } > 0 {
+                    rearm_fd
+                } else {
+                    port_fd
+                };
+                let mut fds = [
+                    libc::pollfd {
+                        fd: watched,
+                        events: libc::POLLIN,
+                        revents: 0,
+                    },
+                    libc::pollfd {
+                        fd: stop_fd,
+                        events: libc::POLLIN,
+                        revents: 0,
+                    },
+                ];
+                let ready = unsafe { libc::poll(fds.as_mut_ptr(), 2, -1) };
+                if ready < 0 {
+                    if std::io::Error::last_os_error().kind() == std::io::ErrorKind::Interrupted {
+                        continue;
+                    }
+                    return;
+                }
+                if fds[1].revents != 0 {
+                    return;
+                }
+                if fds[0].revents == 0 {
+                    continue;
+                }
+                if watched == rearm_fd {
+                    let mut counter = 0u64;
+                    unsafe {
+                        libc::read(rearm_fd, &mut counter as *mut u64 as *mut libc::c_void, 8);
+                    }
+                } else {
+                    // Hangups and errors are signalled too, so the consumer's read reports them
+                    Self::signal_fd(event_fd);
+                }
+            });
+        match spawned {
+            Ok(thread) => Ok(Self {
+                event_fd,
+                rearm_fd,
+                stop_fd,
+                thread: Some(thread),
+            }),
+            Err(e) => {
+                unsafe {
+                    libc::close(event_fd);
+                    libc::close(rearm_fd);
+                    libc::close(stop_fd);
+                }
+                Err(e)
+            }
+        }
+    }
+
+    fn signal_fd(fd: RawFd) {
+        let counter = 1u64;
+        unsafe {
+            libc::write(fd, &counter as *const u64 as *const libc::c_void, 8);
+        }
+    }
+
+    /// Signal the event for input the thread cannot see on the port
+    fn signal(&self) {
+        Self::signal_fd(self.event_fd);
+    }
+
+    /// Clear the event, so it is signalled again only once the port has input.
+    /// Returns true if it was signalled.
+    fn reset(&self) -> bool {
+        let mut counter = 0u64;
+        let result = unsafe {
+            libc::read(self.event_fd, &mut counter as *mut u64 as *mut libc::c_void, 8)
+        };
+        Self::signal_fd(self.rearm_fd);
+        result == 8
+    }
+}
+
+impl Drop for ReadableNotifier {
+    fn drop(&mut self) {
+        Self::signal_fd(self.stop_fd);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+        unsafe {
+            libc::close(self.event_fd);
+            libc::close(self.rearm_fd);
+            libc::close(self.stop_fd);
+        }
+    }
+}
+
+/// Open `path` again, configure it from `snapshot` and move it onto descriptor `fd`.
+///
+/// The dead device is released first, by moving an O_PATH descriptor onto `fd`: while the
+/// old tty stays open, a replugged adapter comes back under a new name (ttyUSB1 instead of
+/// ttyUSB0) and `path` cannot be reopened. The placeholder keeps the descriptor number
+/// reserved and fails all I/O with EBADF until the device is back.
+fn reopen_onto(
+    fd: RawFd,
+    path: &CString,
+    open_flags: libc::c_int,
+    snapshot: &PortSnapshot,
+) -> Result<(), std::io::Error> {
+    release_dead_fd(fd)?;
+    let flags = open_flags | libc::O_NOCTTY | libc::O_NONBLOCK | libc::O_CLOEXEC;
+    let new_fd = unsafe { libc::open(path.as_ptr(), flags) };
+    if new_fd < 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    let result = snapshot.apply(new_fd).and_then(|_| {
+        if unsafe { libc::dup3(new_fd, fd, libc::O_CLOEXEC) } < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(())
+    });
+    unsafe {
+        libc::close(new_fd);
+    }
+    result
+}
+
+/// Replace the descriptor `fd` of a vanished device with a placeholder, unless that was done
+/// already
+fn release_dead_fd(fd: RawFd) -> Result<(), std::io::Error> {
+    let flags = unsafe { libc::fcntl(fd, libc::F_GETFL) };
+    if flags >= 0 && flags & libc::O_PATH != 0 {
+        return Ok(());
+    }
+    let placeholder = unsafe { libc::open(c"/dev/null".as_ptr(), libc::O_PATH | libc::O_CLOEXEC) };
+    if placeholder < 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    let result = unsafe { libc::dup3(placeholder, fd, libc::O_CLOEXEC) };
+    let e = std::io::Error::last_os_error();
+    unsafe {
+        libc::close(placeholder);
+    }
+    if result < 0 {
+        return Err(e);
+    }
+    Ok(())
+}
+
+/// Run `open` on the tty at `path`, capturing the termios settings it had before.
+///
+/// The settings are read in termios2 form, which includes custom (BOTHER) speeds, through a
+/// second descriptor opened with O_NONBLOCK so that it does not wait for carrier. It stays
+/// open until `open` returns: with the port then open through the new descriptor, closing it
+/// is not the last close and so does not hang up the line.
+pub fn open_capturing_termios<T>(
+    path: &str,
+    open: impl FnOnce() -> T,
+) -> (T, Option<libc::termios2>) {
+    let fd = CString::new(path).ok().map(|path| unsafe {
+        libc::open(
+            path.as_ptr(),
+            libc::O_RDONLY | libc::O_NONBLOCK | libc::O_NOCTTY | libc::O_CLOEXEC,
+        )
+    });
+    let fd = fd.filter(|&fd| fd >= 0);
+    let termios = fd.and_then(|fd| {
+        let mut termios = unsafe { std::mem::zeroed::<libc::termios2>() };
+        let result = unsafe { libc::ioctl(fd, libc::TCGETS2, &mut termios as *mut libc::termios2) };
+        (result == 0).then_some(termios)
+    });
+    let result = open();
+    if let Some(fd) = fd {
+        unsafe {
+            libc::close(fd);
+        }
+    }
+    (result, termios)
+}
+
+/// Open a port with the given access mode.
+///
+/// `serialport` always opens ports read/write, so for a restricted mode the port is opened
+/// (exclusively) and fully configured by the builder first, then its descriptor is swapped for
+/// one reopened through `/proc/self/fd` with `O_RDONLY` or `O_WRONLY`. TIOCEXCL would make
+/// that reopen fail, so it is lifted only around the `open` call and set again on the new
+/// descriptor before the first one is closed.
+pub fn open_with_access(
+    builder: SerialPortBuilder,
+    access_mode: AccessMode,
+) -> Result<TTYPort, serialport::Error> {
+    let flags = match access_mode {
+        AccessMode::ReadWrite => return builder.open_native(),
+        AccessMode::ReadOnly => libc::O_RDONLY,
+        AccessMode::WriteOnly => libc::O_WRONLY,
+    };
+
+    let configured = builder.open_native()?;
+    let configured_fd = configured.as_raw_fd();
+    let path = CString::new(format!("/proc/self/fd/{}", configured_fd))
+        .expect("path contains no NUL bytes");
+    let fd = unsafe {
+        libc::ioctl(configured_fd, libc::TIOCNXCL);
+        let fd = libc::open(path.as_ptr(), flags | libc::O_NOCTTY | libc::O_CLOEXEC);
+        if fd < 0 {
+            return Err(std::io::Error::last_os_error().into());
+        }
+        libc::ioctl(fd, libc::TIOCEXCL);
+        fd
+    };
+
+    // Close the first descriptor directly: dropping the TTYPort would clear TIOCEXCL again.
+    // The restricted descriptor keeps the device open, so this does not hang up.
+    let timeout = configured.timeout();
+    unsafe {
+        libc::close(configured.into_raw_fd());
+    }
+
+    let mut port = unsafe { TTYPort::from_raw_fd(fd) };
+    port.set_timeout(timeout)?;
+    Ok(port)
+}
+
 pub struct PortWrapper {
     pub port: TTYPort,
     pub control_mode: Rs485ControlMode,
     pub control_pin: Rs485ControlPin,
+    /// How writes react when the port would block
+    pub write_blocking_policy: WriteBlockingPolicy,
+    /// Directions of I/O the port was opened for
+    pub access_mode: AccessMode,
+    /// Chunking of large writes for slow receivers
+    pub write_pacing: WritePacing,
+    /// Limit on the sustained write rate
+    pub throughput_limit: ThroughputLimit,
+    /// Releases the direction pin if no write succeeds in time, if enabled
+    failsafe: Option<FailsafeWatchdog>,
+    /// True to reverse the bit order of each byte written and read
+    pub bit_reversal: bool,
+    /// Records reads and writes to a file, if started
+    capture: Option<TrafficCapture>,
+    /// Bit periods of idle line held before each write, 0 for none
+    pub preamble_idle_bits: u32,
+    /// Preamble included in the kernel RS-485 delay before sending
+    kernel_preamble: Duration,
+    /// Manual RS-485 mode: time between asserting the direction pin and sending data
+    pub rs485_pre_delay: Duration,
+    /// Manual RS-485 mode: time between the data being sent and releasing the direction pin
+    pub rs485_post_delay: Duration,
+    /// Deepest input/output queues seen by reads, writes and status queries
+    pub queue_high_water: QueueHighWater,
+    /// Reusable buffer for reads that copy into a pinned Java array
+    pub read_scratch: Vec<u8>,
+    /// Maximum bytes requested from a single underlying read, or 0 for no limit
+    pub max_read_chunk: usize,
+    /// Set once the fallback from a direct buffer to array copying has been warned about
+    pub direct_buffer_fallback_warned: bool,
+    /// Input queue depth above which operations record a warning, or 0 to disable
+    pub overflow_warn_threshold: u32,
+    /// True to discard pending input at the start of each write
+    pub clear_input_before_write: bool,
+    /// Longest a write waits for its output to drain, or None to wait indefinitely
+    pub drain_timeout: Option<Duration>,
+    /// True if the last readFully or readOne returned early because its deadline elapsed
+    pub last_read_timed_out: bool,
+    /// Last non-fatal problem noticed by a read, cleared when retrieved
+    pub last_warning: Arc<Mutex<Option<String>>>,
+    /// Line settings the driver applied differently than requested on open
+    pub config_discrepancies: Vec<String>,
+    /// Baud rate the port was opened at, which the baud tolerance may have changed from the
+    /// requested one; 0 if the port was opened without the tolerance (quiet open)
+    pub opened_baud_rate: u32,
+    /// True if the last read found the port closed by its peer
+    pub eof: bool,
+    /// Bytes appended to each line written with `write_line`
+    pub write_terminator: Vec<u8>,
+    /// Times a read is retried after a transient error before failing
+    pub read_retries: u32,
+    /// Retries made by reads since the port was opened
+    pub read_retry_count: u64,
+    /// Background thread splitting input into frames at idle gaps, if started
+    pub frame_reader: Option<FrameReader>,
+    /// Input overrun count at the last read, or None if the driver does not report it
+    input_overruns: Option<u32>,
+    /// Ring indicator count when the port was opened, or None if the driver does not report it
+    ring_baseline: Option<u32>,
+    /// eventfd used to wake up a blocked read, or -1 if unavailable
+    cancel_fd: RawFd,
     /// True if kernel RS-485 mode was successfully enabled
     kernel_rs485_active: bool,
+    /// How direction control is actually performed, and why (RS485_ACTUAL_*)
+    rs485_actual_mode: u8,
     /// True if RTS should be active high during transmission
     rts_active_high: bool,
     /// True to enable receiving during transmission
@@ -58,26 +611,149 @@ pub struct PortWrapper {
     delay_before_send_micros: u32,
     /// Delay in microseconds after sending (for kernel mode)
     delay_after_send_micros: u32,
+    /// Differences between the requested and applied kernel RS-485 settings
+    rs485_warnings: Vec<String>,
+    /// True to keep the transceiver in receive mode and reject writes
+    listen_only: bool,
+    /// True to hold DTR asserted and wait for DSR before each write
+    dtr_flow_control: bool,
+    /// True to leave the manual RS-485 direction pin asserted between writes
+    hold_transmit_enable: bool,
+    /// True while the direction pin is held asserted after a write
+    transmit_held: bool,
+    /// True to drain the output at the end of each write
+    auto_flush: bool,
+    /// Bytes read past the end of a pattern match, returned by the next reads
+    pub read_residual: Vec<u8>,
+    /// Last level set on RTS since open, or None if it was never set
+    current_rts: Option<bool>,
+    /// Last level set on DTR since open, or None if it was never set
+    current_dtr: Option<bool>,
+    /// Thread reopening the port after a disconnect, if enabled
+    reconnect: Option<ReconnectWatchdog>,
+    /// Thread signalling an eventfd when input arrives, started by `readable_event_fd`
+    readable_notifier: Option<ReadableNotifier>,
+    /// Termios settings the tty had before it was opened, if they could be read
+    pub original_termios: Option<libc::termios2>,
+    /// True to restore `original_termios` when the port is closed
+    restore_on_close: bool,
 }
 
 impl PortWrapper {
     pub fn new(port: TTYPort) -> Self {
-        Self {
+        let mut wrapper = Self {
             port,
             control_mode: Rs485ControlMode::None,
             control_pin: Rs485ControlPin::RTS,
+            write_blocking_policy: WriteBlockingPolicy::Block,
+            access_mode: AccessMode::ReadWrite,
+            write_pacing: WritePacing::default(),
+            throughput_limit: ThroughputLimit::default(),
+            failsafe: None,
+            bit_reversal: false,
+            capture: None,
+            preamble_idle_bits: 0,
+            kernel_preamble: Duration::ZERO,
+            rs485_pre_delay: Duration::ZERO,
+            rs485_post_delay: Duration::ZERO,
+            queue_high_water: QueueHighWater::default(),
+            read_scratch: Vec::new(),
+            max_read_chunk: 0,
+            direct_buffer_fallback_warned: false,
+            overflow_warn_threshold: 0,
+            clear_input_before_write: false,
+            drain_timeout: Some(DEFAULT_DRAIN_TIMEOUT),
+            last_read_timed_out: false,
+            last_warning: Arc::new(Mutex::new(None)),
+            config_discrepancies: Vec::new(),
+            opened_baud_rate: 0,
+            eof: false,
+            write_terminator: b"\n".to_vec(),
+            read_retries: 0,
+            read_retry_count: 0,
+            frame_reader: None,
+            input_overruns: None,
+            ring_baseline: None,
+            cancel_fd: unsafe { libc::eventfd(0, libc::EFD_CLOEXEC | libc::EFD_NONBLOCK) },
             kernel_rs485_active: false,
+            rs485_actual_mode: RS485_ACTUAL_NONE,
             rts_active_high: true,
             rx_during_tx: false,
             termination_enabled: false,
             delay_before_send_micros: 0,
             delay_after_send_micros: 0,
+            rs485_warnings: Vec::new(),
+            listen_only: false,
+            dtr_flow_control: false,
+            hold_transmit_enable: false,
+            transmit_held: false,
+            auto_flush: true,
+            read_residual: Vec::new(),
+            current_rts: None,
+            current_dtr: None,
+            reconnect: None,
+            readable_notifier: None,
+            original_termios: None,
+            restore_on_close: false,
+        };
+        // Baselines, so events from before the port was opened are not counted
+        if let Ok(counts) = wrapper.read_icounter() {
+            wrapper.input_overruns = Some(Self::input_overruns_of(&counts));
+            wrapper.ring_baseline = Some(counts.rng as u32);
+        }
+        wrapper
+    }
+
+    /// Read the driver's interrupt counters via TIOCGICOUNT
+    fn read_icounter(&self) -> Result<SerialIcounter, std::io::Error> {
+        let fd = self.port.as_raw_fd();
+        let mut counts = SerialIcounter::default();
+        if unsafe { libc::ioctl(fd, TIOCGICOUNT, &mut counts as *mut SerialIcounter) } != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(counts)
+    }
+
+    /// Total UART and tty buffer overrun events. Each event loses at least one character, but
+    /// the driver does not count how many.
+    fn input_overruns_of(counts: &SerialIcounter) -> u32 {
+        (counts.overrun as u32).wrapping_add(counts.buf_overrun as u32)
+    }
+
+    /// Number of ring indicator edges since the port was opened.
+    ///
+    /// The driver counts RI interrupts in the background, so no edge is missed between calls.
+    pub fn ring_count(&self) -> Result<u32, std::io::Error> {
+        let Some(baseline) = self.ring_baseline else {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "driver does not count modem line events",
+            ));
+        };
+        Ok((self.read_icounter()?.rng as u32).wrapping_sub(baseline))
+    }
+
+    /// Record a warning if the input overrun count grew since the last check
+    fn check_input_overruns(&mut self) {
+        let Some(previous) = self.input_overruns else {
+            return;
+        };
+        let Ok(current) = self.read_icounter().map(|counts| Self::input_overruns_of(&counts)) else {
+            return;
+        };
+        let events = current.wrapping_sub(previous);
+        if events > 0 {
+            if let Ok(mut warning) = self.last_warning.lock() {
+                *warning = Some(format!("input overrun: data lost in {} overrun(s)", events));
+            }
         }
+        self.input_overruns = Some(current);
     }
 
     /// Try to enable kernel RS-485 mode via ioctl
     fn try_enable_kernel_rs485(&mut self) -> bool {
         let fd = self.port.as_raw_fd();
+        self.rs485_warnings.clear();
 
         // Build flags based on configuration
         let mut flags = SER_RS485_ENABLED;
@@ -89,6 +765,11 @@ fn try_enable_kernel_rs485(&mut self) -> bool {
             flags |= SER_RS485_RTS_AFTER_SEND;
         }
 
+        // Listen-only: never raise RTS for sending
+        if self.listen_only {
+            flags &= !SER_RS485_RTS_ON_SEND;
+        }
+
         // Enable RX during TX if requested
         if self.rx_during_tx {
             flags |= SER_RS485_RX_DURING_TX;
@@ -99,8 +780,10 @@ fn try_enable_kernel_rs485(&mut self) -> bool {
             flags |= SER_RS485_TERMINATE_BUS;
         }
 
-        // Linux kernel uses milliseconds for delays, convert from microseconds
-        let delay_before_ms = self.delay_before_send_micros / 1000;
+        // Linux kernel uses milliseconds for delays, convert from microseconds; the preamble
+        // is rounded up so that it is never shorter than requested
+        let delay_before_ms = self.delay_before_send_micros / 1000
+            + self.kernel_preamble.as_micros().div_ceil(1000) as u32;
         let delay_after_ms = self.delay_after_send_micros / 1000;
 
         let mut config = SerialRs485 {
@@ -120,6 +803,7 @@ fn try_enable_kernel_rs485(&mut self) -> bool {
                 unsafe { libc::ioctl(fd, TIOCGRS485, &mut verify as *mut SerialRs485) };
 
             if verify_result == 0 && (verify.flags & SER_RS485_ENABLED) != 0 {
+                self.check_rs485_delays(delay_before_ms, delay_after_ms, &verify);
                 return true;
             }
         }
@@ -127,6 +811,28 @@ fn try_enable_kernel_rs485(&mut self) -> bool {
         false
     }
 
+    /// Record a warning for each delay the driver did not apply as requested.
+    /// Drivers may clamp delays to a maximum (100 ms in serial_core) or ignore them entirely.
+    fn check_rs485_delays(&mut self, before_ms: u32, after_ms: u32, applied: &SerialRs485) {
+        if applied.delay_rts_before_send != before_ms {
+            self.rs485_warnings.push(format!(
+                "delay_rts_before_send: requested {} ms, kernel applied {} ms",
+                before_ms, applied.delay_rts_before_send
+            ));
+        }
+        if applied.delay_rts_after_send != after_ms {
+            self.rs485_warnings.push(format!(
+                "delay_rts_after_send: requested {} ms, kernel applied {} ms",
+                after_ms, applied.delay_rts_after_send
+            ));
+        }
+    }
+
+    /// Warnings recorded when kernel RS-485 mode was last enabled
+    pub fn rs485_warnings(&self) -> &[String] {
+        &self.rs485_warnings
+    }
+
     /// Disable kernel RS-485 mode
     fn disable_kernel_rs485(&mut self) -> bool {
         let fd = self.port.as_raw_fd();
@@ -143,39 +849,53 @@ pub fn configure_rs485(
         mode: Rs485ControlMode,
         pin: Rs485ControlPin,
     ) -> Result<(), serialport::Error> {
+        if self.dtr_flow_control && mode != Rs485ControlMode::None && pin == Rs485ControlPin::DTR
+        {
+            return Err(serialport::Error::new(
+                serialport::ErrorKind::InvalidInput,
+                "DTR is already used for DTR/DSR flow control",
+            ));
+        }
+
+        // Reconfiguring ends a hold of the direction pin, and the failsafe guarding it
+        let _ = self.set_hold_transmit_enable(false);
+        self.failsafe = None;
+
         // First, disable any existing kernel RS-485 mode
         if self.kernel_rs485_active {
             self.disable_kernel_rs485();
             self.kernel_rs485_active = false;
         }
+        self.rs485_warnings.clear();
 
         self.control_mode = mode;
         self.control_pin = pin;
 
-        match mode {
-            Rs485ControlMode::None => {
-                // Nothing to do
-            }
+        self.rs485_actual_mode = match mode {
+            Rs485ControlMode::None => RS485_ACTUAL_NONE,
             Rs485ControlMode::Auto => {
                 // Try kernel mode first (only works with RTS, not DTR)
-                if pin == Rs485ControlPin::RTS {
-                    if self.try_enable_kernel_rs485() {
-                        self.kernel_rs485_active = true;
-                        // Kernel mode enabled, no manual control needed
-                    }
-                    // If kernel mode fails, fall back to manual (no error)
+                // If kernel mode fails, fall back to manual (no error)
+                if pin == Rs485ControlPin::RTS && self.try_enable_kernel_rs485() {
+                    self.kernel_rs485_active = true;
+                    // Kernel mode enabled, no manual control needed
+                    RS485_ACTUAL_KERNEL_AUTO
+                } else if pin == Rs485ControlPin::RTS {
+                    RS485_ACTUAL_MANUAL_BY_FALLBACK
+                } else {
+                    // For DTR, always use manual mode (kernel doesn't support it)
+                    RS485_ACTUAL_MANUAL_BY_PIN
                 }
-                // For DTR, always use manual mode (kernel doesn't support it)
-            }
-            Rs485ControlMode::Manual => {
-                // Explicitly use manual mode, don't try kernel
             }
-        }
+            // Explicitly use manual mode, don't try kernel
+            Rs485ControlMode::Manual => RS485_ACTUAL_MANUAL_BY_REQUEST,
+        };
 
         Ok(())
     }
 
     /// Configure extended RS-485 settings
+    #[allow(clippy::too_many_arguments)]
     pub fn configure_rs485_extended(
         &mut self,
         mode: Rs485ControlMode,
@@ -208,45 +928,1730 @@ pub fn set_rs485_delays(&mut self, before_send_micros: u32, after_send_micros: u
         }
     }
 
-    /// Check if kernel RS-485 mode is active
-    pub fn is_kernel_rs485_active(&self) -> bool {
-        self.kernel_rs485_active
+    /// Enable or disable listen-only mode.
+    ///
+    /// While enabled, `write_rs485` rejects all writes. In manual mode the direction pin is
+    /// driven to receive immediately; in kernel mode RS-485 is reconfigured without
+    /// SER_RS485_RTS_ON_SEND so the driver does not raise RTS either.
+    pub fn set_listen_only(&mut self, enabled: bool) -> Result<(), std::io::Error> {
+        self.listen_only = enabled;
+        if self.kernel_rs485_active {
+            if !self.try_enable_kernel_rs485() {
+                return Err(std::io::Error::other("could not reconfigure kernel RS-485 mode"));
+            }
+        } else if enabled && self.control_mode != Rs485ControlMode::None {
+            self.transmit_held = false;
+            self.update_failsafe();
+            self.set_transmit_enable(false)?;
+        }
+        Ok(())
     }
 
-    pub fn write_rs485(&mut self, data: &[u8]) -> Result<usize, std::io::Error> {
-        match self.control_mode {
-            Rs485ControlMode::None => {
-                // No RS-485 control, just write normally
-                self.port.write(data)
+    /// Keep the manual RS-485 direction pin asserted between writes, or release it.
+    ///
+    /// While held, the first write asserts the pin and waits the pre-delay; later writes go
+    /// out without toggling the pin, draining or waiting the delays. Releasing drains the
+    /// output, waits the post-delay and deasserts the pin. Holding is rejected without RS-485
+    /// control and in kernel mode, where the driver drives RTS itself.
+    pub fn set_hold_transmit_enable(&mut self, hold: bool) -> Result<(), std::io::Error> {
+        if hold && self.kernel_rs485_active {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "RTS is controlled by kernel RS-485 mode",
+            ));
+        }
+        if hold && self.control_mode == Rs485ControlMode::None {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "RS-485 direction control is not enabled",
+            ));
+        }
+        self.hold_transmit_enable = hold;
+        if !hold && self.transmit_held {
+            self.transmit_held = false;
+            self.update_failsafe();
+            self.drain_for(None);
+            sleep_unless_zero(self.rs485_post_delay);
+            self.set_transmit_enable(false)?;
+        }
+        Ok(())
+    }
+
+    /// Release the direction pin if it is left held with no write for `timeout`, or stop
+    /// doing so with None. The timer restarts after each write; see `FailsafeWatchdog`.
+    /// Requires RS-485 direction control. In kernel RS-485 mode the
+    /// driver releases RTS after each frame itself, so the failsafe is rejected there.
+    pub fn set_failsafe(&mut self, timeout: Option<Duration>) -> Result<(), std::io::Error> {
+        self.failsafe = None;
+        let Some(timeout) = timeout else {
+            return Ok(());
+        };
+        if self.kernel_rs485_active {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "RTS is controlled by kernel RS-485 mode",
+            ));
+        }
+        if self.control_mode == Rs485ControlMode::None {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "RS-485 direction control is not enabled",
+            ));
+        }
+        let mut port = self.port.try_clone()?;
+        let pin = self.control_pin;
+        // Reconfiguring the polarity drops the failsafe, so the level cannot go stale
+        let receive_level = self.receive_level();
+        let release = move || match pin {
+            Rs485ControlPin::RTS => port.write_request_to_send(receive_level),
+            Rs485ControlPin::DTR => port.write_data_terminal_ready(receive_level),
+        };
+        let watchdog = FailsafeWatchdog::spawn(timeout, release, Arc::clone(&self.last_warning))?;
+        self.failsafe = Some(watchdog);
+        self.update_failsafe();
+        Ok(())
+    }
+
+    /// Arm the failsafe while the direction pin is held between writes, disarm it otherwise
+    fn update_failsafe(&self) {
+        if let Some(failsafe) = &self.failsafe {
+            if self.transmit_held {
+                failsafe.arm();
+            } else {
+                failsafe.disarm();
             }
-            Rs485ControlMode::Auto if self.kernel_rs485_active => {
-                // Kernel handles RTS automatically, just write
-                let result = self.port.write(data);
-                // Still flush to ensure data is sent before kernel toggles RTS
-                let _ = self.port.flush();
-                result
+        }
+    }
+
+    /// Enable or disable draining the output at the end of each write.
+    ///
+    /// With auto-flush off, `write_rs485` returns once the data is queued and `flush_output`
+    /// drains it. In manual RS-485 mode the direction pin then stays asserted after a write
+    /// until the next flush, so the bus is never released with data still queued. Turning
+    /// auto-flush back on flushes a write that is still waiting for it. Writes with a
+    /// deadline always drain, since they promise to free the bus by the deadline.
+    pub fn set_auto_flush(&mut self, enabled: bool) -> Result<(), std::io::Error> {
+        self.auto_flush = enabled;
+        if enabled && self.transmit_held && !self.hold_transmit_enable {
+            self.flush_output()?;
+        }
+        Ok(())
+    }
+
+    /// Drain the output, then release the manual RS-485 direction pin if a write left it
+    /// asserted for this flush because auto-flush is off
+    pub fn flush_output(&mut self) -> Result<(), std::io::Error> {
+        self.port.flush()?;
+        if self.transmit_held && !self.hold_transmit_enable {
+            self.transmit_held = false;
+            self.update_failsafe();
+            sleep_unless_zero(self.rs485_post_delay);
+            self.set_transmit_enable(false)?;
+        }
+        Ok(())
+    }
+
+    /// Record the input queue depth seen by an operation, warning if it is above the
+    /// overflow warning threshold
+    pub fn observe_input_depth(&mut self, depth: u32) {
+        self.queue_high_water.observe_input(depth);
+        let threshold = self.overflow_warn_threshold;
+        if threshold > 0 && depth > threshold {
+            if let Ok(mut warning) = self.last_warning.lock() {
+                *warning = Some(format!(
+                    "input queue at {} bytes, above the warning threshold of {}: reads are \
+                     falling behind",
+                    depth, threshold
+                ));
             }
-            Rs485ControlMode::Auto | Rs485ControlMode::Manual => {
-                // Manual RTS/DTR control
-                // Enable transmit
-                match self.control_pin {
-                    Rs485ControlPin::RTS => self.port.write_request_to_send(true)?,
-                    Rs485ControlPin::DTR => self.port.write_data_terminal_ready(true)?,
+        }
+    }
+
+    /// Set RTS, remembering the level for `output_line_state`
+    pub fn set_rts(&mut self, level: bool) -> serialport::Result<()> {
+        self.port.write_request_to_send(level)?;
+        self.current_rts = Some(level);
+        Ok(())
+    }
+
+    /// Set DTR, remembering the level for `output_line_state`
+    pub fn set_dtr(&mut self, level: bool) -> serialport::Result<()> {
+        self.port.write_data_terminal_ready(level)?;
+        self.current_dtr = Some(level);
+        Ok(())
+    }
+
+    /// Get the last level commanded on RTS (`dtr` false) or DTR (`dtr` true).
+    ///
+    /// The commanded level is checked against the line state read with TIOCMGET, and a
+    /// mismatch is recorded as a warning. If the line was never set since the port was
+    /// opened, the hardware state is returned instead. So is RTS while kernel RS-485 mode is
+    /// active, since the driver then toggles it on its own and the commanded level is stale.
+    pub fn output_line_state(&mut self, dtr: bool) -> Result<bool, std::io::Error> {
+        let (name, commanded, mask) = if dtr {
+            ("DTR", self.current_dtr, libc::TIOCM_DTR)
+        } else if self.kernel_rs485_active {
+            ("RTS", None, libc::TIOCM_RTS)
+        } else {
+            ("RTS", self.current_rts, libc::TIOCM_RTS)
+        };
+        let fd = self.port.as_raw_fd();
+        let mut lines: libc::c_int = 0;
+        let actual = if unsafe { libc::ioctl(fd, libc::TIOCMGET, &mut lines) } == 0 {
+            Ok(lines & mask != 0)
+        } else {
+            Err(std::io::Error::last_os_error())
+        };
+        match (commanded, actual) {
+            (Some(commanded), Ok(actual)) => {
+                if commanded != actual {
+                    if let Ok(mut warning) = self.last_warning.lock() {
+                        *warning = Some(format!(
+                            "{} mismatch: set to {} but the line reads {}",
+                            name, commanded, actual
+                        ));
+                    }
                 }
+                Ok(commanded)
+            }
+            (Some(commanded), Err(_)) => Ok(commanded),
+            (None, actual) => actual,
+        }
+    }
 
-                // Write data
-                let result = self.port.write(data);
+    /// Level of the RS-485 direction pin while receiving, given its polarity
+    fn receive_level(&self) -> bool {
+        !self.rts_active_high
+    }
 
-                // Flush to ensure data is sent
-                let _ = self.port.flush();
+    /// Drive the manual RS-485 direction pin to transmit or back to receive (respecting
+    /// polarity)
+    fn set_transmit_enable(&mut self, transmit: bool) -> Result<(), std::io::Error> {
+        let level = transmit != self.receive_level();
+        match self.control_pin {
+            Rs485ControlPin::RTS => self.set_rts(level)?,
+            Rs485ControlPin::DTR => self.set_dtr(level)?,
+        }
+        Ok(())
+    }
 
-                // Disable transmit (back to receive mode)
-                match self.control_pin {
-                    Rs485ControlPin::RTS => self.port.write_request_to_send(false)?,
-                    Rs485ControlPin::DTR => self.port.write_data_terminal_ready(false)?,
+    /// Enable or disable DTR/DSR handshaking, independently of RTS/CTS flow control.
+    ///
+    /// Linux termios has no DTR/DSR flow control flag, so the handshake is done here: DTR is
+    /// asserted to tell the peer the port is ready to receive, and each write waits for the
+    /// peer to assert DSR before sending. Fails if DTR is the RS-485 direction pin.
+    ///
+    /// Long writes go out in chunks of DSR_CHECK_CHUNK bytes, each drained before DSR is
+    /// checked again, so at most one chunk is sent after the peer deasserts DSR.
+    pub fn set_dtr_flow_control(&mut self, enabled: bool) -> Result<(), std::io::Error> {
+        if enabled
+            && self.control_mode != Rs485ControlMode::None
+            && self.control_pin == Rs485ControlPin::DTR
+        {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "DTR is already used as the RS-485 direction pin",
+            ));
+        }
+        if enabled {
+            self.set_dtr(true)?;
+        }
+        self.dtr_flow_control = enabled;
+        Ok(())
+    }
+
+    /// Wait until the peer asserts DSR, up to the port timeout (0 waits indefinitely) or
+    /// `deadline`, whichever comes first.
+    ///
+    /// TIOCMIWAIT cannot be given a timeout, so it is only used for an indefinite wait; a
+    /// bounded wait, or a driver without TIOCMIWAIT, polls DSR every DSR_POLL_INTERVAL.
+    fn wait_for_dsr(&mut self, deadline: Option<Instant>) -> Result<(), std::io::Error> {
+        let timeout = self.port.timeout();
+        let limit = match (timeout.is_zero(), deadline) {
+            (true, deadline) => deadline,
+            (false, Some(deadline)) => Some(deadline.min(Instant::now() + timeout)),
+            (false, None) => Some(Instant::now() + timeout),
+        };
+        let mut modem_wait = limit.is_none();
+        while self.modem_status()? & MODEM_DSR == 0 {
+            if modem_wait {
+                let fd = self.port.as_raw_fd();
+                // Returns once DSR changes; on failure the driver lacks it, so poll instead
+                modem_wait = unsafe { libc::ioctl(fd, TIOCMIWAIT, libc::TIOCM_DSR) } == 0;
+                continue;
+            }
+            let remaining = limit.map_or(DSR_POLL_INTERVAL, |limit| {
+                limit.saturating_duration_since(Instant::now())
+            });
+            if remaining.is_zero() {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::TimedOut,
+                    "peer did not assert DSR (DTR/DSR flow control)",
+                ));
+            }
+            std::thread::sleep(remaining.min(DSR_POLL_INTERVAL));
+        }
+        Ok(())
+    }
+
+    /// Read the port's termios, let `update` modify it, and apply it immediately
+    fn update_termios(
+        &mut self,
+        update: impl FnOnce(&mut libc::termios),
+    ) -> Result<(), std::io::Error> {
+        let fd = self.port.as_raw_fd();
+
+        let mut termios = unsafe { std::mem::zeroed::<libc::termios>() };
+        if unsafe { libc::tcgetattr(fd, &mut termios) } != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        update(&mut termios);
+        if unsafe { libc::tcsetattr(fd, libc::TCSANOW, &termios) } != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// Write 9-bit characters using mark/space (stick) parity as the 9th bit.
+    ///
+    /// Consecutive bytes with the same 9th bit are sent in one write, so only changes of
+    /// the 9th bit cost a termios update. Each update uses TCSADRAIN, which waits until the
+    /// previous bytes have left the driver, so every switch adds about one character time
+    /// plus syscall latency. The original termios settings are restored afterwards.
+    pub fn write_9bit(&mut self, data: &[u8], ninth_bits: &[bool]) -> Result<(), std::io::Error> {
+        self.access_mode.check(true)?;
+        let fd = self.port.as_raw_fd();
+        let mut original = unsafe { std::mem::zeroed::<libc::termios>() };
+        if unsafe { libc::tcgetattr(fd, &mut original) } != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+
+        // Input only needs clearing once, before the first run
+        let clear_input_before_write = self.clear_input_before_write;
+        let mut result = Ok(());
+        let mut start = 0;
+        while start < data.len() {
+            let ninth_bit = ninth_bits[start];
+            let end = ninth_bits[start..]
+                .iter()
+                .position(|&bit| bit != ninth_bit)
+                .map_or(data.len(), |len| start + len);
+
+            let mut termios = original;
+            termios.c_cflag |= libc::PARENB | libc::CMSPAR;
+            if ninth_bit {
+                termios.c_cflag |= libc::PARODD; // mark
+            } else {
+                termios.c_cflag &= !libc::PARODD; // space
+            }
+            if unsafe { libc::tcsetattr(fd, libc::TCSADRAIN, &termios) } != 0 {
+                result = Err(std::io::Error::last_os_error());
+                break;
+            }
+
+            result = self.write_all_rs485(&data[start..end]);
+            self.clear_input_before_write = false;
+            if result.is_err() {
+                break;
+            }
+            start = end;
+        }
+        self.clear_input_before_write = clear_input_before_write;
+
+        if unsafe { libc::tcsetattr(fd, libc::TCSADRAIN, &original) } != 0 && result.is_ok() {
+            result = Err(std::io::Error::last_os_error());
+        }
+        result
+    }
+
+    /// Write all of `data` with RS-485 control, failing if the port stops accepting data
+    fn write_all_rs485(&mut self, mut data: &[u8]) -> Result<(), std::io::Error> {
+        while !data.is_empty() {
+            match self.write_rs485(data)? {
+                0 => {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::WriteZero,
+                        "write stalled",
+                    ))
                 }
+                n => data = &data[n..],
+            }
+        }
+        Ok(())
+    }
+
+    /// Set or clear HUPCL, which controls whether closing the port drops DTR/RTS.
+    ///
+    /// With HUPCL set, every close deasserts the modem lines and the next open raises
+    /// them again, producing a pulse that resets boards such as Arduinos.
+    pub fn set_hangup_on_close(&mut self, enabled: bool) -> Result<(), std::io::Error> {
+        self.update_termios(|termios| {
+            if enabled {
+                termios.c_cflag |= libc::HUPCL;
+            } else {
+                termios.c_cflag &= !libc::HUPCL;
+            }
+        })
+    }
+
+    /// Choose whether closing the port restores the termios settings it had before it was
+    /// opened, so that the next program to open it finds them unchanged. Fails if they were
+    /// not captured on open.
+    pub fn set_restore_on_close(&mut self, enabled: bool) -> Result<(), std::io::Error> {
+        if enabled && self.original_termios.is_none() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "the original settings were not captured when the port was opened",
+            ));
+        }
+        self.restore_on_close = enabled;
+        Ok(())
+    }
+
+    /// Restore the termios settings captured on open, if enabled. HUPCL keeps its current
+    /// state, so that the close itself follows `set_hangup_on_close`; pending output is sent
+    /// with the current settings first (TCSETSW2, the termios2 form of TCSADRAIN).
+    fn restore_original_termios(&mut self) {
+        let Some(mut original) = self.original_termios.filter(|_| self.restore_on_close) else {
+            return;
+        };
+        let fd = self.port.as_raw_fd();
+        let mut current = unsafe { std::mem::zeroed::<libc::termios2>() };
+        if unsafe { libc::ioctl(fd, libc::TCGETS2, &mut current as *mut libc::termios2) } != 0 {
+            return;
+        }
+        original.c_cflag = (original.c_cflag & !libc::HUPCL) | (current.c_cflag & libc::HUPCL);
+        unsafe { libc::ioctl(fd, libc::TCSETSW2, &original as *const libc::termios2) };
+    }
+
+    /// Set or clear CLOCAL, which makes the port ignore the modem control lines.
+    ///
+    /// `serialport` sets CLOCAL on open, so 3-wire connections without DCD work out of the
+    /// box. Clearing it makes the driver honor carrier detect: opens may wait for DCD, and
+    /// losing DCD hangs up the port.
+    pub fn set_ignore_modem_lines(&mut self, ignore: bool) -> Result<(), std::io::Error> {
+        self.update_termios(|termios| {
+            if ignore {
+                termios.c_cflag |= libc::CLOCAL;
+            } else {
+                termios.c_cflag &= !libc::CLOCAL;
+            }
+        })
+    }
+
+    /// Write the raw termios VMIN and VTIME values.
+    ///
+    /// Reads first wait for the port to become readable (up to the port timeout), so these
+    /// only govern how much data a read collects once the first byte is there.
+    pub fn set_vmin_vtime(&mut self, vmin: u8, vtime: u8) -> Result<(), std::io::Error> {
+        self.update_termios(|termios| {
+            termios.c_cc[libc::VMIN] = vmin;
+            termios.c_cc[libc::VTIME] = vtime;
+        })
+    }
+
+    /// Set or clear ICANON, switching the input side between canonical and raw mode.
+    ///
+    /// In canonical mode the line discipline collects input into lines, so the port only
+    /// becomes readable once a line terminator has arrived and VMIN/VTIME are ignored. The
+    /// special editing characters (erase, kill, EOF) are interpreted while it is on.
+    pub fn set_canonical_mode(&mut self, canonical: bool) -> Result<(), std::io::Error> {
+        self.update_termios(|termios| {
+            if canonical {
+                termios.c_lflag |= libc::ICANON;
+            } else {
+                termios.c_lflag &= !libc::ICANON;
+            }
+        })
+    }
+
+    /// Find the baud rates the driver accepts.
+    ///
+    /// Each rate of the standard termios speed table is applied with TCSETS2 and read back;
+    /// drivers reject or replace rates their clock cannot produce. Custom rates are tried
+    /// the same way through BOTHER with a rate outside the table. The original settings
+    /// are restored afterwards. Returns the accepted standard rates and whether custom
+    /// rates are available.
+    pub fn probe_baud_rates(&mut self) -> Result<(Vec<u32>, bool), std::io::Error> {
+        let fd = self.port.as_raw_fd();
+        let get = |termios: &mut libc::termios2| {
+            if unsafe { libc::ioctl(fd, libc::TCGETS2, termios as *mut libc::termios2) } != 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(())
+        };
+        let mut original = unsafe { std::mem::zeroed::<libc::termios2>() };
+        get(&mut original)?;
+
+        // Apply a speed, with the input speed following the output speed, and read it back
+        let try_speed = |code: libc::tcflag_t, rate: u32| {
+            let mut termios = original;
+            termios.c_cflag &= !(libc::CBAUD | libc::CIBAUD);
+            termios.c_cflag |= code;
+            termios.c_ispeed = rate;
+            termios.c_ospeed = rate;
+            if unsafe { libc::ioctl(fd, libc::TCSETS2, &termios as *const libc::termios2) } != 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            let mut applied = unsafe { std::mem::zeroed::<libc::termios2>() };
+            get(&mut applied).map(|_| applied)
+        };
+
+        let mut rates = Vec::new();
+        for &(code, rate) in STANDARD_BAUD_RATES {
+            if let Ok(applied) = try_speed(code, rate) {
+                if applied.c_cflag & libc::CBAUD == code {
+                    rates.push(rate);
+                }
+            }
+        }
+        let custom = match try_speed(libc::BOTHER, CUSTOM_BAUD_PROBE_RATE) {
+            Ok(applied) => {
+                applied.c_cflag & libc::CBAUD == libc::BOTHER
+                    && applied.c_ospeed.abs_diff(CUSTOM_BAUD_PROBE_RATE)
+                        <= CUSTOM_BAUD_PROBE_RATE / 50
+            }
+            Err(_) => false,
+        };
+
+        if unsafe { libc::ioctl(fd, libc::TCSETS2, &original as *const libc::termios2) } != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok((rates, custom))
+    }
+
+    /// Run the receiver and transmitter at different baud rates.
+    ///
+    /// Each rate uses its termios speed code if it is in the standard table and BOTHER
+    /// otherwise, with the input speed in CIBAUD and the output speed in CBAUD. Most UART
+    /// drivers clock both directions from one divisor and silently apply the output rate to
+    /// both, so the applied speeds are read back; if they do not match the request within
+    /// 2%, the previous settings are restored and an `Unsupported` error is returned.
+    pub fn set_split_baud(&mut self, input: u32, output: u32) -> Result<(), std::io::Error> {
+        let fd = self.port.as_raw_fd();
+        let mut original = unsafe { std::mem::zeroed::<libc::termios2>() };
+        if unsafe { libc::ioctl(fd, libc::TCGETS2, &mut original as *mut libc::termios2) } != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+
+        let speed_code = |rate: u32| {
+            STANDARD_BAUD_RATES
+                .iter()
+                .find(|&&(_, standard)| standard == rate)
+                .map_or(libc::BOTHER, |&(code, _)| code)
+        };
+        let mut termios = original;
+        termios.c_cflag &= !(libc::CBAUD | libc::CIBAUD);
+        termios.c_cflag |= speed_code(output) | (speed_code(input) << libc::IBSHIFT);
+        termios.c_ispeed = input;
+        termios.c_ospeed = output;
+        if unsafe { libc::ioctl(fd, libc::TCSETS2, &termios as *const libc::termios2) } != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+
+        let mut applied = unsafe { std::mem::zeroed::<libc::termios2>() };
+        if unsafe { libc::ioctl(fd, libc::TCGETS2, &mut applied as *mut libc::termios2) } != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        let close = |applied: u32, requested: u32| applied.abs_diff(requested) <= requested / 50;
+        if close(applied.c_ispeed, input) && close(applied.c_ospeed, output) {
+            return Ok(());
+        }
+        unsafe { libc::ioctl(fd, libc::TCSETS2, &original as *const libc::termios2) };
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            format!(
+                "driver does not support split speeds (applied input {}, output {})",
+                applied.c_ispeed, applied.c_ospeed
+            ),
+        ))
+    }
+
+    /// Line speeds the driver reports as applied, as (input, output) in baud.
+    ///
+    /// Read back with TCGETS2, so custom BOTHER rates are reported exactly. Drivers that
+    /// encode the rate their clock divisor actually achieves report it here, which reveals
+    /// the divisor rounding error behind framing problems at high speeds.
+    pub fn actual_baud_rates(&self) -> Result<(u32, u32), std::io::Error> {
+        let fd = self.port.as_raw_fd();
+        let mut termios = unsafe { std::mem::zeroed::<libc::termios2>() };
+        if unsafe { libc::ioctl(fd, libc::TCGETS2, &mut termios as *mut libc::termios2) } != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        // An input speed of 0 means "same as output"
+        let input = if termios.c_ispeed == 0 { termios.c_ospeed } else { termios.c_ispeed };
+        Ok((input, termios.c_ospeed))
+    }
+
+    /// Switch the line settings without corrupting a character in flight.
+    ///
+    /// Pending output is drained (subject to the drain timeout) and stale input cleared, then
+    /// all settings are applied in one TCSETSW2 update, the termios2 form of `tcsetattr` with
+    /// TCSADRAIN. The applied settings are read back; if the driver did not apply one of them
+    /// (the baud rate within 2%), the previous settings are restored and an `Unsupported`
+    /// error naming the rejected settings is returned.
+    pub fn reconfigure(
+        &mut self,
+        baud_rate: u32,
+        data_bits: DataBits,
+        stop_bits: StopBits,
+        parity: Parity,
+    ) -> Result<(), std::io::Error> {
+        self.drain_for(None);
+        self.clear_input()?;
+
+        let fd = self.port.as_raw_fd();
+        let mut original = unsafe { std::mem::zeroed::<libc::termios2>() };
+        if unsafe { libc::ioctl(fd, libc::TCGETS2, &mut original as *mut libc::termios2) } != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+
+        let speed_code = STANDARD_BAUD_RATES
+            .iter()
+            .find(|&&(_, standard)| standard == baud_rate)
+            .map_or(libc::BOTHER, |&(code, _)| code);
+        // CMSPAR is left as it is, the way `set_parity` leaves it, so mark/space parity set up
+        // outside the serialport settings survives a reconfigure
+        let mut termios = original;
+        termios.c_cflag &= !(libc::CBAUD
+            | libc::CIBAUD
+            | libc::CSIZE
+            | libc::CSTOPB
+            | libc::PARENB
+            | libc::PARODD);
+        termios.c_cflag |= speed_code;
+        termios.c_cflag |= match data_bits {
+            DataBits::Five => libc::CS5,
+            DataBits::Six => libc::CS6,
+            DataBits::Seven => libc::CS7,
+            DataBits::Eight => libc::CS8,
+        };
+        if stop_bits == StopBits::Two {
+            termios.c_cflag |= libc::CSTOPB;
+        }
+        match parity {
+            Parity::None => {
+                termios.c_iflag &= !libc::INPCK;
+                termios.c_iflag |= libc::IGNPAR;
+            }
+            Parity::Odd | Parity::Even => {
+                termios.c_cflag |= libc::PARENB;
+                if parity == Parity::Odd {
+                    termios.c_cflag |= libc::PARODD;
+                }
+                termios.c_iflag |= libc::INPCK;
+                termios.c_iflag &= !libc::IGNPAR;
+            }
+        }
+        termios.c_ispeed = baud_rate;
+        termios.c_ospeed = baud_rate;
+        if unsafe { libc::ioctl(fd, libc::TCSETSW2, &termios as *const libc::termios2) } != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+
+        let mut applied = unsafe { std::mem::zeroed::<libc::termios2>() };
+        if unsafe { libc::ioctl(fd, libc::TCGETS2, &mut applied as *mut libc::termios2) } != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        let mut rejected = Vec::new();
+        if applied.c_ospeed.abs_diff(baud_rate) > baud_rate / 50 {
+            rejected.push(format!("baud rate {} (applied {})", baud_rate, applied.c_ospeed));
+        }
+        let differs = |mask: libc::tcflag_t| applied.c_cflag & mask != termios.c_cflag & mask;
+        if differs(libc::CSIZE) {
+            rejected.push(format!("data bits {}", u8::from(data_bits)));
+        }
+        if differs(libc::CSTOPB) {
+            rejected.push(format!("stop bits {}", u8::from(stop_bits)));
+        }
+        if differs(libc::PARENB | libc::PARODD) {
+            rejected.push(format!("parity {}", parity));
+        }
+        if rejected.is_empty() {
+            return Ok(());
+        }
+        unsafe { libc::ioctl(fd, libc::TCSETS2, &original as *const libc::termios2) };
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            format!("driver did not apply {}", rejected.join(", ")),
+        ))
+    }
+
+    /// Change one line setting, such as the parity, without corrupting a character in flight.
+    ///
+    /// Pending output is drained first (subject to the drain timeout); unlike `reconfigure`,
+    /// received input is kept. The setting is read back, and if the driver did not apply it,
+    /// the previous value is restored and an `Unsupported` error naming `description` is
+    /// returned.
+    pub fn set_line_setting<T: PartialEq + Copy>(
+        &mut self,
+        description: &str,
+        value: T,
+        get: impl Fn(&TTYPort) -> serialport::Result<T>,
+        set: impl Fn(&mut TTYPort, T) -> serialport::Result<()>,
+    ) -> Result<(), std::io::Error> {
+        let previous = get(&self.port)?;
+        self.drain_for(None);
+        set(&mut self.port, value)?;
+        if get(&self.port)? == value {
+            return Ok(());
+        }
+        let _ = set(&mut self.port, previous);
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            format!("driver did not apply {}", description),
+        ))
+    }
+
+    /// Transmit the START (XON) or STOP (XOFF) character with `tcflow`.
+    ///
+    /// The character is sent by the driver ahead of any queued output, and is not subject
+    /// to the port's own output being stopped, so it can resume a peer even while we are
+    /// paused ourselves.
+    pub fn send_flow_char(&mut self, xon: bool) -> Result<(), std::io::Error> {
+        let action = if xon { libc::TCION } else { libc::TCIOFF };
+        if unsafe { libc::tcflow(self.port.as_raw_fd(), action) } != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// Check whether output is currently held back by software (XON/XOFF) flow control.
+    ///
+    /// The line discipline consumes XON/XOFF itself and does not expose its stopped state,
+    /// so this samples the output queue twice, a few character times apart: with XON/XOFF
+    /// enabled and pending output that does not drain at all, the peer has sent XOFF.
+    /// Drivers without an output queue (such as pseudo-terminals) instead stop accepting
+    /// writes while stopped, so with an empty queue the port is checked for writability.
+    /// A UART keeps accepting writes while stopped, so with an empty queue its XOFF state is
+    /// not visible (not even through TIOCGICOUNT or the line status) and false is returned.
+    pub fn output_stopped(&mut self) -> Result<bool, std::io::Error> {
+        if self.port.flow_control()? != FlowControl::Software {
+            return Ok(false);
+        }
+        let before = self.port.bytes_to_write()?;
+        if before == 0 {
+            let mut fd = libc::pollfd {
+                fd: self.port.as_raw_fd(),
+                events: libc::POLLOUT,
+                revents: 0,
+            };
+            if unsafe { libc::poll(&mut fd, 1, 0) } < 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            return Ok(fd.revents & libc::POLLOUT == 0);
+        }
+
+        // About 10 bits per character; wait long enough for several to leave the UART
+        let baud_rate = self.port.baud_rate()?.max(1) as u64;
+        let sample = Duration::from_micros(4 * 10 * 1_000_000 / baud_rate)
+            .clamp(OUTPUT_STOPPED_MIN_SAMPLE, OUTPUT_STOPPED_MAX_SAMPLE);
+        std::thread::sleep(sample);
+
+        Ok(self.port.bytes_to_write()? >= before)
+    }
+
+    /// Switch between raw mode and text output processing.
+    ///
+    /// Raw mode (the state after open) disables all input/output processing like
+    /// `cfmakeraw`, but leaves the control flags alone so data bits and parity are
+    /// kept, and leaves IXON/IXOFF alone so software flow control is kept. Text mode
+    /// enables OPOST/ONLCR, translating `\n` to `\r\n` on output.
+    pub fn set_raw_mode(&mut self, raw: bool) -> Result<(), std::io::Error> {
+        self.update_termios(|termios| {
+            if raw {
+                termios.c_iflag &= !(libc::IGNBRK
+                    | libc::BRKINT
+                    | libc::ISTRIP
+                    | libc::INLCR
+                    | libc::IGNCR
+                    | libc::ICRNL);
+                termios.c_oflag &= !libc::OPOST;
+                termios.c_lflag &=
+                    !(libc::ECHO | libc::ECHONL | libc::ICANON | libc::ISIG | libc::IEXTEN);
+            } else {
+                termios.c_oflag |= libc::OPOST | libc::ONLCR;
+            }
+        })
+    }
+
+    /// Read the driver's serial_struct via TIOCGSERIAL
+    fn get_serial_struct(&self) -> Result<SerialStruct, std::io::Error> {
+        let fd = self.port.as_raw_fd();
+        let mut serial = unsafe { std::mem::zeroed::<SerialStruct>() };
+        if unsafe { libc::ioctl(fd, TIOCGSERIAL, &mut serial as *mut SerialStruct) } != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(serial)
+    }
+
+    /// Apply a serial_struct via TIOCSSERIAL
+    fn set_serial_struct(&mut self, serial: &SerialStruct) -> Result<(), std::io::Error> {
+        let fd = self.port.as_raw_fd();
+        if unsafe { libc::ioctl(fd, TIOCSSERIAL, serial as *const SerialStruct) } != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// Configure a custom baud divisor (line rate = base_baud / divisor).
+    ///
+    /// Sets `custom_divisor` and `ASYNC_SPD_CUST`, and switches the termios speed to
+    /// 38400, which is the rate the kernel replaces with the custom one. A base_baud
+    /// of 0 keeps the driver's value. A divisor of 0 clears the custom speed, after
+    /// which the baud rate must be set again.
+    pub fn set_custom_divisor(&mut self, base_baud: u32, divisor: u32) -> Result<(), std::io::Error> {
+        let mut serial = self.get_serial_struct()?;
+        serial.flags &= !ASYNC_SPD_MASK;
+        if divisor != 0 {
+            serial.flags |= ASYNC_SPD_CUST;
+        }
+        serial.custom_divisor = divisor as libc::c_int;
+        if base_baud != 0 {
+            serial.baud_base = base_baud as libc::c_int;
+        }
+        self.set_serial_struct(&serial)?;
+
+        if divisor != 0 {
+            self.update_termios(|termios| unsafe {
+                libc::cfsetispeed(termios, libc::B38400);
+                libc::cfsetospeed(termios, libc::B38400);
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Set how long closing the port waits, in hundredths of a second.
+    ///
+    /// `close_delay` is how long DTR/RTS stay low after close before the port can be
+    /// reopened. `closing_wait` limits how long close waits for pending output to drain;
+    /// 0 waits indefinitely and 65535 does not wait at all.
+    pub fn set_close_timing(&mut self, close_delay: u16, closing_wait: u16) -> Result<(), std::io::Error> {
+        let mut serial = self.get_serial_struct()?;
+        serial.close_delay = close_delay;
+        serial.closing_wait = closing_wait;
+        self.set_serial_struct(&serial)
+    }
+
+    /// Path of the RX FIFO trigger level attribute of the tty behind the port's descriptor.
+    ///
+    /// The 8250 driver publishes the level as `rx_trig_bytes` in the tty's sysfs directory,
+    /// which is named after the device node the descriptor refers to (e.g. ttyS0). There is
+    /// no serial_struct field for it, and other drivers do not expose it at all.
+    fn rx_trigger_path(&self) -> Result<std::path::PathBuf, std::io::Error> {
+        let device = std::fs::read_link(format!("/proc/self/fd/{}", self.port.as_raw_fd()))?;
+        let path = device
+            .file_name()
+            .map(|name| std::path::Path::new("/sys/class/tty").join(name).join("rx_trig_bytes"))
+            .filter(|path| path.exists());
+        path.ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "driver does not expose an RX FIFO trigger level (rx_trig_bytes)",
+            )
+        })
+    }
+
+    /// Read the UART's RX FIFO trigger level, in bytes
+    pub fn fifo_trigger_level(&self) -> Result<u32, std::io::Error> {
+        let level = std::fs::read_to_string(self.rx_trigger_path()?)?;
+        level.trim().parse().map_err(|_| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("unexpected rx_trig_bytes value: {:?}", level.trim()),
+            )
+        })
+    }
+
+    /// Set the UART's RX FIFO trigger level, in bytes.
+    ///
+    /// The driver rounds the request down to the nearest level the UART supports (e.g. 1, 4,
+    /// 8 or 14 on a 16550A) and rejects levels below the smallest one; the applied level is
+    /// read back and returned. Writing the attribute usually requires root.
+    pub fn set_fifo_trigger_level(&mut self, level: u32) -> Result<u32, std::io::Error> {
+        std::fs::write(self.rx_trigger_path()?, level.to_string())?;
+        self.fifo_trigger_level()
+    }
+
+    /// Read all modem input lines with a single TIOCMGET.
+    ///
+    /// Returns a bitmask of `MODEM_*` flags.
+    pub fn modem_status(&mut self) -> Result<u32, std::io::Error> {
+        let fd = self.port.as_raw_fd();
+        let mut lines: libc::c_int = 0;
+        if unsafe { libc::ioctl(fd, libc::TIOCMGET, &mut lines) } != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+
+        let mut bits = 0;
+        if lines & libc::TIOCM_CTS != 0 {
+            bits |= MODEM_CTS;
+        }
+        if lines & libc::TIOCM_DSR != 0 {
+            bits |= MODEM_DSR;
+        }
+        if lines & libc::TIOCM_RI != 0 {
+            bits |= MODEM_RI;
+        }
+        if lines & libc::TIOCM_CD != 0 {
+            bits |= MODEM_CD;
+        }
+        Ok(bits)
+    }
+
+    /// Check if kernel RS-485 mode is active
+    pub fn is_kernel_rs485_active(&self) -> bool {
+        self.kernel_rs485_active
+    }
+
+    /// How RS-485 direction control is actually performed (RS485_ACTUAL_*)
+    pub fn rs485_actual_mode(&self) -> u8 {
+        self.rs485_actual_mode
+    }
+
+    /// Check that the file descriptor is still open and the device has not hung up
+    pub fn is_valid(&self) -> bool {
+        fd_is_valid(self.port.as_raw_fd())
+    }
+
+    /// Start a thread that probes the port every `probe_interval` and reopens it after a
+    /// disconnect, replacing any thread started before.
+    ///
+    /// The device is reopened by the name the port was opened with, falling back to the
+    /// device the descriptor refers to now, which matters for ports opened through a
+    /// stable symlink such as `/dev/serial/by-id/...`. `lock` locks the port's handle around
+    /// the descriptor swap.
+    pub fn enable_auto_reconnect(
+        &mut self,
+        probe_interval: Duration,
+        lock: PortLocker,
+    ) -> Result<(), std::io::Error> {
+        self.reconnect = None;
+        let fd = self.port.as_raw_fd();
+        let path = match self.port.name() {
+            Some(name) => name.into_bytes(),
+            None => std::fs::read_link(format!("/proc/self/fd/{}", fd))?
+                .as_os_str()
+                .as_bytes()
+                .to_vec(),
+        };
+        let path = CString::new(path).map_err(|_| {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, "port name contains a NUL byte")
+        })?;
+        let open_flags = match self.access_mode {
+            AccessMode::ReadWrite => libc::O_RDWR,
+            AccessMode::ReadOnly => libc::O_RDONLY,
+            AccessMode::WriteOnly => libc::O_WRONLY,
+        };
+        self.reconnect =
+            Some(ReconnectWatchdog::spawn(fd, path, open_flags, probe_interval, lock)?);
+        Ok(())
+    }
+
+    /// Stop the reconnect thread, if any, and wait for it to exit
+    pub fn disable_auto_reconnect(&mut self) {
+        self.reconnect = None;
+    }
+
+    /// Stop the frame reader thread, if any, and wait for it to exit.
+    ///
+    /// Closing the reader's clone of the port clears TIOCEXCL, which applies to the whole tty,
+    /// so exclusive access is asserted again for the port itself.
+    pub fn stop_frame_reader(&mut self) {
+        if self.frame_reader.take().is_some() && self.port.exclusive() {
+            unsafe { libc::ioctl(self.port.as_raw_fd(), libc::TIOCEXCL) };
+        }
+    }
+
+    /// Connection state as one of the `CONNECTION_*` values.
+    ///
+    /// Without a reconnect thread, a disconnected port is reported as failed.
+    pub fn connection_state(&self) -> u8 {
+        match &self.reconnect {
+            Some(watchdog) => watchdog.state(),
+            None if self.is_valid() => CONNECTION_CONNECTED,
+            None => CONNECTION_FAILED,
+        }
+    }
+
+    /// Read from the port, waking up early if `cancel_read` is called.
+    ///
+    /// Polls the port together with the cancellation eventfd using the port timeout.
+    /// A cancellation returns the error from `read_cancelled_error`; a timeout returns
+    /// `TimedOut`, as the serialport read does.
+    pub fn read_data(&mut self, buf: &mut [u8]) -> Result<usize, std::io::Error> {
+        self.access_mode.check(false)?;
+        let len = cap_read_len(buf.len(), self.max_read_chunk);
+        let buf = &mut buf[..len];
+        if !self.read_residual.is_empty() {
+            let n = len.min(self.read_residual.len());
+            buf[..n].copy_from_slice(&self.read_residual[..n]);
+            self.read_residual.drain(..n);
+            return Ok(n);
+        }
+        if self.cancel_fd >= 0 {
+            let timeout_ms =
+                self.port.timeout().as_millis().min(libc::c_int::MAX as u128) as libc::c_int;
+            self.wait_readable(timeout_ms)?;
+        }
+
+        let depth = self.port.bytes_to_read().unwrap_or(0);
+        self.observe_input_depth(depth);
+        // Read the descriptor directly, as the serialport read drops the errno that tells a
+        // transient EIO from a fatal error
+        let fd = self.port.as_raw_fd();
+        let timeout_ms =
+            self.port.timeout().as_millis().min(libc::c_int::MAX as u128) as libc::c_int;
+        let (result, retries) = read_with_retries(self.read_retries, || {
+            wait_readable(fd, -1, timeout_ms)?;
+            read_fd(fd, buf)
+        });
+        self.read_retry_count += u64::from(retries);
+        let result = detect_eof(result, buf.len());
+        self.eof = is_eof(&result);
+        if let Ok(n) = result {
+            capture_traffic(&mut self.capture, &self.last_warning, CAPTURE_RECEIVED, &buf[..n]);
+            if self.bit_reversal {
+                reverse_bits_in_place(&mut buf[..n]);
+            }
+        }
+        self.check_input_overruns();
+        result
+    }
+
+    /// Discard pending input, including bytes kept from a pattern match
+    pub fn clear_input(&mut self) -> Result<(), std::io::Error> {
+        self.read_residual.clear();
+        self.port.clear(serialport::ClearBuffer::Input)?;
+        Ok(())
+    }
+
+    /// Read a single byte, blocking indefinitely if `timeout` is None.
+    ///
+    /// Blocking without a timeout behaves like termios VMIN=1/VTIME=0: the call returns
+    /// as soon as one byte has arrived. Returns None on timeout; a cancellation returns the
+    /// error from `read_cancelled_error`.
+    pub fn read_one(&mut self, timeout: Option<Duration>) -> Result<Option<u8>, std::io::Error> {
+        self.access_mode.check(false)?;
+        if !self.read_residual.is_empty() {
+            return Ok(Some(self.read_residual.remove(0)));
+        }
+        let timeout_ms = match timeout {
+            Some(timeout) => timeout.as_millis().min(libc::c_int::MAX as u128) as libc::c_int,
+            None => -1,
+        };
+        match self.wait_readable(timeout_ms) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::TimedOut => return Ok(None),
+            Err(e) => return Err(e),
+        }
+
+        let mut byte = [0u8; 1];
+        let result = detect_eof(self.port.read(&mut byte), byte.len());
+        self.eof = is_eof(&result);
+        if let Ok(n) = result {
+            capture_traffic(&mut self.capture, &self.last_warning, CAPTURE_RECEIVED, &byte[..n]);
+            if self.bit_reversal {
+                reverse_bits_in_place(&mut byte[..n]);
+            }
+        }
+        match result {
+            Ok(1) => Ok(Some(byte[0])),
+            Ok(_) => Ok(None),
+            Err(e) if e.kind() == std::io::ErrorKind::TimedOut => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Wait until the port is readable, polling the cancellation eventfd as well.
+    /// A timeout_ms of -1 waits indefinitely.
+    fn wait_readable(&self, timeout_ms: libc::c_int) -> Result<(), std::io::Error> {
+        wait_readable(self.port.as_raw_fd(), self.cancel_fd, timeout_ms)
+    }
+
+    /// Check whether a read would find input without waiting: bytes kept from a pattern match,
+    /// queued input, or a hang-up or error for the read to report
+    pub fn has_input(&mut self) -> Result<bool, std::io::Error> {
+        self.access_mode.check(false)?;
+        if !self.read_residual.is_empty() {
+            return Ok(true);
+        }
+        let mut fd = libc::pollfd {
+            fd: self.port.as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        match unsafe { libc::poll(&mut fd, 1, 0) } {
+            ready if ready < 0 => Err(std::io::Error::last_os_error()),
+            ready => Ok(ready > 0),
+        }
+    }
+
+    /// The port's file descriptor, for waiting on it without the wrapper
+    pub fn raw_fd(&self) -> RawFd {
+        self.port.as_raw_fd()
+    }
+
+    /// Wake up a read blocked in `read_data` on the port with this cancellation fd.
+    ///
+    /// Takes the fd rather than `&self` because it is called from another thread
+    /// while the reading thread holds the wrapper. If no read is in progress, the
+    /// next read returns immediately as cancelled.
+    pub fn cancel_read(cancel_fd: RawFd) -> Result<(), std::io::Error> {
+        if cancel_fd < 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "Read cancellation is not available for this port",
+            ));
+        }
+        let counter = 1u64;
+        let result =
+            unsafe { libc::write(cancel_fd, &counter as *const u64 as *const libc::c_void, 8) };
+        if result < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// Get an eventfd that becomes readable when the port has input, starting the thread
+    /// that feeds it on first use. The descriptor stays owned by the port and is closed
+    /// with it.
+    pub fn readable_event_fd(&mut self) -> Result<RawFd, std::io::Error> {
+        if let Some(notifier) = &self.readable_notifier {
+            return Ok(notifier.event_fd);
+        }
+        let notifier = ReadableNotifier::spawn(self.port.as_raw_fd())?;
+        let event_fd = notifier.event_fd;
+        self.readable_notifier = Some(notifier);
+        self.signal_residual_input();
+        Ok(event_fd)
+    }
+
+    /// Reset the eventfd from `readable_event_fd` after it has been signalled.
+    /// Returns true if it was signalled, false if not or if it was never requested.
+    pub fn reset_readable_event(&self) -> bool {
+        let signalled = self
+            .readable_notifier
+            .as_ref()
+            .is_some_and(ReadableNotifier::reset);
+        self.signal_residual_input();
+        signalled
+    }
+
+    /// Signal the readable event while bytes kept in `read_residual` are waiting to be read,
+    /// as the notifier thread only sees input on the port
+    pub fn signal_residual_input(&self) {
+        if let Some(notifier) = &self.readable_notifier {
+            if !self.read_residual.is_empty() {
+                notifier.signal();
+            }
+        }
+    }
+
+    /// Get the cancellation eventfd for use with `cancel_read`
+    pub fn cancel_fd(&self) -> RawFd {
+        self.cancel_fd
+    }
+
+    /// Start recording reads and writes to a libpcap file at `path`, replacing any capture
+    /// already running
+    pub fn start_capture(&mut self, path: &str) -> Result<(), std::io::Error> {
+        self.stop_capture()?;
+        self.capture = Some(TrafficCapture::create(path)?);
+        Ok(())
+    }
+
+    /// Stop recording and flush the capture file; does nothing if no capture is running
+    pub fn stop_capture(&mut self) -> Result<(), std::io::Error> {
+        match self.capture.take() {
+            Some(capture) => capture.finish(),
+            None => Ok(()),
+        }
+    }
+
+    /// Record data received through another handle to the device, such as the frame
+    /// reader's, if a capture is running
+    pub fn capture_received(&mut self, data: &[u8]) {
+        capture_traffic(&mut self.capture, &self.last_warning, CAPTURE_RECEIVED, data);
+    }
+
+    /// Write data honoring the configured write blocking policy and pacing
+    fn write_data(&mut self, data: &[u8]) -> Result<usize, std::io::Error> {
+        let timeout = self.port.timeout();
+        let result = write_paced(
+            &mut self.port,
+            data,
+            self.write_blocking_policy,
+            timeout,
+            self.write_pacing,
+        );
+        if let Ok(n) = result {
+            let data = &data[..n];
+            capture_traffic(&mut self.capture, &self.last_warning, CAPTURE_TRANSMITTED, data);
+        }
+        // Sample before any drain, while the output queue is at its deepest
+        self.queue_high_water
+            .observe_output(self.port.bytes_to_write().unwrap_or(0));
+        result
+    }
+
+    /// Write data, stopping early at the deadline if there is one. With DTR/DSR flow control
+    /// the data goes out in drained chunks, waiting for DSR before each one.
+    fn write_for(
+        &mut self,
+        data: &[u8],
+        deadline: Option<Instant>,
+    ) -> Result<usize, std::io::Error> {
+        if !self.dtr_flow_control || data.len() <= DSR_CHECK_CHUNK {
+            return self.write_chunk_for(data, deadline);
+        }
+        let mut written: usize = 0;
+        for chunk in data.chunks(DSR_CHECK_CHUNK) {
+            if written > 0 {
+                let discarded = self.drain_for(deadline);
+                if discarded > 0 {
+                    return Ok(written.saturating_sub(discarded));
+                }
+                if self.wait_for_dsr(deadline).is_err() {
+                    break;
+                }
+            }
+            match self.write_chunk_for(chunk, deadline) {
+                Ok(n) => {
+                    written += n;
+                    if n < chunk.len() {
+                        break;
+                    }
+                }
+                Err(e) if written == 0 => return Err(e),
+                Err(_) => break,
+            }
+        }
+        Ok(written)
+    }
+
+    /// Write data without DSR checks, stopping early at the deadline if there is one
+    fn write_chunk_for(
+        &mut self,
+        data: &[u8],
+        deadline: Option<Instant>,
+    ) -> Result<usize, std::io::Error> {
+        let Some(deadline) = deadline else {
+            return self.write_data(data);
+        };
+        let result = write_until(
+            &mut self.port,
+            data,
+            self.write_blocking_policy,
+            self.write_pacing,
+            deadline,
+        );
+        if let Ok(n) = result {
+            let data = &data[..n];
+            capture_traffic(&mut self.capture, &self.last_warning, CAPTURE_TRANSMITTED, data);
+        }
+        self.queue_high_water
+            .observe_output(self.port.bytes_to_write().unwrap_or(0));
+        result
+    }
+
+    /// Drain the output before releasing the bus. With a deadline, output still queued
+    /// when it passes is discarded; returns the number of bytes discarded.
+    /// Without one, the drain timeout applies instead and giving up records a warning.
+    fn drain_for(&mut self, deadline: Option<Instant>) -> usize {
+        if let Some(deadline) = deadline {
+            return drain_until(&mut self.port, deadline).unwrap_or(0);
+        }
+        let Some(timeout) = self.drain_timeout else {
+            let _ = self.port.flush();
+            return 0;
+        };
+        let discarded = drain_until(&mut self.port, Instant::now() + timeout).unwrap_or(0);
+        if discarded > 0 {
+            if let Ok(mut warning) = self.last_warning.lock() {
+                *warning = Some(format!(
+                    "drain timeout: output did not drain within {} ms, {} bytes discarded",
+                    timeout.as_millis(),
+                    discarded
+                ));
+            }
+        }
+        discarded
+    }
+
+    /// Send a DMX512 packet: the break and mark-after-break, then `data`, which is the start
+    /// code followed by the slots. DMX transceivers keep their driver enabled, so this is
+    /// rejected with RS-485 direction control configured.
+    pub fn send_dmx_frame(&mut self, data: &[u8]) -> Result<usize, std::io::Error> {
+        self.access_mode.check(true)?;
+        if self.listen_only {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::PermissionDenied,
+                "port is in listen-only mode",
+            ));
+        }
+        if self.control_mode != Rs485ControlMode::None {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "DMX512 frames cannot be sent with RS-485 direction control",
+            ));
+        }
+
+        send_dmx_break(&mut self.port)?;
+        write_fully(data, |rest| self.write_data(rest))
+    }
+
+    pub fn write_rs485(&mut self, data: &[u8]) -> Result<usize, std::io::Error> {
+        self.write_rs485_with(data, None)
+    }
+
+    /// Write data before `deadline`, releasing the RS-485 direction pin even if it passes
+    /// mid-write. Returns the bytes written, not counting any discarded to free the bus.
+    pub fn write_rs485_before(
+        &mut self,
+        data: &[u8],
+        deadline: Instant,
+    ) -> Result<usize, std::io::Error> {
+        self.write_rs485_with(data, Some(deadline))
+    }
+
+    /// True if a write only hands its data to the driver: no RS-485 direction pin, DSR check,
+    /// preamble or pacing has to be held around it, and the blocking policy waits for room.
+    /// Such a write can wait for room in the output queue with the port unlocked and go out
+    /// in the pieces the driver accepts.
+    pub fn writes_in_pieces(&self) -> bool {
+        self.control_mode == Rs485ControlMode::None
+            && !self.dtr_flow_control
+            && self.preamble_idle_bits == 0
+            && self.write_pacing.chunk_bytes == 0
+            && self.write_blocking_policy == WriteBlockingPolicy::Block
+    }
+
+    /// Write the start of `data`, at most `WRITABLE_PIECE` bytes, once the port polls
+    /// writable, otherwise like `write_rs485`. A piece that small fits in the room the driver
+    /// reported, so the write does not wait for more. The descriptor stays blocking, since the
+    /// frame reader and failsafe threads share its flags. The input is only cleared before the
+    /// write if `first` is true, so that a write that goes out in several pieces clears it once.
+    pub fn write_available(&mut self, data: &[u8], first: bool) -> Result<usize, std::io::Error> {
+        let piece = &data[..data.len().min(WRITABLE_PIECE)];
+        let clear_input_before_write = self.clear_input_before_write;
+        self.clear_input_before_write &= first;
+
+        let result = self.write_rs485(piece);
+
+        self.clear_input_before_write = clear_input_before_write;
+        result
+    }
+
+    fn write_rs485_with(
+        &mut self,
+        data: &[u8],
+        deadline: Option<Instant>,
+    ) -> Result<usize, std::io::Error> {
+        self.access_mode.check(true)?;
+        if self.listen_only {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::PermissionDenied,
+                "port is in listen-only mode",
+            ));
+        }
+
+        // Drop stale RX (leftovers from a previous exchange) before transmitting
+        if self.clear_input_before_write {
+            self.clear_input()?;
+        }
+
+        // Wait for the peer before driving the bus, not while holding the direction pin
+        if self.dtr_flow_control {
+            self.wait_for_dsr(deadline)?;
+        }
+
+        // No release while the write is under way. The failsafe may have released the pin
+        // behind our back already; assert it again for this write
+        if let Some(failsafe) = &self.failsafe {
+            failsafe.disarm();
+        }
+        if self.failsafe.as_ref().is_some_and(FailsafeWatchdog::take_tripped) {
+            self.transmit_held = false;
+            let receive_level = self.receive_level();
+            match self.control_pin {
+                Rs485ControlPin::RTS => self.current_rts = Some(receive_level),
+                Rs485ControlPin::DTR => self.current_dtr = Some(receive_level),
+            }
+        }
+
+        // Throttle between frames, before the direction pin is asserted
+        let delay = self.throughput_limit.delay(Instant::now());
+        let delay = match deadline {
+            Some(deadline) => delay.min(deadline.saturating_duration_since(Instant::now())),
+            None => delay,
+        };
+        sleep_unless_zero(delay);
+
+        // Reverse a copy, the caller's data is left as it is
+        let mut reversed = Vec::new();
+        let data = if self.bit_reversal {
+            reversed.extend(data.iter().map(|&b| BIT_REVERSE[b as usize]));
+            &reversed[..]
+        } else {
+            data
+        };
+
+        let result = self.write_frame(data, deadline);
+        if let Ok(written) = result {
+            self.throughput_limit.consume(written);
+        }
+        self.update_failsafe();
+        result
+    }
+
+    /// Hold the line idle for the configured preamble, counted from when the previous frame
+    /// has left the transmitter. Returns the number of bytes of the previous frame discarded
+    /// because they had not left by `deadline`; they were counted as written by the previous
+    /// write, so the caller takes them off its own count.
+    fn send_preamble(&mut self, deadline: Option<Instant>) -> Result<usize, std::io::Error> {
+        if self.preamble_idle_bits == 0 {
+            return Ok(0);
+        }
+        let duration = preamble_duration(self.preamble_idle_bits, self.port.baud_rate()?);
+        let discarded = self.drain_for(deadline);
+        let duration = match deadline {
+            Some(deadline) => duration.min(deadline.saturating_duration_since(Instant::now())),
+            None => duration,
+        };
+        precise_wait(duration);
+        Ok(discarded)
+    }
+
+    /// Fold the preamble for the current baud rate into the kernel RS-485 delay before
+    /// sending, reconfiguring the kernel when it has changed. The kernel only enables the
+    /// driver once data is queued, so this keeps the driver enabled for the preamble.
+    fn sync_kernel_preamble(&mut self) -> Result<(), std::io::Error> {
+        let preamble = match self.preamble_idle_bits {
+            0 => Duration::ZERO,
+            bits => preamble_duration(bits, self.port.baud_rate()?),
+        };
+        if preamble == self.kernel_preamble {
+            return Ok(());
+        }
+        self.kernel_preamble = preamble;
+        if !self.try_enable_kernel_rs485() {
+            return Err(std::io::Error::other("could not reconfigure kernel RS-485 mode"));
+        }
+        Ok(())
+    }
+
+    /// Write one frame, driving the RS-485 direction pin around it if configured
+    fn write_frame(
+        &mut self,
+        data: &[u8],
+        deadline: Option<Instant>,
+    ) -> Result<usize, std::io::Error> {
+        match self.control_mode {
+            Rs485ControlMode::None => {
+                // No RS-485 control, just write normally
+                let discarded = self.send_preamble(deadline)?;
+                self.write_for(data, deadline).map(|n| n.saturating_sub(discarded))
+            }
+            Rs485ControlMode::Auto if self.kernel_rs485_active => {
+                // Kernel handles RTS automatically, just write; it asserts RTS only once data
+                // is queued, so the preamble is part of its delay before sending
+                self.sync_kernel_preamble()?;
+                let result = self.write_for(data, deadline);
+                if !self.auto_flush && deadline.is_none() {
+                    return result;
+                }
+                // Still flush to ensure data is sent before kernel toggles RTS
+                let discarded = self.drain_for(deadline);
+                result.map(|n| n.saturating_sub(discarded))
+            }
+            Rs485ControlMode::Auto | Rs485ControlMode::Manual => {
+                // Manual RTS/DTR control
+                // Enable transmit, unless the pin is still held from the previous write
+                if !self.transmit_held {
+                    self.set_transmit_enable(true)?;
+                    sleep_unless_zero(self.rs485_pre_delay);
+                    self.transmit_held = self.hold_transmit_enable;
+                }
+                let discarded_before = self.send_preamble(deadline)?;
+
+                // Write the whole frame; a short write must not release the pin early
+                let result = write_fully(data, |rest| self.write_for(rest, deadline))
+                    .map(|n| n.saturating_sub(discarded_before));
+                if !self.auto_flush && deadline.is_none() {
+                    self.transmit_held = true;
+                }
+                if self.transmit_held {
+                    // Released by set_hold_transmit_enable(false) or flush_output
+                    return result;
+                }
+
+                // Flush to ensure data is sent
+                let discarded = self.drain_for(deadline);
+                sleep_unless_zero(self.rs485_post_delay);
+
+                // Disable transmit (back to receive mode)
+                self.set_transmit_enable(false)?;
+
+                result.map(|n| n.saturating_sub(discarded))
+            }
+        }
+    }
+}
+
+/// Read from a descriptor, keeping the errno of a failed read
+fn read_fd(fd: RawFd, buf: &mut [u8]) -> Result<usize, std::io::Error> {
+    let n = unsafe { libc::read(fd, buf.as_mut_ptr() as *mut libc::c_void, buf.len()) };
+    if n < 0 {
+        Err(std::io::Error::last_os_error())
+    } else {
+        Ok(n as usize)
+    }
+}
+
+/// Wait until the port with descriptor `port_fd` has room for output, for up to `timeout_ms`.
+/// Returns false on timeout.
+///
+/// Works on the descriptor rather than a wrapper, so that it can wait with the port unlocked.
+/// A poll interrupted by a signal counts as a timeout; the caller checks again.
+pub fn wait_writable(port_fd: RawFd, timeout_ms: libc::c_int) -> Result<bool, std::io::Error> {
+    let mut fd = libc::pollfd {
+        fd: port_fd,
+        events: libc::POLLOUT,
+        revents: 0,
+    };
+    match unsafe { libc::poll(&mut fd, 1, timeout_ms) } {
+        ready if ready > 0 => Ok(true),
+        0 => Ok(false),
+        _ => {
+            let e = std::io::Error::last_os_error();
+            if e.kind() == std::io::ErrorKind::Interrupted {
+                return Ok(false);
+            }
+            Err(e)
+        }
+    }
+}
+
+/// Wait until the port with descriptor `port_fd` is readable, polling the cancellation eventfd
+/// `cancel_fd` as well (-1 for none). A timeout_ms of -1 waits indefinitely.
+///
+/// Works on descriptors rather than a wrapper, so that it can wait with the port unlocked.
+/// A poll interrupted by a signal is resumed for the rest of the timeout. Only a readable
+/// `cancel_fd` counts as a cancellation, which returns the error from `read_cancelled_error`;
+/// a timeout returns `TimedOut`.
+pub fn wait_readable(
+    port_fd: RawFd,
+    cancel_fd: RawFd,
+    timeout_ms: libc::c_int,
+) -> Result<(), std::io::Error> {
+    let mut fds = [
+        libc::pollfd {
+            fd: port_fd,
+            events: libc::POLLIN,
+            revents: 0,
+        },
+        libc::pollfd {
+            fd: cancel_fd,
+            events: libc::POLLIN,
+            revents: 0,
+        },
+    ];
+    // Without an eventfd, only the port is polled
+    let nfds = if cancel_fd < 0 { 1 } else { fds.len() };
+
+    let deadline =
+        (timeout_ms >= 0).then(|| Instant::now() + Duration::from_millis(timeout_ms as u64));
+    let ready = loop {
+        let timeout_ms = match deadline {
+            Some(deadline) => {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                remaining.as_micros().div_ceil(1000).min(libc::c_int::MAX as u128) as libc::c_int
+            }
+            None => -1,
+        };
+        let ready = unsafe { libc::poll(fds.as_mut_ptr(), nfds as libc::nfds_t, timeout_ms) };
+        if ready >= 0 {
+            break ready;
+        }
+        let e = std::io::Error::last_os_error();
+        if e.kind() != std::io::ErrorKind::Interrupted {
+            return Err(e);
+        }
+    };
+
+    if fds[1].revents & libc::POLLIN != 0 {
+        // Consume the cancellation so the next read blocks normally again
+        let mut counter = 0u64;
+        unsafe {
+            libc::read(cancel_fd, &mut counter as *mut u64 as *mut libc::c_void, 8);
+        }
+        return Err(read_cancelled_error());
+    }
+
+    if ready == 0 {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::TimedOut,
+            "Operation timed out",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Wait until at least one of the ports has data to read, or the timeout expires.
+///
+/// Uses a single `poll()` over the ports' file descriptors, so that it can wait with the ports
+/// unlocked. Returns once any of them is readable or the timeout has passed.
+pub fn poll_readable(port_fds: &[RawFd], timeout: Duration) -> Result<(), std::io::Error> {
+    let mut fds: Vec<libc::pollfd> = port_fds
+        .iter()
+        .map(|&fd| libc::pollfd {
+            fd,
+            events: libc::POLLIN,
+            revents: 0,
+        })
+        .collect();
+    let timeout_ms = timeout.as_millis().clamp(1, libc::c_int::MAX as u128) as libc::c_int;
+
+    let ready = unsafe { libc::poll(fds.as_mut_ptr(), fds.len() as libc::nfds_t, timeout_ms) };
+    if ready < 0 {
+        let e = std::io::Error::last_os_error();
+        if e.kind() != std::io::ErrorKind::Interrupted {
+            return Err(e);
+        }
+    }
+    Ok(())
+}
+
+/// The termios speed table, as probed by `probe_baud_rates` (B0 means hang up, so it is left out)
+const STANDARD_BAUD_RATES: &[(libc::tcflag_t, u32)] = &[
+    (libc::B50, 50),
+    (libc::B75, 75),
+    (libc::B110, 110),
+    (libc::B134, 134),
+    (libc::B150, 150),
+    (libc::B200, 200),
+    (libc::B300, 300),
+    (libc::B600, 600),
+    (libc::B1200, 1200),
+    (libc::B1800, 1800),
+    (libc::B2400, 2400),
+    (libc::B4800, 4800),
+    (libc::B9600, 9600),
+    (libc::B19200, 19200),
+    (libc::B38400, 38400),
+    (libc::B57600, 57600),
+    (libc::B115200, 115200),
+    (libc::B230400, 230400),
+    (libc::B460800, 460800),
+    (libc::B500000, 500000),
+    (libc::B576000, 576000),
+    (libc::B921600, 921600),
+    (libc::B1000000, 1000000),
+    (libc::B1152000, 1152000),
+    (libc::B1500000, 1500000),
+    (libc::B2000000, 2000000),
+    (libc::B2500000, 2500000),
+    (libc::B3000000, 3000000),
+    (libc::B3500000, 3500000),
+    (libc::B4000000, 4000000),
+];
+
+/// Rate outside the speed table used to check for BOTHER support (the MIDI rate). A driver
+/// that can only approximate it to within 2% is not counted as supporting custom rates.
+const CUSTOM_BAUD_PROBE_RATE: u32 = 31250;
+
+/// How often DSR is polled when waiting for it without TIOCMIWAIT
+const DSR_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Bytes written between DSR checks with DTR/DSR flow control
+const DSR_CHECK_CHUNK: usize = 64;
+
+/// Most bytes `write_available` writes at once. The tty layer only polls writable while fewer
+/// than this many bytes are queued in the driver (WAKEUP_CHARS), and drivers hand out room in
+/// units at least this large, so a piece this size is accepted without waiting.
+const WRITABLE_PIECE: usize = 256;
+
+/// Shortest and longest time the output queue is watched when checking for XOFF
+const OUTPUT_STOPPED_MIN_SAMPLE: Duration = Duration::from_millis(10);
+const OUTPUT_STOPPED_MAX_SAMPLE: Duration = Duration::from_millis(250);
+
+/// Real-time priority given to a reading thread. It is above every normally scheduled
+/// thread but well below the kernel's threaded interrupt handlers (priority 50), so the
+/// serial driver itself can still run while the reader is busy.
+const READ_THREAD_RT_PRIORITY: libc::c_int = 10;
+
+/// Standard rates a driver that rejects a custom baud rate may accept instead
+pub fn standard_baud_rates() -> Vec<u32> {
+    STANDARD_BAUD_RATES.iter().map(|&(_, rate)| rate).collect()
+}
+
+/// Move the calling thread to `SCHED_FIFO` real-time scheduling, or back to the normal
+/// `SCHED_OTHER` policy. Enabling needs `CAP_SYS_NICE` or a sufficient `RLIMIT_RTPRIO`.
+pub fn set_current_thread_realtime(enabled: bool) -> Result<(), std::io::Error> {
+    let (policy, priority) = if enabled {
+        (libc::SCHED_FIFO, READ_THREAD_RT_PRIORITY)
+    } else {
+        (libc::SCHED_OTHER, 0)
+    };
+    let param = libc::sched_param {
+        sched_priority: priority,
+    };
+
+    // pthread functions return the error number instead of setting errno
+    let result = unsafe { libc::pthread_setschedparam(libc::pthread_self(), policy, &param) };
+    match result {
+        0 => Ok(()),
+        libc::EPERM => Err(std::io::Error::new(
+            std::io::ErrorKind::PermissionDenied,
+            "real-time scheduling denied (requires CAP_SYS_NICE or an RLIMIT_RTPRIO limit)",
+        )),
+        errno => Err(std::io::Error::from_raw_os_error(errno)),
+    }
+}
+
+/// Check whether the process may use real-time scheduling, by moving the calling thread
+/// to `SCHED_FIFO` and straight back to its original policy.
+pub fn realtime_scheduling_allowed() -> bool {
+    let thread = unsafe { libc::pthread_self() };
+    let mut policy: libc::c_int = 0;
+    let mut original = libc::sched_param { sched_priority: 0 };
+    if unsafe { libc::pthread_getschedparam(thread, &mut policy, &mut original) } != 0 {
+        return false;
+    }
+    let param = libc::sched_param {
+        sched_priority: READ_THREAD_RT_PRIORITY,
+    };
+    if unsafe { libc::pthread_setschedparam(thread, libc::SCHED_FIFO, &param) } != 0 {
+        return false;
+    }
+    unsafe { libc::pthread_setschedparam(thread, policy, &original) };
+    true
+}
+
+/// Check whether read cancellation is available, which needs an eventfd per port
+pub fn read_cancellation_available() -> bool {
+    let fd = unsafe { libc::eventfd(0, libc::EFD_CLOEXEC | libc::EFD_NONBLOCK) };
+    if fd < 0 {
+        return false;
+    }
+    unsafe { libc::close(fd) };
+    true
+}
 
-                result
+impl Drop for PortWrapper {
+    fn drop(&mut self) {
+        // Join the background threads before the port's descriptor is closed
+        self.failsafe = None;
+        self.stop_frame_reader();
+        let _ = self.set_hold_transmit_enable(false);
+        self.disable_auto_reconnect();
+        self.readable_notifier = None;
+        self.restore_original_termios();
+        if self.cancel_fd >= 0 {
+            unsafe {
+                libc::close(self.cancel_fd);
             }
         }
     }
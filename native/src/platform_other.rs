@@ -14,16 +14,110 @@
 
 //! Non-Linux serial port wrapper with manual RS-485 control only.
 
-use crate::{Rs485ControlMode, Rs485ControlPin};
-use serialport::SerialPort;
-use std::io::Write;
+use crate::{
+    cap_read_len, capture_traffic, detect_eof, drain_until, is_eof, preamble_duration,
+    precise_wait, read_with_retries, reverse_bits_in_place, send_dmx_break, sleep_unless_zero,
+    write_fully, write_paced, write_until, AccessMode, FailsafeWatchdog, FrameReader,
+    QueueHighWater, Rs485ControlMode, Rs485ControlPin, ThroughputLimit, TrafficCapture,
+    WriteBlockingPolicy, WritePacing, BIT_REVERSE, CAPTURE_RECEIVED, CAPTURE_TRANSMITTED,
+    CONNECTION_CONNECTED, CONNECTION_FAILED, DEFAULT_DRAIN_TIMEOUT, MODEM_CD, MODEM_CTS, MODEM_DSR,
+    MODEM_RI, RS485_ACTUAL_MANUAL_BY_FALLBACK, RS485_ACTUAL_MANUAL_BY_PIN,
+    RS485_ACTUAL_MANUAL_BY_REQUEST, RS485_ACTUAL_NONE,
+};
+use serialport::{DataBits, Parity, SerialPort, StopBits};
+use std::io::{Read, Write};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 pub struct PortWrapper {
     pub port: Box<dyn SerialPort>,
     pub control_mode: Rs485ControlMode,
     pub control_pin: Rs485ControlPin,
+    /// How direction control is actually performed, and why (RS485_ACTUAL_*)
+    rs485_actual_mode: u8,
+    /// How writes react when the port would block
+    pub write_blocking_policy: WriteBlockingPolicy,
+    /// Directions of I/O the port was opened for
+    pub access_mode: AccessMode,
+    /// Chunking of large writes for slow receivers
+    pub write_pacing: WritePacing,
+    /// Limit on the sustained write rate
+    pub throughput_limit: ThroughputLimit,
+    /// Releases the direction pin if no write succeeds in time, if enabled
+    failsafe: Option<FailsafeWatchdog>,
+    /// True to reverse the bit order of each byte written and read
+    pub bit_reversal: bool,
+    /// Records reads and writes to a file, if started
+    capture: Option<TrafficCapture>,
+    /// Bit periods of idle line held before each write, 0 for none
+    pub preamble_idle_bits: u32,
+    /// Manual RS-485 mode: time between asserting the direction pin and sending data
+    pub rs485_pre_delay: Duration,
+    /// Manual RS-485 mode: time between the data being sent and releasing the direction pin
+    pub rs485_post_delay: Duration,
+    /// Deepest input/output queues seen by reads, writes and status queries
+    pub queue_high_water: QueueHighWater,
+    /// Reusable buffer for reads that copy into a pinned Java array
+    pub read_scratch: Vec<u8>,
+    /// Maximum bytes requested from a single underlying read, or 0 for no limit
+    pub max_read_chunk: usize,
+    /// Set once the fallback from a direct buffer to array copying has been warned about
+    pub direct_buffer_fallback_warned: bool,
+    /// Input queue depth above which operations record a warning, or 0 to disable
+    pub overflow_warn_threshold: u32,
+    /// True to discard pending input at the start of each write
+    pub clear_input_before_write: bool,
+    /// Longest a write waits for its output to drain, or None to wait indefinitely
+    pub drain_timeout: Option<Duration>,
+    /// True if the last readFully or readOne returned early because its deadline elapsed
+    pub last_read_timed_out: bool,
+    /// Last non-fatal problem noticed by a read, cleared when retrieved
+    pub last_warning: Arc<Mutex<Option<String>>>,
+    /// Line settings the driver applied differently than requested on open
+    pub config_discrepancies: Vec<String>,
+    /// Baud rate the port was opened at, which the baud tolerance may have changed from the
+    /// requested one; 0 if the port was opened without the tolerance (quiet open)
+    pub opened_baud_rate: u32,
+    /// True if the last read found the port closed by its peer
+    pub eof: bool,
+    /// Bytes appended to each line written with `write_line`
+    pub write_terminator: Vec<u8>,
+    /// Times a read is retried after a transient error before failing
+    pub read_retries: u32,
+    /// Retries made by reads since the port was opened
+    pub read_retry_count: u64,
+    /// Background thread splitting input into frames at idle gaps, if started
+    pub frame_reader: Option<FrameReader>,
     /// True if RTS should be active high during transmission
     rts_active_high: bool,
+    /// True to keep the transceiver in receive mode and reject writes
+    listen_only: bool,
+    /// True to leave the manual RS-485 direction pin asserted between writes
+    hold_transmit_enable: bool,
+    /// True while the direction pin is held asserted after a write
+    transmit_held: bool,
+    /// True to drain the output at the end of each write
+    auto_flush: bool,
+    /// Bytes read past the end of a pattern match, returned by the next reads
+    pub read_residual: Vec<u8>,
+    /// Last level set on RTS since open, or None if it was never set
+    current_rts: Option<bool>,
+    /// Last level set on DTR since open, or None if it was never set
+    current_dtr: Option<bool>,
+}
+
+/// Common baud rates probed by `probe_baud_rates`
+const STANDARD_BAUD_RATES: &[u32] = &[
+    110, 300, 600, 1200, 2400, 4800, 9600, 14400, 19200, 38400, 57600, 115200, 128000, 230400,
+    256000, 460800, 921600,
+];
+
+/// Rate outside the list used to check for custom rate support (the MIDI rate)
+const CUSTOM_BAUD_PROBE_RATE: u32 = 31250;
+
+/// Standard rates a driver that rejects a custom baud rate may accept instead
+pub fn standard_baud_rates() -> Vec<u32> {
+    STANDARD_BAUD_RATES.to_vec()
 }
 
 impl PortWrapper {
@@ -32,7 +126,41 @@ pub fn new(port: Box<dyn SerialPort>) -> Self {
             port,
             control_mode: Rs485ControlMode::None,
             control_pin: Rs485ControlPin::RTS,
+            rs485_actual_mode: RS485_ACTUAL_NONE,
+            write_blocking_policy: WriteBlockingPolicy::Block,
+            access_mode: AccessMode::ReadWrite,
+            write_pacing: WritePacing::default(),
+            throughput_limit: ThroughputLimit::default(),
+            failsafe: None,
+            bit_reversal: false,
+            capture: None,
+            preamble_idle_bits: 0,
+            rs485_pre_delay: Duration::ZERO,
+            rs485_post_delay: Duration::ZERO,
+            queue_high_water: QueueHighWater::default(),
+            read_scratch: Vec::new(),
+            max_read_chunk: 0,
+            direct_buffer_fallback_warned: false,
+            overflow_warn_threshold: 0,
+            clear_input_before_write: false,
+            drain_timeout: Some(DEFAULT_DRAIN_TIMEOUT),
+            last_read_timed_out: false,
+            last_warning: Arc::new(Mutex::new(None)),
+            config_discrepancies: Vec::new(),
+            opened_baud_rate: 0,
+            eof: false,
+            write_terminator: b"\n".to_vec(),
+            read_retries: 0,
+            read_retry_count: 0,
+            frame_reader: None,
             rts_active_high: true,
+            listen_only: false,
+            hold_transmit_enable: false,
+            transmit_held: false,
+            auto_flush: true,
+            read_residual: Vec::new(),
+            current_rts: None,
+            current_dtr: None,
         }
     }
 
@@ -41,13 +169,29 @@ pub fn configure_rs485(
         mode: Rs485ControlMode,
         pin: Rs485ControlPin,
     ) -> Result<(), serialport::Error> {
+        // Reconfiguring ends a hold of the direction pin, and the failsafe guarding it
+        let _ = self.set_hold_transmit_enable(false);
+        self.failsafe = None;
+
         self.control_mode = mode;
         self.control_pin = pin;
         // On non-Linux platforms, we only support manual mode
+        self.rs485_actual_mode = match (mode, pin) {
+            (Rs485ControlMode::None, _) => RS485_ACTUAL_NONE,
+            (Rs485ControlMode::Auto, Rs485ControlPin::RTS) => RS485_ACTUAL_MANUAL_BY_FALLBACK,
+            (Rs485ControlMode::Auto, Rs485ControlPin::DTR) => RS485_ACTUAL_MANUAL_BY_PIN,
+            (Rs485ControlMode::Manual, _) => RS485_ACTUAL_MANUAL_BY_REQUEST,
+        };
         Ok(())
     }
 
+    /// How RS-485 direction control is actually performed (RS485_ACTUAL_*)
+    pub fn rs485_actual_mode(&self) -> u8 {
+        self.rs485_actual_mode
+    }
+
     /// Configure extended RS-485 settings (non-Linux platforms only support manual control)
+    #[allow(clippy::too_many_arguments)]
     pub fn configure_rs485_extended(
         &mut self,
         mode: Rs485ControlMode,
@@ -62,33 +206,617 @@ pub fn configure_rs485_extended(
         self.configure_rs485(mode, pin)
     }
 
+    /// Enable or disable listen-only mode.
+    ///
+    /// While enabled, `write_rs485` rejects all writes and, if RS-485 control is configured,
+    /// the direction pin is driven to receive immediately.
+    pub fn set_listen_only(&mut self, enabled: bool) -> Result<(), std::io::Error> {
+        self.listen_only = enabled;
+        if enabled && self.control_mode != Rs485ControlMode::None {
+            self.transmit_held = false;
+            self.update_failsafe();
+            self.set_transmit_enable(false)?;
+        }
+        Ok(())
+    }
+
+    /// Keep the manual RS-485 direction pin asserted between writes, or release it.
+    ///
+    /// While held, the first write asserts the pin and waits the pre-delay; later writes go
+    /// out without toggling the pin, draining or waiting the delays. Releasing drains the
+    /// output, waits the post-delay and deasserts the pin. Holding is rejected without RS-485
+    /// control.
+    pub fn set_hold_transmit_enable(&mut self, hold: bool) -> Result<(), std::io::Error> {
+        if hold && self.control_mode == Rs485ControlMode::None {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "RS-485 direction control is not enabled",
+            ));
+        }
+        self.hold_transmit_enable = hold;
+        if !hold && self.transmit_held {
+            self.transmit_held = false;
+            self.update_failsafe();
+            self.drain_for(None);
+            sleep_unless_zero(self.rs485_post_delay);
+            self.set_transmit_enable(false)?;
+        }
+        Ok(())
+    }
+
+    /// Release the direction pin if it is left held with no write for `timeout`, or stop
+    /// doing so with None. The timer restarts after each write; see `FailsafeWatchdog`.
+    /// Requires RS-485 direction control.
+    pub fn set_failsafe(&mut self, timeout: Option<Duration>) -> Result<(), std::io::Error> {
+        self.failsafe = None;
+        let Some(timeout) = timeout else {
+            return Ok(());
+        };
+        if self.control_mode == Rs485ControlMode::None {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "RS-485 direction control is not enabled",
+            ));
+        }
+        let mut port = self.port.try_clone()?;
+        let pin = self.control_pin;
+        // Reconfiguring the polarity drops the failsafe, so the level cannot go stale
+        let receive_level = self.receive_level();
+        let release = move || match pin {
+            Rs485ControlPin::RTS => port.write_request_to_send(receive_level),
+            Rs485ControlPin::DTR => port.write_data_terminal_ready(receive_level),
+        };
+        let watchdog = FailsafeWatchdog::spawn(timeout, release, Arc::clone(&self.last_warning))?;
+        self.failsafe = Some(watchdog);
+        self.update_failsafe();
+        Ok(())
+    }
+
+    /// Arm the failsafe while the direction pin is held between writes, disarm it otherwise
+    fn update_failsafe(&self) {
+        if let Some(failsafe) = &self.failsafe {
+            if self.transmit_held {
+                failsafe.arm();
+            } else {
+                failsafe.disarm();
+            }
+        }
+    }
+
+    /// Enable or disable draining the output at the end of each write.
+    ///
+    /// With auto-flush off, `write_rs485` returns once the data is queued and `flush_output`
+    /// drains it. In manual RS-485 mode the direction pin then stays asserted after a write
+    /// until the next flush, so the bus is never released with data still queued. Turning
+    /// auto-flush back on flushes a write that is still waiting for it. Writes with a
+    /// deadline always drain, since they promise to free the bus by the deadline.
+    pub fn set_auto_flush(&mut self, enabled: bool) -> Result<(), std::io::Error> {
+        self.auto_flush = enabled;
+        if enabled && self.transmit_held && !self.hold_transmit_enable {
+            self.flush_output()?;
+        }
+        Ok(())
+    }
+
+    /// Drain the output, then release the manual RS-485 direction pin if a write left it
+    /// asserted for this flush because auto-flush is off
+    pub fn flush_output(&mut self) -> Result<(), std::io::Error> {
+        self.port.flush()?;
+        if self.transmit_held && !self.hold_transmit_enable {
+            self.transmit_held = false;
+            self.update_failsafe();
+            sleep_unless_zero(self.rs485_post_delay);
+            self.set_transmit_enable(false)?;
+        }
+        Ok(())
+    }
+
+    /// Record the input queue depth seen by an operation, warning if it is above the
+    /// overflow warning threshold
+    pub fn observe_input_depth(&mut self, depth: u32) {
+        self.queue_high_water.observe_input(depth);
+        let threshold = self.overflow_warn_threshold;
+        if threshold > 0 && depth > threshold {
+            if let Ok(mut warning) = self.last_warning.lock() {
+                *warning = Some(format!(
+                    "input queue at {} bytes, above the warning threshold of {}: reads are \
+                     falling behind",
+                    depth, threshold
+                ));
+            }
+        }
+    }
+
+    /// Set RTS, remembering the level for `output_line_state`
+    pub fn set_rts(&mut self, level: bool) -> serialport::Result<()> {
+        self.port.write_request_to_send(level)?;
+        self.current_rts = Some(level);
+        Ok(())
+    }
+
+    /// Set DTR, remembering the level for `output_line_state`
+    pub fn set_dtr(&mut self, level: bool) -> serialport::Result<()> {
+        self.port.write_data_terminal_ready(level)?;
+        self.current_dtr = Some(level);
+        Ok(())
+    }
+
+    /// Get the last level commanded on RTS (`dtr` false) or DTR (`dtr` true).
+    /// Fails if the line was never set since the port was opened.
+    pub fn output_line_state(&mut self, dtr: bool) -> Result<bool, std::io::Error> {
+        let (name, commanded) = if dtr {
+            ("DTR", self.current_dtr)
+        } else {
+            ("RTS", self.current_rts)
+        };
+        commanded.ok_or_else(|| {
+            std::io::Error::other(format!("{} has not been set since the port was opened", name))
+        })
+    }
+
+    /// Level of the RS-485 direction pin while receiving, given its polarity
+    fn receive_level(&self) -> bool {
+        !self.rts_active_high
+    }
+
+    /// Drive the manual RS-485 direction pin to transmit or back to receive (respecting
+    /// polarity)
+    fn set_transmit_enable(&mut self, transmit: bool) -> Result<(), std::io::Error> {
+        let level = transmit != self.receive_level();
+        match self.control_pin {
+            Rs485ControlPin::RTS => self.set_rts(level)?,
+            Rs485ControlPin::DTR => self.set_dtr(level)?,
+        }
+        Ok(())
+    }
+
+    /// Check that the device still responds, using a cheap input queue query as a probe
+    pub fn is_valid(&self) -> bool {
+        self.port.bytes_to_read().is_ok()
+    }
+
+    /// Stop the frame reader thread, if any, and wait for it to exit
+    pub fn stop_frame_reader(&mut self) {
+        self.frame_reader = None;
+    }
+
+    /// Connection state as one of the `CONNECTION_*` values (automatic reconnect is Linux only)
+    pub fn connection_state(&self) -> u8 {
+        if self.is_valid() {
+            CONNECTION_CONNECTED
+        } else {
+            CONNECTION_FAILED
+        }
+    }
+
+    /// Find the baud rates the driver accepts (best effort).
+    ///
+    /// Each common rate is set and read back; custom rates are assumed to be available if a
+    /// rate outside the list is accepted as well. The original rate is restored afterwards.
+    /// Returns the accepted rates and whether custom rates are available.
+    pub fn probe_baud_rates(&mut self) -> Result<(Vec<u32>, bool), std::io::Error> {
+        let original = self.port.baud_rate()?;
+        let mut accepts = |rate: u32| {
+            self.port.set_baud_rate(rate).is_ok() && self.port.baud_rate().ok() == Some(rate)
+        };
+        let rates: Vec<u32> = STANDARD_BAUD_RATES
+            .iter()
+            .copied()
+            .filter(|&rate| accepts(rate))
+            .collect();
+        let custom = accepts(CUSTOM_BAUD_PROBE_RATE);
+        self.port.set_baud_rate(original)?;
+        Ok((rates, custom))
+    }
+
+    /// Read all modem input lines.
+    ///
+    /// Returns a bitmask of `MODEM_*` flags.
+    pub fn modem_status(&mut self) -> Result<u32, std::io::Error> {
+        let mut bits = 0;
+        if self.port.read_clear_to_send()? {
+            bits |= MODEM_CTS;
+        }
+        if self.port.read_data_set_ready()? {
+            bits |= MODEM_DSR;
+        }
+        if self.port.read_ring_indicator()? {
+            bits |= MODEM_RI;
+        }
+        if self.port.read_carrier_detect()? {
+            bits |= MODEM_CD;
+        }
+        Ok(bits)
+    }
+
+    /// Read from the port (read cancellation is only available on Linux)
+    pub fn read_data(&mut self, buf: &mut [u8]) -> Result<usize, std::io::Error> {
+        self.access_mode.check(false)?;
+        let len = cap_read_len(buf.len(), self.max_read_chunk);
+        let buf = &mut buf[..len];
+        if !self.read_residual.is_empty() {
+            let n = len.min(self.read_residual.len());
+            buf[..n].copy_from_slice(&self.read_residual[..n]);
+            self.read_residual.drain(..n);
+            return Ok(n);
+        }
+        let depth = self.port.bytes_to_read().unwrap_or(0);
+        self.observe_input_depth(depth);
+        let port = &mut self.port;
+        let (result, retries) = read_with_retries(self.read_retries, || port.read(buf));
+        self.read_retry_count += u64::from(retries);
+        let result = detect_eof(result, buf.len());
+        self.eof = is_eof(&result);
+        if let Ok(n) = result {
+            capture_traffic(&mut self.capture, &self.last_warning, CAPTURE_RECEIVED, &buf[..n]);
+            if self.bit_reversal {
+                reverse_bits_in_place(&mut buf[..n]);
+            }
+        }
+        result
+    }
+
+    /// Check whether a read would find input without waiting: bytes kept from a pattern match
+    /// or queued input
+    pub fn has_input(&mut self) -> Result<bool, std::io::Error> {
+        self.access_mode.check(false)?;
+        Ok(!self.read_residual.is_empty() || self.port.bytes_to_read()? > 0)
+    }
+
+    /// Discard pending input, including bytes kept from a pattern match
+    pub fn clear_input(&mut self) -> Result<(), std::io::Error> {
+        self.read_residual.clear();
+        self.port.clear(serialport::ClearBuffer::Input)?;
+        Ok(())
+    }
+
+    /// Read a single byte, blocking indefinitely if `timeout` is None.
+    /// Returns None on timeout.
+    pub fn read_one(&mut self, timeout: Option<Duration>) -> Result<Option<u8>, std::io::Error> {
+        self.access_mode.check(false)?;
+        if !self.read_residual.is_empty() {
+            return Ok(Some(self.read_residual.remove(0)));
+        }
+        let original_timeout = self.port.timeout();
+        // Without a timeout, keep retrying reads of bounded length
+        self.port.set_timeout(timeout.unwrap_or(Duration::from_secs(1)))?;
+
+        let mut byte = [0u8; 1];
+        let result = loop {
+            let read = detect_eof(self.port.read(&mut byte), byte.len());
+            self.eof = is_eof(&read);
+            if let Ok(n) = read {
+                let data = &byte[..n];
+                capture_traffic(&mut self.capture, &self.last_warning, CAPTURE_RECEIVED, data);
+                if self.bit_reversal {
+                    reverse_bits_in_place(&mut byte[..n]);
+                }
+            }
+            match read {
+                Ok(1) => break Ok(Some(byte[0])),
+                Ok(_) if timeout.is_some() => break Ok(None),
+                Err(e) if e.kind() == std::io::ErrorKind::TimedOut && timeout.is_some() => {
+                    break Ok(None)
+                }
+                Ok(_) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::TimedOut => {}
+                Err(e) => break Err(e),
+            }
+        };
+
+        let _ = self.port.set_timeout(original_timeout);
+        result
+    }
+
+    /// Start recording reads and writes to a libpcap file at `path`, replacing any capture
+    /// already running
+    pub fn start_capture(&mut self, path: &str) -> Result<(), std::io::Error> {
+        self.stop_capture()?;
+        self.capture = Some(TrafficCapture::create(path)?);
+        Ok(())
+    }
+
+    /// Stop recording and flush the capture file; does nothing if no capture is running
+    pub fn stop_capture(&mut self) -> Result<(), std::io::Error> {
+        match self.capture.take() {
+            Some(capture) => capture.finish(),
+            None => Ok(()),
+        }
+    }
+
+    /// Record data received through another handle to the device, such as the frame
+    /// reader's, if a capture is running
+    pub fn capture_received(&mut self, data: &[u8]) {
+        capture_traffic(&mut self.capture, &self.last_warning, CAPTURE_RECEIVED, data);
+    }
+
+    /// Write data honoring the configured write blocking policy and pacing
+    fn write_data(&mut self, data: &[u8]) -> Result<usize, std::io::Error> {
+        let timeout = self.port.timeout();
+        let result = write_paced(
+            &mut self.port,
+            data,
+            self.write_blocking_policy,
+            timeout,
+            self.write_pacing,
+        );
+        if let Ok(n) = result {
+            let data = &data[..n];
+            capture_traffic(&mut self.capture, &self.last_warning, CAPTURE_TRANSMITTED, data);
+        }
+        // Sample before any drain, while the output queue is at its deepest
+        self.queue_high_water
+            .observe_output(self.port.bytes_to_write().unwrap_or(0));
+        result
+    }
+
+    /// Write data, stopping early at the deadline if there is one
+    fn write_for(
+        &mut self,
+        data: &[u8],
+        deadline: Option<Instant>,
+    ) -> Result<usize, std::io::Error> {
+        let Some(deadline) = deadline else {
+            return self.write_data(data);
+        };
+        let result = write_until(
+            self.port.as_mut(),
+            data,
+            self.write_blocking_policy,
+            self.write_pacing,
+            deadline,
+        );
+        if let Ok(n) = result {
+            let data = &data[..n];
+            capture_traffic(&mut self.capture, &self.last_warning, CAPTURE_TRANSMITTED, data);
+        }
+        self.queue_high_water
+            .observe_output(self.port.bytes_to_write().unwrap_or(0));
+        result
+    }
+
+    /// Drain the output before releasing the bus. With a deadline, output still queued
+    /// when it passes is discarded; returns the number of bytes discarded.
+    /// Without one, the drain timeout applies instead and giving up records a warning.
+    fn drain_for(&mut self, deadline: Option<Instant>) -> usize {
+        if let Some(deadline) = deadline {
+            return drain_until(self.port.as_mut(), deadline).unwrap_or(0);
+        }
+        let Some(timeout) = self.drain_timeout else {
+            let _ = self.port.flush();
+            return 0;
+        };
+        let discarded = drain_until(self.port.as_mut(), Instant::now() + timeout).unwrap_or(0);
+        if discarded > 0 {
+            if let Ok(mut warning) = self.last_warning.lock() {
+                *warning = Some(format!(
+                    "drain timeout: output did not drain within {} ms, {} bytes discarded",
+                    timeout.as_millis(),
+                    discarded
+                ));
+            }
+        }
+        discarded
+    }
+
+    /// Line speeds the driver reports as applied, as (input, output) in baud. The platform
+    /// has a single line rate, so both are the same.
+    pub fn actual_baud_rates(&self) -> Result<(u32, u32), std::io::Error> {
+        let rate = self.port.baud_rate()?;
+        Ok((rate, rate))
+    }
+
+    /// Switch the line settings after draining pending output (subject to the drain timeout)
+    /// and clearing stale input. The settings are applied one at a time, so a failure can
+    /// leave the earlier ones applied; the error names the setting that failed.
+    pub fn reconfigure(
+        &mut self,
+        baud_rate: u32,
+        data_bits: DataBits,
+        stop_bits: StopBits,
+        parity: Parity,
+    ) -> Result<(), std::io::Error> {
+        self.drain_for(None);
+        self.clear_input()?;
+        let failed = |setting: &str, e: serialport::Error| {
+            std::io::Error::other(format!("could not set {}: {}", setting, e))
+        };
+        self.port
+            .set_baud_rate(baud_rate)
+            .map_err(|e| failed("baud rate", e))?;
+        self.port
+            .set_data_bits(data_bits)
+            .map_err(|e| failed("data bits", e))?;
+        self.port
+            .set_stop_bits(stop_bits)
+            .map_err(|e| failed("stop bits", e))?;
+        self.port.set_parity(parity).map_err(|e| failed("parity", e))
+    }
+
+    /// Change one line setting, such as the parity, without corrupting a character in flight.
+    ///
+    /// Pending output is drained first (subject to the drain timeout); unlike `reconfigure`,
+    /// received input is kept. The setting is read back, and if the driver did not apply it,
+    /// the previous value is restored and an `Unsupported` error naming `description` is
+    /// returned.
+    pub fn set_line_setting<T: PartialEq + Copy>(
+        &mut self,
+        description: &str,
+        value: T,
+        get: impl Fn(&Box<dyn SerialPort>) -> serialport::Result<T>,
+        set: impl Fn(&mut Box<dyn SerialPort>, T) -> serialport::Result<()>,
+    ) -> Result<(), std::io::Error> {
+        let previous = get(&self.port)?;
+        self.drain_for(None);
+        set(&mut self.port, value)?;
+        if get(&self.port)? == value {
+            return Ok(());
+        }
+        let _ = set(&mut self.port, previous);
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            format!("driver did not apply {}", description),
+        ))
+    }
+
+    /// Send a DMX512 packet: the break and mark-after-break, then `data`, which is the start
+    /// code followed by the slots. DMX transceivers keep their driver enabled, so this is
+    /// rejected with RS-485 direction control configured.
+    pub fn send_dmx_frame(&mut self, data: &[u8]) -> Result<usize, std::io::Error> {
+        self.access_mode.check(true)?;
+        if self.listen_only {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::PermissionDenied,
+                "port is in listen-only mode",
+            ));
+        }
+        if self.control_mode != Rs485ControlMode::None {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "DMX512 frames cannot be sent with RS-485 direction control",
+            ));
+        }
+
+        send_dmx_break(self.port.as_mut())?;
+        write_fully(data, |rest| self.write_data(rest))
+    }
+
     pub fn write_rs485(&mut self, data: &[u8]) -> Result<usize, std::io::Error> {
+        self.write_rs485_with(data, None)
+    }
+
+    /// Write data before `deadline`, releasing the RS-485 direction pin even if it passes
+    /// mid-write. Returns the bytes written, not counting any discarded to free the bus.
+    pub fn write_rs485_before(
+        &mut self,
+        data: &[u8],
+        deadline: Instant,
+    ) -> Result<usize, std::io::Error> {
+        self.write_rs485_with(data, Some(deadline))
+    }
+
+    fn write_rs485_with(
+        &mut self,
+        data: &[u8],
+        deadline: Option<Instant>,
+    ) -> Result<usize, std::io::Error> {
+        self.access_mode.check(true)?;
+        if self.listen_only {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::PermissionDenied,
+                "port is in listen-only mode",
+            ));
+        }
+
+        // Drop stale RX (leftovers from a previous exchange) before transmitting
+        if self.clear_input_before_write {
+            self.clear_input()?;
+        }
+
+        // No release while the write is under way. The failsafe may have released the pin
+        // behind our back already; assert it again for this write
+        if let Some(failsafe) = &self.failsafe {
+            failsafe.disarm();
+        }
+        if self.failsafe.as_ref().is_some_and(FailsafeWatchdog::take_tripped) {
+            self.transmit_held = false;
+            let receive_level = self.receive_level();
+            match self.control_pin {
+                Rs485ControlPin::RTS => self.current_rts = Some(receive_level),
+                Rs485ControlPin::DTR => self.current_dtr = Some(receive_level),
+            }
+        }
+
+        // Throttle between frames, before the direction pin is asserted
+        let delay = self.throughput_limit.delay(Instant::now());
+        let delay = match deadline {
+            Some(deadline) => delay.min(deadline.saturating_duration_since(Instant::now())),
+            None => delay,
+        };
+        sleep_unless_zero(delay);
+
+        // Reverse a copy, the caller's data is left as it is
+        let mut reversed = Vec::new();
+        let data = if self.bit_reversal {
+            reversed.extend(data.iter().map(|&b| BIT_REVERSE[b as usize]));
+            &reversed[..]
+        } else {
+            data
+        };
+
+        let result = self.write_frame(data, deadline);
+        if let Ok(written) = result {
+            self.throughput_limit.consume(written);
+        }
+        self.update_failsafe();
+        result
+    }
+
+    /// Hold the line idle for the configured preamble, counted from when the previous frame
+    /// has left the transmitter. Returns the number of bytes of the previous frame discarded
+    /// because they had not left by `deadline`; they were counted as written by the previous
+    /// write, so the caller takes them off its own count.
+    fn send_preamble(&mut self, deadline: Option<Instant>) -> Result<usize, std::io::Error> {
+        if self.preamble_idle_bits == 0 {
+            return Ok(0);
+        }
+        let duration = preamble_duration(self.preamble_idle_bits, self.port.baud_rate()?);
+        let discarded = self.drain_for(deadline);
+        let duration = match deadline {
+            Some(deadline) => duration.min(deadline.saturating_duration_since(Instant::now())),
+            None => duration,
+        };
+        precise_wait(duration);
+        Ok(discarded)
+    }
+
+    /// Write one frame, driving the RS-485 direction pin around it if configured
+    fn write_frame(
+        &mut self,
+        data: &[u8],
+        deadline: Option<Instant>,
+    ) -> Result<usize, std::io::Error> {
         // Manual mode on non-Linux platforms
         if self.control_mode != Rs485ControlMode::None {
-            // Enable transmit (respecting polarity)
-            let transmit_level = self.rts_active_high;
-            match self.control_pin {
-                Rs485ControlPin::RTS => self.port.write_request_to_send(transmit_level)?,
-                Rs485ControlPin::DTR => self.port.write_data_terminal_ready(transmit_level)?,
+            // Enable transmit, unless the pin is still held from the previous write
+            if !self.transmit_held {
+                self.set_transmit_enable(true)?;
+                sleep_unless_zero(self.rs485_pre_delay);
+                self.transmit_held = self.hold_transmit_enable;
             }
+            let discarded_before = self.send_preamble(deadline)?;
 
-            // Write data
-            let result = self.port.write(data);
+            // Write the whole frame; a short write must not release the pin early
+            let result = write_fully(data, |rest| self.write_for(rest, deadline))
+                .map(|n| n.saturating_sub(discarded_before));
+            if !self.auto_flush && deadline.is_none() {
+                self.transmit_held = true;
+            }
+            if self.transmit_held {
+                // Released by set_hold_transmit_enable(false) or flush_output
+                return result;
+            }
 
             // Flush to ensure data is sent
-            let _ = self.port.flush();
+            let discarded = self.drain_for(deadline);
+            sleep_unless_zero(self.rs485_post_delay);
 
             // Disable transmit (back to receive mode)
-            let receive_level = !self.rts_active_high;
-            match self.control_pin {
-                Rs485ControlPin::RTS => self.port.write_request_to_send(receive_level)?,
-                Rs485ControlPin::DTR => self.port.write_data_terminal_ready(receive_level)?,
-            }
+            self.set_transmit_enable(false)?;
 
-            result
+            result.map(|n| n.saturating_sub(discarded))
         } else {
             // No RS-485 control, just write normally
-            self.port.write(data)
+            let discarded = self.send_preamble(deadline)?;
+            self.write_for(data, deadline).map(|n| n.saturating_sub(discarded))
         }
     }
 }
+
+impl Drop for PortWrapper {
+    fn drop(&mut self) {
+        self.failsafe = None;
+        // Return a held direction pin to receive before the port is closed
+        let _ = self.set_hold_transmit_enable(false);
+    }
+}
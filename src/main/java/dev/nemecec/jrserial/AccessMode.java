@@ -0,0 +1,61 @@
+/*
+ * Copyright (C) 2026 Neeme Praks
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package dev.nemecec.jrserial;
+
+/**
+ * Which directions of I/O a serial port is opened for.
+ *
+ * <p>On Linux, restricted modes are enforced by the operating system: the device is opened with
+ * {@code O_RDONLY} or {@code O_WRONLY}, so a passive monitor cannot disturb the bus even by
+ * accident. On other platforms the port is opened read/write and the restriction is enforced by
+ * the library. In both cases the disallowed operation fails with an {@link java.io.IOException}.
+ */
+public enum AccessMode {
+  /**
+   * Reading and writing (default).
+   */
+  READ_WRITE(0),
+
+  /**
+   * Reading only, for passive monitors.
+   *
+   * <p>Writes fail. RS-485 direction control needs write access and cannot be combined with
+   * this mode.
+   */
+  READ_ONLY(1),
+
+  /**
+   * Writing only, for one-way senders such as telemetry transmitters.
+   *
+   * <p>Reads fail.
+   */
+  WRITE_ONLY(2);
+
+  private final int value;
+
+  AccessMode(int value) {
+    this.value = value;
+  }
+
+  /**
+   * Get the numeric value of the access mode.
+   *
+   * @return the numeric value (0=ReadWrite, 1=ReadOnly, 2=WriteOnly)
+   */
+  public int getValue() {
+    return value;
+  }
+}
@@ -0,0 +1,107 @@
+/*
+ * Copyright (C) 2026 Neeme Praks
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package dev.nemecec.jrserial;
+
+/**
+ * Optional feature of the native library, as reported by {@link SerialPort#getCapabilities()}.
+ *
+ * <p>Many features depend on the platform, and some on the system the application runs on. A
+ * capability tells whether the feature is available at all; a particular port or driver may
+ * still not support it (for example, kernel RS-485 mode needs a driver that implements it).
+ */
+public enum Capability {
+
+  /**
+   * Kernel RS-485 mode, where the driver switches the transceiver direction.
+   */
+  KERNEL_RS485(0),
+
+  /**
+   * Baud rates outside the standard list, passed through to the driver.
+   */
+  CUSTOM_BAUD_RATE(1),
+
+  /**
+   * Custom baud divisors, see {@link SerialPort#setCustomDivisor(int, int)}.
+   */
+  CUSTOM_DIVISOR(2),
+
+  /**
+   * Cancelling a blocked read from another thread, see {@link SerialPort#cancelRead()}.
+   */
+  READ_CANCELLATION(3),
+
+  /**
+   * Reopening a port after its device disconnects, see
+   * {@link SerialPort#enableAutoReconnect(int)}.
+   */
+  AUTO_RECONNECT(4),
+
+  /**
+   * 9-bit characters using mark/space parity, see
+   * {@link SerialPort#write9bit(byte[], boolean[])}.
+   */
+  NINE_BIT(5),
+
+  /**
+   * Driver counters of modem line events, see {@link SerialPort#getRingCount()}.
+   */
+  MODEM_EVENT_COUNTERS(6),
+
+  /**
+   * Direct control of terminal settings such as raw and canonical mode, VMIN/VTIME, CLOCAL and
+   * hangup on close.
+   */
+  TERMIOS_CONTROL(7),
+
+  /**
+   * Sending XON/XOFF and detecting output paused by XOFF, see {@link SerialPort#sendXon()}.
+   */
+  SOFTWARE_FLOW_CONTROL(8),
+
+  /**
+   * Real-time scheduling of reading threads, which needs privileges the process was found to
+   * have, see {@link SerialPort#setReadThreadRealtime(boolean)}.
+   */
+  REALTIME_SCHEDULING(9),
+
+  /**
+   * Checking permission to open a port without opening it, see
+   * {@link SerialPort#checkAccess(String)}.
+   */
+  ACCESS_CHECK(10),
+
+  /**
+   * DTR/DSR flow control, see {@link SerialPort#setDtrFlowControl(boolean)}.
+   */
+  DTR_FLOW_CONTROL(11);
+
+  private final int value;
+
+  Capability(int value) {
+    this.value = value;
+  }
+
+  /**
+   * Get the native value for this capability, which is its bit number in the native bitmask.
+   *
+   * @return the native bit number
+   */
+  public int getValue() {
+    return value;
+  }
+
+}
@@ -0,0 +1,54 @@
+/*
+ * Copyright (C) 2026 Neeme Praks
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package dev.nemecec.jrserial;
+
+/**
+ * Connection state of a port, as reported by {@link SerialPort#getConnectionState()}.
+ */
+public enum ConnectionState {
+
+  /**
+   * The device is present and the port is usable.
+   */
+  CONNECTED(0),
+
+  /**
+   * The device has disconnected and the automatic reconnect thread is waiting for it to return.
+   */
+  RECONNECTING(1),
+
+  /**
+   * The device has disconnected and will not be reconnected, either because automatic reconnect
+   * is not enabled or because the returning device rejected the port's settings.
+   */
+  FAILED(2);
+
+  private final int value;
+
+  ConnectionState(int value) {
+    this.value = value;
+  }
+
+  /**
+   * Get the native value for this connection state.
+   *
+   * @return the native value (0=Connected, 1=Reconnecting, 2=Failed)
+   */
+  public int getValue() {
+    return value;
+  }
+
+}
@@ -0,0 +1,48 @@
+/*
+ * Copyright (C) 2026 Neeme Praks
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package dev.nemecec.jrserial;
+
+/**
+ * Modem control output line.
+ */
+public enum ControlLine {
+
+  /**
+   * RTS (Request To Send).
+   */
+  RTS(0),
+
+  /**
+   * DTR (Data Terminal Ready).
+   */
+  DTR(1);
+
+  private final int value;
+
+  ControlLine(int value) {
+    this.value = value;
+  }
+
+  /**
+   * Get the native value for this line.
+   *
+   * @return the native value (0=RTS, 1=DTR)
+   */
+  public int getValue() {
+    return value;
+  }
+
+}
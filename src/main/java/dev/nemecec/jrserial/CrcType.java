@@ -0,0 +1,63 @@
+/*
+ * Copyright (C) 2026 Neeme Praks
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package dev.nemecec.jrserial;
+
+/**
+ * A CRC algorithm for {@link SerialPort#writeWithCrc(byte[], int, int, CrcType, boolean)} and
+ * {@link SerialPort#readAndVerifyCrc(byte[], int, int, CrcType, boolean, int, int)}.
+ */
+public enum CrcType {
+
+  /**
+   * CRC-16/CCITT-FALSE: polynomial 0x1021, initial value 0xFFFF, not reflected, no final XOR.
+   * The CRC of {@code "123456789"} is 0x29B1. Usually sent big-endian.
+   */
+  CRC_CCITT(0, 2),
+
+  /**
+   * CRC-32 as used by Ethernet, zlib and {@link java.util.zip.CRC32}: polynomial 0x04C11DB7,
+   * reflected, initial value and final XOR 0xFFFFFFFF. The CRC of {@code "123456789"} is
+   * 0xCBF43926. Usually sent little-endian.
+   */
+  CRC32(1, 4);
+
+  private final int value;
+  private final int length;
+
+  CrcType(int value, int length) {
+    this.value = value;
+    this.length = length;
+  }
+
+  /**
+   * Get the native value for this CRC type.
+   *
+   * @return the native value (0=CRC-CCITT, 1=CRC-32)
+   */
+  public int getValue() {
+    return value;
+  }
+
+  /**
+   * Get the length of the CRC on the wire.
+   *
+   * @return the number of CRC bytes
+   */
+  public int getLength() {
+    return length;
+  }
+
+}
@@ -0,0 +1,103 @@
+/*
+ * Copyright (C) 2026 Neeme Praks
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package dev.nemecec.jrserial;
+
+/**
+ * The baud rate requested for a serial port next to the line speeds its driver reports as
+ * applied.
+ *
+ * <p>A custom baud rate is produced by dividing a fixed clock, so the driver may only be able
+ * to approximate it. A mismatch of more than a few percent between both ends of a link causes
+ * framing errors, most often at high speeds with adapters that have coarse clocks.
+ *
+ * @see SerialPort#getActualBaudRate()
+ */
+public final class LineSpeed {
+
+  private final int requestedBaudRate;
+  private final int inputBaudRate;
+  private final int outputBaudRate;
+
+  LineSpeed(int requestedBaudRate, int inputBaudRate, int outputBaudRate) {
+    this.requestedBaudRate = requestedBaudRate;
+    this.inputBaudRate = inputBaudRate;
+    this.outputBaudRate = outputBaudRate;
+  }
+
+  /**
+   * Parse the native actual baud rate format: {@code input\toutput}.
+   *
+   * @param requestedBaudRate the baud rate that was requested
+   * @param data              the tab-separated line speeds
+   * @return the parsed line speed
+   * @throws IllegalArgumentException if the data is not in the expected format
+   */
+  static LineSpeed parse(int requestedBaudRate, String data) {
+    String[] parts = data.split("\t");
+    if (parts.length < 2) {
+      throw new IllegalArgumentException("Invalid line speed: " + data);
+    }
+    return new LineSpeed(
+        requestedBaudRate, Integer.parseInt(parts[0]), Integer.parseInt(parts[1]));
+  }
+
+  /**
+   * Get the baud rate that was requested.
+   *
+   * <p>This is the rate the driver was asked for, {@link SerialPort#getBaudRate()}. If the port
+   * was opened at a nearby standard rate because of the baud tolerance, it is that rate.
+   *
+   * @return the requested baud rate
+   */
+  public int getRequestedBaudRate() {
+    return requestedBaudRate;
+  }
+
+  /**
+   * Get the speed the driver reports for received data.
+   *
+   * @return the applied input baud rate
+   */
+  public int getInputBaudRate() {
+    return inputBaudRate;
+  }
+
+  /**
+   * Get the speed the driver reports for transmitted data.
+   *
+   * @return the applied output baud rate
+   */
+  public int getOutputBaudRate() {
+    return outputBaudRate;
+  }
+
+  /**
+   * Get the deviation of the applied output speed from the requested baud rate.
+   *
+   * @return the error in percent, positive if the line runs faster than requested
+   */
+  public double getErrorPercent() {
+    return (outputBaudRate - requestedBaudRate) * 100.0 / requestedBaudRate;
+  }
+
+  @Override
+  public String toString() {
+    return "LineSpeed{requestedBaudRate=" + requestedBaudRate
+        + ", inputBaudRate=" + inputBaudRate
+        + ", outputBaudRate=" + outputBaudRate + "}";
+  }
+
+}
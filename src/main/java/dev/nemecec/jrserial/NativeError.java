@@ -0,0 +1,99 @@
+/*
+ * Copyright (C) 2026 Neeme Praks
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package dev.nemecec.jrserial;
+
+/**
+ * An error recorded by the native library, split into its message and the native source
+ * location that recorded it.
+ *
+ * <p>The same errors are available as single strings of the form
+ * {@code message (at file:line)}, which {@link #toString()} reproduces. The separate fields
+ * let callers build their own exceptions or log entries without parsing that string.
+ *
+ * @see SerialPort#getLastErrorDetails()
+ * @see SerialPort#getLastThreadErrorDetails()
+ */
+public final class NativeError {
+
+  private final String message;
+  private final String file;
+  private final int line;
+
+  NativeError(String message, String file, int line) {
+    this.message = message;
+    this.file = file;
+    this.line = line;
+  }
+
+  /**
+   * Parse the native error details format: {@code message\tfile\tline}.
+   *
+   * <p>The fields are split from the end, since only the message may contain tabs.
+   *
+   * @param data the tab-separated error details
+   * @return the parsed error
+   * @throws IllegalArgumentException if the data is not in the expected format
+   */
+  static NativeError parse(String data) {
+    int lineSeparator = data.lastIndexOf('\t');
+    int fileSeparator = lineSeparator > 0 ? data.lastIndexOf('\t', lineSeparator - 1) : -1;
+    if (fileSeparator < 0) {
+      throw new IllegalArgumentException("Invalid native error details: " + data);
+    }
+    return new NativeError(
+        data.substring(0, fileSeparator),
+        data.substring(fileSeparator + 1, lineSeparator),
+        Integer.parseInt(data.substring(lineSeparator + 1)));
+  }
+
+  /**
+   * Get the error message, without the source location.
+   *
+   * @return the message
+   */
+  public String getMessage() {
+    return message;
+  }
+
+  /**
+   * Get the native source file that recorded the error.
+   *
+   * @return the source file path, relative to the native crate
+   */
+  public String getFile() {
+    return file;
+  }
+
+  /**
+   * Get the line in the native source file that recorded the error.
+   *
+   * @return the line number
+   */
+  public int getLine() {
+    return line;
+  }
+
+  /**
+   * Format the error the way {@link SerialPort#getLastError()} reports it.
+   *
+   * @return {@code message (at file:line)}
+   */
+  @Override
+  public String toString() {
+    return message + " (at " + file + ":" + line + ")";
+  }
+
+}
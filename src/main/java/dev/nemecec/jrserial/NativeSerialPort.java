@@ -15,6 +15,8 @@
  */
 package dev.nemecec.jrserial;
 
+import java.nio.ByteBuffer;
+
 /**
  * JNI interface to the Rust native serial port implementation.
  */
@@ -37,6 +39,7 @@ private NativeSerialPort() {}
    * @param timeoutMs  the timeout in milliseconds
    * @param rs485Mode  RS-485 mode (0=None, 1=Auto, 2=Manual)
    * @param rs485Pin   RS-485 control pin (0=RTS, 1=DTR)
+   * @param settleMs   time to wait after the port is configured, in milliseconds (0 for none)
    * @return a handle to the native serial port, or 0 if failed
    */
   static native long open(
@@ -47,38 +50,440 @@ static native long open(
       int parity,
       int timeoutMs,
       int rs485Mode,
-      int rs485Pin
+      int rs485Pin,
+      int settleMs
   );
 
   /**
-   * Close a serial port.
+   * Close a serial port. Handles that are not open, including already closed ones, are ignored.
    *
    * @param handle the handle to the native serial port
    */
   static native void close(long handle);
 
+  /**
+   * Close every serial port that is still open.
+   *
+   * @return the number of ports closed
+   */
+  static native int closeAll();
+
   /**
    * Write data to a serial port.
    *
    * @param handle the handle to the native serial port
    * @param data   the data to write
    * @param offset the offset in the data array
-   * @param length the number of bytes to write
-   * @return the number of bytes written, or -1 if failed
+   * @param length the number of bytes to write; 0 returns 0 without touching the port
+   * @return the number of bytes written, -2 if the write would block (ERROR policy), or -1 if failed
    */
   static native int write(long handle, byte[] data, int offset, int length);
 
+  /**
+   * Write data followed by the configured line terminator, as one write.
+   *
+   * @param handle the handle to the native serial port
+   * @param data   the data to write
+   * @param offset the offset in the data array
+   * @param length the number of bytes to write, not counting the terminator
+   * @return the number of bytes written including the terminator, -2 if the write would block
+   *     (ERROR policy), or -1 if failed
+   */
+  static native int writeLine(long handle, byte[] data, int offset, int length);
+
+  /**
+   * Write data followed by its CRC, in a single write.
+   *
+   * @param handle       the handle to the native serial port
+   * @param data         the data to write
+   * @param offset       the offset in the data array
+   * @param length       the number of bytes to write, not counting the CRC
+   * @param crcType      the CRC type (0=CRC-CCITT, 1=CRC-32)
+   * @param littleEndian true to append the CRC least significant byte first
+   * @return the number of bytes written including the CRC, -2 if the write would block (ERROR
+   *     policy), or -1 if failed
+   */
+  static native int writeWithCrc(
+      long handle, byte[] data, int offset, int length, int crcType, boolean littleEndian);
+
+  /**
+   * Write data to a serial port, giving up at an absolute deadline.
+   *
+   * @param handle     the handle to the native serial port
+   * @param data       the data to write
+   * @param offset     the offset in the data array
+   * @param length     the number of bytes to write; 0 returns 0 without touching the port
+   * @param deadlineMs the deadline in milliseconds since the Unix epoch
+   * @return the number of bytes written, fewer than length if the deadline passed, or -1 if failed
+   */
+  static native int writeWithDeadline(long handle, byte[] data, int offset, int length, long deadlineMs);
+
+  /**
+   * Send a DMX512 packet: a break, a mark-after-break, then the start code and slots.
+   *
+   * @param handle the handle to the native serial port
+   * @param data   the packet, starting with the start code
+   * @param offset the offset in the data array
+   * @param length the number of bytes to send (1-513)
+   * @return the number of bytes written, or -1 if failed
+   */
+  static native int sendDmxFrame(long handle, byte[] data, int offset, int length);
+
+  /**
+   * Write an unsigned integer as {@code size} bytes in the given byte order.
+   *
+   * @param handle    the handle to the native serial port
+   * @param value     the value, which must fit in {@code size} bytes
+   * @param size      the number of bytes to write
+   * @param bigEndian true for most significant byte first, false for least significant first
+   * @return the number of bytes written, -2 if the write would block, or -1 if failed
+   */
+  static native int writeUnsigned(long handle, long value, int size, boolean bigEndian);
+
+  /**
+   * Write 9-bit characters using mark/space parity as the 9th bit (Linux only).
+   *
+   * @param handle    the handle to the native serial port
+   * @param data      the data bytes
+   * @param ninthBits the 9th bit of each data byte (same length as data)
+   * @return the number of bytes written, or -1 on failure or if not on Linux
+   */
+  static native int write9bit(long handle, byte[] data, boolean[] ninthBits);
+
   /**
    * Read data from a serial port.
    *
    * @param handle the handle to the native serial port
    * @param buffer the buffer to read into
    * @param offset the offset in the buffer
-   * @param length the maximum number of bytes to read
-   * @return the number of bytes read, or -1 if failed
+   * @param length the maximum number of bytes to read; 0 returns 0 without a read syscall
+   * @return the number of bytes read, -2 if the read was cancelled, or -1 if failed
    */
   static native int read(long handle, byte[] buffer, int offset, int length);
 
+  /**
+   * Read data from a serial port directly into the array's backing storage.
+   * <p>
+   * The array is pinned with a JNI critical region only for the final copy,
+   * after the blocking read has completed.
+   *
+   * @param handle the handle to the native serial port
+   * @param buffer the buffer to read into
+   * @param offset the offset in the buffer
+   * @param length the maximum number of bytes to read
+   * @return the number of bytes read, -2 if the read was cancelled, or -1 if failed
+   */
+  static native int readCritical(long handle, byte[] buffer, int offset, int length);
+
+  /**
+   * Read data from a serial port directly into native memory.
+   * <p>
+   * The caller must guarantee that {@code address} points to at least {@code length}
+   * writable bytes that remain valid for the duration of the call.
+   *
+   * @param handle  the handle to the native serial port
+   * @param address the native memory address to read into
+   * @param length  the maximum number of bytes to read
+   * @return the number of bytes read, -2 if the read was cancelled, or -1 if failed
+   */
+  static native int readToAddress(long handle, long address, int length);
+
+  /**
+   * Write data to a serial port directly from native memory.
+   * <p>
+   * The caller must guarantee that {@code address + offset} points to at least
+   * {@code length} readable bytes that remain valid for the duration of the call.
+   *
+   * @param handle  the handle to the native serial port
+   * @param address the native memory address to write from
+   * @param offset  the offset from the address
+   * @param length  the number of bytes to write
+   * @return the number of bytes written, -2 if the write would block, or -1 if failed
+   */
+  static native int writeFromAddress(long handle, long address, int offset, int length);
+
+  /**
+   * Read data from the serial port straight into a direct buffer.
+   *
+   * @param handle the handle to the native serial port
+   * @param buffer the direct buffer to read into
+   * @param offset the offset in the buffer
+   * @param length the maximum number of bytes to read
+   * @return the number of bytes read, -2 if the read was cancelled, -3 if the buffer's address
+   *     is not available (nothing was read), or -1 if failed
+   */
+  static native int readDirect(long handle, ByteBuffer buffer, int offset, int length);
+
+  /**
+   * Write data to the serial port straight from a direct buffer.
+   *
+   * @param handle the handle to the native serial port
+   * @param buffer the direct buffer to write from
+   * @param offset the offset in the buffer
+   * @param length the number of bytes to write
+   * @return the number of bytes written, -2 if the write would block, -3 if the buffer's address
+   *     is not available (nothing was written), or -1 if failed
+   */
+  static native int writeDirect(long handle, ByteBuffer buffer, int offset, int length);
+
+  /**
+   * Perform a request/response exchange: clear input, write the request, drain, then read the response.
+   *
+   * @param handle    the handle to the native serial port
+   * @param request   the request bytes to write
+   * @param response  the buffer to read the response into
+   * @param maxLen    the maximum number of response bytes to read
+   * @param timeoutMs the deadline for the response in milliseconds
+   * @return the number of bytes read, 0 on response timeout, or -1 if failed
+   */
+  static native int transact(long handle, byte[] request, byte[] response, int maxLen, int timeoutMs);
+
+  /**
+   * Measure round-trip latency: repeatedly clear input, write the probe and time the arrival of
+   * the response.
+   *
+   * @param handle      the handle to the native serial port
+   * @param probe       the bytes to write in each round trip
+   * @param responseLen the number of response bytes to wait for
+   * @param timeoutMs   the deadline for each response in milliseconds
+   * @param iterations  the number of round trips
+   * @return tab-separated min, average and max latency in microseconds, or null if failed
+   */
+  static native String measureRoundTrip(
+      long handle, byte[] probe, int responseLen, int timeoutMs, int iterations);
+
+  /**
+   * Read until the requested number of bytes arrive or the timeout elapses.
+   *
+   * @param handle    the handle to the native serial port
+   * @param buffer    the buffer to read into
+   * @param offset    the offset in the buffer
+   * @param length    the number of bytes to read
+   * @param timeoutMs the deadline for the whole read in milliseconds
+   * @return the number of bytes read, -1 on failure, or -2 if the read was cancelled
+   */
+  static native int readFully(long handle, byte[] buffer, int offset, int length, int timeoutMs);
+
+  /**
+   * Read until the line stays idle for a gap after data has arrived, the maximum length is
+   * reached, or the overall timeout elapses.
+   *
+   * @param handle    the handle to the native serial port
+   * @param buffer    the buffer to read into
+   * @param offset    the offset in the buffer
+   * @param maxLen    the maximum number of bytes to read
+   * @param idleGapMs the silence after received data that ends the read, in milliseconds
+   * @param timeoutMs the deadline for the whole read in milliseconds
+   * @return the number of bytes read, -1 on failure, or -2 if the read was cancelled
+   */
+  static native int readUntilIdle(long handle, byte[] buffer, int offset, int maxLen, int idleGapMs,
+      int timeoutMs);
+
+  /**
+   * Read a frame that ends when the line goes idle and check the CRC at its end.
+   *
+   * @param handle       the handle to the native serial port
+   * @param buffer       the buffer to read the frame, including its CRC, into
+   * @param offset       the offset in the buffer
+   * @param maxLen       the maximum frame length, including the CRC
+   * @param crcType      the CRC type (0=CRC-CCITT, 1=CRC-32)
+   * @param littleEndian true if the CRC is sent least significant byte first
+   * @param idleGapMs    the silence after received data that ends the frame, in milliseconds
+   * @param timeoutMs    the deadline for the whole read in milliseconds
+   * @return the payload length, 0 if nothing arrived, -1 if failed or the CRC does not match,
+   *     or -2 if the read was cancelled
+   */
+  static native int readAndVerifyCrc(long handle, byte[] buffer, int offset, int maxLen,
+      int crcType, boolean littleEndian, int idleGapMs, int timeoutMs);
+
+  /**
+   * Read until the data read ends with a multi-byte pattern, the maximum length is reached, or
+   * the timeout elapses. Bytes read past the pattern are returned by the next reads.
+   *
+   * @param handle    the handle to the native serial port
+   * @param pattern   the byte sequence that ends the read (not empty)
+   * @param buffer    the buffer to read into
+   * @param offset    the offset in the buffer
+   * @param maxLen    the maximum number of bytes to read
+   * @param timeoutMs the deadline for the whole read in milliseconds
+   * @return the number of bytes read including the pattern if found, -1 on failure, or -2 if
+   *     the read was cancelled
+   */
+  static native int readUntilPattern(long handle, byte[] pattern, byte[] buffer, int offset,
+      int maxLen, int timeoutMs);
+
+  /**
+   * Read a frame whose fixed-size header carries the frame length.
+   *
+   * @param handle            the handle to the native serial port
+   * @param buffer            the buffer to read into
+   * @param offset            the offset in the buffer
+   * @param headerLen         the length of the header in bytes
+   * @param lengthFieldOffset the offset of the length field within the header
+   * @param lengthFieldSize   the size of the length field, 1 to 4 bytes
+   * @param lengthIsBigEndian true if the length field is big-endian
+   * @param includesHeader    true if the length counts the header as well as the payload
+   * @param maxLen            the maximum frame length, including the header
+   * @param timeoutMs         the deadline for the whole read in milliseconds
+   * @return the number of bytes read (less than the frame length on timeout), -2 if cancelled,
+   *     or -1 if failed
+   */
+  static native int readLengthPrefixedFrame(long handle, byte[] buffer, int offset, int headerLen,
+      int lengthFieldOffset, int lengthFieldSize, boolean lengthIsBigEndian,
+      boolean includesHeader, int maxLen, int timeoutMs);
+
+  /**
+   * Start a background thread that splits incoming data into frames at idle gaps.
+   *
+   * @param handle         the handle to the native serial port
+   * @param idleGapMicros  the silence after received data that ends a frame, in microseconds
+   * @return true if successful, false on failure or if a frame reader is already running
+   */
+  static native boolean startFrameReader(long handle, int idleGapMicros);
+
+  /**
+   * Stop the frame reader thread and discard its queued frames.
+   *
+   * @param handle the handle to the native serial port
+   * @return true if successful (including when no frame reader is running), false otherwise
+   */
+  static native boolean stopFrameReader(long handle);
+
+  /**
+   * Take the next complete frame from the frame reader.
+   *
+   * @param handle    the handle to the native serial port
+   * @param buffer    the buffer to copy the frame into, from index 0
+   * @param maxLen    the maximum frame length the buffer can take
+   * @param timeoutMs how long to wait for a frame in milliseconds
+   * @return the frame length, 0 on timeout, or -1 on failure (a frame longer than maxLen stays
+   *     queued)
+   */
+  static native int nextFrame(long handle, byte[] buffer, int maxLen, int timeoutMs);
+
+  /**
+   * Get the number of frames the frame reader dropped because its queue was full.
+   *
+   * @param handle the handle to the native serial port
+   * @return the number of dropped frames, or -1 on failure
+   */
+  static native long getDroppedFrameCount(long handle);
+
+  /**
+   * Read a single byte.
+   *
+   * @param handle    the handle to the native serial port
+   * @param timeoutMs the maximum time to wait in milliseconds, or 0 to wait indefinitely
+   * @return the byte (0-255), -1 on timeout or failure, or -2 if the read was cancelled
+   */
+  static native int readOne(long handle, int timeoutMs);
+
+  /**
+   * Check whether the last readFully or readOne returned early because its deadline elapsed.
+   *
+   * @param handle the handle to the native serial port
+   * @return true if the last readFully or readOne timed out
+   */
+  static native boolean lastReadTimedOut(long handle);
+
+  /**
+   * Get the current value of the native monotonic clock.
+   *
+   * @return the native monotonic time in nanoseconds
+   */
+  static native long monotonicNanos();
+
+  /**
+   * Format part of a byte array as a hex and ASCII dump, one line per {@code bytesPerLine} bytes.
+   *
+   * <p>Only the first 64 KiB are rendered; a final line tells how many bytes were left out.
+   *
+   * @param data         the bytes to dump
+   * @param offset       the offset in the data array
+   * @param length       the number of bytes to dump
+   * @param bytesPerLine the number of bytes per line (1-256)
+   * @return the dump, or null on failure
+   */
+  static native String formatDump(byte[] data, int offset, int length, int bytesPerLine);
+
+  /**
+   * Compute the CRC-32 (as used by Ethernet and zlib) of part of an array.
+   *
+   * @param data   the data
+   * @param offset the offset in the data array
+   * @param length the number of bytes
+   * @return the CRC (0 to 0xFFFFFFFF), or -1 if failed
+   */
+  static native long crc32(byte[] data, int offset, int length);
+
+  /**
+   * Compute the CRC-16/CCITT-FALSE of part of an array.
+   *
+   * @param data   the data
+   * @param offset the offset in the data array
+   * @param length the number of bytes
+   * @return the CRC (0 to 0xFFFF), or -1 if failed
+   */
+  static native int crcCcitt(byte[] data, int offset, int length);
+
+  /**
+   * Read data from a serial port and record when the read returned.
+   *
+   * @param handle       the handle to the native serial port
+   * @param buffer       the buffer to read into
+   * @param offset       the offset in the buffer
+   * @param length       the maximum number of bytes to read; 0 returns 0 without a read syscall
+   * @param timestampOut a one-element array that receives the native monotonic receive time in nanoseconds
+   * @return the number of bytes read, -2 if the read was cancelled, or -1 if failed
+   */
+  static native int readTimestamped(long handle, byte[] buffer, int offset, int length, long[] timestampOut);
+
+  /**
+   * Read once from a serial port and scatter the data across several buffers.
+   *
+   * @param handle    the handle to the native serial port
+   * @param buffers   the buffers to read into, filled in order
+   * @param offsets   the offset in each buffer
+   * @param lengths   the maximum number of bytes to read into each buffer
+   * @param timeoutMs the timeout for this read in milliseconds
+   * @return the total number of bytes read, 0 on timeout, -2 if cancelled, or -1 if failed
+   */
+  static native int readScatter(long handle, byte[][] buffers, int[] offsets, int[] lengths, int timeoutMs);
+
+  /**
+   * Cancel a read blocked on the port from another thread (Linux only).
+   *
+   * @param handle the handle to the native serial port
+   * @return true if successful, false on failure or if not on Linux
+   */
+  static native boolean cancelRead(long handle);
+
+  /**
+   * Get an eventfd that becomes readable when the port has input (Linux only).
+   *
+   * @param handle the handle to the native serial port
+   * @return the file descriptor, or -1 on failure or if not on Linux
+   */
+  static native int getReadableEventFd(long handle);
+
+  /**
+   * Reset the eventfd returned by {@link #getReadableEventFd(long)}.
+   *
+   * @param handle the handle to the native serial port
+   * @return true if the event was signalled, false otherwise
+   */
+  static native boolean resetReadableEvent(long handle);
+
+  /**
+   * Wait until any of the given ports has data to read, or the timeout expires.
+   *
+   * @param handles     the handles to the native serial ports
+   * @param readableOut receives, per handle, whether the port has data available
+   * @param timeoutMs   the maximum time to wait in milliseconds
+   * @return the number of readable ports (0 on timeout), or -1 if failed
+   */
+  static native int pollReadable(long[] handles, boolean[] readableOut, int timeoutMs);
+
   /**
    * Get the number of bytes available to read.
    *
@@ -87,6 +492,50 @@ static native long open(
    */
   static native int bytesAvailable(long handle);
 
+  /**
+   * Block until at least the given number of bytes are available to read or the timeout elapses.
+   *
+   * @param handle    the handle to the native serial port
+   * @param count     the number of bytes to wait for
+   * @param timeoutMs the maximum time to wait in milliseconds
+   * @return the number of bytes available, or -1 on failure
+   */
+  static native int waitForBytes(long handle, int count, int timeoutMs);
+
+  /**
+   * Block until all output queued so far has been transmitted or the timeout elapses.
+   *
+   * @param handle    the handle to the native serial port
+   * @param timeoutMs the maximum time to wait in milliseconds
+   * @return 1 if the output emptied, 0 on timeout, or -1 on failure
+   */
+  static native int waitOutputEmpty(long handle, int timeoutMs);
+
+  /**
+   * Get a snapshot of the port status in one call.
+   *
+   * @param handle the handle to the native serial port
+   * @return a tab-separated line: input bytes, output bytes, modem bitmask (-1 if unavailable),
+   *     kernel RS-485 flag ("1"/"0"); or null if failed
+   */
+  static native String getPortStatus(long handle);
+
+  /**
+   * Get the deepest input and output queues observed since open or the last reset.
+   *
+   * @param handle the handle to the native serial port
+   * @return a tab-separated line: input bytes, output bytes; or null if failed
+   */
+  static native String getQueueHighWater(long handle);
+
+  /**
+   * Reset the queue high-water marks to zero.
+   *
+   * @param handle the handle to the native serial port
+   * @return 0 on success, -1 on failure
+   */
+  static native int resetQueueHighWater(long handle);
+
   /**
    * Flush the output buffer.
    *
@@ -102,6 +551,62 @@ static native long open(
    */
   static native String listPorts();
 
+  /**
+   * List available serial ports as JSON.
+   *
+   * @return a JSON array of port objects, or null if enumeration failed
+   */
+  static native String listPortsJson();
+
+  /**
+   * Get the per-port problems noticed by the last port listing on the current thread.
+   *
+   * @return newline-separated warnings, or null if there are none
+   */
+  static native String getEnumerationWarnings();
+
+  /**
+   * Find the device path of the USB serial port with the given USB serial number.
+   *
+   * @param serialNumber the USB serial number to match
+   * @return the port name, or null if no port or more than one port matches
+   */
+  static native String findPortByUsbSerial(String serialNumber);
+
+  /**
+   * Check whether the current user can open a port, without opening it (Linux only).
+   *
+   * @param portName the name of the port
+   * @return 0 if accessible, 1 if the device does not exist, 2 if read/write permission is
+   *         missing, or -1 on failure or if not on Linux
+   */
+  static native int checkAccess(String portName);
+
+  /**
+   * Check whether a port is a virtual device (PTY, null-modem emulator or virtual COM port).
+   *
+   * @param portName the name of the port
+   * @return true if the port is recognized as virtual
+   */
+  static native boolean isVirtualPort(String portName);
+
+  /**
+   * Detect the USB-to-serial chipset of the adapter behind a port.
+   *
+   * @param portName the name of the port
+   * @return "ftdi", "cp210x", "ch340", "pl2303" or "unknown", or null on error
+   */
+  static native String getChipset(String portName);
+
+  /**
+   * Probe which baud rates a port's driver accepts, opening it briefly without touching DTR/RTS.
+   *
+   * @param portName the name of the port (e.g., "COM1" or "/dev/ttyUSB0")
+   * @return the accepted rates comma-separated, a tab, then 1 if custom rates are available or
+   *         0 if not; or null if failed
+   */
+  static native String getSupportedBaudRates(String portName);
+
   /**
    * Set the timeout for read operations.
    *
@@ -135,6 +640,30 @@ static native long open(
    */
   static native boolean clearAll(long handle);
 
+  /**
+   * Clear the input buffer, counting the bytes discarded.
+   *
+   * @param handle the handle to the native serial port
+   * @return the number of bytes discarded, or -1 if failed
+   */
+  static native long clearInputCounted(long handle);
+
+  /**
+   * Clear the output buffer, counting the bytes discarded.
+   *
+   * @param handle the handle to the native serial port
+   * @return the number of bytes discarded, or -1 if failed
+   */
+  static native long clearOutputCounted(long handle);
+
+  /**
+   * Clear both input and output buffers, counting the bytes discarded.
+   *
+   * @param handle the handle to the native serial port
+   * @return the number of bytes discarded, or -1 if failed
+   */
+  static native long clearAllCounted(long handle);
+
   /**
    * Set the RTS pin state.
    *
@@ -154,18 +683,207 @@ static native long open(
   static native boolean setDTR(long handle, boolean level);
 
   /**
-   * Check if kernel RS-485 mode is active (Linux only).
+   * Get the last level set on the RTS pin.
    *
    * @param handle the handle to the native serial port
-   * @return true if kernel RS-485 mode is active, false otherwise
+   * @return 1 if high, 0 if low, or -1 if failed
    */
-  static native boolean isKernelRs485Active(long handle);
+  static native int getRTS(long handle);
 
   /**
-   * Set RS-485 timing delays (Linux kernel mode only).
+   * Get the last level set on the DTR pin.
    *
-   * @param handle                 the handle to the native serial port
-   * @param delayBeforeSendMicros  delay before sending in microseconds
+   * @param handle the handle to the native serial port
+   * @return 1 if high, 0 if low, or -1 if failed
+   */
+  static native int getDTR(long handle);
+
+  /**
+   * Pulse RTS or DTR for the given duration, then restore the opposite level.
+   *
+   * @param handle      the handle to the native serial port
+   * @param line        0 for RTS, 1 for DTR
+   * @param assertLevel the level to hold during the pulse
+   * @param durationMs  the pulse duration in milliseconds
+   * @return true if successful, false otherwise
+   */
+  static native boolean pulseLine(long handle, int line, boolean assertLevel, int durationMs);
+
+  /**
+   * Report why writes may be stalled.
+   *
+   * @param handle the handle to the native serial port
+   * @return 0 if idle, 1 if the output buffer is not empty, 2 if blocked by hardware flow
+   *         control, or -1 on failure
+   */
+  static native int getWriteStallReason(long handle);
+
+  /**
+   * Transmit the XON (START) flow control character (Linux only).
+   *
+   * @param handle the handle to the native serial port
+   * @return true if successful, false on failure or if not on Linux
+   */
+  static native boolean sendXon(long handle);
+
+  /**
+   * Transmit the XOFF (STOP) flow control character (Linux only).
+   *
+   * @param handle the handle to the native serial port
+   * @return true if successful, false on failure or if not on Linux
+   */
+  static native boolean sendXoff(long handle);
+
+  /**
+   * Check whether the last read on this port found it closed by its peer (end of file).
+   *
+   * @param handle the handle to the native serial port
+   * @return true if the last read hit end of file, false otherwise
+   */
+  static native boolean isEof(long handle);
+
+  /**
+   * Check whether output is held back by XON/XOFF flow control (Linux only).
+   *
+   * @param handle the handle to the native serial port
+   * @return 1 if output is stopped, 0 if not, -1 on failure or if not on Linux
+   */
+  static native int isOutputStopped(long handle);
+
+  /**
+   * Read the CTS pin state.
+   *
+   * @param handle the handle to the native serial port
+   * @return 1 if asserted, 0 if not asserted, -1 on failure
+   */
+  static native int readCTS(long handle);
+
+  /**
+   * Verify RTS/CTS wiring by toggling RTS and checking that CTS follows.
+   *
+   * @param handle the handle to the native serial port
+   * @return 1 if CTS tracks RTS, 0 if it does not, -1 on failure
+   */
+  static native int verifyHardwareFlowControl(long handle);
+
+  /**
+   * Check if the handle still refers to a usable port.
+   *
+   * @param handle the handle to the native serial port
+   * @return true if the handle is non-null and the device is still open and connected
+   */
+  static native boolean isValid(long handle);
+
+  /**
+   * Start a thread that reopens the port after its device disconnects (Linux only).
+   *
+   * @param handle          the handle to the native serial port
+   * @param probeIntervalMs how often to check the connection, in milliseconds (must be positive)
+   * @return true if successful, false on failure or if not on Linux
+   */
+  static native boolean enableAutoReconnect(long handle, int probeIntervalMs);
+
+  /**
+   * Stop the automatic reconnect thread and wait for it to exit (Linux only).
+   *
+   * @param handle the handle to the native serial port
+   * @return true if successful (also if no thread was running), false on failure or if not on
+   *         Linux
+   */
+  static native boolean disableAutoReconnect(long handle);
+
+  /**
+   * Get the connection state of a serial port.
+   *
+   * @param handle the handle to the native serial port
+   * @return 0 = connected, 1 = reconnecting, 2 = failed, or -1 if failed to query
+   */
+  static native int getConnectionState(long handle);
+
+  /**
+   * Get the number of ring indicator edges counted since the port was opened (Linux only).
+   *
+   * @param handle the handle to the native serial port
+   * @return the count, or -1 on failure, if the driver does not count them, or if not on Linux
+   */
+  static native long getRingCount(long handle);
+
+  /**
+   * Check if kernel RS-485 mode is active (Linux only).
+   *
+   * @param handle the handle to the native serial port
+   * @return true if kernel RS-485 mode is active, false otherwise
+   */
+  static native boolean isKernelRs485Active(long handle);
+
+  /**
+   * Get how RS-485 direction control is actually performed.
+   *
+   * @param handle the handle to the native serial port
+   * @return 0=None, 1=KernelAuto, 2=ManualByFallback, 3=ManualByRequest, 4=ManualByPin, or -1 on
+   *     failure
+   */
+  static native int getRs485ActualMode(long handle);
+
+  /**
+   * Enable or disable listen-only mode.
+   *
+   * @param handle  the handle to the native serial port
+   * @param enabled true to reject writes and keep the transceiver in receive mode
+   * @return true if successful, false otherwise
+   */
+  static native boolean setListenOnly(long handle, boolean enabled);
+
+  /**
+   * Keep the manual RS-485 direction pin asserted between writes, or drain the output and
+   * release it.
+   *
+   * @param handle the handle to the native serial port
+   * @param hold   true to hold the pin asserted after the next write, false to release it
+   * @return true if successful, false otherwise (including in kernel RS-485 mode)
+   */
+  static native boolean holdTransmitEnable(long handle, boolean hold);
+
+  /**
+   * Release the manual RS-485 direction pin if it is left asserted with no write for a timeout.
+   *
+   * @param handle    the handle to the native serial port
+   * @param timeoutMs the longest time the pin may stay asserted without a write, or 0 to
+   *                  disable
+   * @return true if successful, false otherwise (including without manual RS-485 control)
+   */
+  static native boolean setFailsafe(long handle, int timeoutMs);
+
+  /**
+   * Get warnings about kernel RS-485 settings the driver did not apply as requested.
+   *
+   * @param handle the handle to the native serial port
+   * @return newline-separated warnings, or null if there are none
+   */
+  static native String getRs485Warnings(long handle);
+
+  /**
+   * Get the line settings the driver applied differently than requested when the port was opened.
+   *
+   * @param handle the handle to the native serial port
+   * @return newline-separated discrepancies, or null if there are none
+   */
+  static native String getConfigDiscrepancies(long handle);
+
+  /**
+   * Get the baud rate the port was opened at, which the baud tolerance may have changed from the
+   * requested one.
+   *
+   * @param handle the handle to the native serial port
+   * @return the baud rate, or 0 if the port was opened without the tolerance
+   */
+  static native int getOpenedBaudRate(long handle);
+
+  /**
+   * Set RS-485 timing delays (Linux kernel mode only).
+   *
+   * @param handle                 the handle to the native serial port
+   * @param delayBeforeSendMicros  delay before sending in microseconds
    * @param delayAfterSendMicros   delay after sending in microseconds
    * @return true if successful, false otherwise
    */
@@ -189,6 +907,10 @@ static native long open(
    * @param terminationEnabled true to enable bus termination (hardware-dependent)
    * @param delayBeforeMicros  delay before sending in microseconds
    * @param delayAfterMicros   delay after sending in microseconds
+   * @param accessMode         the access mode (0=ReadWrite, 1=ReadOnly, 2=WriteOnly)
+   * @param baudTolerancePercent how far off, in percent, a standard rate used when the exact
+   *                             baud rate fails may be (0 to require the exact rate)
+   * @param settleMs           time to wait after the port is configured, in milliseconds (0 for none)
    * @return a handle to the native serial port, or 0 if failed
    */
   static native long openWithRs485Config(
@@ -206,7 +928,133 @@ static native long openWithRs485Config(
       boolean rxDuringTx,
       boolean terminationEnabled,
       int delayBeforeMicros,
-      int delayAfterMicros
+      int delayAfterMicros,
+      int accessMode,
+      int baudTolerancePercent,
+      int settleMs
+  );
+
+  /**
+   * Open several serial ports with the same settings in one call.
+   *
+   * <p>A port that fails to open does not stop the others: its entry in {@code handlesOut} is
+   * 0 and the combined failure summary is available from {@link #getLastError()}. The settle
+   * delay is applied once after all ports are opened. The caller owns every non-zero handle.
+   *
+   * @param portNames          the names of the ports to open
+   * @param baudRate           the baud rate
+   * @param dataBits           the number of data bits (5, 6, 7, or 8)
+   * @param stopBits           the number of stop bits (1 or 2)
+   * @param parity             the parity (0=None, 1=Odd, 2=Even)
+   * @param flowControl        the flow control mode (0=None, 1=Software, 2=Hardware)
+   * @param dtrOnOpen          true to assert DTR on open, false to suppress
+   * @param timeoutMs          the timeout in milliseconds
+   * @param rs485Mode          RS-485 mode (0=None, 1=Auto, 2=Manual)
+   * @param rs485Pin           RS-485 control pin (0=RTS, 1=DTR)
+   * @param rtsActiveHigh      true if RTS is active high, false for active low
+   * @param rxDuringTx         true to enable receiving during transmission
+   * @param terminationEnabled true to enable bus termination (hardware-dependent)
+   * @param delayBeforeMicros  delay before sending in microseconds
+   * @param delayAfterMicros   delay after sending in microseconds
+   * @param accessMode         the access mode (0=ReadWrite, 1=ReadOnly, 2=WriteOnly)
+   * @param baudTolerancePercent how far off, in percent, a standard rate used when the exact
+   *                             baud rate fails may be (0 to require the exact rate)
+   * @param settleMs           time to wait after the ports are configured, in milliseconds
+   * @param handlesOut         receives one handle per port name, 0 for ports that failed
+   * @return the number of ports opened, or -1 if the call itself failed
+   */
+  static native int openMany(
+      String[] portNames,
+      int baudRate,
+      int dataBits,
+      int stopBits,
+      int parity,
+      int flowControl,
+      boolean dtrOnOpen,
+      int timeoutMs,
+      int rs485Mode,
+      int rs485Pin,
+      boolean rtsActiveHigh,
+      boolean rxDuringTx,
+      boolean terminationEnabled,
+      int delayBeforeMicros,
+      int delayAfterMicros,
+      int accessMode,
+      int baudTolerancePercent,
+      int settleMs,
+      long[] handlesOut
+  );
+
+  /**
+   * Open a serial port with extended RS-485 configuration and verify that it responds.
+   *
+   * <p>Queue depths and modem lines are queried after opening; nothing is written.
+   *
+   * @param portName           the name of the port (e.g., "COM1" or "/dev/ttyUSB0")
+   * @param baudRate           the baud rate
+   * @param dataBits           the number of data bits (5, 6, 7, or 8)
+   * @param stopBits           the number of stop bits (1 or 2)
+   * @param parity             the parity (0=None, 1=Odd, 2=Even)
+   * @param flowControl        the flow control mode (0=None, 1=Software, 2=Hardware)
+   * @param dtrOnOpen          true to assert DTR on open, false to suppress
+   * @param timeoutMs          the timeout in milliseconds
+   * @param rs485Mode          RS-485 mode (0=None, 1=Auto, 2=Manual)
+   * @param rs485Pin           RS-485 control pin (0=RTS, 1=DTR)
+   * @param rtsActiveHigh      true if RTS is active high, false for active low
+   * @param rxDuringTx         true to enable receiving during transmission
+   * @param terminationEnabled true to enable bus termination (hardware-dependent)
+   * @param delayBeforeMicros  delay before sending in microseconds
+   * @param delayAfterMicros   delay after sending in microseconds
+   * @param accessMode         the access mode (0=ReadWrite, 1=ReadOnly, 2=WriteOnly)
+   * @param baudTolerancePercent how far off, in percent, a standard rate used when the exact
+   *                             baud rate fails may be (0 to require the exact rate)
+   * @param settleMs           time to wait after the port is configured, in milliseconds (0 for none)
+   * @return a handle to the native serial port, or 0 if opening or verification failed
+   */
+  static native long openAndVerify(
+      String portName,
+      int baudRate,
+      int dataBits,
+      int stopBits,
+      int parity,
+      int flowControl,
+      boolean dtrOnOpen,
+      int timeoutMs,
+      int rs485Mode,
+      int rs485Pin,
+      boolean rtsActiveHigh,
+      boolean rxDuringTx,
+      boolean terminationEnabled,
+      int delayBeforeMicros,
+      int delayAfterMicros,
+      int accessMode,
+      int baudTolerancePercent,
+      int settleMs
+  );
+
+  /**
+   * Open a serial port without driving the DTR/RTS control lines, and clear HUPCL on Linux so
+   * that closing it does not pulse DTR.
+   *
+   * @param portName    the name of the port (e.g., "COM1" or "/dev/ttyUSB0")
+   * @param baudRate    the baud rate
+   * @param dataBits    the number of data bits (5, 6, 7, or 8)
+   * @param stopBits    the number of stop bits (1 or 2)
+   * @param parity      the parity (0=None, 1=Odd, 2=Even)
+   * @param flowControl the flow control mode (0=None, 1=Software, 2=Hardware)
+   * @param timeoutMs   the timeout in milliseconds
+   * @param settleMs    time to wait after the port is configured, in milliseconds (0 for none)
+   * @return a handle to the native serial port, or 0 if failed
+   */
+  static native long openQuiet(
+      String portName,
+      int baudRate,
+      int dataBits,
+      int stopBits,
+      int parity,
+      int flowControl,
+      int timeoutMs,
+      int settleMs
   );
 
   /**
@@ -233,6 +1081,319 @@ static native boolean setRs485Config(
       int delayAfterMicros
   );
 
+  /**
+   * Set how writes react when the port would block.
+   *
+   * @param handle the handle to the native serial port
+   * @param policy the write blocking policy (0=Block, 1=Error, 2=Partial)
+   * @return true if successful, false otherwise
+   */
+  static native boolean setWriteBlockingPolicy(long handle, int policy);
+
+  /**
+   * Split writes into chunks separated by gaps, for receivers with small buffers.
+   *
+   * @param handle     the handle to the native serial port
+   * @param chunkBytes the maximum bytes per chunk, or 0 to disable pacing
+   * @param gapMicros  the pause between chunks in microseconds
+   * @return true if successful, false otherwise
+   */
+  static native boolean setWritePacing(long handle, int chunkBytes, int gapMicros);
+
+  /**
+   * Limit the sustained write rate, delaying whole writes between frames.
+   *
+   * @param handle      the handle to the native serial port
+   * @param bytesPerSec the maximum sustained rate in bytes per second, or 0 for unlimited
+   * @return true if successful, false otherwise
+   */
+  static native boolean setMaxThroughput(long handle, int bytesPerSec);
+
+  /**
+   * Hold the line idle for a number of bit periods before each write.
+   *
+   * @param handle the handle to the native serial port
+   * @param bits   the preamble length in bit periods, or 0 for none
+   * @return true if successful, false otherwise
+   */
+  static native boolean setPreambleIdleBits(long handle, int bits);
+
+  /**
+   * Reverse the bit order of every byte written and read, in software.
+   *
+   * @param handle  the handle to the native serial port
+   * @param enabled true to reverse the bits of each byte, false to send and receive bytes as is
+   * @return true if successful, false otherwise
+   */
+  static native boolean setBitReversal(long handle, boolean enabled);
+
+  /**
+   * Set the bytes appended to each line written with {@link #writeLine}.
+   *
+   * @param handle     the handle to the native serial port
+   * @param terminator the line terminator, empty for none
+   * @return true if successful, false otherwise
+   */
+  static native boolean setWriteTerminator(long handle, byte[] terminator);
+
+  /**
+   * Set how many times a read is retried after a transient error before it fails.
+   *
+   * @param handle the handle to the native serial port
+   * @param count  the number of retries, or 0 to disable retrying
+   * @return true if successful, false otherwise
+   */
+  static native boolean setReadRetries(long handle, int count);
+
+  /**
+   * Get the number of retries reads have made after transient errors since the port was opened.
+   *
+   * @param handle the handle to the native serial port
+   * @return the number of retries, or -1 on failure
+   */
+  static native long getReadRetryCount(long handle);
+
+  /**
+   * Enable or disable DTR/DSR handshaking, independently of RTS/CTS flow control (Linux only).
+   *
+   * @param handle  the handle to the native serial port
+   * @param enabled true to hold DTR asserted and wait for DSR before each write
+   * @return true if successful, false on failure or if not on Linux
+   */
+  static native boolean setDtrFlowControl(long handle, boolean enabled);
+
+  /**
+   * Set the direction pin timing for manual RS-485 control.
+   *
+   * @param handle      the handle to the native serial port
+   * @param preMicros   delay after asserting the direction pin, before sending, in microseconds
+   * @param postMicros  delay after sending, before releasing the direction pin, in microseconds
+   * @return true if successful, false on failure
+   */
+  static native boolean setManualRs485Timing(long handle, int preMicros, int postMicros);
+
+  /**
+   * Enable or disable clearing the input buffer at the start of each write.
+   *
+   * @param handle  the handle to the native serial port
+   * @param enabled true to clear pending input before each write
+   * @return true if successful, false otherwise
+   */
+  static native boolean setClearInputBeforeWrite(long handle, boolean enabled);
+
+  /**
+   * Enable or disable draining the output at the end of each write.
+   *
+   * @param handle  the handle to the native serial port
+   * @param enabled true to drain after each write (default), false to leave it to flush
+   * @return true if successful, false otherwise
+   */
+  static native boolean setAutoFlush(long handle, boolean enabled);
+
+  /**
+   * Set whether closing the port hangs up (drops DTR/RTS) (Linux only).
+   *
+   * @param handle  the handle to the native serial port
+   * @param enabled true to drop DTR/RTS on close, false to keep them asserted
+   * @return true if successful, false on failure or if not on Linux
+   */
+  static native boolean setHangupOnClose(long handle, boolean enabled);
+
+  /**
+   * Set whether closing the port restores the settings it had before it was opened (Linux only;
+   * accepted with no effect elsewhere).
+   *
+   * @param handle  the handle to the native serial port
+   * @param enabled true to restore the original settings on close
+   * @return true if successful, false on failure or if the settings were not captured on open
+   */
+  static native boolean setRestoreOnClose(long handle, boolean enabled);
+
+  /**
+   * Set whether the port ignores the modem control lines (CLOCAL) (Linux only).
+   *
+   * @param handle the handle to the native serial port
+   * @param ignore true to ignore the modem control lines, false to honor carrier detect
+   * @return true if successful, false on failure or if not on Linux
+   */
+  static native boolean setIgnoreModemLines(long handle, boolean ignore);
+
+  /**
+   * Set whether the port's input is line-buffered (ICANON) (Linux only).
+   *
+   * @param handle    the handle to the native serial port
+   * @param canonical true for canonical (line-buffered) input, false for raw input
+   * @return true if successful, false on failure or if not on Linux
+   */
+  static native boolean setCanonicalMode(long handle, boolean canonical);
+
+  /**
+   * Drain pending output, clear stale input and change the line settings of an open port.
+   *
+   * @param handle   the handle to the native serial port
+   * @param baudRate the new baud rate
+   * @param dataBits the new data bits (5-8)
+   * @param stopBits the new stop bits (1 or 2)
+   * @param parity   the new parity (0 = None, 1 = Odd, 2 = Even)
+   * @return true if successful, false on failure
+   */
+  static native boolean reconfigure(
+      long handle, int baudRate, int dataBits, int stopBits, int parity);
+
+  /**
+   * Drain pending output and change the data bits of an open port, keeping received input.
+   *
+   * @param handle   the handle to the native serial port
+   * @param dataBits the new data bits (5-8)
+   * @return true if successful, false on failure or if the driver did not apply the setting
+   */
+  static native boolean setDataBits(long handle, int dataBits);
+
+  /**
+   * Drain pending output and change the stop bits of an open port, keeping received input.
+   *
+   * @param handle   the handle to the native serial port
+   * @param stopBits the new stop bits (1 or 2)
+   * @return true if successful, false on failure or if the driver did not apply the setting
+   */
+  static native boolean setStopBits(long handle, int stopBits);
+
+  /**
+   * Drain pending output and change the parity of an open port, keeping received input.
+   *
+   * @param handle the handle to the native serial port
+   * @param parity the new parity (0 = None, 1 = Odd, 2 = Even)
+   * @return true if successful, false on failure or if the driver did not apply the setting
+   */
+  static native boolean setParity(long handle, int parity);
+
+  /**
+   * Run the receiver and transmitter at different baud rates (Linux only).
+   *
+   * @param handle     the handle to the native serial port
+   * @param inputBaud  the receive baud rate
+   * @param outputBaud the transmit baud rate
+   * @return true if successful, false on failure, if the driver cannot split the speeds, or if
+   *     not on Linux
+   */
+  static native boolean setSplitBaud(long handle, int inputBaud, int outputBaud);
+
+  /**
+   * Get the line speeds the driver reports as applied.
+   *
+   * @param handle the handle to the native serial port
+   * @return a tab-separated line: input baud rate, output baud rate; or null if failed
+   */
+  static native String getActualBaudRate(long handle);
+
+  /**
+   * Move the calling thread to real-time scheduling, or back to normal scheduling (Linux only).
+   *
+   * @param handle  the handle to the native serial port
+   * @param enabled true for SCHED_FIFO real-time scheduling, false for normal scheduling
+   * @return true if successful, false on failure or if not on Linux
+   */
+  static native boolean setReadThreadRealtime(long handle, boolean enabled);
+
+  /**
+   * Set the raw termios VMIN and VTIME values (Linux only).
+   *
+   * @param handle the handle to the native serial port
+   * @param vmin   the minimum number of bytes for a read to return (0-255)
+   * @param vtime  the read timer in tenths of a second (0-255)
+   * @return true if successful, false on failure or if not on Linux
+   */
+  static native boolean setVminVtime(long handle, int vmin, int vtime);
+
+  /**
+   * Cap how many bytes a single underlying read requests.
+   *
+   * @param handle   the handle to the native serial port
+   * @param maxChunk the maximum bytes per read, or 0 for no limit
+   * @return true if successful, false otherwise
+   */
+  static native boolean setMaxReadChunk(long handle, int maxChunk);
+
+  /**
+   * Warn when operations that query the input queue find more than the given number of bytes.
+   *
+   * @param handle the handle to the native serial port
+   * @param bytes  the threshold in bytes, or 0 to disable the warning
+   * @return true if successful, false otherwise
+   */
+  static native boolean setOverflowWarnThreshold(long handle, int bytes);
+
+  /**
+   * Limit how long a write waits for its output to drain before releasing the RS-485 bus.
+   *
+   * @param handle    the handle to the native serial port
+   * @param timeoutMs the drain timeout in milliseconds, or 0 to wait indefinitely
+   * @return true if successful, false otherwise
+   */
+  static native boolean setDrainTimeout(long handle, int timeoutMs);
+
+  /**
+   * Switch between raw mode and text output processing (Linux only).
+   *
+   * @param handle the handle to the native serial port
+   * @param raw    true for raw mode, false to translate \n to \r\n on output
+   * @return true if successful, false on failure or if not on Linux
+   */
+  static native boolean setRawMode(long handle, boolean raw);
+
+  /**
+   * Configure a custom baud divisor via TIOCSSERIAL (Linux only).
+   *
+   * @param handle   the handle to the native serial port
+   * @param baseBaud the UART base baud, or 0 to keep the driver's value
+   * @param divisor  the custom divisor, or 0 to clear it
+   * @return true if successful, false on failure or if not on Linux
+   */
+  static native boolean setCustomDivisor(long handle, int baseBaud, int divisor);
+
+  /**
+   * Set the kernel close timing via TIOCSSERIAL (Linux only).
+   *
+   * @param handle      the handle to the native serial port
+   * @param closeDelay  the close delay in hundredths of a second
+   * @param closingWait the closing wait in hundredths of a second (0 = forever, 65535 = none)
+   * @return true if successful, false on failure or if not on Linux
+   */
+  static native boolean setCloseTiming(long handle, int closeDelay, int closingWait);
+
+  /**
+   * Get the UART's receive FIFO trigger level (Linux only).
+   *
+   * @param handle the handle to the native serial port
+   * @return the trigger level in bytes, or -1 on failure, if the driver does not expose it, or
+   *     if not on Linux
+   */
+  static native int getFifoTriggerLevel(long handle);
+
+  /**
+   * Set the UART's receive FIFO trigger level (Linux only).
+   *
+   * @param handle the handle to the native serial port
+   * @param level  the requested trigger level in bytes
+   * @return the trigger level the driver applied, or -1 on failure, if the driver does not
+   *     expose it, or if not on Linux
+   */
+  static native int setFifoTriggerLevel(long handle, int level);
+
+  /**
+   * Get the native library version and build information.
+   *
+   * @return the version string
+   */
+  static native String getNativeVersion();
+
+  /**
+   * Get the features this native build supports on the current platform.
+   *
+   * @return a bitmask with one bit per capability, numbered by the capability's native value
+   */
+  static native int getCapabilities();
+
   /**
    * Get the last error message from native code.
    * <p>
@@ -244,8 +1405,90 @@ static native boolean setRs485Config(
   static native String getLastError();
 
   /**
-   * Clear the last error.
+   * Get why the last open on the current thread failed.
+   *
+   * @return 0 = other reason, 1 = port busy, 2 = no such device, 3 = permission denied
+   */
+  static native int getLastOpenFailure();
+
+  /**
+   * Get the last error recorded for a specific port, regardless of the calling thread.
+   *
+   * @param handle the handle to the native serial port
+   * @return the last error message of the port, or null if it has not recorded an error
+   */
+  static native String getLastErrorForHandle(long handle);
+
+  /**
+   * Get the newest error retained for the current thread, even if it was already reported.
+   *
+   * @return the error as {@code message\tfile\tline}, or null if no errors are retained
+   */
+  static native String getLastErrorDetails();
+
+  /**
+   * Get the last error recorded for a specific port as separate fields.
+   *
+   * @param handle the handle to the native serial port
+   * @return the error as {@code message\tfile\tline}, or null if it has not recorded an error
+   */
+  static native String getLastErrorDetailsForHandle(long handle);
+
+  /**
+   * Get and clear the last non-fatal warning recorded for a port.
+   *
+   * @param handle the handle to the native serial port
+   * @return the warning message, or null if there is none
+   */
+  static native String getLastWarning(long handle);
+
+  /**
+   * Start recording every read and write of a port to a libpcap file.
+   *
+   * @param handle   the handle to the native serial port
+   * @param filePath the capture file to create or overwrite
+   * @return true if successful, false on failure
+   */
+  static native boolean startCapture(long handle, String filePath);
+
+  /**
+   * Stop recording and flush the capture file.
+   *
+   * @param handle the handle to the native serial port
+   * @return true if successful or no capture was running, false on failure
+   */
+  static native boolean stopCapture(long handle);
+
+  /**
+   * Clear the error history, including the last error.
    */
   static native void clearLastError();
 
+  /**
+   * Mark the last error as reported, so {@link #getLastError()} returns null until the next
+   * error. The error stays in the history.
+   */
+  static native void acknowledgeLastError();
+
+  /**
+   * Get all errors retained for the current thread, oldest first, joined by newlines.
+   *
+   * @return the error history, or null if no errors are retained
+   */
+  static native String getErrorHistory();
+
+  /**
+   * Set how many errors are retained per thread.
+   *
+   * @param size the number of errors to retain (values below 1 are treated as 1)
+   */
+  static native void setErrorHistorySize(int size);
+
+  /**
+   * Enable or disable strict validation of the line setting codes passed to the open calls.
+   *
+   * @param enabled true to reject unknown codes, false to replace them with defaults
+   */
+  static native void setStrictConfigValidation(boolean enabled);
+
 }
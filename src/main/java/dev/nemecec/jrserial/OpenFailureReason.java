@@ -0,0 +1,62 @@
+/*
+ * Copyright (C) 2026 Neeme Praks
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package dev.nemecec.jrserial;
+
+/**
+ * Why a serial port could not be opened, as reported by {@link PortOpenException#getReason()}.
+ */
+public enum OpenFailureReason {
+
+  /**
+   * Any other failure, such as settings the device rejected, or a reason the platform does not
+   * report. On Windows, every failure to open the device itself is reported as this.
+   */
+  OTHER(0),
+
+  /**
+   * The port is in use by another application, which opened it with exclusive access.
+   */
+  PORT_BUSY(1),
+
+  /**
+   * The device does not exist, for example because the adapter is unplugged or the name is wrong.
+   */
+  NOT_FOUND(2),
+
+  /**
+   * The device exists, but the user lacks permission to open it.
+   *
+   * <p>On most Linux distributions, serial devices belong to the {@code dialout} group (or
+   * {@code uucp} on some), so the fix is usually to add the user to that group and log in again.
+   */
+  PERMISSION_DENIED(3);
+
+  private final int value;
+
+  OpenFailureReason(int value) {
+    this.value = value;
+  }
+
+  /**
+   * Get the native value for this failure reason.
+   *
+   * @return the native value (0=Other, 1=PortBusy, 2=NotFound, 3=PermissionDenied)
+   */
+  public int getValue() {
+    return value;
+  }
+
+}
@@ -0,0 +1,83 @@
+/*
+ * Copyright (C) 2026 Neeme Praks
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package dev.nemecec.jrserial;
+
+import java.util.ArrayList;
+import java.util.Collections;
+import java.util.List;
+
+/**
+ * The outcome of opening several serial ports with {@link SerialPort#openMany}.
+ *
+ * <p>Every requested port is represented, in the order the names were given. Ports that could
+ * not be opened are returned closed, so they can be retried individually with
+ * {@link SerialPort#open()}, which reports the specific reason for that port.
+ *
+ * @see SerialPort#openMany(SerialPort.Builder, String...)
+ */
+public final class OpenManyResult {
+
+  private final List<SerialPort> ports;
+  private final String errorSummary;
+
+  OpenManyResult(List<SerialPort> ports, String errorSummary) {
+    this.ports = Collections.unmodifiableList(ports);
+    this.errorSummary = errorSummary;
+  }
+
+  /**
+   * Get all requested ports, open or not, in the order the names were given.
+   *
+   * @return one port per requested name
+   */
+  public List<SerialPort> getPorts() {
+    return ports;
+  }
+
+  /**
+   * Get the ports that were opened. The caller is responsible for closing them.
+   *
+   * @return the open ports, in the order the names were given
+   */
+  public List<SerialPort> getOpenedPorts() {
+    List<SerialPort> opened = new ArrayList<>();
+    for (SerialPort port : ports) {
+      if (port.isOpen()) {
+        opened.add(port);
+      }
+    }
+    return opened;
+  }
+
+  /**
+   * Check whether every requested port was opened.
+   *
+   * @return true if no port failed to open
+   */
+  public boolean isAllOpened() {
+    return errorSummary == null;
+  }
+
+  /**
+   * Get a summary of the ports that failed to open, with the error for each of them.
+   *
+   * @return the failure summary, or null if every port was opened
+   */
+  public String getErrorSummary() {
+    return errorSummary;
+  }
+
+}
@@ -0,0 +1,57 @@
+/*
+ * Copyright (C) 2026 Neeme Praks
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package dev.nemecec.jrserial;
+
+/**
+ * Whether the current user can open a serial port device, as reported by
+ * {@link SerialPort#checkAccess(String)}.
+ */
+public enum PortAccess {
+
+  /**
+   * The device exists and the user has read and write permission.
+   */
+  OK(0),
+
+  /**
+   * The device does not exist, for example because the adapter is unplugged or the name is wrong.
+   */
+  NO_SUCH_FILE(1),
+
+  /**
+   * The device exists, but the user lacks read or write permission.
+   *
+   * <p>On most Linux distributions, serial devices belong to the {@code dialout} group (or
+   * {@code uucp} on some), so the fix is usually to add the user to that group and log in again.
+   */
+  NO_PERMISSION(2);
+
+  private final int value;
+
+  PortAccess(int value) {
+    this.value = value;
+  }
+
+  /**
+   * Get the native value for this access status.
+   *
+   * @return the native value (0=OK, 1=NoSuchFile, 2=NoPermission)
+   */
+  public int getValue() {
+    return value;
+  }
+
+}
@@ -0,0 +1,50 @@
+/*
+ * Copyright (C) 2026 Neeme Praks
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package dev.nemecec.jrserial;
+
+import java.io.IOException;
+
+/**
+ * Thrown when a serial port cannot be opened, telling common causes apart so that applications
+ * can show a helpful message (for example "port in use by another application").
+ */
+public class PortOpenException extends IOException {
+
+  private static final long serialVersionUID = 1L;
+
+  private final OpenFailureReason reason;
+
+  /**
+   * Create a new exception.
+   *
+   * @param message the detail message
+   * @param reason  why the port could not be opened
+   */
+  public PortOpenException(String message, OpenFailureReason reason) {
+    super(message);
+    this.reason = reason;
+  }
+
+  /**
+   * Get why the port could not be opened.
+   *
+   * @return the failure reason, never null
+   */
+  public OpenFailureReason getReason() {
+    return reason;
+  }
+
+}
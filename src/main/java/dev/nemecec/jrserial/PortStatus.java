@@ -0,0 +1,132 @@
+/*
+ * Copyright (C) 2026 Neeme Praks
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package dev.nemecec.jrserial;
+
+/**
+ * A snapshot of a serial port's status, sampled in a single native call.
+ *
+ * <p>This is intended for dashboards and monitoring loops that refresh frequently:
+ * obtaining all values at once avoids a separate JNI round-trip for each of them.
+ *
+ * @see SerialPort#getPortStatus()
+ */
+public final class PortStatus {
+
+  /**
+   * Modem status bit for CTS (Clear To Send).
+   */
+  public static final int CTS = 1;
+
+  /**
+   * Modem status bit for DSR (Data Set Ready).
+   */
+  public static final int DSR = 1 << 1;
+
+  /**
+   * Modem status bit for RI (Ring Indicator).
+   */
+  public static final int RI = 1 << 2;
+
+  /**
+   * Modem status bit for CD (Carrier Detect).
+   */
+  public static final int CD = 1 << 3;
+
+  private final int bytesToRead;
+  private final int bytesToWrite;
+  private final int modemStatus;
+  private final boolean kernelRs485Active;
+
+  PortStatus(int bytesToRead, int bytesToWrite, int modemStatus, boolean kernelRs485Active) {
+    this.bytesToRead = bytesToRead;
+    this.bytesToWrite = bytesToWrite;
+    this.modemStatus = modemStatus;
+    this.kernelRs485Active = kernelRs485Active;
+  }
+
+  /**
+   * Parse the native status format: {@code input\toutput\tmodem\tkernelRs485}.
+   *
+   * @param data the tab-separated status line
+   * @return the parsed status
+   * @throws IllegalArgumentException if the data is not in the expected format
+   */
+  static PortStatus parse(String data) {
+    String[] parts = data.split("\t");
+    if (parts.length < 4) {
+      throw new IllegalArgumentException("Invalid port status: " + data);
+    }
+    return new PortStatus(
+        Integer.parseInt(parts[0]),
+        Integer.parseInt(parts[1]),
+        Integer.parseInt(parts[2]),
+        "1".equals(parts[3]));
+  }
+
+  /**
+   * Get the number of bytes waiting in the input queue.
+   *
+   * @return the number of bytes available to read
+   */
+  public int getBytesToRead() {
+    return bytesToRead;
+  }
+
+  /**
+   * Get the number of bytes waiting in the output queue.
+   *
+   * @return the number of bytes not yet transmitted
+   */
+  public int getBytesToWrite() {
+    return bytesToWrite;
+  }
+
+  /**
+   * Get the modem input lines as a bitmask of {@link #CTS}, {@link #DSR}, {@link #RI} and {@link #CD}.
+   *
+   * @return the modem status bitmask, or -1 if the lines cannot be read (e.g. on a PTY)
+   */
+  public int getModemStatus() {
+    return modemStatus;
+  }
+
+  /**
+   * Check if the modem lines could be read.
+   *
+   * @return true if {@link #getModemStatus()} holds valid line states
+   */
+  public boolean isModemStatusAvailable() {
+    return modemStatus >= 0;
+  }
+
+  /**
+   * Check if kernel RS-485 mode was active when the snapshot was taken.
+   *
+   * @return true if kernel RS-485 mode is active
+   */
+  public boolean isKernelRs485Active() {
+    return kernelRs485Active;
+  }
+
+  @Override
+  public String toString() {
+    return "PortStatus{bytesToRead=" + bytesToRead
+        + ", bytesToWrite=" + bytesToWrite
+        + ", modemStatus=" + modemStatus
+        + ", kernelRs485Active=" + kernelRs485Active + "}";
+  }
+
+}
@@ -0,0 +1,77 @@
+/*
+ * Copyright (C) 2026 Neeme Praks
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package dev.nemecec.jrserial;
+
+/**
+ * The deepest input and output queues observed on a serial port since it was opened or
+ * the marks were last reset.
+ *
+ * <p>Queue depths are sampled on every read, write and {@link SerialPort#getPortStatus()}
+ * call. Values close to the driver's buffer size indicate that the application is not
+ * keeping up with the data rate and data may be lost to overruns.
+ *
+ * @see SerialPort#getQueueHighWater()
+ */
+public final class QueueHighWater {
+
+  private final int maxBytesToRead;
+  private final int maxBytesToWrite;
+
+  QueueHighWater(int maxBytesToRead, int maxBytesToWrite) {
+    this.maxBytesToRead = maxBytesToRead;
+    this.maxBytesToWrite = maxBytesToWrite;
+  }
+
+  /**
+   * Parse the native high-water format: {@code input\toutput}.
+   *
+   * @param data the tab-separated high-water line
+   * @return the parsed high-water marks
+   * @throws IllegalArgumentException if the data is not in the expected format
+   */
+  static QueueHighWater parse(String data) {
+    String[] parts = data.split("\t");
+    if (parts.length < 2) {
+      throw new IllegalArgumentException("Invalid queue high-water marks: " + data);
+    }
+    return new QueueHighWater(Integer.parseInt(parts[0]), Integer.parseInt(parts[1]));
+  }
+
+  /**
+   * Get the largest number of bytes seen waiting in the input queue.
+   *
+   * @return the input queue high-water mark
+   */
+  public int getMaxBytesToRead() {
+    return maxBytesToRead;
+  }
+
+  /**
+   * Get the largest number of bytes seen waiting in the output queue.
+   *
+   * @return the output queue high-water mark
+   */
+  public int getMaxBytesToWrite() {
+    return maxBytesToWrite;
+  }
+
+  @Override
+  public String toString() {
+    return "QueueHighWater{maxBytesToRead=" + maxBytesToRead
+        + ", maxBytesToWrite=" + maxBytesToWrite + "}";
+  }
+
+}
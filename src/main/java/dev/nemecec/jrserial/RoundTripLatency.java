@@ -0,0 +1,89 @@
+/*
+ * Copyright (C) 2026 Neeme Praks
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package dev.nemecec.jrserial;
+
+/**
+ * Round-trip latency measured by {@link SerialPort#measureRoundTrip(byte[], int, int, int)}.
+ *
+ * <p>Each round trip is timed in the native layer from the start of writing the probe to the
+ * arrival of the last response byte, so it includes the transmission time of probe and
+ * response at the configured baud rate, the driver and device latency, and the response time
+ * of the peer, but not the overhead of calling into native code.
+ *
+ * @see SerialPort#measureRoundTrip(byte[], int, int, int)
+ */
+public final class RoundTripLatency {
+
+  private final long minMicros;
+  private final long avgMicros;
+  private final long maxMicros;
+
+  RoundTripLatency(long minMicros, long avgMicros, long maxMicros) {
+    this.minMicros = minMicros;
+    this.avgMicros = avgMicros;
+    this.maxMicros = maxMicros;
+  }
+
+  /**
+   * Parse the native latency format: {@code min\tavg\tmax} in microseconds.
+   *
+   * @param data the tab-separated latency line
+   * @return the parsed latencies
+   * @throws IllegalArgumentException if the data is not in the expected format
+   */
+  static RoundTripLatency parse(String data) {
+    String[] parts = data.split("\t");
+    if (parts.length < 3) {
+      throw new IllegalArgumentException("Invalid round-trip latency: " + data);
+    }
+    return new RoundTripLatency(
+        Long.parseLong(parts[0]), Long.parseLong(parts[1]), Long.parseLong(parts[2]));
+  }
+
+  /**
+   * Get the shortest round trip.
+   *
+   * @return the minimum latency in microseconds
+   */
+  public long getMinMicros() {
+    return minMicros;
+  }
+
+  /**
+   * Get the mean of all round trips.
+   *
+   * @return the average latency in microseconds
+   */
+  public long getAvgMicros() {
+    return avgMicros;
+  }
+
+  /**
+   * Get the longest round trip.
+   *
+   * @return the maximum latency in microseconds
+   */
+  public long getMaxMicros() {
+    return maxMicros;
+  }
+
+  @Override
+  public String toString() {
+    return "RoundTripLatency{minMicros=" + minMicros + ", avgMicros=" + avgMicros
+        + ", maxMicros=" + maxMicros + "}";
+  }
+
+}
@@ -0,0 +1,66 @@
+/*
+ * Copyright (C) 2026 Neeme Praks
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package dev.nemecec.jrserial;
+
+/**
+ * How RS-485 direction control is actually performed on an open port, as reported by
+ * {@link SerialPort#getRs485ActualMode()}.
+ */
+public enum Rs485ActualMode {
+
+  /**
+   * RS-485 control is not configured.
+   */
+  NONE(0),
+
+  /**
+   * The kernel driver toggles RTS around each transmission (Linux only).
+   */
+  KERNEL_AUTO(1),
+
+  /**
+   * Kernel mode was requested but the driver rejected it, or the platform has none, so the
+   * library toggles RTS itself. Expect looser turnaround timing than in kernel mode.
+   */
+  MANUAL_BY_FALLBACK(2),
+
+  /**
+   * Manual control was explicitly requested, so kernel mode was not attempted.
+   */
+  MANUAL_BY_REQUEST(3),
+
+  /**
+   * DTR is the control pin, which the kernel cannot drive, so the library toggles it itself.
+   */
+  MANUAL_BY_PIN(4);
+
+  private final int value;
+
+  Rs485ActualMode(int value) {
+    this.value = value;
+  }
+
+  /**
+   * Get the native value for this mode.
+   *
+   * @return the native value (0=None, 1=KernelAuto, 2=ManualByFallback, 3=ManualByRequest,
+   *     4=ManualByPin)
+   */
+  public int getValue() {
+    return value;
+  }
+
+}
@@ -16,12 +16,20 @@
 package dev.nemecec.jrserial;
 
 import java.io.Closeable;
+import java.io.EOFException;
 import java.io.IOException;
 import java.io.InputStream;
+import java.io.InterruptedIOException;
 import java.io.OutputStream;
+import java.nio.ByteBuffer;
+import java.nio.ReadOnlyBufferException;
 import java.util.ArrayList;
+import java.util.Arrays;
 import java.util.Collections;
+import java.util.EnumSet;
+import java.util.IdentityHashMap;
 import java.util.List;
+import java.util.Set;
 
 /**
  * A serial port for communicating with serial devices.
@@ -31,6 +39,11 @@
  * <p>
  * <b>Thread Safety:</b> This class is NOT thread-safe. A single SerialPort instance should only be accessed from one
  * thread at a time. If you need to access the port from multiple threads, you must provide your own synchronization.
+ * The native layer does guard against misuse: calls on the same port from several threads wait for each other, and a
+ * call racing with {@link #close()} fails with an {@link IOException} instead of using a port that is already closed.
+ * A read blocked waiting for input does not hold up writes or configuration calls on another thread, so one thread
+ * may read while another writes. On Linux, neither does a plain write waiting for room in the output queue; a write
+ * with RS-485 direction control, DTR/DSR flow control, a preamble or pacing holds the port until it has been sent.
  * <p>
  * Example usage:
  * <pre>
@@ -51,20 +64,59 @@
  */
 public class SerialPort implements Closeable {
 
+  /**
+   * Closing wait for {@link #setCloseTiming(int, int)} that waits indefinitely for output to drain.
+   */
+  public static final int CLOSING_WAIT_INFINITE = 0;
+
+  /**
+   * Closing wait for {@link #setCloseTiming(int, int)} that does not wait for output to drain.
+   */
+  public static final int CLOSING_WAIT_NONE = 0xFFFF;
+
+  /**
+   * Native return code for a write that would block under {@link WriteBlockingPolicy#ERROR}.
+   */
+  private static final int WRITE_WOULD_BLOCK = -2;
+
+  /**
+   * Native return code for a read interrupted by {@link #cancelRead()}.
+   */
+  private static final int READ_CANCELLED = -2;
+
+  /**
+   * Native return code for a direct buffer whose address the JVM does not expose.
+   */
+  private static final int DIRECT_BUFFER_UNAVAILABLE = -3;
+
+  /**
+   * Ports opened through this class and not yet closed, guarded by its own monitor.
+   */
+  private static final Set<SerialPort> OPEN_PORTS =
+      Collections.newSetFromMap(new IdentityHashMap<SerialPort, Boolean>());
+
   private final String portName;
-  private final int baudRate;
-  private final DataBits dataBits;
-  private final StopBits stopBits;
-  private final Parity parity;
+  private final String usbSerialNumber;
+  /** Device path resolved from the USB serial number on the last open, null before that. */
+  private String resolvedPortName;
+  private int baudRate;
+  private DataBits dataBits;
+  private StopBits stopBits;
+  private Parity parity;
   private final FlowControl flowControl;
   private final boolean dtrOnOpen;
   private final int timeoutMs;
   private final Rs485Config rs485Config;
+  private final boolean quietOpen;
+  private final int settleMs;
+  private final AccessMode accessMode;
+  private final int baudTolerancePercent;
 
   private long handle;
   private boolean isOpen;
   private SerialInputStream cachedInputStream;
   private SerialOutputStream cachedOutputStream;
+  private long lastReceiveTimeNanos;
 
   /**
    * Create an IOException with native error context if available.
@@ -75,14 +127,100 @@ public class SerialPort implements Closeable {
   private static IOException createIOException(String message) {
     String nativeError = NativeSerialPort.getLastError();
     if (nativeError != null) {
-      NativeSerialPort.clearLastError();
+      NativeSerialPort.acknowledgeLastError();
       return new IOException(message + ": " + nativeError);
     }
     return new IOException(message);
   }
 
+  /**
+   * Create a PortOpenException for a failed open on the current thread, with the failure reason
+   * reported by the native code and native error context if available.
+   *
+   * @param message the base error message
+   * @return a PortOpenException describing why the port could not be opened
+   */
+  private static PortOpenException createPortOpenException(String message) {
+    int value = NativeSerialPort.getLastOpenFailure();
+    OpenFailureReason reason = OpenFailureReason.OTHER;
+    for (OpenFailureReason candidate : OpenFailureReason.values()) {
+      if (candidate.getValue() == value) {
+        reason = candidate;
+      }
+    }
+    return createPortOpenException(message, reason);
+  }
+
+  /**
+   * Create a PortOpenException with the given reason and native error context if available.
+   *
+   * @param message the base error message
+   * @param reason  why the port could not be opened
+   * @return a PortOpenException with the reason and native error details appended
+   */
+  private static PortOpenException createPortOpenException(String message, OpenFailureReason reason) {
+    switch (reason) {
+      case PORT_BUSY:
+        message += " (port in use by another application)";
+        break;
+      case NOT_FOUND:
+        message += " (device not found)";
+        break;
+      case PERMISSION_DENIED:
+        message += " (permission denied)";
+        break;
+      default:
+        break;
+    }
+    String nativeError = NativeSerialPort.getLastError();
+    if (nativeError != null) {
+      NativeSerialPort.acknowledgeLastError();
+      return new PortOpenException(message + ": " + nativeError, reason);
+    }
+    return new PortOpenException(message, reason);
+  }
+
+  /**
+   * Create an InterruptedIOException with native error context if available.
+   *
+   * @param message the base error message
+   * @return an InterruptedIOException with native error details appended if available
+   */
+  private static InterruptedIOException createInterruptedIOException(String message) {
+    String nativeError = NativeSerialPort.getLastError();
+    if (nativeError != null) {
+      NativeSerialPort.acknowledgeLastError();
+      return new InterruptedIOException(message + ": " + nativeError);
+    }
+    return new InterruptedIOException(message);
+  }
+
+  /**
+   * Create the exception for a failed read: an EOFException if the read found the port closed
+   * by its peer, otherwise an IOException, each with native error context if available.
+   *
+   * @param message the base error message
+   * @return an EOFException or IOException with native error details appended if available
+   */
+  private IOException createReadIOException(String message) {
+    if (!NativeSerialPort.isEof(handle)) {
+      return createIOException(message);
+    }
+    String nativeError = NativeSerialPort.getLastError();
+    if (nativeError != null) {
+      NativeSerialPort.acknowledgeLastError();
+      return new EOFException(message + ": " + nativeError);
+    }
+    return new EOFException(message);
+  }
+
   private SerialPort(Builder builder) {
-    this.portName = builder.portName;
+    this(builder, builder.portName);
+  }
+
+  private SerialPort(Builder builder, String portName) {
+    this.portName = portName;
+    this.usbSerialNumber = builder.usbSerialNumber;
     this.baudRate = builder.baudRate;
     this.dataBits = builder.dataBits;
     this.stopBits = builder.stopBits;
@@ -91,6 +229,10 @@ private SerialPort(Builder builder) {
     this.dtrOnOpen = builder.dtrOnOpen;
     this.timeoutMs = builder.timeoutMs;
     this.rs485Config = builder.rs485Config;
+    this.quietOpen = builder.quietOpen;
+    this.settleMs = builder.settleMs;
+    this.accessMode = builder.accessMode;
+    this.baudTolerancePercent = builder.baudTolerancePercent;
     this.handle = 0;
     this.isOpen = false;
   }
@@ -119,7 +261,7 @@ public static List<SerialPortInfo> listPorts() {
       return Collections.emptyList();
     }
 
-    // Parse tab-separated format: name\tsymlink\tpty\tbluetooth per line
+    // Parse tab-separated format: name\tsymlink\tpty\tbluetooth\tbtAddress\tbtChannel per line
     List<SerialPortInfo> ports = new ArrayList<>();
     for (String line : data.split("\n")) {
       String[] parts = line.split("\t");
@@ -128,43 +270,394 @@ public static List<SerialPortInfo> listPorts() {
         boolean symlink = "1".equals(parts[1]);
         boolean pty = "1".equals(parts[2]);
         boolean bluetooth = "1".equals(parts[3]);
-        ports.add(new SerialPortInfo(name, symlink, pty, bluetooth));
+        // Trailing empty fields are dropped by split, so the binding is present only if known
+        if (parts.length >= 6) {
+          ports.add(new SerialPortInfo(name, symlink, pty, bluetooth, parts[4], Integer.parseInt(parts[5])));
+        }
+        else {
+          ports.add(new SerialPortInfo(name, symlink, pty, bluetooth));
+        }
       }
     }
     return ports;
   }
 
+  /**
+   * List all available serial ports on the system as a JSON document.
+   *
+   * <p>Returns a JSON array with one object per port. Each object contains:
+   * <ul>
+   *   <li>{@code name} - the port name</li>
+   *   <li>{@code is_symlink}, {@code is_pty}, {@code is_bluetooth} - device type flags</li>
+   *   <li>{@code is_virtual} - whether the port is a virtual device, see
+   *       {@link #isVirtualPort(String)}</li>
+   *   <li>{@code type} - one of {@code "usb"}, {@code "pci"}, {@code "bluetooth"}, {@code "unknown"}</li>
+   *   <li>{@code chipset} - the adapter chipset, see {@link #getChipset(String)}</li>
+   *   <li>{@code vid}, {@code pid}, {@code serial_number}, {@code manufacturer}, {@code product} -
+   *       USB details, or {@code null} for non-USB ports</li>
+   *   <li>{@code bluetooth_address}, {@code bluetooth_channel} - the remote device a Bluetooth
+   *       RFCOMM port is bound to (Linux only), or {@code null} if unknown</li>
+   * </ul>
+   *
+   * <p>Unlike {@link #listPorts()}, this format can gain new fields without breaking parsers,
+   * which makes it suitable for passing on to tools or other languages.
+   *
+   * @return a JSON array of port objects, or {@code "[]"} if enumeration failed
+   */
+  public static String listPortsJson() {
+    String json = NativeSerialPort.listPortsJson();
+    return json != null ? json : "[]";
+  }
+
+  /**
+   * Get the per-port problems noticed by the last {@link #listPorts()} or
+   * {@link #listPortsJson()} call on the current thread.
+   *
+   * <p>A listing succeeds even if the details of some ports cannot be read, for example when a
+   * device node disappears while the ports are enumerated or a symbolic link cannot be resolved.
+   * Such ports are still listed, with the unreadable details left at their defaults, and each
+   * problem is reported here. An empty or incomplete listing with warnings therefore points at a
+   * partial enumeration failure rather than at missing hardware.
+   *
+   * @return the warnings, or an empty list if the last listing had none
+   */
+  public static List<String> getEnumerationWarnings() {
+    String warnings = NativeSerialPort.getEnumerationWarnings();
+    if (warnings == null) {
+      return Collections.emptyList();
+    }
+    return Collections.unmodifiableList(Arrays.asList(warnings.split("\n")));
+  }
+
+  /**
+   * Check whether the current user can open a port, without opening it (Linux only).
+   *
+   * <p>A "permission denied" error on open usually means the user is not in the group that owns
+   * serial devices ({@code dialout} on most distributions). This check lets a setup wizard tell
+   * the user what is wrong before attempting to open the port. It checks that the device exists
+   * and that the current user has read and write permission on it; a device that passes may
+   * still fail to open if it is locked or in use.
+   *
+   * @param portName the name of the port (e.g., "/dev/ttyUSB0")
+   * @return the access status
+   * @throws NullPointerException if portName is null
+   * @throws IOException          if the check fails or the platform is not Linux
+   */
+  public static PortAccess checkAccess(String portName) throws IOException {
+    if (portName == null) {
+      throw new NullPointerException("portName cannot be null");
+    }
+    int result = NativeSerialPort.checkAccess(portName);
+    for (PortAccess access : PortAccess.values()) {
+      if (access.getValue() == result) {
+        return access;
+      }
+    }
+    throw createIOException("Failed to check access to " + portName);
+  }
+
+  /**
+   * Check whether a port is a virtual device rather than a real UART.
+   *
+   * <p>Virtual ports have no transmitter behind them: baud rate and timing settings have no
+   * effect, and RS-485 direction control does not behave as on a real transceiver. Test harnesses
+   * can use this check to skip timing-sensitive tests. The check is heuristic; it recognizes:
+   * <ul>
+   *   <li>pseudo-terminals, such as the pairs created by {@code socat}</li>
+   *   <li>on Linux, ttys with no hardware device behind them or bound to a known virtual
+   *       driver, such as {@code tty0tty} null-modem pairs</li>
+   *   <li>known virtual COM port names, such as {@code com0com} pairs ({@code CNCA0},
+   *       {@code CNCB0})</li>
+   * </ul>
+   * Vendor virtual COM ports with ordinary names are not detected, and ports that cannot be
+   * inspected are reported as not virtual.
+   *
+   * @param portName the name of the port (e.g., "/dev/ttyUSB0")
+   * @return true if the port is recognized as virtual
+   * @throws NullPointerException if portName is null
+   */
+  public static boolean isVirtualPort(String portName) {
+    if (portName == null) {
+      throw new NullPointerException("portName cannot be null");
+    }
+    return NativeSerialPort.isVirtualPort(portName);
+  }
+
+  /**
+   * Detect the chipset of the USB-to-serial adapter behind a port.
+   *
+   * <p>Knowing the chipset lets an application apply chip-specific workarounds, such as the baud
+   * rate quirks of CH340 adapters or tuning the FTDI latency timer. On Linux the chipset is
+   * recognized from the kernel driver and USB vendor ID in sysfs, following symbolic links such
+   * as {@code /dev/serial/by-id}; on other platforms it is recognized from the USB vendor ID
+   * (on Windows, from the device instance ID). The result is one of:
+   * <ul>
+   *   <li>{@code "ftdi"} - FTDI (FT232, FT2232 and similar)</li>
+   *   <li>{@code "cp210x"} - Silicon Labs CP210x</li>
+   *   <li>{@code "ch340"} - WCH CH340, CH341 and similar</li>
+   *   <li>{@code "pl2303"} - Prolific PL2303</li>
+   *   <li>{@code "unknown"} - any other adapter, a non-USB or virtual port, or a port that
+   *       cannot be inspected</li>
+   * </ul>
+   *
+   * @param portName the name of the port (e.g., "/dev/ttyUSB0")
+   * @return the chipset classification
+   * @throws NullPointerException if portName is null
+   */
+  public static String getChipset(String portName) {
+    if (portName == null) {
+      throw new NullPointerException("portName cannot be null");
+    }
+    String chipset = NativeSerialPort.getChipset(portName);
+    return chipset != null ? chipset : "unknown";
+  }
+
+  /**
+   * Find the baud rates a port's driver actually supports, for example to fill a selection list.
+   *
+   * <p>The port is opened briefly, without touching the DTR and RTS lines, and each rate is
+   * applied and read back: drivers reject or replace rates their hardware cannot produce. On
+   * Linux, the rates of the standard termios speed table (50 to 4000000) are probed, and custom
+   * rates are reported as available if the driver accepts an arbitrary rate through
+   * {@code BOTHER}. Most USB serial adapters accept any rate this way, so the standard list is
+   * only a suggestion for them. On other platforms, a list of common rates is probed on a best
+   * effort basis. The port's original settings are restored before it is closed.
+   *
+   * <p>The port must not be open, including by this application.
+   *
+   * @param portName the name of the port (e.g., "COM1" or "/dev/ttyUSB0")
+   * @return the supported baud rates
+   * @throws NullPointerException if portName is null
+   * @throws IOException          if the port cannot be opened or probed
+   */
+  public static SupportedBaudRates getSupportedBaudRates(String portName) throws IOException {
+    if (portName == null) {
+      throw new NullPointerException("portName cannot be null");
+    }
+    String result = NativeSerialPort.getSupportedBaudRates(portName);
+    if (result == null) {
+      throw createIOException("Failed to probe baud rates of " + portName);
+    }
+    String[] parts = result.split("\t");
+    List<Integer> rates = new ArrayList<>();
+    if (!parts[0].isEmpty()) {
+      for (String rate : parts[0].split(",")) {
+        rates.add(Integer.parseInt(rate));
+      }
+    }
+    return new SupportedBaudRates(rates, "1".equals(parts[1]));
+  }
+
+  /**
+   * Get the version and build information of the loaded native library.
+   *
+   * <p>Include this in bug reports to identify exactly which native build is in use, for example
+   * {@code jrserial 0.1.0 (serialport 4.10.1, x86_64-unknown-linux-gnu, git 1a2b3c4)}. The git
+   * hash is {@code unknown} if the library was built outside a git checkout.
+   *
+   * @return the native library version, serialport crate version, target triple and git hash
+   */
+  public static String getNativeVersion() {
+    return NativeSerialPort.getNativeVersion();
+  }
+
+  /**
+   * Get the optional features that the loaded native library supports on this platform.
+   *
+   * <p>This lets an application hide or disable options that cannot work, instead of finding
+   * out when a call fails. Most capabilities follow from the platform the native library was
+   * built for; some, such as {@link Capability#REALTIME_SCHEDULING}, are probed on the running
+   * system each time this is called. A capability does not guarantee that every port supports
+   * the feature, as some also depend on the driver.
+   *
+   * @return the supported capabilities
+   */
+  public static Set<Capability> getCapabilities() {
+    int mask = NativeSerialPort.getCapabilities();
+    Set<Capability> capabilities = EnumSet.noneOf(Capability.class);
+    for (Capability capability : Capability.values()) {
+      if ((mask & (1 << capability.getValue())) != 0) {
+        capabilities.add(capability);
+      }
+    }
+    return capabilities;
+  }
+
+  /**
+   * Wait until any of the given ports has data to read, or the timeout expires.
+   *
+   * <p>This lets one thread service many ports (for example, several RS-485 segments)
+   * without polling each port in a loop. It returns as soon as at least one port is
+   * readable, with every port that has data available at that moment.
+   *
+   * <p><b>Platform behavior:</b> on Linux this is a single {@code poll()} call over all
+   * ports. On other platforms it falls back to checking the available byte count of each
+   * port in turn, with a short sleep between rounds.
+   *
+   * @param ports     the open ports to wait on
+   * @param timeoutMs the maximum time to wait in milliseconds
+   * @return the ports that have data available, in the given order, or an empty list on timeout
+   * @throws IOException              if polling fails or any port is not open
+   * @throws IllegalArgumentException if ports is null
+   */
+  public static List<SerialPort> pollReadable(List<SerialPort> ports, int timeoutMs) throws IOException {
+    if (ports == null) {
+      throw new IllegalArgumentException("Ports cannot be null");
+    }
+    long[] handles = new long[ports.size()];
+    for (int i = 0; i < handles.length; i++) {
+      SerialPort port = ports.get(i);
+      if (!port.isOpen) {
+        throw new IOException("Port is not open: " + port.getPortName());
+      }
+      handles[i] = port.handle;
+    }
+
+    boolean[] readable = new boolean[handles.length];
+    if (NativeSerialPort.pollReadable(handles, readable, timeoutMs) < 0) {
+      throw createIOException("Failed to poll serial ports");
+    }
+
+    List<SerialPort> result = new ArrayList<>();
+    for (int i = 0; i < readable.length; i++) {
+      if (readable[i]) {
+        result.add(ports.get(i));
+      }
+    }
+    return result;
+  }
+
   /**
    * Open the serial port.
    *
-   * @throws IOException if the port cannot be opened
+   * <p>If the port cannot be opened, the exception is a {@link PortOpenException} whose
+   * {@link PortOpenException#getReason() reason} tells the most common causes apart: the port
+   * being in use by another application, the device not existing, and missing permission.
+   *
+   * @throws PortOpenException if the port cannot be opened
+   * @throws IOException       if the port is already open
    */
   public void open() throws IOException {
+    open(false);
+  }
+
+  /**
+   * Open the serial port and check that it responds before returning.
+   *
+   * <p>This bundles the usual "open, then sanity check" boilerplate into one call. After the
+   * port is opened and fully configured, the input and output queue depths and the modem lines
+   * are queried; if any of these fails, the port is closed again and an exception describes
+   * the problem. Ports without modem lines, such as pseudo-terminals, still pass. Nothing is
+   * written to the port, so the check has no side effects on the connected device.
+   *
+   * @throws PortOpenException     if the port cannot be opened or fails the check
+   * @throws IOException           if the port is already open
+   * @throws IllegalStateException if the port is configured for a quiet open
+   */
+  public void openAndVerify() throws IOException {
+    if (quietOpen) {
+      throw new IllegalStateException("openAndVerify cannot be combined with quiet open");
+    }
+    open(true);
+  }
+
+  /**
+   * Open the serial port, optionally verifying it in the same native call.
+   *
+   * @param verify true to sanity check the port before returning
+   * @throws IOException if the port cannot be opened or fails the check
+   */
+  private void open(boolean verify) throws IOException {
     if (isOpen) {
       throw new IOException("Port is already open");
     }
 
-    // Determine RS-485 settings from config (or use defaults if not set)
-    int rs485ModeValue = 0;  // NONE
-    int rs485PinValue = 0;   // RTS
-    boolean rtsActiveHigh = true;
-    boolean rxDuringTx = false;
-    boolean terminationEnabled = false;
-    int delayBeforeMicros = 0;
-    int delayAfterMicros = 0;
+    // Resolve the device path on every open, as USB enumeration order can change
+    if (usbSerialNumber != null) {
+      String resolved = NativeSerialPort.findPortByUsbSerial(usbSerialNumber);
+      if (resolved == null) {
+        throw createPortOpenException(
+            "Failed to find serial port with USB serial number: " + usbSerialNumber,
+            OpenFailureReason.NOT_FOUND);
+      }
+      resolvedPortName = resolved;
+    }
+    String devicePath = getPortName();
+
+    if (quietOpen) {
+      handle = NativeSerialPort.openQuiet(
+          devicePath,
+          baudRate,
+          dataBits.getValue(),
+          stopBits.getValue(),
+          parity.getValue(),
+          flowControl.getValue(),
+          timeoutMs,
+          settleMs
+      );
+    }
+    else {
+      handle = openWithRs485Config(devicePath, verify);
+    }
+
+    if (handle == 0) {
+      throw createPortOpenException("Failed to open serial port: " + devicePath);
+    }
+    updateOpenedBaudRate();
+
+    isOpen = true;
+    synchronized (OPEN_PORTS) {
+      OPEN_PORTS.add(this);
+    }
+  }
 
-    if (rs485Config != null) {
-      rs485ModeValue = rs485Config.isEnabled() ? 1 : 0;  // AUTO or NONE
-      rs485PinValue = rs485Config.getControlPin().getValue();
-      rtsActiveHigh = rs485Config.isRtsActiveHigh();
-      rxDuringTx = rs485Config.isRxDuringTx();
-      terminationEnabled = rs485Config.isTerminationEnabled();
-      delayBeforeMicros = rs485Config.getDelayBeforeSendMicros();
-      delayAfterMicros = rs485Config.getDelayAfterSendMicros();
+  /**
+   * Take over the baud rate the port was opened at, which is a nearby standard rate instead of
+   * the requested one if the baud tolerance was needed.
+   */
+  private void updateOpenedBaudRate() {
+    if (baudTolerancePercent > 0) {
+      int opened = NativeSerialPort.getOpenedBaudRate(handle);
+      if (opened > 0) {
+        baudRate = opened;
+      }
     }
+  }
+
+  /**
+   * Open the native port with the configured DTR and RS-485 settings.
+   *
+   * @param devicePath the device path to open
+   * @param verify     true to sanity check the port before returning
+   * @return the native handle, or 0 if the port could not be opened or failed the check
+   */
+  private long openWithRs485Config(String devicePath, boolean verify) {
+    NativeRs485Settings rs485 = new NativeRs485Settings(rs485Config);
 
-    handle = NativeSerialPort.openWithRs485Config(
-        portName,
+    if (verify) {
+      return NativeSerialPort.openAndVerify(
+          devicePath,
+          baudRate,
+          dataBits.getValue(),
+          stopBits.getValue(),
+          parity.getValue(),
+          flowControl.getValue(),
+          dtrOnOpen,
+          timeoutMs,
+          rs485.mode,
+          rs485.controlPin,
+          rs485.rtsActiveHigh,
+          rs485.rxDuringTx,
+          rs485.terminationEnabled,
+          rs485.delayBeforeMicros,
+          rs485.delayAfterMicros,
+          accessMode.getValue(),
+          baudTolerancePercent,
+          settleMs
+      );
+    }
+    return NativeSerialPort.openWithRs485Config(
+        devicePath,
         baudRate,
         dataBits.getValue(),
         stopBits.getValue(),
@@ -172,31 +665,165 @@ public void open() throws IOException {
         flowControl.getValue(),
         dtrOnOpen,
         timeoutMs,
-        rs485ModeValue,
-        rs485PinValue,
-        rtsActiveHigh,
-        rxDuringTx,
-        terminationEnabled,
-        delayBeforeMicros,
-        delayAfterMicros
+        rs485.mode,
+        rs485.controlPin,
+        rs485.rtsActiveHigh,
+        rs485.rxDuringTx,
+        rs485.terminationEnabled,
+        rs485.delayBeforeMicros,
+        rs485.delayAfterMicros,
+        accessMode.getValue(),
+        baudTolerancePercent,
+        settleMs
     );
+  }
 
-    if (handle == 0) {
-      throw createIOException("Failed to open serial port: " + portName);
+  /**
+   * Open several serial ports with the same settings in one call.
+   *
+   * <p>This is intended for applications that bring up many identical ports at startup, such
+   * as a gateway with one port per RS-485 segment. Each name gets its own {@link SerialPort},
+   * configured from the builder; the builder's own port name is ignored. A port that fails to
+   * open does not stop the others, and the settle delay is applied once for all of them.
+   *
+   * <p>Ports that were opened are left open even if others failed: the caller decides whether
+   * to carry on with them or close them.
+   *
+   * @param builder   the settings shared by all ports
+   * @param portNames the names of the ports to open
+   * @return every requested port, with a summary of the ports that failed to open
+   * @throws IOException              if the native call itself fails
+   * @throws IllegalArgumentException if the builder or a port name is null, or the builder
+   *                                  uses a USB serial number or quiet open
+   */
+  public static OpenManyResult openMany(Builder builder, String... portNames) throws IOException {
+    if (builder == null) {
+      throw new IllegalArgumentException("Builder cannot be null");
+    }
+    if (portNames == null) {
+      throw new IllegalArgumentException("Port names cannot be null");
+    }
+    if (builder.usbSerialNumber != null) {
+      throw new IllegalArgumentException("openMany cannot be combined with a USB serial number");
+    }
+    if (builder.quietOpen) {
+      throw new IllegalArgumentException("openMany cannot be combined with quiet open");
+    }
+    List<SerialPort> ports = new ArrayList<>();
+    for (String name : portNames) {
+      if (name == null || name.isEmpty()) {
+        throw new IllegalArgumentException("Port name must be set");
+      }
+      ports.add(new SerialPort(builder, name));
+    }
+    if (ports.isEmpty()) {
+      return new OpenManyResult(ports, null);
     }
 
-    isOpen = true;
+    long[] handles = new long[ports.size()];
+    if (ports.get(0).openManyWithRs485Config(portNames, handles) < 0) {
+      throw createIOException("Failed to open serial ports");
+    }
+
+    String errorSummary = null;
+    synchronized (OPEN_PORTS) {
+      for (int i = 0; i < handles.length; i++) {
+        SerialPort port = ports.get(i);
+        if (handles[i] != 0) {
+          port.handle = handles[i];
+          port.updateOpenedBaudRate();
+          port.isOpen = true;
+          OPEN_PORTS.add(port);
+        }
+        else if (errorSummary == null) {
+          errorSummary = NativeSerialPort.getLastError();
+          NativeSerialPort.acknowledgeLastError();
+        }
+      }
+    }
+    return new OpenManyResult(ports, errorSummary);
+  }
+
+  /**
+   * Open the native ports for {@link #openMany} with this port's settings.
+   *
+   * @param portNames  the names of the ports to open
+   * @param handlesOut receives one handle per port name, 0 for ports that failed
+   * @return the number of ports opened, or -1 if the native call failed
+   */
+  private int openManyWithRs485Config(String[] portNames, long[] handlesOut) {
+    NativeRs485Settings rs485 = new NativeRs485Settings(rs485Config);
+
+    return NativeSerialPort.openMany(
+        portNames,
+        baudRate,
+        dataBits.getValue(),
+        stopBits.getValue(),
+        parity.getValue(),
+        flowControl.getValue(),
+        dtrOnOpen,
+        timeoutMs,
+        rs485.mode,
+        rs485.controlPin,
+        rs485.rtsActiveHigh,
+        rs485.rxDuringTx,
+        rs485.terminationEnabled,
+        rs485.delayBeforeMicros,
+        rs485.delayAfterMicros,
+        accessMode.getValue(),
+        baudTolerancePercent,
+        settleMs,
+        handlesOut
+    );
   }
 
   /**
    * Close the serial port.
+   *
+   * <p>Closing a port that is already closed has no effect. If another thread is using the port,
+   * the close waits for its call to finish; on Linux, a blocked read is cancelled first, and a
+   * plain write waiting for room in the output queue stops early.
    */
   public void close() {
-    if (isOpen) {
-      NativeSerialPort.close(handle);
+    long closingHandle;
+    synchronized (OPEN_PORTS) {
+      if (!isOpen) {
+        return;
+      }
+      closingHandle = handle;
       handle = 0;
       isOpen = false;
+      OPEN_PORTS.remove(this);
+    }
+    // Outside the monitor: the native close waits for calls in progress on this port, which
+    // must not hold up opening and closing other ports
+    NativeSerialPort.close(closingHandle);
+  }
+
+  /**
+   * Close every serial port that is still open in this JVM.
+   *
+   * <p>This is intended for a shutdown hook, so that ports the application did not close do not
+   * keep their device locks or leave control lines asserted:
+   * <pre>
+   * Runtime.getRuntime().addShutdownHook(new Thread(SerialPort::closeAll));
+   * </pre>
+   *
+   * <p>All {@link SerialPort} instances are marked closed, so further calls on them fail with
+   * "Port is not open". An operation in progress on another thread is finished before its port
+   * is closed; on Linux, a blocked read is cancelled.
+   *
+   * @return the number of ports closed
+   */
+  public static int closeAll() {
+    synchronized (OPEN_PORTS) {
+      for (SerialPort port : OPEN_PORTS) {
+        port.handle = 0;
+        port.isOpen = false;
+      }
+      OPEN_PORTS.clear();
     }
+    return NativeSerialPort.closeAll();
   }
 
   /**
@@ -213,6 +840,9 @@ public int write(byte[] data) throws IOException {
   /**
    * Write data to the serial port.
    *
+   * <p>A zero {@code length} returns 0 immediately without touching the port; in particular,
+   * the RS-485 direction pin is not toggled.
+   *
    * @param data   the data to write
    * @param offset the offset in the data array
    * @param length the number of bytes to write
@@ -234,6 +864,9 @@ public int write(byte[] data, int offset, int length) throws IOException {
     }
 
     int result = NativeSerialPort.write(handle, data, offset, length);
+    if (result == WRITE_WOULD_BLOCK) {
+      throw createIOException("Write to serial port would block");
+    }
     if (result < 0) {
       throw createIOException("Failed to write to serial port");
     }
@@ -242,567 +875,3873 @@ public int write(byte[] data, int offset, int length) throws IOException {
   }
 
   /**
-   * Write a string to the serial port using the specified charset.
+   * Write the remaining bytes of a buffer to the serial port.
    *
-   * @param text    the string to write
-   * @param charset the charset to use for encoding
-   * @return the number of bytes written
-   * @throws IOException if the write fails or the port is not open
-   */
-  public int writeString(String text, java.nio.charset.Charset charset) throws IOException {
-    return write(text.getBytes(charset));
-  }
-
-  /**
-   * Write a string to the serial port using UTF-8 encoding.
+   * <p>The bytes from the buffer's position up to its limit are written, and the position is
+   * advanced by the number of bytes written. Direct buffers are written straight from their
+   * native memory. If the JVM does not expose a buffer's address, the bytes are copied through
+   * a temporary array instead; the first such fallback on a port records a warning (see
+   * {@link #getLastWarning()}).
    *
-   * @param text the string to write
+   * @param buffer the buffer to write from
    * @return the number of bytes written
-   * @throws IOException if the write fails or the port is not open
+   * @throws IOException          if the write fails or the port is not open
+   * @throws NullPointerException if buffer is null
    */
-  public int writeString(String text) throws IOException {
-    return writeString(text, java.nio.charset.StandardCharsets.UTF_8);
+  public int write(ByteBuffer buffer) throws IOException {
+    if (buffer == null) {
+      throw new NullPointerException("buffer cannot be null");
+    }
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+
+    int position = buffer.position();
+    int length = buffer.remaining();
+    int result;
+    if (buffer.hasArray()) {
+      result = write(buffer.array(), buffer.arrayOffset() + position, length);
+    } else {
+      result = NativeSerialPort.writeDirect(handle, buffer, position, length);
+      if (result == DIRECT_BUFFER_UNAVAILABLE) {
+        byte[] data = new byte[length];
+        buffer.duplicate().get(data);
+        result = write(data, 0, length);
+      } else if (result == WRITE_WOULD_BLOCK) {
+        throw createIOException("Write to serial port would block");
+      } else if (result < 0) {
+        throw createIOException("Failed to write to serial port");
+      }
+    }
+
+    buffer.position(position + result);
+    return result;
   }
 
   /**
-   * Write a string followed by a newline to the serial port using the specified charset.
+   * Write data to the serial port, giving up at an absolute deadline.
    *
-   * @param text    the string to write (newline will be appended)
-   * @param charset the charset to use for encoding
-   * @return the number of bytes written
-   * @throws IOException if the write fails or the port is not open
-   */
+   * <p>Bytes are written until all are sent or {@code deadlineMillis} passes, whichever comes
+   * first; reaching the deadline is not an error. With RS-485 direction control, the output is
+   * drained and the direction pin released even when the deadline passes mid-write, so a stuck
+   * transmitter (for example one held off by flow control) cannot keep driving a shared bus. Any
+   * bytes still queued in the driver at the deadline are discarded and not counted as written.
+   * Releasing the bus may take up to the configured RS-485 post delay beyond the deadline.
+   *
+   * @param data           the data to write
+   * @param offset         the offset in the data array
+   * @param length         the number of bytes to write
+   * @param deadlineMillis the deadline, as returned by {@link System#currentTimeMillis()}
+   * @return the number of bytes written, fewer than {@code length} if the deadline passed
+   * @throws IOException               if the write fails or the port is not open
+   * @throws IndexOutOfBoundsException if offset or length are invalid
+   * @throws NullPointerException      if data is null
+   */
+  public int writeWithDeadline(byte[] data, int offset, int length, long deadlineMillis)
+      throws IOException {
+    if (data == null) {
+      throw new NullPointerException("data cannot be null");
+    }
+    if (offset < 0 || length < 0 || offset + length > data.length) {
+      throw new IndexOutOfBoundsException(
+          "offset=" + offset + ", length=" + length + ", data.length=" + data.length);
+    }
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+
+    int result = NativeSerialPort.writeWithDeadline(handle, data, offset, length, deadlineMillis);
+    if (result < 0) {
+      throw createIOException("Failed to write to serial port");
+    }
+    return result;
+  }
+
+  /**
+   * Write an unsigned 16-bit value in big-endian (most significant byte first) order.
+   *
+   * <p>For example, {@code 0x1234} is sent as {@code 0x12 0x34}. The bytes go out in a
+   * single write, so RS-485 direction control toggles once for the value.
+   *
+   * @param value the value, 0 to 0xFFFF
+   * @return the number of bytes written
+   * @throws IOException              if the write fails or the port is not open
+   * @throws IllegalArgumentException if the value is out of range
+   */
+  public int writeU16BE(int value) throws IOException {
+    return writeUnsigned(value, 2, true);
+  }
+
+  /**
+   * Write an unsigned 16-bit value in little-endian (least significant byte first) order.
+   *
+   * <p>For example, {@code 0x1234} is sent as {@code 0x34 0x12}. The bytes go out in a
+   * single write, so RS-485 direction control toggles once for the value.
+   *
+   * @param value the value, 0 to 0xFFFF
+   * @return the number of bytes written
+   * @throws IOException              if the write fails or the port is not open
+   * @throws IllegalArgumentException if the value is out of range
+   */
+  public int writeU16LE(int value) throws IOException {
+    return writeUnsigned(value, 2, false);
+  }
+
+  /**
+   * Write an unsigned 32-bit value in big-endian (most significant byte first) order.
+   *
+   * <p>For example, {@code 0x12345678} is sent as {@code 0x12 0x34 0x56 0x78}. The bytes go out
+   * in a single write, so RS-485 direction control toggles once for the value.
+   *
+   * @param value the value, 0 to 0xFFFFFFFFL
+   * @return the number of bytes written
+   * @throws IOException              if the write fails or the port is not open
+   * @throws IllegalArgumentException if the value is out of range
+   */
+  public int writeU32BE(long value) throws IOException {
+    return writeUnsigned(value, 4, true);
+  }
+
+  /**
+   * Write an unsigned 32-bit value in little-endian (least significant byte first) order.
+   *
+   * <p>For example, {@code 0x12345678} is sent as {@code 0x78 0x56 0x34 0x12}. The bytes go out
+   * in a single write, so RS-485 direction control toggles once for the value.
+   *
+   * @param value the value, 0 to 0xFFFFFFFFL
+   * @return the number of bytes written
+   * @throws IOException              if the write fails or the port is not open
+   * @throws IllegalArgumentException if the value is out of range
+   */
+  public int writeU32LE(long value) throws IOException {
+    return writeUnsigned(value, 4, false);
+  }
+
+  /**
+   * Write the low {@code size} bytes of an unsigned value in the given byte order.
+   *
+   * @param value     the value, which must fit in {@code size} bytes
+   * @param size      the number of bytes to write
+   * @param bigEndian true for most significant byte first
+   * @return the number of bytes written
+   * @throws IOException              if the write fails or the port is not open
+   * @throws IllegalArgumentException if the value is out of range
+   */
+  private int writeUnsigned(long value, int size, boolean bigEndian) throws IOException {
+    if (value < 0 || value >>> (size * 8) != 0) {
+      throw new IllegalArgumentException(
+          "value " + value + " does not fit in " + size * 8 + " unsigned bits");
+    }
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+
+    int result = NativeSerialPort.writeUnsigned(handle, value, size, bigEndian);
+    if (result == WRITE_WOULD_BLOCK) {
+      throw createIOException("Write to serial port would block");
+    }
+    if (result < 0) {
+      throw createIOException("Failed to write to serial port");
+    }
+    return result;
+  }
+
+  /**
+   * Send a DMX512 packet.
+   *
+   * @param packet the start code followed by up to 512 slots
+   * @return the number of bytes written
+   * @throws IOException               if the send fails or the port is not open
+   * @throws IllegalArgumentException  if the packet is empty or longer than 513 bytes
+   * @throws NullPointerException      if packet is null
+   * @see #sendDmxFrame(byte[], int, int)
+   */
+  public int sendDmxFrame(byte[] packet) throws IOException {
+    if (packet == null) {
+      throw new NullPointerException("packet cannot be null");
+    }
+    return sendDmxFrame(packet, 0, packet.length);
+  }
+
+  /**
+   * Send a DMX512 packet: a break, a mark-after-break (MAB), then the start code and slots.
+   *
+   * <p>DMX512 lighting receivers find the start of each packet by its break. The port must be
+   * open at 250000 baud with 8 data bits, no parity and 2 stop bits. The output is drained
+   * first, so the break never cuts off the previous packet. Then the line is held in the break
+   * state for 176 microseconds (the standard requires at least 88 microseconds and at most
+   * 1 second), followed by a 12 microsecond mark (at least 8 microseconds, less than 1 second)
+   * before the data. The timing is generated natively with busy waits rather than sleeps, so
+   * the durations are minimums that scheduling delays can only lengthen, which stays within
+   * the tolerances. The usual start code is 0 for dimmer data.
+   *
+   * <p>DMX512 transceivers keep their RS-485 driver permanently enabled, so this fails if RS-485
+   * direction control is configured.
+   *
+   * @param packet the buffer holding the start code followed by up to 512 slots
+   * @param offset the offset of the start code in the buffer
+   * @param length the number of bytes to send, 1 to 513
+   * @return the number of bytes written
+   * @throws IOException               if the send fails, the port is not at 250000 baud 8N2,
+   *                                   RS-485 direction control is configured, or the port is not
+   *                                   open
+   * @throws IllegalArgumentException  if length is not between 1 and 513
+   * @throws IndexOutOfBoundsException if offset or length are invalid
+   * @throws NullPointerException      if packet is null
+   */
+  public int sendDmxFrame(byte[] packet, int offset, int length) throws IOException {
+    if (packet == null) {
+      throw new NullPointerException("packet cannot be null");
+    }
+    if (offset < 0 || length < 0 || offset + length > packet.length) {
+      throw new IndexOutOfBoundsException(
+          "offset=" + offset + ", length=" + length + ", packet.length=" + packet.length);
+    }
+    if (length < 1 || length > 513) {
+      throw new IllegalArgumentException("DMX packet length must be 1-513, was " + length);
+    }
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+
+    int result = NativeSerialPort.sendDmxFrame(handle, packet, offset, length);
+    if (result < 0) {
+      throw createIOException("Failed to send DMX frame");
+    }
+    return result;
+  }
+
+  /**
+   * Write 9-bit characters for multidrop protocols (Linux only).
+   *
+   * <p>9-bit multidrop buses mark address bytes by setting a 9th bit and send data bytes with
+   * it cleared. This is emulated with stick parity: each byte is sent with mark parity if its
+   * {@code ninthBits} entry is true and with space parity otherwise, so the port's data bits
+   * should be 8. The parity setting is restored when the call returns.
+   *
+   * <p><b>Performance:</b> consecutive bytes with the same 9th bit are sent in a single write,
+   * but every change of the 9th bit waits for the previous bytes to be transmitted before the
+   * parity is switched. Each switch therefore costs about one character time plus system call
+   * latency, and the gap between characters may be visible on the wire. A typical frame (one
+   * address byte followed by data bytes) needs two switches.
+   *
+   * @param data      the data bytes
+   * @param ninthBits the 9th bit of each data byte, true for address bytes
+   * @throws NullPointerException     if data or ninthBits is null
+   * @throws IllegalArgumentException if data and ninthBits differ in length
+   * @throws IOException              if the write fails, the port is not open, or the platform
+   *                                  is not Linux
+   */
+  public void write9bit(byte[] data, boolean[] ninthBits) throws IOException {
+    if (data == null) {
+      throw new NullPointerException("data cannot be null");
+    }
+    if (ninthBits == null) {
+      throw new NullPointerException("ninthBits cannot be null");
+    }
+    if (data.length != ninthBits.length) {
+      throw new IllegalArgumentException(
+          "data.length=" + data.length + " does not match ninthBits.length=" + ninthBits.length);
+    }
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+
+    if (NativeSerialPort.write9bit(handle, data, ninthBits) < 0) {
+      throw createIOException("Failed to write 9-bit data to serial port");
+    }
+  }
+
+  /**
+   * Write a string to the serial port using the specified charset.
+   *
+   * @param text    the string to write
+   * @param charset the charset to use for encoding
+   * @return the number of bytes written
+   * @throws IOException if the write fails or the port is not open
+   */
+  public int writeString(String text, java.nio.charset.Charset charset) throws IOException {
+    return write(text.getBytes(charset));
+  }
+
+  /**
+   * Write a string to the serial port using UTF-8 encoding.
+   *
+   * @param text the string to write
+   * @return the number of bytes written
+   * @throws IOException if the write fails or the port is not open
+   */
+  public int writeString(String text) throws IOException {
+    return writeString(text, java.nio.charset.StandardCharsets.UTF_8);
+  }
+
+  /**
+   * Write data followed by the line terminator to the serial port.
+   *
+   * @param data the data to write (the line terminator will be appended)
+   * @return the number of bytes written, including the terminator
+   * @throws IOException if the write fails or the port is not open
+   * @see #setWriteTerminator(byte[])
+   */
+  public int writeLine(byte[] data) throws IOException {
+    return writeLine(data, 0, data.length);
+  }
+
+  /**
+   * Write data followed by the line terminator to the serial port.
+   *
+   * <p>The terminator set with {@link #setWriteTerminator(byte[])}, a newline by default, is
+   * appended in the native layer and sent in the same write as the data. With RS-485 control,
+   * the direction pin therefore stays asserted across data and terminator, so the line goes out
+   * as one frame. Plain {@link #write(byte[], int, int)} never appends the terminator.
+   *
+   * @param data   the data to write
+   * @param offset the offset in the data array
+   * @param length the number of bytes to write, not counting the terminator
+   * @return the number of bytes written, including the terminator
+   * @throws IOException               if the write fails or the port is not open
+   * @throws IndexOutOfBoundsException if offset or length are invalid
+   * @throws NullPointerException      if data is null
+   */
+  public int writeLine(byte[] data, int offset, int length) throws IOException {
+    if (data == null) {
+      throw new NullPointerException("data cannot be null");
+    }
+    if (offset < 0 || length < 0 || offset + length > data.length) {
+      throw new IndexOutOfBoundsException(
+          "offset=" + offset + ", length=" + length + ", data.length=" + data.length);
+    }
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+
+    int result = NativeSerialPort.writeLine(handle, data, offset, length);
+    if (result == WRITE_WOULD_BLOCK) {
+      throw createIOException("Write to serial port would block");
+    }
+    if (result < 0) {
+      throw createIOException("Failed to write to serial port");
+    }
+
+    return result;
+  }
+
+  /**
+   * Write data followed by its CRC to the serial port.
+   *
+   * <p>The CRC of the data is computed in the native layer and appended in the chosen byte
+   * order, and data and CRC are sent in the same write. With RS-485 control, the direction pin
+   * therefore stays asserted across both, so the frame goes out as one.
+   *
+   * @param data         the data to write
+   * @param offset       the offset in the data array
+   * @param length       the number of bytes to write, not counting the CRC
+   * @param crcType      the CRC to append
+   * @param littleEndian true to append the CRC least significant byte first, false for most
+   *                     significant byte first
+   * @return the number of bytes written, including the CRC
+   * @throws IOException               if the write fails or the port is not open
+   * @throws IndexOutOfBoundsException if offset or length are invalid
+   * @throws NullPointerException      if data or crcType is null
+   * @see #readAndVerifyCrc(byte[], int, int, CrcType, boolean, int, int)
+   */
+  public int writeWithCrc(byte[] data, int offset, int length, CrcType crcType,
+      boolean littleEndian) throws IOException {
+    if (data == null) {
+      throw new NullPointerException("data cannot be null");
+    }
+    if (crcType == null) {
+      throw new NullPointerException("crcType cannot be null");
+    }
+    if (offset < 0 || length < 0 || offset + length > data.length) {
+      throw new IndexOutOfBoundsException(
+          "offset=" + offset + ", length=" + length + ", data.length=" + data.length);
+    }
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+
+    int result = NativeSerialPort.writeWithCrc(handle, data, offset, length, crcType.getValue(),
+        littleEndian);
+    if (result == WRITE_WOULD_BLOCK) {
+      throw createIOException("Write to serial port would block");
+    }
+    if (result < 0) {
+      throw createIOException("Failed to write to serial port");
+    }
+
+    return result;
+  }
+
+  /**
+   * Write a string followed by the line terminator to the serial port using the specified
+   * charset.
+   *
+   * @param text    the string to write (the line terminator will be appended)
+   * @param charset the charset to use for encoding
+   * @return the number of bytes written, including the terminator
+   * @throws IOException if the write fails or the port is not open
+   * @see #setWriteTerminator(byte[])
+   */
   public int writeLine(String text, java.nio.charset.Charset charset) throws IOException {
-    return writeString(text + "\n", charset);
+    return writeLine(text.getBytes(charset));
+  }
+
+  /**
+   * Write a string followed by the line terminator to the serial port using UTF-8 encoding.
+   *
+   * @param text the string to write (the line terminator will be appended)
+   * @return the number of bytes written, including the terminator
+   * @throws IOException if the write fails or the port is not open
+   */
+  public int writeLine(String text) throws IOException {
+    return writeLine(text, java.nio.charset.StandardCharsets.UTF_8);
+  }
+
+  /**
+   * Read data from the serial port.
+   *
+   * @param buffer the buffer to read into
+   * @return the number of bytes read, or 0 if no data is available
+   * @throws IOException if the read fails or the port is not open
+   */
+  public int read(byte[] buffer) throws IOException {
+    return read(buffer, 0, buffer.length);
+  }
+
+  /**
+   * Read data from the serial port.
+   *
+   * <p>A zero {@code length} returns 0 immediately without reading from the port.
+   *
+   * @param buffer the buffer to read into
+   * @param offset the offset in the buffer
+   * @param length the maximum number of bytes to read
+   * @return the number of bytes read, or 0 if no data is available
+   * @throws EOFException              if the port was closed by its peer (see {@link #isEof()})
+   * @throws IOException               if the read fails or the port is not open
+   * @throws IndexOutOfBoundsException if offset or length are invalid
+   * @throws NullPointerException      if buffer is null
+   */
+  public int read(byte[] buffer, int offset, int length) throws IOException {
+    if (buffer == null) {
+      throw new NullPointerException("buffer cannot be null");
+    }
+    if (offset < 0 || length < 0 || offset + length > buffer.length) {
+      throw new IndexOutOfBoundsException(
+          "offset=" + offset + ", length=" + length + ", buffer.length=" + buffer.length);
+    }
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+
+    int result = NativeSerialPort.read(handle, buffer, offset, length);
+    if (result == READ_CANCELLED) {
+      throw createInterruptedIOException("Read from serial port was cancelled");
+    }
+    if (result < 0) {
+      throw createReadIOException("Failed to read from serial port");
+    }
+
+    return result;
+  }
+
+  /**
+   * Read data from the serial port into the remaining space of a buffer.
+   *
+   * <p>Bytes are stored from the buffer's position up to its limit, and the position is
+   * advanced by the number of bytes read. Direct buffers are filled straight in their native
+   * memory. If the JVM does not expose a buffer's address, the bytes are read into a temporary
+   * array and copied instead; the first such fallback on a port records a warning (see
+   * {@link #getLastWarning()}).
+   *
+   * @param buffer the buffer to read into
+   * @return the number of bytes read, or 0 if no data is available
+   * @throws EOFException            if the port was closed by its peer (see {@link #isEof()})
+   * @throws IOException             if the read fails or the port is not open
+   * @throws NullPointerException    if buffer is null
+   * @throws ReadOnlyBufferException if buffer is read-only
+   */
+  public int read(ByteBuffer buffer) throws IOException {
+    if (buffer == null) {
+      throw new NullPointerException("buffer cannot be null");
+    }
+    if (buffer.isReadOnly()) {
+      throw new ReadOnlyBufferException();
+    }
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+
+    int position = buffer.position();
+    int length = buffer.remaining();
+    int result;
+    if (buffer.hasArray()) {
+      result = read(buffer.array(), buffer.arrayOffset() + position, length);
+    } else {
+      result = NativeSerialPort.readDirect(handle, buffer, position, length);
+      if (result == DIRECT_BUFFER_UNAVAILABLE) {
+        byte[] data = new byte[length];
+        result = read(data, 0, length);
+        buffer.duplicate().put(data, 0, result);
+      } else if (result == READ_CANCELLED) {
+        throw createInterruptedIOException("Read from serial port was cancelled");
+      } else if (result < 0) {
+        throw createReadIOException("Failed to read from serial port");
+      }
+    }
+
+    buffer.position(position + result);
+    return result;
+  }
+
+  /**
+   * Read data from the serial port and record the monotonic time at which it was received.
+   *
+   * <p>The timestamp is taken in native code immediately after the underlying read returns,
+   * so it is not affected by JNI copying or Java scheduling delays. Retrieve it with
+   * {@link #getLastReceiveTimeNanos()} and compare it with {@link #monotonicNanos()}.
+   *
+   * <p>A zero {@code length} returns 0 immediately without reading from the port; the last
+   * receive time is left unchanged.
+   *
+   * @param buffer the buffer to read into
+   * @param offset the offset in the buffer
+   * @param length the maximum number of bytes to read
+   * @return the number of bytes read, or 0 if no data is available
+   * @throws IOException               if the read fails or the port is not open
+   * @throws IndexOutOfBoundsException if offset or length are invalid
+   * @throws NullPointerException      if buffer is null
+   */
+  public int readTimestamped(byte[] buffer, int offset, int length) throws IOException {
+    if (buffer == null) {
+      throw new NullPointerException("buffer cannot be null");
+    }
+    if (offset < 0 || length < 0 || offset + length > buffer.length) {
+      throw new IndexOutOfBoundsException(
+          "offset=" + offset + ", length=" + length + ", buffer.length=" + buffer.length);
+    }
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+
+    long[] timestamp = new long[1];
+    int result = NativeSerialPort.readTimestamped(handle, buffer, offset, length, timestamp);
+    if (result == READ_CANCELLED) {
+      throw createInterruptedIOException("Read from serial port was cancelled");
+    }
+    if (result < 0) {
+      throw createReadIOException("Failed to read from serial port");
+    }
+
+    if (length > 0) {
+      lastReceiveTimeNanos = timestamp[0];
+    }
+    return result;
+  }
+
+  /**
+   * Get the receive time recorded by the last {@link #readTimestamped(byte[], int, int)} call.
+   *
+   * <p>The value is on the native monotonic clock used by {@link #monotonicNanos()}. It is
+   * only meaningful for computing intervals, not as wall-clock time.
+   *
+   * @return the monotonic receive time in nanoseconds, or 0 if no timestamped read has been made
+   */
+  public long getLastReceiveTimeNanos() {
+    return lastReceiveTimeNanos;
+  }
+
+  /**
+   * Get the current value of the native monotonic clock used for receive timestamps.
+   *
+   * @return the native monotonic time in nanoseconds
+   */
+  public static long monotonicNanos() {
+    return NativeSerialPort.monotonicNanos();
+  }
+
+  /**
+   * Format bytes as a hex and ASCII dump with 16 bytes per line, for logging received frames.
+   *
+   * @param data the bytes to dump
+   * @return the dump
+   * @throws NullPointerException if data is null
+   * @see #formatDump(byte[], int, int, int)
+   */
+  public static String formatDump(byte[] data) {
+    return formatDump(data, 0, data.length, 16);
+  }
+
+  /**
+   * Format part of a byte array as a hex and ASCII dump, for logging received frames.
+   *
+   * <p>Each line shows the offset, the bytes in hex, and the bytes as ASCII, with non-printable
+   * bytes shown as {@code .}:
+   *
+   * <pre>
+   * 00000000  48 65 6c 6c 6f 0d 0a 57  |Hello..W|
+   * 00000008  6f 72 6c 64 21           |orld!|
+   * </pre>
+   *
+   * <p>To keep logging of large buffers cheap, only the first 64 KiB are rendered; a final line
+   * tells how many bytes were left out. Every line, including the last, ends with a newline.
+   *
+   * @param data         the bytes to dump
+   * @param offset       the offset in the data array
+   * @param length       the number of bytes to dump
+   * @param bytesPerLine the number of bytes per line (1-256)
+   * @return the dump, or an empty string if length is 0
+   * @throws IndexOutOfBoundsException if offset or length are invalid
+   * @throws IllegalArgumentException  if bytesPerLine is out of range
+   * @throws NullPointerException      if data is null
+   */
+  public static String formatDump(byte[] data, int offset, int length, int bytesPerLine) {
+    if (data == null) {
+      throw new NullPointerException("data cannot be null");
+    }
+    if (offset < 0 || length < 0 || offset + length > data.length) {
+      throw new IndexOutOfBoundsException(
+          "offset=" + offset + ", length=" + length + ", data.length=" + data.length);
+    }
+    if (bytesPerLine < 1 || bytesPerLine > 256) {
+      throw new IllegalArgumentException("bytesPerLine must be between 1 and 256: " + bytesPerLine);
+    }
+    String dump = NativeSerialPort.formatDump(data, offset, length, bytesPerLine);
+    if (dump == null) {
+      throw new IllegalStateException("Failed to format dump: " + NativeSerialPort.getLastError());
+    }
+    return dump;
+  }
+
+  /**
+   * Compute the CRC-32 of part of an array, as used by Ethernet and zlib.
+   *
+   * <p>The result is the same as {@link java.util.zip.CRC32}; see {@link CrcType#CRC32}.
+   *
+   * @param data   the data
+   * @param offset the offset in the data array
+   * @param length the number of bytes
+   * @return the CRC, 0 to 0xFFFFFFFF
+   * @throws IndexOutOfBoundsException if offset or length are invalid
+   * @throws NullPointerException      if data is null
+   */
+  public static long crc32(byte[] data, int offset, int length) {
+    if (data == null) {
+      throw new NullPointerException("data cannot be null");
+    }
+    if (offset < 0 || length < 0 || offset + length > data.length) {
+      throw new IndexOutOfBoundsException(
+          "offset=" + offset + ", length=" + length + ", data.length=" + data.length);
+    }
+    long crc = NativeSerialPort.crc32(data, offset, length);
+    if (crc < 0) {
+      throw new IllegalStateException("Failed to compute CRC: " + NativeSerialPort.getLastError());
+    }
+    return crc;
+  }
+
+  /**
+   * Compute the CRC-16/CCITT-FALSE of part of an array; see {@link CrcType#CRC_CCITT}.
+   *
+   * @param data   the data
+   * @param offset the offset in the data array
+   * @param length the number of bytes
+   * @return the CRC, 0 to 0xFFFF
+   * @throws IndexOutOfBoundsException if offset or length are invalid
+   * @throws NullPointerException      if data is null
+   */
+  public static int crcCcitt(byte[] data, int offset, int length) {
+    if (data == null) {
+      throw new NullPointerException("data cannot be null");
+    }
+    if (offset < 0 || length < 0 || offset + length > data.length) {
+      throw new IndexOutOfBoundsException(
+          "offset=" + offset + ", length=" + length + ", data.length=" + data.length);
+    }
+    int crc = NativeSerialPort.crcCcitt(data, offset, length);
+    if (crc < 0) {
+      throw new IllegalStateException("Failed to compute CRC: " + NativeSerialPort.getLastError());
+    }
+    return crc;
+  }
+
+  /**
+   * Get the last native error recorded for this port.
+   *
+   * <p>Unlike the message attached to exceptions, which is tracked per thread, this error is
+   * stored with the port itself. It can therefore be retrieved from any thread, and errors of
+   * other ports serviced by the same thread do not overwrite it. The error is kept until the
+   * port records a newer one or is closed. Errors that occur before the port is open, such as an
+   * invalid port name, are only available through exceptions and {@link #getErrorHistory()}.
+   *
+   * @return the last error of this port including its native source location, or null if the
+   *         port has not recorded an error or is not open
+   */
+  public String getLastError() {
+    if (!isOpen) {
+      return null;
+    }
+    return NativeSerialPort.getLastErrorForHandle(handle);
+  }
+
+  /**
+   * Get the last native error recorded for this port, with its message and source location
+   * as separate fields.
+   *
+   * <p>This is the same error as {@link #getLastError()}, which remains available as a single
+   * string.
+   *
+   * @return the last error of this port, or null if the port has not recorded an error or is
+   *         not open
+   */
+  public NativeError getLastErrorDetails() {
+    if (!isOpen) {
+      return null;
+    }
+    String details = NativeSerialPort.getLastErrorDetailsForHandle(handle);
+    return details == null ? null : NativeError.parse(details);
+  }
+
+  /**
+   * Get the newest native error recorded on the calling thread, with its message and source
+   * location as separate fields.
+   *
+   * <p>This is the newest entry of {@link #getErrorHistory()}, and is returned even after it
+   * has been reported through an exception. Call it right after catching an exception thrown
+   * by this library to get the details of the native failure behind it, for example to build
+   * an exception of your own.
+   *
+   * @return the newest error of the calling thread, or null if none is retained
+   */
+  public static NativeError getLastThreadErrorDetails() {
+    String details = NativeSerialPort.getLastErrorDetails();
+    return details == null ? null : NativeError.parse(details);
+  }
+
+  /**
+   * Get and clear the last non-fatal warning recorded for this port.
+   *
+   * <p>Warnings report problems that did not make an operation fail. Currently, on Linux, each
+   * read compares the driver's input overrun counters (hardware FIFO and tty buffer overruns)
+   * with their values at the previous read, and records a warning such as
+   * {@code "input overrun: data lost in 2 overrun(s)"} if they grew, while still returning the
+   * bytes that were read. The drivers count overrun events, not characters: each one lost at
+   * least one character, but how many is unknown. A warning means data arrived faster than the
+   * application reads it and frames may be corrupted. Drivers that do not report overrun
+   * counters, such as pseudo terminals and USB adapters without support for it, never produce
+   * this warning.
+   *
+   * <p>With an {@linkplain #setOverflowWarnThreshold(int) overflow warning threshold} set, an
+   * input queue deeper than the threshold records a warning such as {@code "input queue at 3000
+   * bytes, above the warning threshold of 2048: reads are falling behind"} on all platforms.
+   *
+   * <p>A write whose output does not drain within the {@linkplain #setDrainTimeout(int) drain
+   * timeout} records a warning such as {@code "drain timeout: output did not drain within 30000
+   * ms, 12 bytes discarded"} on all platforms.
+   *
+   * <p>A port opened at a standard baud rate in place of a rejected one (see
+   * {@link Builder#baudTolerancePercent(int)}) records a warning such as {@code "baud rate
+   * 115000 could not be set, opened at 115200 instead (+0.17%, within the tolerance of 1%)"}.
+   *
+   * <p>A {@linkplain #startCapture(String) traffic capture} that cannot write its file is
+   * stopped and records a warning such as {@code "capture stopped: No space left on device"}.
+   *
+   * <p>Only the most recent warning is kept; retrieving it clears it.
+   *
+   * @return the last warning, or null if there is none or the port is not open
+   */
+  public String getLastWarning() {
+    if (!isOpen) {
+      return null;
+    }
+    return NativeSerialPort.getLastWarning(handle);
+  }
+
+  /**
+   * Start recording every read and write to a capture file, for protocol analysis.
+   *
+   * <p>The file uses the libpcap format with link type {@code DLT_USER0} (147), so it opens in
+   * Wireshark and other pcap tools. Each read or write is one packet, timestamped to the
+   * microsecond, whose first byte is the direction ({@code 0} for data received, {@code 1} for
+   * data transmitted) followed by the data; reads and writes longer than 65534 bytes are split
+   * over several packets. To decode the data in Wireshark, assign a dissector to
+   * {@code DLT_USER0} in the DLT_USER preferences (for example with a header size of 1 to skip
+   * the direction byte), or write a custom dissector. Frames of the
+   * {@linkplain #startFrameReader(int) frame reader} are recorded when they are taken with
+   * {@link #nextFrame(byte[], int)}, one packet per frame.
+   *
+   * <p>The file is written through a buffer, so recording costs little on the I/O path, but the
+   * file is only complete once the capture is {@linkplain #stopCapture() stopped} or the port
+   * is closed. Starting a capture while one is running stops the previous one first. If
+   * writing the file fails, the capture is stopped and a warning is recorded (see
+   * {@link #getLastWarning()}); the port's reads and writes are not affected.
+   *
+   * @param filePath the capture file to create, overwriting an existing file
+   * @throws NullPointerException if filePath is null
+   * @throws IOException          if the file cannot be created, or the port is not open
+   */
+  public void startCapture(String filePath) throws IOException {
+    if (filePath == null) {
+      throw new NullPointerException("filePath cannot be null");
+    }
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    if (!NativeSerialPort.startCapture(handle, filePath)) {
+      throw createIOException("Failed to start capture");
+    }
+  }
+
+  /**
+   * Stop recording traffic and flush the capture file. Does nothing if no capture is running.
+   *
+   * @throws IOException if the buffered packets cannot be written, or the port is not open
+   * @see #startCapture(String)
+   */
+  public void stopCapture() throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    if (!NativeSerialPort.stopCapture(handle)) {
+      throw createIOException("Failed to stop capture");
+    }
+  }
+
+  /**
+   * Get the recent native errors recorded on the calling thread.
+   *
+   * <p>Exceptions only carry the error of the failing call, so a burst of failures (for example
+   * an open failure followed by every later operation failing) can hide the root cause. The
+   * native layer keeps the most recent errors of each thread in a ring buffer, which holds 8
+   * entries unless changed with {@link #setErrorHistorySize(int)}. Each entry includes the
+   * native source location.
+   *
+   * @return the retained errors, oldest first and separated by newlines, or null if there are none
+   */
+  public static String getErrorHistory() {
+    return NativeSerialPort.getErrorHistory();
+  }
+
+  /**
+   * Set how many native errors are retained per thread for {@link #getErrorHistory()}.
+   *
+   * <p>The size applies to all threads; existing histories shrink on their next error.
+   *
+   * @param size the number of errors to retain (default: 8)
+   * @throws IllegalArgumentException if size is less than 1
+   */
+  public static void setErrorHistorySize(int size) {
+    if (size < 1) {
+      throw new IllegalArgumentException("size must be at least 1");
+    }
+    NativeSerialPort.setErrorHistorySize(size);
+  }
+
+  /**
+   * Enable or disable strict validation of the line settings when ports are opened.
+   *
+   * <p>By default, the native layer replaces an unknown data bits, stop bits, parity or flow
+   * control code with a default (8 data bits, 1 stop bit, no parity, no flow control), which
+   * can hide a configuration mistake. In strict mode, such a code makes the open fail with an
+   * error naming the bad setting instead. The setting applies to all ports opened afterwards,
+   * from any thread.
+   *
+   * @param enabled true to fail on unknown codes, false to substitute defaults (default: false)
+   */
+  public static void setStrictConfigValidation(boolean enabled) {
+    NativeSerialPort.setStrictConfigValidation(enabled);
+  }
+
+  /**
+   * Discard the native error history of the calling thread.
+   */
+  public static void clearErrorHistory() {
+    NativeSerialPort.clearLastError();
+  }
+
+  /**
+   * Read data from the serial port, copying directly into the array's backing storage.
+   *
+   * <p>This behaves like {@link #read(byte[], int, int)} but avoids the per-call native
+   * allocation and byte conversion, which helps high-throughput readers that reuse buffers.
+   *
+   * <p><b>GC note:</b> the array is pinned with a JNI critical region while the received
+   * bytes are copied in. The blocking read happens before the region is entered, so the
+   * pause is limited to one memory copy of at most {@code length} bytes, but some garbage
+   * collectors may still delay collection for that short window. {@code examples/ReadBenchmark}
+   * compares this with the other read paths, including the time spent in garbage collection.
+   *
+   * @param buffer the buffer to read into
+   * @param offset the offset in the buffer
+   * @param length the maximum number of bytes to read
+   * @return the number of bytes read, or 0 if no data is available
+   * @throws IOException               if the read fails or the port is not open
+   * @throws IndexOutOfBoundsException if offset or length are invalid
+   * @throws NullPointerException      if buffer is null
+   */
+  public int readCritical(byte[] buffer, int offset, int length) throws IOException {
+    if (buffer == null) {
+      throw new NullPointerException("buffer cannot be null");
+    }
+    if (offset < 0 || length < 0 || offset + length > buffer.length) {
+      throw new IndexOutOfBoundsException(
+          "offset=" + offset + ", length=" + length + ", buffer.length=" + buffer.length);
+    }
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+
+    int result = NativeSerialPort.readCritical(handle, buffer, offset, length);
+    if (result == READ_CANCELLED) {
+      throw createInterruptedIOException("Read from serial port was cancelled");
+    }
+    if (result < 0) {
+      throw createReadIOException("Failed to read from serial port");
+    }
+
+    return result;
+  }
+
+  /**
+   * Read data from the serial port directly into native memory, such as the address of a
+   * direct {@code ByteBuffer} or a region allocated through the Foreign Memory API.
+   *
+   * <p>This bypasses JNI array marshaling entirely: the bytes are read straight into
+   * {@code address}.
+   *
+   * <p><b>Safety:</b> the address is not checked beyond being non-zero. The caller is solely
+   * responsible for ensuring it points to at least {@code length} writable bytes that stay
+   * allocated until this method returns (keep any owning buffer strongly reachable). Passing an
+   * invalid address corrupts memory or crashes the JVM.
+   *
+   * @param address the native memory address to read into
+   * @param length  the maximum number of bytes to read
+   * @return the number of bytes read, or 0 if no data is available
+   * @throws IOException              if the read fails or the port is not open
+   * @throws IllegalArgumentException if address is 0 or length is negative
+   */
+  public int readToAddress(long address, int length) throws IOException {
+    if (address == 0) {
+      throw new IllegalArgumentException("address cannot be 0");
+    }
+    if (length < 0) {
+      throw new IllegalArgumentException("length cannot be negative: " + length);
+    }
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+
+    int result = NativeSerialPort.readToAddress(handle, address, length);
+    if (result == READ_CANCELLED) {
+      throw createInterruptedIOException("Read from serial port was cancelled");
+    }
+    if (result < 0) {
+      throw createReadIOException("Failed to read from serial port");
+    }
+
+    return result;
+  }
+
+  /**
+   * Write data to the serial port directly from native memory, such as the address of a
+   * direct {@code ByteBuffer} or a region allocated through the Foreign Memory API.
+   *
+   * <p>This bypasses JNI array marshaling entirely: the bytes at {@code address + offset} are
+   * written in place. Like {@link #write(byte[], int, int)}, a zero {@code length} returns 0
+   * without touching the port.
+   *
+   * <p><b>Safety:</b> the address is not checked beyond being non-zero. The caller is solely
+   * responsible for ensuring {@code address + offset} points to at least {@code length}
+   * readable bytes that stay allocated until this method returns (keep any owning buffer
+   * strongly reachable). Passing an invalid address may crash the JVM or leak unrelated memory
+   * onto the wire.
+   *
+   * @param address the native memory address to write from
+   * @param offset  the offset from the address
+   * @param length  the number of bytes to write
+   * @return the number of bytes written
+   * @throws IOException              if the write fails or the port is not open
+   * @throws IllegalArgumentException if address is 0 or offset or length are negative
+   */
+  public int writeFromAddress(long address, int offset, int length) throws IOException {
+    if (address == 0) {
+      throw new IllegalArgumentException("address cannot be 0");
+    }
+    if (offset < 0 || length < 0) {
+      throw new IllegalArgumentException("offset=" + offset + ", length=" + length);
+    }
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+
+    int result = NativeSerialPort.writeFromAddress(handle, address, offset, length);
+    if (result == WRITE_WOULD_BLOCK) {
+      throw createIOException("Write to serial port would block");
+    }
+    if (result < 0) {
+      throw createIOException("Failed to write to serial port");
+    }
+
+    return result;
+  }
+
+  /**
+   * Read once from the serial port and scatter the data across several buffers.
+   *
+   * <p>Segment {@code i} is {@code buffers[i]} starting at {@code offsets[i]}, up to
+   * {@code lengths[i]} bytes. Segments are filled in order, like {@code readv()}: a
+   * segment only receives data once all previous segments are full, so a short read
+   * leaves trailing segments untouched. This lets a caller read a fixed-size header
+   * into one buffer and the body into another without reassembly.
+   *
+   * <p>The read waits for input for up to {@code timeoutMs} instead of the port timeout,
+   * which is left unchanged, and then takes whatever has arrived.
+   *
+   * @param buffers   the buffers to read into
+   * @param offsets   the offset in each buffer
+   * @param lengths   the maximum number of bytes to read into each buffer
+   * @param timeoutMs how long to wait for input in milliseconds
+   * @return the total number of bytes read, or 0 if no data arrived before the timeout
+   * @throws IOException               if the read fails or the port is not open
+   * @throws IllegalArgumentException  if the arrays do not have the same length
+   * @throws IndexOutOfBoundsException if a segment does not fit its buffer
+   * @throws NullPointerException      if any array or buffer is null
+   */
+  public int readScatter(byte[][] buffers, int[] offsets, int[] lengths, int timeoutMs) throws IOException {
+    if (buffers == null || offsets == null || lengths == null) {
+      throw new NullPointerException("buffers, offsets and lengths cannot be null");
+    }
+    if (buffers.length != offsets.length || buffers.length != lengths.length) {
+      throw new IllegalArgumentException("buffers, offsets and lengths must have the same length");
+    }
+    for (int i = 0; i < buffers.length; i++) {
+      if (buffers[i] == null) {
+        throw new NullPointerException("buffers[" + i + "] cannot be null");
+      }
+      if (offsets[i] < 0 || lengths[i] < 0 || offsets[i] + lengths[i] > buffers[i].length) {
+        throw new IndexOutOfBoundsException(
+            "segment " + i + ": offset=" + offsets[i] + ", length=" + lengths[i] + ", buffer.length=" + buffers[i].length);
+      }
+    }
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+
+    int result = NativeSerialPort.readScatter(handle, buffers, offsets, lengths, timeoutMs);
+    if (result == READ_CANCELLED) {
+      throw createInterruptedIOException("Read from serial port was cancelled");
+    }
+    if (result < 0) {
+      throw createReadIOException("Failed to read from serial port");
+    }
+    return result;
+  }
+
+  /**
+   * Cancel a read that is blocked on this port (Linux only).
+   *
+   * <p>This is the one method intended to be called from a different thread than the one
+   * using the port: it wakes up a thread blocked in one of the {@code read} methods, which
+   * then throws an {@link InterruptedIOException}. Use it to shut down reader threads
+   * promptly instead of waiting for a long read timeout to expire.
+   *
+   * <p>If no read is in progress, the next read on this port is cancelled immediately.
+   *
+   * @throws IOException if the operation fails, the port is not open, or the platform is not Linux
+   */
+  public void cancelRead() throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    if (!NativeSerialPort.cancelRead(handle)) {
+      throw createIOException("Failed to cancel read");
+    }
+  }
+
+  /**
+   * Get a file descriptor that becomes readable when this port has input (Linux only).
+   *
+   * <p>This lets event loops that work with descriptors, such as a Java NIO {@code Selector}
+   * (through a channel wrapping the descriptor) or a native {@code epoll} loop, wait on many
+   * ports from one thread. The first call starts a background thread that polls the port and
+   * signals the descriptor, an {@code eventfd}; later calls return the same descriptor.
+   *
+   * <p>Once signalled, the descriptor stays readable until {@link #resetReadableEvent()} is
+   * called, and the background thread does not poll the port again until then. Reset it before
+   * reading the port, so input arriving after the read signals it again. Bytes already received
+   * but not yet read, such as those past the match of {@link #readUntilPattern}, keep it
+   * signalled too.
+   *
+   * <p>The descriptor is owned by this port: do not close it, and stop using it before closing
+   * the port, which closes it and stops the background thread.
+   *
+   * @return the file descriptor
+   * @throws IOException if the operation fails, the port is not open, or the platform is not Linux
+   */
+  public int getReadableEventFd() throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    int fd = NativeSerialPort.getReadableEventFd(handle);
+    if (fd < 0) {
+      throw createIOException("Failed to get readable event descriptor");
+    }
+    return fd;
+  }
+
+  /**
+   * Reset the descriptor returned by {@link #getReadableEventFd()}, so that it is signalled
+   * again when the port has input.
+   *
+   * @return true if the descriptor was signalled, false if it was not, if
+   *     {@link #getReadableEventFd()} was never called, or if the port is not open
+   */
+  public boolean resetReadableEvent() {
+    if (!isOpen) {
+      return false;
+    }
+    return NativeSerialPort.resetReadableEvent(handle);
+  }
+
+  /**
+   * Get the number of bytes available to read.
+   *
+   * @return the number of bytes available
+   * @throws IOException if the port is not open
+   */
+  public int available() throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+
+    return NativeSerialPort.bytesAvailable(handle);
+  }
+
+  /**
+   * Wait until at least {@code count} bytes are available to read or the timeout elapses.
+   *
+   * <p>This is an efficient replacement for polling {@link #available()} in a loop, for example
+   * to avoid reading until a whole frame header has arrived. On Linux it sleeps in
+   * {@code poll()} while no data is queued. No data is consumed.
+   *
+   * @param count     the number of bytes to wait for
+   * @param timeoutMs the maximum time to wait in milliseconds
+   * @return the number of bytes available, which is less than count if the timeout elapsed
+   * @throws IllegalArgumentException if count or timeoutMs is negative
+   * @throws IOException              if the operation fails or the port is not open
+   */
+  public int waitForBytes(int count, int timeoutMs) throws IOException {
+    if (count < 0) {
+      throw new IllegalArgumentException("count must not be negative");
+    }
+    if (timeoutMs < 0) {
+      throw new IllegalArgumentException("timeoutMs must not be negative");
+    }
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    int result = NativeSerialPort.waitForBytes(handle, count, timeoutMs);
+    if (result < 0) {
+      throw createIOException("Failed to wait for bytes");
+    }
+    return result;
+  }
+
+  /**
+   * Wait until everything written so far has left the port, or the timeout elapses.
+   *
+   * <p>This polls the driver's output queue until it is empty, then waits for the last
+   * character to be shifted out, so it is safe to switch an RS-485 transceiver to receive or to
+   * close the port afterwards. Unlike {@link #flush()}, it gives up after the timeout instead of
+   * waiting for a transmitter held off by flow control, and unlike the drain timeout of writes
+   * (see {@link #setDrainTimeout(int)}) it never discards output: on timeout the data stays
+   * queued.
+   *
+   * @param timeoutMs the maximum time to wait in milliseconds
+   * @return true if the output emptied, false if the timeout elapsed first
+   * @throws IllegalArgumentException if timeoutMs is negative
+   * @throws IOException              if the operation fails or the port is not open
+   */
+  public boolean waitOutputEmpty(int timeoutMs) throws IOException {
+    if (timeoutMs < 0) {
+      throw new IllegalArgumentException("timeoutMs must not be negative");
+    }
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    int result = NativeSerialPort.waitOutputEmpty(handle, timeoutMs);
+    if (result < 0) {
+      throw createIOException("Failed to wait for output to empty");
+    }
+    return result > 0;
+  }
+
+  /**
+   * Get a snapshot of the port status, sampled in a single native call.
+   *
+   * <p>This returns the input and output queue sizes, the modem input lines and whether
+   * kernel RS-485 mode is active, which is much cheaper than querying each separately
+   * when refreshing a dashboard frequently.
+   *
+   * @return the port status
+   * @throws IOException if the operation fails or the port is not open
+   */
+  public PortStatus getPortStatus() throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    String data = NativeSerialPort.getPortStatus(handle);
+    if (data == null) {
+      throw createIOException("Failed to get port status");
+    }
+    return PortStatus.parse(data);
+  }
+
+  /**
+   * Get the deepest input and output queues observed since the port was opened or
+   * {@link #resetQueueHighWater()} was last called.
+   *
+   * <p>Queue depths are sampled on each read, write and {@link #getPortStatus()} call,
+   * so this shows whether the buffers came close to overflowing under load, which helps
+   * when tuning buffer sizes and read intervals.
+   *
+   * @return the queue high-water marks
+   * @throws IOException if the operation fails or the port is not open
+   */
+  public QueueHighWater getQueueHighWater() throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    String data = NativeSerialPort.getQueueHighWater(handle);
+    if (data == null) {
+      throw createIOException("Failed to get queue high-water marks");
+    }
+    return QueueHighWater.parse(data);
+  }
+
+  /**
+   * Reset the queue high-water marks reported by {@link #getQueueHighWater()} to zero.
+   *
+   * @throws IOException if the operation fails or the port is not open
+   */
+  public void resetQueueHighWater() throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    if (NativeSerialPort.resetQueueHighWater(handle) != 0) {
+      throw createIOException("Failed to reset queue high-water marks");
+    }
+  }
+
+  /**
+   * Read exactly the specified number of bytes, blocking until all bytes are received.
+   *
+   * <p>This method will repeatedly call {@link #read(byte[], int, int)} until exactly
+   * {@code length} bytes have been read. If a timeout occurs before all bytes are received,
+   * an IOException is thrown.
+   *
+   * @param buffer the buffer to read into
+   * @param offset the offset in the buffer
+   * @param length the exact number of bytes to read
+   * @return the number of bytes read (always equals length on success)
+   * @throws IOException               if a timeout occurs or the port is not open
+   * @throws IndexOutOfBoundsException if offset or length are invalid
+   * @throws NullPointerException      if buffer is null
+   */
+  public int readExactly(byte[] buffer, int offset, int length) throws IOException {
+    if (buffer == null) {
+      throw new NullPointerException("buffer cannot be null");
+    }
+    if (offset < 0 || length < 0 || offset + length > buffer.length) {
+      throw new IndexOutOfBoundsException(
+          "offset=" + offset + ", length=" + length + ", buffer.length=" + buffer.length);
+    }
+
+    int total = 0;
+    while (total < length) {
+      int bytesRead = read(buffer, offset + total, length - total);
+      if (bytesRead == 0) {
+        throw new IOException("Timeout waiting for data (received " + total + " of " + length + " bytes)");
+      }
+      total += bytesRead;
+    }
+    return total;
+  }
+
+  /**
+   * Read exactly the specified number of bytes into a new buffer.
+   *
+   * <p>This is a convenience method that creates a buffer and calls
+   * {@link #readExactly(byte[], int, int)}.
+   *
+   * @param length the exact number of bytes to read
+   * @return a new byte array containing exactly {@code length} bytes
+   * @throws IOException if a timeout occurs or the port is not open
+   */
+  public byte[] readExactly(int length) throws IOException {
+    byte[] buffer = new byte[length];
+    readExactly(buffer, 0, length);
+    return buffer;
+  }
+
+  /**
+   * Read exactly the specified number of bytes with a custom timeout.
+   *
+   * <p>This method temporarily changes the port timeout for this operation,
+   * then restores the original timeout when done.
+   *
+   * @param buffer  the buffer to read into
+   * @param offset  the offset in the buffer
+   * @param length  the exact number of bytes to read
+   * @param timeout the timeout in milliseconds for this operation
+   * @return the number of bytes read (always equals length on success)
+   * @throws IOException               if a timeout occurs or the port is not open
+   * @throws IndexOutOfBoundsException if offset or length are invalid
+   * @throws NullPointerException      if buffer is null
+   */
+  public int readExactly(byte[] buffer, int offset, int length, long timeout) throws IOException {
+    int originalTimeout = this.timeoutMs;
+    try {
+      setTimeout((int) timeout);
+      return readExactly(buffer, offset, length);
+    }
+    finally {
+      setTimeout(originalTimeout);
+    }
+  }
+
+  /**
+   * Read all currently available bytes without blocking.
+   *
+   * <p>This method returns immediately with whatever data is available in the
+   * receive buffer. If no data is available, it returns 0 without waiting.
+   *
+   * @param buffer the buffer to read into
+   * @return the number of bytes read, or 0 if no data is available
+   * @throws IOException if the port is not open
+   */
+  public int readAvailable(byte[] buffer) throws IOException {
+    return readAvailable(buffer, 0, buffer.length);
+  }
+
+  /**
+   * Read all currently available bytes without blocking.
+   *
+   * <p>This method returns immediately with whatever data is available in the
+   * receive buffer, up to {@code length} bytes. If no data is available, it
+   * returns 0 without waiting.
+   *
+   * @param buffer the buffer to read into
+   * @param offset the offset in the buffer
+   * @param length the maximum number of bytes to read
+   * @return the number of bytes read, or 0 if no data is available
+   * @throws IOException               if the port is not open
+   * @throws IndexOutOfBoundsException if offset or length are invalid
+   */
+  public int readAvailable(byte[] buffer, int offset, int length) throws IOException {
+    int avail = available();
+    if (avail == 0) {
+      return 0;
+    }
+    return read(buffer, offset, Math.min(avail, length));
+  }
+
+  /**
+   * Read a line of text, terminated by a newline character, using the specified charset.
+   *
+   * <p>This method reads bytes until a newline character ({@code \n}) is encountered.
+   * The newline character is not included in the returned string. Carriage return
+   * characters ({@code \r}) immediately before the newline are also stripped.
+   *
+   * <p>If a timeout occurs before a complete line is received, an IOException is thrown.
+   *
+   * @param charset the charset to use for decoding
+   * @return the line of text without the line terminator
+   * @throws IOException if a timeout occurs or the port is not open
+   */
+  public String readLine(java.nio.charset.Charset charset) throws IOException {
+    java.io.ByteArrayOutputStream baos = new java.io.ByteArrayOutputStream();
+    byte[] single = new byte[1];
+
+    while (true) {
+      int bytesRead = read(single, 0, 1);
+      if (bytesRead == 0) {
+        if (baos.size() == 0) {
+          throw new IOException("Timeout waiting for line data");
+        }
+        // Return partial line on timeout if we have some data
+        break;
+      }
+
+      byte b = single[0];
+      if (b == '\n') {
+        break;
+      }
+      if (b != '\r') {
+        baos.write(b);
+      }
+    }
+
+    return baos.toString(charset.name());
+  }
+
+  /**
+   * Read a line of text, terminated by a newline character, using UTF-8 encoding.
+   *
+   * <p>This method reads bytes until a newline character ({@code \n}) is encountered.
+   * The newline character is not included in the returned string. Carriage return
+   * characters ({@code \r}) immediately before the newline are also stripped.
+   *
+   * <p>If a timeout occurs before a complete line is received, an IOException is thrown.
+   *
+   * @return the line of text without the line terminator
+   * @throws IOException if a timeout occurs or the port is not open
+   */
+  public String readLine() throws IOException {
+    return readLine(java.nio.charset.StandardCharsets.UTF_8);
+  }
+
+  /**
+   * Perform a request/response exchange as a single operation.
+   *
+   * <p>This clears the input buffer, writes the request (with RS-485 direction control
+   * if configured), waits for it to be transmitted, and then reads the response. Clearing
+   * first ensures stale bytes from a previous exchange cannot corrupt the reply.
+   *
+   * <p>The response is read until {@code maxLength} bytes have arrived or {@code timeoutMs}
+   * elapses, whichever comes first. For fixed-size responses, pass the exact expected length
+   * so the call returns as soon as the full response is received.
+   *
+   * @param request   the request bytes to write
+   * @param response  the buffer to read the response into
+   * @param maxLength the maximum number of response bytes to read
+   * @param timeoutMs the deadline for the response in milliseconds
+   * @return the number of response bytes read, or 0 if no response arrived in time
+   * @throws IOException               if the exchange fails or the port is not open
+   * @throws IndexOutOfBoundsException if maxLength is invalid for the response buffer
+   * @throws NullPointerException      if request or response is null
+   * @throws IllegalArgumentException  if timeoutMs is not positive
+   */
+  public int transact(byte[] request, byte[] response, int maxLength, int timeoutMs) throws IOException {
+    if (request == null) {
+      throw new NullPointerException("request cannot be null");
+    }
+    if (response == null) {
+      throw new NullPointerException("response cannot be null");
+    }
+    if (maxLength < 0 || maxLength > response.length) {
+      throw new IndexOutOfBoundsException("maxLength=" + maxLength + ", response.length=" + response.length);
+    }
+    if (timeoutMs <= 0) {
+      throw new IllegalArgumentException("timeoutMs must be positive");
+    }
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+
+    int result = NativeSerialPort.transact(handle, request, response, maxLength, timeoutMs);
+    if (result < 0) {
+      throw createIOException("Failed to perform transaction");
+    }
+    return result;
+  }
+
+  /**
+   * Measure the round-trip latency to a cooperating echo device or a loopback.
+   *
+   * <p>For each of {@code iterations} round trips, the input buffer is cleared, {@code probe}
+   * is written (with RS-485 direction control if configured), and the call waits for
+   * {@code responseLength} bytes to come back. Timing happens in the native layer on a
+   * monotonic clock, so JNI and Java overhead do not skew the results. Use this to tune
+   * timeouts and compare adapters, baud rates or driver settings such as the USB latency timer.
+   *
+   * <p>The peer must answer every probe with at least {@code responseLength} bytes; a loopback
+   * plug or an echo device answering with the probe itself works with
+   * {@code responseLength == probe.length}. Any unexpected input is discarded before each probe.
+   *
+   * @param probe          the bytes to write in each round trip
+   * @param responseLength the number of response bytes to wait for
+   * @param timeoutMs      the deadline for each response in milliseconds
+   * @param iterations     the number of round trips to measure
+   * @return the minimum, average and maximum latency
+   * @throws IOException              if a response does not arrive in time, the exchange fails,
+   *                                  or the port is not open
+   * @throws NullPointerException     if probe is null
+   * @throws IllegalArgumentException if responseLength, timeoutMs or iterations is not positive
+   */
+  public RoundTripLatency measureRoundTrip(
+      byte[] probe, int responseLength, int timeoutMs, int iterations) throws IOException {
+    if (probe == null) {
+      throw new NullPointerException("probe cannot be null");
+    }
+    if (responseLength <= 0 || timeoutMs <= 0 || iterations <= 0) {
+      throw new IllegalArgumentException(
+          "responseLength, timeoutMs and iterations must be positive");
+    }
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+
+    String data =
+        NativeSerialPort.measureRoundTrip(handle, probe, responseLength, timeoutMs, iterations);
+    if (data == null) {
+      throw createIOException("Failed to measure round trip");
+    }
+    return RoundTripLatency.parse(data);
+  }
+
+  /**
+   * Perform a request/response exchange, reading up to the size of the response buffer.
+   *
+   * @param request   the request bytes to write
+   * @param response  the buffer to read the response into
+   * @param timeoutMs the deadline for the response in milliseconds
+   * @return the number of response bytes read, or 0 if no response arrived in time
+   * @throws IOException if the exchange fails or the port is not open
+   * @see #transact(byte[], byte[], int, int)
+   */
+  public int transact(byte[] request, byte[] response, int timeoutMs) throws IOException {
+    return transact(request, response, response.length, timeoutMs);
+  }
+
+  /**
+   * Read until {@code length} bytes arrive or {@code timeoutMs} elapses.
+   *
+   * <p>The return value alone cannot tell a partial timeout apart from a read that simply
+   * asked for fewer bytes, so the outcome is also recorded in {@link #lastReadTimedOut()}:
+   * <ul>
+   *   <li>count == length, flag clear: the buffer was filled</li>
+   *   <li>count &lt; length, flag set: the deadline elapsed; the first count bytes are valid
+   *       partial data</li>
+   * </ul>
+   *
+   * <p>A {@code timeoutMs} of 0 does not wait: it returns the bytes already received, up to
+   * {@code length} and possibly none, and leaves the flag clear.
+   *
+   * @param buffer    the buffer to read into
+   * @param offset    the offset in the buffer
+   * @param length    the number of bytes to read
+   * @param timeoutMs the deadline for the whole read in milliseconds, or 0 to read only the
+   *                  bytes already received
+   * @return the number of bytes read, which is less than length if the deadline elapsed
+   * @throws IOException               if the read fails or the port is not open
+   * @throws InterruptedIOException    if the read was cancelled with {@link #cancelRead()}
+   * @throws IndexOutOfBoundsException if offset or length are invalid
+   * @throws IllegalArgumentException  if timeoutMs is negative
+   * @throws NullPointerException      if buffer is null
+   */
+  public int readFully(byte[] buffer, int offset, int length, int timeoutMs) throws IOException {
+    if (buffer == null) {
+      throw new NullPointerException("buffer cannot be null");
+    }
+    if (offset < 0 || length < 0 || offset + length > buffer.length) {
+      throw new IndexOutOfBoundsException(
+          "offset=" + offset + ", length=" + length + ", buffer.length=" + buffer.length);
+    }
+    if (timeoutMs < 0) {
+      throw new IllegalArgumentException("timeoutMs must not be negative");
+    }
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+
+    int result = NativeSerialPort.readFully(handle, buffer, offset, length, timeoutMs);
+    if (result == READ_CANCELLED) {
+      throw createInterruptedIOException("Read from serial port was cancelled");
+    }
+    if (result < 0) {
+      throw createReadIOException("Failed to read from serial port");
+    }
+    return result;
+  }
+
+  /**
+   * Read until the buffer is full or {@code timeoutMs} elapses.
+   *
+   * @param buffer    the buffer to read into
+   * @param timeoutMs the deadline for the whole read in milliseconds
+   * @return the number of bytes read, which is less than buffer.length if the deadline elapsed
+   * @throws IOException if the read fails or the port is not open
+   * @see #readFully(byte[], int, int, int)
+   */
+  public int readFully(byte[] buffer, int timeoutMs) throws IOException {
+    return readFully(buffer, 0, buffer.length, timeoutMs);
+  }
+
+  /**
+   * Read a frame that ends when the line goes idle.
+   *
+   * <p>For protocols without a delimiter or length prefix, such as Modbus RTU, a frame ends
+   * when no byte arrives for a while. This accumulates bytes and returns once no byte has
+   * arrived for {@code idleGapMs} after the last one, {@code maxLen} bytes have been read, or
+   * {@code overallTimeoutMs} has elapsed, whichever comes first. The idle gap only starts
+   * counting with the first byte, so a frame that has not started yet is waited for until the
+   * overall timeout. The gap is detected by polling the input queue about once per millisecond,
+   * so it should be at least a few milliseconds and longer than any pause within a frame.
+   *
+   * <p>This read is not woken up by {@link #cancelRead()} while it waits for data.
+   *
+   * @param buffer           the buffer to read into
+   * @param offset           the offset in the buffer
+   * @param maxLen           the maximum number of bytes to read
+   * @param idleGapMs        the silence after received data that ends the frame, in milliseconds
+   * @param overallTimeoutMs the deadline for the whole read in milliseconds
+   * @return the number of bytes read, or 0 if nothing arrived before the deadline
+   * @throws IOException               if the read fails or the port is not open
+   * @throws IndexOutOfBoundsException if offset or maxLen are invalid
+   * @throws IllegalArgumentException  if idleGapMs is not positive or overallTimeoutMs is negative
+   * @throws NullPointerException      if buffer is null
+   */
+  public int readUntilIdle(byte[] buffer, int offset, int maxLen, int idleGapMs,
+      int overallTimeoutMs) throws IOException {
+    if (buffer == null) {
+      throw new NullPointerException("buffer cannot be null");
+    }
+    if (offset < 0 || maxLen < 0 || offset + maxLen > buffer.length) {
+      throw new IndexOutOfBoundsException(
+          "offset=" + offset + ", maxLen=" + maxLen + ", buffer.length=" + buffer.length);
+    }
+    if (idleGapMs <= 0) {
+      throw new IllegalArgumentException("idleGapMs must be positive");
+    }
+    if (overallTimeoutMs < 0) {
+      throw new IllegalArgumentException("overallTimeoutMs must not be negative");
+    }
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+
+    int result = NativeSerialPort.readUntilIdle(handle, buffer, offset, maxLen, idleGapMs,
+        overallTimeoutMs);
+    if (result == READ_CANCELLED) {
+      throw createInterruptedIOException("Read from serial port was cancelled");
+    }
+    if (result < 0) {
+      throw createReadIOException("Failed to read from serial port");
+    }
+    return result;
+  }
+
+  /**
+   * Read a frame that ends when the line goes idle and check the CRC at its end.
+   *
+   * <p>The frame is delimited as by {@link #readUntilIdle(byte[], int, int, int, int)}: it ends
+   * once no byte has arrived for {@code idleGapMs}, when {@code maxLen} bytes have been read, or
+   * when {@code timeoutMs} has elapsed. Its last {@link CrcType#getLength()} bytes are then
+   * compared with the CRC of the bytes before them. The whole frame, CRC included, is stored in
+   * the buffer, and the payload length is returned, so the CRC follows the payload in the
+   * buffer.
+   *
+   * <p>A frame whose CRC does not match, or that is shorter than the CRC, fails with an
+   * IOException whose message shows the received and computed CRCs. The received bytes are
+   * still stored in the buffer for inspection.
+   *
+   * @param buffer       the buffer to read the frame into
+   * @param offset       the offset in the buffer
+   * @param maxLen       the maximum frame length, including the CRC
+   * @param crcType      the CRC at the end of the frame
+   * @param littleEndian true if the CRC is sent least significant byte first, false for most
+   *                     significant byte first
+   * @param idleGapMs    the silence after received data that ends the frame, in milliseconds
+   * @param timeoutMs    the deadline for the whole read in milliseconds
+   * @return the payload length (the frame length without the CRC), or 0 if nothing arrived
+   *         before the deadline
+   * @throws IOException               if the CRC does not match, the read fails, or the port is
+   *                                   not open
+   * @throws InterruptedIOException    if the read was cancelled with {@link #cancelRead()}
+   * @throws IndexOutOfBoundsException if offset or maxLen are invalid
+   * @throws IllegalArgumentException  if idleGapMs is not positive or timeoutMs is negative
+   * @throws NullPointerException      if buffer or crcType is null
+   * @see #writeWithCrc(byte[], int, int, CrcType, boolean)
+   */
+  public int readAndVerifyCrc(byte[] buffer, int offset, int maxLen, CrcType crcType,
+      boolean littleEndian, int idleGapMs, int timeoutMs) throws IOException {
+    if (buffer == null) {
+      throw new NullPointerException("buffer cannot be null");
+    }
+    if (crcType == null) {
+      throw new NullPointerException("crcType cannot be null");
+    }
+    if (offset < 0 || maxLen < 0 || offset + maxLen > buffer.length) {
+      throw new IndexOutOfBoundsException(
+          "offset=" + offset + ", maxLen=" + maxLen + ", buffer.length=" + buffer.length);
+    }
+    if (idleGapMs <= 0) {
+      throw new IllegalArgumentException("idleGapMs must be positive");
+    }
+    if (timeoutMs < 0) {
+      throw new IllegalArgumentException("timeoutMs must not be negative");
+    }
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+
+    int result = NativeSerialPort.readAndVerifyCrc(handle, buffer, offset, maxLen,
+        crcType.getValue(), littleEndian, idleGapMs, timeoutMs);
+    if (result == READ_CANCELLED) {
+      throw createInterruptedIOException("Read from serial port was cancelled");
+    }
+    if (result < 0) {
+      throw createReadIOException("Failed to read a frame with a valid CRC");
+    }
+    return result;
+  }
+
+  /**
+   * Read a frame that ends with a multi-byte marker, such as {@code \r\n} or an ETX followed by
+   * a fixed trailer.
+   *
+   * <p>This reads until the data read ends with the full {@code pattern}, {@code maxLen} bytes
+   * have been read, or {@code timeoutMs} has elapsed, whichever comes first, and returns the
+   * data up to and including the pattern. The stream is scanned in native code as it arrives,
+   * including patterns split across reads. Data is read in chunks, so it can run past the
+   * pattern; those bytes are kept and returned first by the next reads, counted by
+   * {@link #available()} and discarded by {@link #clearInput()}. Readers that watch the port
+   * directly, such as {@link #getReadableEventFd()} and {@link #startFrameReader}, do not see
+   * them.
+   *
+   * <p>The outcome can be told from the result:
+   * <ul>
+   *   <li>the data ends with the pattern: the pattern was found</li>
+   *   <li>count == maxLen without the pattern: the buffer filled up first</li>
+   *   <li>count &lt; maxLen without the pattern: the deadline elapsed, which is also recorded in
+   *       {@link #lastReadTimedOut()}; the bytes read are valid partial data</li>
+   * </ul>
+   *
+   * @param pattern   the byte sequence that ends the frame
+   * @param buffer    the buffer to read into
+   * @param offset    the offset in the buffer
+   * @param maxLen    the maximum number of bytes to read, including the pattern
+   * @param timeoutMs the deadline for the whole read in milliseconds
+   * @return the number of bytes read, including the pattern if it was found
+   * @throws IOException               if the read fails or the port is not open
+   * @throws InterruptedIOException    if the read was cancelled with {@link #cancelRead()}
+   * @throws IndexOutOfBoundsException if offset or maxLen are invalid
+   * @throws IllegalArgumentException  if pattern is empty or timeoutMs is negative
+   * @throws NullPointerException      if pattern or buffer is null
+   */
+  public int readUntilPattern(byte[] pattern, byte[] buffer, int offset, int maxLen,
+      int timeoutMs) throws IOException {
+    if (pattern == null) {
+      throw new NullPointerException("pattern cannot be null");
+    }
+    if (buffer == null) {
+      throw new NullPointerException("buffer cannot be null");
+    }
+    if (pattern.length == 0) {
+      throw new IllegalArgumentException("pattern must not be empty");
+    }
+    if (offset < 0 || maxLen < 0 || offset + maxLen > buffer.length) {
+      throw new IndexOutOfBoundsException(
+          "offset=" + offset + ", maxLen=" + maxLen + ", buffer.length=" + buffer.length);
+    }
+    if (timeoutMs < 0) {
+      throw new IllegalArgumentException("timeoutMs must not be negative");
+    }
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+
+    int result = NativeSerialPort.readUntilPattern(handle, pattern, buffer, offset, maxLen,
+        timeoutMs);
+    if (result == READ_CANCELLED) {
+      throw createInterruptedIOException("Read from serial port was cancelled");
+    }
+    if (result < 0) {
+      throw createReadIOException("Failed to read from serial port");
+    }
+    return result;
+  }
+
+  /**
+   * Read a frame that ends with a multi-byte marker into the whole buffer.
+   *
+   * @param pattern   the byte sequence that ends the frame
+   * @param buffer    the buffer to read into
+   * @param timeoutMs the deadline for the whole read in milliseconds
+   * @return the number of bytes read, including the pattern if it was found
+   * @throws IOException if the read fails or the port is not open
+   * @see #readUntilPattern(byte[], byte[], int, int, int)
+   */
+  public int readUntilPattern(byte[] pattern, byte[] buffer, int timeoutMs) throws IOException {
+    return readUntilPattern(pattern, buffer, 0, buffer.length, timeoutMs);
+  }
+
+  /**
+   * Read a frame whose fixed-size header carries the frame length, as used by many binary
+   * protocols.
+   *
+   * <p>This reads the {@code headerLen}-byte header, decodes the unsigned length field of
+   * {@code lengthFieldSize} bytes at {@code lengthFieldOffset} within it, then reads exactly the
+   * rest of the frame, all within {@code timeoutMs}. The frame is stored header first. With
+   * {@code includesHeader} the length field counts the whole frame, otherwise only the bytes
+   * after the header.
+   *
+   * <p>A frame longer than {@code maxLen} fails with an IOException before its payload is
+   * read. Its header is left unread, so the next read returns it, which lets the caller skip a
+   * byte and resynchronize, or discard the input with {@link #clearInput()}.
+   *
+   * <p>If the deadline elapses first, the bytes read so far are returned and
+   * {@link #lastReadTimedOut()} reports it; the count is then shorter than the frame.
+   *
+   * @param buffer            the buffer to read into
+   * @param offset            the offset in the buffer
+   * @param headerLen         the length of the header in bytes
+   * @param lengthFieldOffset the offset of the length field within the header
+   * @param lengthFieldSize   the size of the length field, 1 to 4 bytes
+   * @param lengthIsBigEndian true if the length field is big-endian, false for little-endian
+   * @param includesHeader    true if the length counts the header as well as the payload
+   * @param maxLen            the maximum frame length, including the header
+   * @param timeoutMs         the deadline for the whole read in milliseconds
+   * @return the number of bytes read, which is the frame length unless the deadline elapsed
+   * @throws IOException               if the frame is longer than maxLen, its length is shorter
+   *                                   than the header, the read fails, or the port is not open
+   * @throws InterruptedIOException    if the read was cancelled with {@link #cancelRead()}
+   * @throws IndexOutOfBoundsException if offset or maxLen are invalid
+   * @throws IllegalArgumentException  if the header layout is invalid, maxLen is shorter than
+   *                                   the header, or timeoutMs is negative
+   * @throws NullPointerException      if buffer is null
+   */
+  public int readLengthPrefixedFrame(byte[] buffer, int offset, int headerLen,
+      int lengthFieldOffset, int lengthFieldSize, boolean lengthIsBigEndian,
+      boolean includesHeader, int maxLen, int timeoutMs) throws IOException {
+    if (buffer == null) {
+      throw new NullPointerException("buffer cannot be null");
+    }
+    if (offset < 0 || maxLen < 0 || offset + maxLen > buffer.length) {
+      throw new IndexOutOfBoundsException(
+          "offset=" + offset + ", maxLen=" + maxLen + ", buffer.length=" + buffer.length);
+    }
+    if (lengthFieldSize < 1 || lengthFieldSize > 4) {
+      throw new IllegalArgumentException("lengthFieldSize must be 1 to 4 bytes");
+    }
+    if (lengthFieldOffset < 0 || lengthFieldOffset + lengthFieldSize > headerLen) {
+      throw new IllegalArgumentException("length field must lie within the header");
+    }
+    if (maxLen < headerLen) {
+      throw new IllegalArgumentException("maxLen must be at least headerLen");
+    }
+    if (timeoutMs < 0) {
+      throw new IllegalArgumentException("timeoutMs must not be negative");
+    }
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+
+    int result = NativeSerialPort.readLengthPrefixedFrame(handle, buffer, offset, headerLen,
+        lengthFieldOffset, lengthFieldSize, lengthIsBigEndian, includesHeader, maxLen, timeoutMs);
+    if (result == READ_CANCELLED) {
+      throw createInterruptedIOException("Read from serial port was cancelled");
+    }
+    if (result < 0) {
+      throw createReadIOException("Failed to read length-prefixed frame");
+    }
+    return result;
+  }
+
+  /**
+   * Start splitting the incoming byte stream into frames at idle gaps in the background.
+   *
+   * <p>This is the framing used by Modbus RTU and many other industrial protocols, without any
+   * checksum validation: a frame ends when no byte arrives for {@code idleGapMicros} after the
+   * last one. Unlike {@link #readUntilIdle}, a native thread watches the line continuously, so
+   * back-to-back frames are separated correctly even when the application is slow to ask for
+   * them. Take complete frames with {@link #nextFrame(byte[], int)}.
+   *
+   * <p>Up to 64 frames are queued; when the queue is full, the oldest frame is dropped and
+   * counted by {@link #getDroppedFrameCount()}. A burst longer than 4096 bytes without a gap is
+   * delivered as several frames. If the thread hits a read error, frames queued before it are
+   * still delivered, then {@code nextFrame} reports the error.
+   *
+   * <p>While the frame reader runs, do not read from the port by other means: the bytes would
+   * be split unpredictably between the reader and the other reads. The thread reads from its own
+   * handle to the device with its own timeout, and is stopped by {@link #stopFrameReader()} or
+   * when the port is closed. Bytes already received but not yet read, such as those past the
+   * match of {@link #readUntilPattern}, start the first frame.
+   *
+   * @param idleGapMicros the silence after received data that ends a frame, in microseconds
+   * @throws IllegalArgumentException if idleGapMicros is not positive
+   * @throws IOException              if a frame reader is already running, the thread cannot be
+   *                                  started, or the port is not open
+   */
+  public void startFrameReader(int idleGapMicros) throws IOException {
+    if (idleGapMicros <= 0) {
+      throw new IllegalArgumentException("idleGapMicros must be positive");
+    }
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    if (!NativeSerialPort.startFrameReader(handle, idleGapMicros)) {
+      throw createIOException("Failed to start frame reader");
+    }
+  }
+
+  /**
+   * Stop the frame reader started with {@link #startFrameReader(int)}, waiting for its thread to
+   * exit. Frames still queued are discarded. Does nothing if no frame reader is running.
+   *
+   * @throws IOException if the operation fails or the port is not open
+   */
+  public void stopFrameReader() throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    if (!NativeSerialPort.stopFrameReader(handle)) {
+      throw createIOException("Failed to stop frame reader");
+    }
+  }
+
+  /**
+   * Take the next complete frame from the frame reader, waiting up to {@code timeoutMs} for one.
+   *
+   * <p>The frame is copied to the start of {@code buffer}. A frame that does not fit is left in
+   * the queue and an IOException reports its length, so the call can be repeated with a larger
+   * buffer.
+   *
+   * @param buffer    the buffer to copy the frame into
+   * @param timeoutMs how long to wait for a frame in milliseconds, 0 to return immediately
+   * @return the frame length, or 0 if no frame was completed in time
+   * @throws IOException              if the frame does not fit in the buffer, the frame reader
+   *                                  is not running or stopped on a read error, or the port is
+   *                                  not open
+   * @throws IllegalArgumentException if timeoutMs is negative
+   * @throws NullPointerException     if buffer is null
+   * @see #startFrameReader(int)
+   */
+  public int nextFrame(byte[] buffer, int timeoutMs) throws IOException {
+    if (buffer == null) {
+      throw new NullPointerException("buffer cannot be null");
+    }
+    if (timeoutMs < 0) {
+      throw new IllegalArgumentException("timeoutMs must not be negative");
+    }
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+
+    int result = NativeSerialPort.nextFrame(handle, buffer, buffer.length, timeoutMs);
+    if (result < 0) {
+      throw createIOException("Failed to get next frame");
+    }
+    return result;
+  }
+
+  /**
+   * Get the number of frames the frame reader dropped because the application did not take
+   * them with {@link #nextFrame(byte[], int)} fast enough.
+   *
+   * @return the number of dropped frames, or 0 if no frame reader is running
+   * @throws IOException if the operation fails or the port is not open
+   */
+  public long getDroppedFrameCount() throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    long count = NativeSerialPort.getDroppedFrameCount(handle);
+    if (count < 0) {
+      throw createIOException("Failed to get dropped frame count");
+    }
+    return count;
+  }
+
+  /**
+   * Read a single byte, waiting at most {@code timeoutMs} for it to arrive.
+   *
+   * <p>This is the natural primitive for byte-at-a-time parsers and state machines, as it does
+   * not need a buffer per byte. With a timeout of 0, the call blocks until a byte arrives,
+   * like termios VMIN=1/VTIME=0; it can still be woken up with {@link #cancelRead()}.
+   *
+   * @param timeoutMs the maximum time to wait in milliseconds, or 0 to wait indefinitely
+   * @return the byte as an int in the range 0-255, or -1 if the timeout elapsed (in which case
+   *         {@link #lastReadTimedOut()} returns true)
+   * @throws IllegalArgumentException if timeoutMs is negative
+   * @throws InterruptedIOException   if the read was cancelled with {@link #cancelRead()}
+   * @throws IOException              if the read fails or the port is not open
+   */
+  public int readOne(int timeoutMs) throws IOException {
+    if (timeoutMs < 0) {
+      throw new IllegalArgumentException("timeoutMs must not be negative");
+    }
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+
+    int result = NativeSerialPort.readOne(handle, timeoutMs);
+    if (result == READ_CANCELLED) {
+      throw createInterruptedIOException("Read from serial port was cancelled");
+    }
+    if (result < 0 && !NativeSerialPort.lastReadTimedOut(handle)) {
+      throw createReadIOException("Failed to read from serial port");
+    }
+    return result;
+  }
+
+  /**
+   * Check whether the last {@link #readFully(byte[], int, int, int)}, {@link #readOne(int)},
+   * {@link #readUntilPattern(byte[], byte[], int, int, int)} or
+   * {@link #readLengthPrefixedFrame} returned early because its deadline elapsed.
+   *
+   * <p>The flag is reset at the start of every readFully, readOne, readUntilPattern and
+   * readLengthPrefixedFrame call, so it always describes the most recent one.
+   *
+   * @return true if the last readFully, readUntilPattern or readLengthPrefixedFrame returned
+   *         partial data, or the last readOne returned no byte, due to the timeout
+   * @throws IOException if the port is not open
+   */
+  public boolean lastReadTimedOut() throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    return NativeSerialPort.lastReadTimedOut(handle);
+  }
+
+  /**
+   * Flush the output buffer, ensuring all data is written.
+   *
+   * <p>In manual RS-485 mode with {@linkplain #setAutoFlush(boolean) auto-flush} off, this also
+   * waits the post-send delay and releases the direction pin left asserted by the last write.
+   *
+   * @throws IOException if the flush fails or the port is not open
+   */
+  public void flush() throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+
+    if (!NativeSerialPort.flush(handle)) {
+      throw createIOException("Failed to flush serial port");
+    }
+  }
+
+  /**
+   * Set the timeout for read operations.
+   *
+   * <p><b>Note:</b> On Linux, timeouts are rounded up to the nearest 100ms due to
+   * POSIX termios limitations. For example, 50ms becomes 100ms, and 150ms becomes 200ms.
+   *
+   * @param timeoutMs the timeout in milliseconds
+   * @throws IOException if the operation fails or the port is not open
+   * @see <a href="#platform-notes">Platform Notes</a>
+   */
+  public void setTimeout(int timeoutMs) throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+
+    if (!NativeSerialPort.setTimeout(handle, timeoutMs)) {
+      throw createIOException("Failed to set timeout");
+    }
+  }
+
+  /**
+   * Clear the input buffer.
+   *
+   * @throws IOException if the operation fails or the port is not open
+   */
+  public void clearInput() throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+
+    if (!NativeSerialPort.clearInput(handle)) {
+      throw createIOException("Failed to clear input buffer");
+    }
+  }
+
+  /**
+   * Clear the output buffer.
+   *
+   * @throws IOException if the operation fails or the port is not open
+   */
+  public void clearOutput() throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+
+    if (!NativeSerialPort.clearOutput(handle)) {
+      throw createIOException("Failed to clear output buffer");
+    }
+  }
+
+  /**
+   * Clear both input and output buffers.
+   *
+   * @throws IOException if the operation fails or the port is not open
+   */
+  public void clearAll() throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+
+    if (!NativeSerialPort.clearAll(handle)) {
+      throw createIOException("Failed to clear buffers");
+    }
+  }
+
+  /**
+   * Clear the input buffer and report how many bytes were discarded.
+   *
+   * <p>Received data that nobody read is a common sign that a protocol lost synchronization: a
+   * large count means the read loop fell behind or a reply arrived after its request timed out.
+   * The count is sampled just before clearing, so bytes arriving in between are discarded
+   * without being counted.
+   *
+   * @return the number of bytes discarded
+   * @throws IOException if the operation fails or the port is not open
+   * @see #clearInput()
+   */
+  public long clearInputCounted() throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+
+    long discarded = NativeSerialPort.clearInputCounted(handle);
+    if (discarded < 0) {
+      throw createIOException("Failed to clear input buffer");
+    }
+    return discarded;
+  }
+
+  /**
+   * Clear the output buffer and report how many bytes were discarded without being sent.
+   *
+   * <p>The count is sampled just before clearing, so it may include a few bytes that were
+   * transmitted in between.
+   *
+   * @return the number of bytes discarded
+   * @throws IOException if the operation fails or the port is not open
+   * @see #clearOutput()
+   */
+  public long clearOutputCounted() throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+
+    long discarded = NativeSerialPort.clearOutputCounted(handle);
+    if (discarded < 0) {
+      throw createIOException("Failed to clear output buffer");
+    }
+    return discarded;
+  }
+
+  /**
+   * Clear both input and output buffers and report how many bytes were discarded in total.
+   *
+   * @return the number of bytes discarded from both buffers
+   * @throws IOException if the operation fails or the port is not open
+   * @see #clearInputCounted()
+   * @see #clearOutputCounted()
+   */
+  public long clearAllCounted() throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+
+    long discarded = NativeSerialPort.clearAllCounted(handle);
+    if (discarded < 0) {
+      throw createIOException("Failed to clear buffers");
+    }
+    return discarded;
+  }
+
+  /**
+   * Get an InputStream for reading from the serial port.
+   * <p>
+   * The same InputStream instance is returned on subsequent calls. The stream's read methods
+   * will return -1 when no data is available within the configured timeout.
+   *
+   * @return an InputStream
+   */
+  public InputStream getInputStream() {
+    if (cachedInputStream == null) {
+      cachedInputStream = new SerialInputStream(this);
+    }
+    return cachedInputStream;
+  }
+
+  /**
+   * Get an OutputStream for writing to the serial port.
+   * <p>
+   * The same OutputStream instance is returned on subsequent calls.
+   *
+   * @return an OutputStream
+   */
+  public OutputStream getOutputStream() {
+    if (cachedOutputStream == null) {
+      cachedOutputStream = new SerialOutputStream(this);
+    }
+    return cachedOutputStream;
+  }
+
+  /**
+   * Check if the port is open.
+   *
+   * @return true if the port is open
+   */
+  public boolean isOpen() {
+    return isOpen;
+  }
+
+  /**
+   * Check if the port is open and the underlying device is still usable.
+   *
+   * <p>Unlike {@link #isOpen()}, this asks the operating system: it returns false after the
+   * device was unplugged or its file descriptor was closed, even though the port has not been
+   * closed from Java yet. This makes it a safe pre-flight check for a reconnection loop, which
+   * should then {@link #close()} and reopen the port.
+   *
+   * @return true if the port is open and the device is still connected
+   */
+  public boolean isValid() {
+    return isOpen && NativeSerialPort.isValid(handle);
+  }
+
+  /**
+   * Reopen the port automatically after its device disconnects (Linux only).
+   *
+   * <p>A native thread checks the port every {@code probeIntervalMs} milliseconds, the same way
+   * as {@link #isValid()}. After a disconnect, such as a USB adapter being unplugged, it tries to
+   * open the device again at every probe, under the name the port was opened with, so a stable
+   * name like {@code /dev/serial/by-id/...} is recommended. The reopened device gets the line
+   * settings, exclusive access and kernel RS-485 configuration last seen while connected. This
+   * object keeps working afterwards without being closed and opened again; poll
+   * {@link #getConnectionState()} to find out when it is usable.
+   *
+   * <p>The disconnected device is released as soon as the disconnect is noticed, so that a
+   * replugged adapter gets its old name (such as {@code /dev/ttyUSB0}) back. Operations during
+   * a disconnect fail as usual, and data in flight is lost. Modem control line levels are not
+   * restored. Calling this again replaces the running thread, and {@link #close()} stops it.
+   *
+   * @param probeIntervalMs how often to check the connection, in milliseconds
+   * @throws IllegalArgumentException if probeIntervalMs is not positive
+   * @throws IOException              if the operation fails, the port is not open, or the
+   *                                  platform is not Linux
+   */
+  public void enableAutoReconnect(int probeIntervalMs) throws IOException {
+    if (probeIntervalMs <= 0) {
+      throw new IllegalArgumentException("probeIntervalMs must be positive: " + probeIntervalMs);
+    }
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    if (!NativeSerialPort.enableAutoReconnect(handle, probeIntervalMs)) {
+      throw createIOException("Failed to enable auto reconnect");
+    }
+  }
+
+  /**
+   * Stop reopening the port automatically after a disconnect (Linux only).
+   *
+   * <p>Waits for the reconnect thread to exit, so no reconnect is in progress when this returns.
+   * Does nothing if automatic reconnect is not enabled.
+   *
+   * @throws IOException if the operation fails, the port is not open, or the platform is not
+   *                     Linux
+   */
+  public void disableAutoReconnect() throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    if (!NativeSerialPort.disableAutoReconnect(handle)) {
+      throw createIOException("Failed to disable auto reconnect");
+    }
+  }
+
+  /**
+   * Get the connection state of the port.
+   *
+   * <p>With {@link #enableAutoReconnect(int) automatic reconnect}, this reflects the reconnect
+   * thread's last probe. Otherwise, a disconnected port is reported as
+   * {@link ConnectionState#FAILED}.
+   *
+   * @return the connection state
+   * @throws IOException if the operation fails or the port is not open
+   */
+  public ConnectionState getConnectionState() throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    int result = NativeSerialPort.getConnectionState(handle);
+    for (ConnectionState state : ConnectionState.values()) {
+      if (state.getValue() == result) {
+        return state;
+      }
+    }
+    throw createIOException("Failed to get connection state");
+  }
+
+  /**
+   * Get the port name.
+   *
+   * <p>When the port was configured with {@link Builder#usbSerialNumber(String)}, this is
+   * the device path resolved on the last open, or null if the port has not been opened yet;
+   * {@link #getSelector()} describes such a port before it is opened.
+   *
+   * @return the port name
+   */
+  public String getPortName() {
+    return usbSerialNumber != null ? resolvedPortName : portName;
+  }
+
+  /**
+   * Get what selects the device: the USB serial number if one was configured, otherwise the
+   * port name. Unlike {@link #getPortName()}, this is known before the port is opened.
+   *
+   * @return the USB serial number or the port name, never null
+   */
+  public String getSelector() {
+    return usbSerialNumber != null ? usbSerialNumber : portName;
+  }
+
+  /**
+   * Get the USB serial number used to select the device.
+   *
+   * @return the USB serial number, or null if the port is selected by name
+   */
+  public String getUsbSerialNumber() {
+    return usbSerialNumber;
+  }
+
+  /**
+   * Get the baud rate.
+   *
+   * <p>If the port was opened at a nearby standard rate because the requested one could not be
+   * set (see {@link Builder#baudTolerancePercent(int)}), this is the rate it was opened at.
+   *
+   * @return the baud rate
+   */
+  public int getBaudRate() {
+    return baudRate;
+  }
+
+  /**
+   * Get the data bits.
+   *
+   * @return the data bits
+   */
+  public DataBits getDataBits() {
+    return dataBits;
+  }
+
+  /**
+   * Get the stop bits.
+   *
+   * @return the stop bits
+   */
+  public StopBits getStopBits() {
+    return stopBits;
+  }
+
+  /**
+   * Get the parity.
+   *
+   * @return the parity
+   */
+  public Parity getParity() {
+    return parity;
+  }
+
+  /**
+   * Get the flow control mode.
+   *
+   * @return the flow control mode
+   */
+  public FlowControl getFlowControl() {
+    return flowControl;
+  }
+
+  /**
+   * Check if DTR is asserted when opening the port.
+   *
+   * @return true if DTR is asserted on open (default), false if suppressed
+   */
+  public boolean isDtrOnOpen() {
+    return dtrOnOpen;
+  }
+
+  /**
+   * Check if the port is opened without the library driving DTR/RTS, and without the DTR pulse
+   * on close.
+   *
+   * @return true if quiet open is enabled
+   */
+  public boolean isQuietOpen() {
+    return quietOpen;
+  }
+
+  /**
+   * Get the settle delay applied after opening the port.
+   *
+   * @return the settle delay in milliseconds
+   */
+  public int getSettleMs() {
+    return settleMs;
+  }
+
+  /**
+   * Get how far off a standard baud rate used in place of a rejected one may be.
+   *
+   * @return the tolerance in percent, 0 if the exact baud rate is required
+   */
+  public int getBaudTolerancePercent() {
+    return baudTolerancePercent;
+  }
+
+  /**
+   * Get the access mode the port is opened with.
+   *
+   * @return the access mode
+   */
+  public AccessMode getAccessMode() {
+    return accessMode;
+  }
+
+  /**
+   * Get the RS-485 configuration.
+   *
+   * @return the RS-485 configuration, or null if not set
+   */
+  public Rs485Config getRs485Config() {
+    return rs485Config;
+  }
+
+  /**
+   * Check if kernel RS-485 mode is active.
+   *
+   * <p>This returns true only on Linux when the hardware supports kernel-level RS-485
+   * and it was successfully enabled. When kernel mode is active, the hardware handles
+   * RTS timing automatically for optimal performance.
+   *
+   * @return true if kernel RS-485 mode is active
+   * @throws IOException if the port is not open
+   */
+  public boolean isKernelRs485Active() throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    return NativeSerialPort.isKernelRs485Active(handle);
+  }
+
+  /**
+   * Get how RS-485 direction control is actually performed on this port.
+   *
+   * <p>RS-485 control prefers kernel mode but silently falls back to manual control of the
+   * direction pin; this tells whether that happened and why, which helps diagnose timing issues
+   * seen on the bus.
+   *
+   * @return the effective RS-485 mode, {@link Rs485ActualMode#NONE} if RS-485 is not configured
+   * @throws IOException if the mode cannot be read, or the port is not open
+   * @see #isKernelRs485Active()
+   */
+  public Rs485ActualMode getRs485ActualMode() throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    int result = NativeSerialPort.getRs485ActualMode(handle);
+    for (Rs485ActualMode mode : Rs485ActualMode.values()) {
+      if (mode.getValue() == result) {
+        return mode;
+      }
+    }
+    throw createIOException("Failed to get RS-485 actual mode");
+  }
+
+  /**
+   * Enable or disable listen-only mode for passively monitoring a bus.
+   *
+   * <p>While enabled, every write fails with an {@link IOException}, so the adapter never drives
+   * the line by accident. With RS-485 control configured, the direction pin is also forced to
+   * the receive level: in manual mode immediately, and in Linux kernel mode by reconfiguring the
+   * driver without {@code SER_RS485_RTS_ON_SEND}.
+   *
+   * @param enabled true to enable listen-only mode, false to allow writes again
+   * @throws IOException if the operation fails or the port is not open
+   */
+  public void setListenOnly(boolean enabled) throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    if (!NativeSerialPort.setListenOnly(handle, enabled)) {
+      throw createIOException("Failed to set listen-only mode");
+    }
+  }
+
+  /**
+   * Keep the RS-485 direction pin asserted between writes, for bursts of frames to one device.
+   *
+   * <p>With manual RS-485 control, every write normally asserts the direction pin, waits the
+   * delay before sending, writes, drains the output, waits the delay after sending and releases
+   * the pin again. While the pin is held, the first write asserts it as usual but leaves it
+   * asserted, and later writes go out back to back without the toggling, draining and delays.
+   * {@code holdTransmitEnable(false)} ends the burst: it drains the output, waits the delay after
+   * sending and releases the pin. Changing the RS-485 configuration, enabling listen-only mode
+   * and closing the port also release it.
+   *
+   * <p>Holding is rejected without RS-485 control, and in Linux kernel RS-485 mode, where the
+   * driver drives RTS itself.
+   *
+   * @param hold true to keep the pin asserted after the next write, false to release it
+   * @throws IOException if the pin cannot be held or released, or the port is not open
+   * @see #isKernelRs485Active()
+   */
+  public void holdTransmitEnable(boolean hold) throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    if (!NativeSerialPort.holdTransmitEnable(handle, hold)) {
+      throw createIOException(
+          hold ? "Failed to hold transmit enable" : "Failed to release transmit enable");
+    }
+  }
+
+  /**
+   * Release the RS-485 direction pin if it is left asserted with no write for a timeout, so a
+   * hung application cannot leave a shared bus driven.
+   *
+   * <p>A native watchdog thread times how long the pin stays asserted between writes, such as
+   * while it is {@linkplain #holdTransmitEnable(boolean) held} or waits for a flush with
+   * auto-flush off. The timer is stopped while a write is in progress, so a long write is
+   * never cut short, and restarts after each write. If it runs out, the direction pin is driven
+   * to the receive level of its polarity and a warning such as
+   * {@code "failsafe: no write within 500 ms, RS-485 direction pin released"} is recorded (see
+   * {@link #getLastWarning()}). The next write asserts the pin again as usual. A port whose pin
+   * is released after each write never trips, and an idle port trips only once.
+   *
+   * <p>The failsafe requires manual RS-485 control; in Linux kernel RS-485 mode the driver
+   * releases RTS after each frame itself. Changing the RS-485 configuration and closing the
+   * port stop the watchdog.
+   *
+   * @param timeoutMs the longest time the pin may stay asserted without a write, or 0 to
+   *                  disable (default)
+   * @throws IllegalArgumentException if timeoutMs is negative
+   * @throws IOException              if RS-485 control is not configured, is in kernel mode,
+   *                                  the watchdog cannot be started, or the port is not open
+   */
+  public void setFailsafe(int timeoutMs) throws IOException {
+    if (timeoutMs < 0) {
+      throw new IllegalArgumentException("timeoutMs must not be negative");
+    }
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    if (!NativeSerialPort.setFailsafe(handle, timeoutMs)) {
+      throw createIOException("Failed to set failsafe");
+    }
+  }
+
+  /**
+   * Get warnings about kernel RS-485 settings that the driver did not apply as requested.
+   *
+   * <p>Some Linux drivers clamp the RTS delays to a maximum (100 ms in the generic serial core)
+   * or ignore them and report zero. When kernel RS-485 mode is enabled, the applied settings are
+   * read back and compared against the requested delays (rounded down to whole milliseconds,
+   * which is the kernel's resolution). A mismatch means the requested turnaround timing is not
+   * honored, which typically shows up as bus collisions.
+   *
+   * @return the warnings from the last time kernel RS-485 mode was enabled, or an empty list
+   *         if the settings were applied as requested or kernel mode is not active
+   * @throws IOException if the port is not open
+   */
+  public List<String> getRs485Warnings() throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    String warnings = NativeSerialPort.getRs485Warnings(handle);
+    if (warnings == null) {
+      return Collections.emptyList();
+    }
+    return Collections.unmodifiableList(Arrays.asList(warnings.split("\n")));
+  }
+
+  /**
+   * Get the line settings that the driver applied differently than requested on open.
+   *
+   * <p>Some drivers quietly coerce settings they do not support, for example 5 data bits with
+   * 2 stop bits, or round a baud rate to the nearest one their clock can produce. After the port
+   * is opened, the baud rate, data bits, stop bits, parity and flow control are read back from
+   * the driver and compared against the configured values. Each mismatch is described as, for
+   * example, {@code "stop bits: requested 2, driver applied 1"}. Settings that the platform
+   * cannot read back are not checked.
+   *
+   * <p>The comparison is made once, on open; settings changed later are not checked again.
+   *
+   * @return the discrepancies found on open, or an empty list if every setting was applied
+   *         as requested
+   * @throws IOException if the port is not open
+   */
+  public List<String> getConfigDiscrepancies() throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    String discrepancies = NativeSerialPort.getConfigDiscrepancies(handle);
+    if (discrepancies == null) {
+      return Collections.emptyList();
+    }
+    return Collections.unmodifiableList(Arrays.asList(discrepancies.split("\n")));
+  }
+
+  /**
+   * Set RS-485 timing delays (Linux kernel mode only).
+   *
+   * <p>These delays control the timing of RTS signal transitions relative to data
+   * transmission. They are only effective when kernel RS-485 mode is active.
+   *
+   * @param delayBeforeSendMicros delay in microseconds before sending (RTS assertion to first data bit)
+   * @param delayAfterSendMicros  delay in microseconds after sending (last data bit to RTS de-assertion)
+   * @throws IOException if the operation fails or the port is not open
+   */
+  public void setRs485Delays(int delayBeforeSendMicros, int delayAfterSendMicros) throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    if (!NativeSerialPort.setRs485Delays(handle, delayBeforeSendMicros, delayAfterSendMicros)) {
+      throw createIOException("Failed to set RS-485 delays");
+    }
+  }
+
+  /**
+   * Set RS-485 configuration at runtime.
+   *
+   * <p>This allows changing all RS-485 parameters on an open port, including
+   * RTS polarity, RX during TX, termination, and timing delays.
+   *
+   * <p>Note: This is only fully supported on Linux with kernel RS-485 support.
+   * On other platforms, only the delay values may be effective.
+   *
+   * @param config the RS-485 configuration to apply
+   * @throws IOException if the operation fails or the port is not open
+   */
+  public void setRs485Config(Rs485Config config) throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    if (config == null) {
+      throw new IllegalArgumentException("Config cannot be null");
+    }
+    NativeRs485Settings rs485 = new NativeRs485Settings(config);
+    if (!NativeSerialPort.setRs485Config(
+        handle,
+        rs485.enabled,
+        rs485.controlPin,
+        rs485.rtsActiveHigh,
+        rs485.rxDuringTx,
+        rs485.terminationEnabled,
+        rs485.delayBeforeMicros,
+        rs485.delayAfterMicros)) {
+      throw createIOException("Failed to set RS-485 config");
+    }
+  }
+
+  /**
+   * Set the RTS (Request To Send) pin state.
+   *
+   * <p>This is useful for manual RS-485 control or other applications that need
+   * direct control of the RTS pin.
+   *
+   * @param level true to set RTS high, false to set RTS low
+   * @throws IOException if the operation fails or the port is not open
+   */
+  public void setRTS(boolean level) throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    if (!NativeSerialPort.setRTS(handle, level)) {
+      throw createIOException("Failed to set RTS");
+    }
+  }
+
+  /**
+   * Set the DTR (Data Terminal Ready) pin state.
+   *
+   * <p>This is useful for manual RS-485 control when DTR is used instead of RTS,
+   * or for other applications that need direct control of the DTR pin.
+   *
+   * @param level true to set DTR high, false to set DTR low
+   * @throws IOException if the operation fails or the port is not open
+   */
+  public void setDTR(boolean level) throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    if (!NativeSerialPort.setDTR(handle, level)) {
+      throw createIOException("Failed to set DTR");
+    }
+  }
+
+  /**
+   * Get the level this library last set on the RTS pin.
+   *
+   * <p>The level is tracked whenever RTS is changed through this port, including by manual
+   * RS-485 direction control and pulses. This helps to confirm that RS-485 direction logic
+   * actually toggled the pin.
+   *
+   * <p>On Linux, the level is also read back from the driver; if the two differ, the
+   * tracked level is still returned and a mismatch warning is recorded for
+   * {@link #getLastWarning()}. If RTS was never set since the port was opened, the
+   * level read from the driver is returned. The driver's level is also returned, without a
+   * mismatch check, while kernel RS-485 mode is active, because the driver then toggles RTS
+   * itself. Elsewhere, this fails until RTS has been set.
+   *
+   * @return true if RTS is high, false if it is low
+   * @throws IOException if the level is not known or the port is not open
+   */
+  public boolean getRTS() throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    int level = NativeSerialPort.getRTS(handle);
+    if (level < 0) {
+      throw createIOException("Failed to get RTS");
+    }
+    return level != 0;
+  }
+
+  /**
+   * Get the level this library last set on the DTR pin.
+   *
+   * <p>The level is tracked whenever DTR is changed through this port, including by manual
+   * RS-485 direction control, pulses, DTR/DSR flow control and suppressing DTR on open. This
+   * helps to confirm that RS-485 direction logic actually toggled the pin.
+   *
+   * <p>On Linux, the level is also read back from the driver; if the two differ, the
+   * tracked level is still returned and a mismatch warning is recorded for
+   * {@link #getLastWarning()}. If DTR was never set since the port was opened, the
+   * level read from the driver is returned. Elsewhere, this fails until DTR has been set.
+   *
+   * @return true if DTR is high, false if it is low
+   * @throws IOException if the level is not known or the port is not open
+   */
+  public boolean getDTR() throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    int level = NativeSerialPort.getDTR(handle);
+    if (level < 0) {
+      throw createIOException("Failed to get DTR");
+    }
+    return level != 0;
+  }
+
+  /**
+   * Generate a timed pulse on RTS or DTR.
+   *
+   * <p>The line is set to {@code assertLevel}, held for {@code durationMs}, and then set to the
+   * opposite level. Both edges and the wait happen in a single native call, which gives tighter
+   * timing than bracketing {@code Thread.sleep} with two {@link #setRTS(boolean)} calls. This
+   * is typically used to reset a device or enter its bootloader, for example:
+   * <pre>{@code
+   * port.pulseLine(ControlLine.DTR, false, 100);  // hold DTR low for 100 ms, then raise it
+   * }</pre>
+   *
+   * @param line        the line to pulse
+   * @param assertLevel the level to hold during the pulse (true for high)
+   * @param durationMs  the pulse duration in milliseconds
+   * @throws NullPointerException     if line is null
+   * @throws IllegalArgumentException if durationMs is negative
+   * @throws IOException              if the operation fails or the port is not open
+   */
+  public void pulseLine(ControlLine line, boolean assertLevel, int durationMs) throws IOException {
+    if (line == null) {
+      throw new NullPointerException("line cannot be null");
+    }
+    if (durationMs < 0) {
+      throw new IllegalArgumentException("durationMs must not be negative");
+    }
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    if (!NativeSerialPort.pulseLine(handle, line.getValue(), assertLevel, durationMs)) {
+      throw createIOException("Failed to pulse " + line);
+    }
+  }
+
+  /**
+   * Report why writes on this port may not be making progress.
+   *
+   * <p>With hardware flow control, a write blocks while the peer holds CTS deasserted, which
+   * from the outside looks the same as a dead device. Call this when a write times out (or from
+   * another thread while it is blocked) to tell the two apart. Write timeouts also include this
+   * diagnosis in the exception message.
+   *
+   * @return the current stall reason
+   * @throws IOException if the operation fails or the port is not open
+   */
+  public WriteStallReason getWriteStallReason() throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    int result = NativeSerialPort.getWriteStallReason(handle);
+    for (WriteStallReason reason : WriteStallReason.values()) {
+      if (reason.getValue() == result) {
+        return reason;
+      }
+    }
+    throw createIOException("Failed to get write stall reason");
+  }
+
+  /**
+   * Send an XON character to ask the peer to resume transmitting (Linux only).
+   *
+   * <p>The character is transmitted by the driver itself, ahead of any output still queued,
+   * and is sent even while our own output is paused by an XOFF from the peer. This is the way
+   * to resume a sender that is stuck waiting for XON, for example after the application
+   * discarded input without the terminal layer noticing. Unlike writing the byte
+   * {@code 0x11} with {@link #write(byte[])}, it cannot end up behind data in the output
+   * queue.
+   *
+   * @throws IOException if the operation fails, the port is not open, or the platform is not Linux
+   * @see #sendXoff()
+   */
+  public void sendXon() throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    if (!NativeSerialPort.sendXon(handle)) {
+      throw createIOException("Failed to send XON");
+    }
+  }
+
+  /**
+   * Send an XOFF character to ask the peer to pause transmitting (Linux only).
+   *
+   * <p>Like {@link #sendXon()}, the character is transmitted by the driver ahead of any
+   * queued output. The peer stays paused until an XON is sent, either with
+   * {@link #sendXon()} or automatically by the terminal layer if input flow control is
+   * enabled and the input buffer drains.
+   *
+   * @throws IOException if the operation fails, the port is not open, or the platform is not Linux
+   */
+  public void sendXoff() throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    if (!NativeSerialPort.sendXoff(handle)) {
+      throw createIOException("Failed to send XOFF");
+    }
+  }
+
+  /**
+   * Check whether the last read found the port closed by its peer.
+   *
+   * <p>When the other side of a pseudo-terminal or a socket-backed virtual port goes away, a read
+   * returns end of file instead of data. Reads report this by throwing {@link EOFException}
+   * rather than the {@link IOException} used for other failures, and this flag stays set until
+   * the next read, so callers can tell a vanished peer from a read that merely timed out and
+   * reconnect instead of retrying. A read that times out leaves this false.
+   *
+   * @return true if the last read hit end of file
+   * @throws IOException if the port is not open
+   */
+  public boolean isEof() throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    return NativeSerialPort.isEof(handle);
+  }
+
+  /**
+   * Check whether transmission is currently held back by software flow control (Linux only).
+   *
+   * <p>With {@link FlowControl#SOFTWARE} flow control, the peer pauses our output by sending
+   * XOFF and resumes it with XON. The terminal layer handles these characters itself and does
+   * not expose whether output is paused, so this is detected indirectly: the output queue is
+   * sampled twice, about four character times apart (at least 10 ms and at most 250 ms), and
+   * output is reported as stopped if bytes are pending but none of them left in between. The
+   * call blocks for that sampling time. If nothing is pending, output is reported as stopped
+   * when the port does not accept writes, which is how drivers without an output queue, such
+   * as pseudo-terminals, hold back a writer.
+   *
+   * <p>Limitations: with software flow control off, this always returns false. On a real UART
+   * with nothing pending, a received XOFF cannot be detected at all: the driver keeps accepting
+   * writes into its queue while stopped, so this returns false until something has been written
+   * and fails to drain. Neither the modem status, the line status nor the interrupt counters
+   * reflect the XOFF state. USB serial adapters that hand data to the device in large blocks
+   * may report an unchanged queue while a block is in flight, which at low baud rates can
+   * briefly look like a pause. On a pseudo-terminal, a peer that simply stops reading also makes
+   * the port refuse writes and is reported as stopped. Output suspended locally with
+   * {@code tcflow()} is indistinguishable from an XOFF.
+   *
+   * @return true if pending output is not draining under software flow control
+   * @throws IOException if the operation fails, the port is not open, or the platform is not Linux
+   */
+  public boolean isOutputStopped() throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    int result = NativeSerialPort.isOutputStopped(handle);
+    if (result < 0) {
+      throw createIOException("Failed to check whether output is stopped");
+    }
+    return result == 1;
+  }
+
+  /**
+   * Get the number of RI (Ring Indicator) edges since the port was opened (Linux only).
+   *
+   * <p>Modems and some sensors pulse RI to signal events. The driver counts these pulses in its
+   * interrupt handler, so unlike polling {@link #getPortStatus()}, no pulse is missed however
+   * short it is or however rarely this is called. Take the difference between two calls to count
+   * the pulses in between. Linux counts an edge when RI is released, so a complete pulse counts
+   * once.
+   *
+   * <p>This needs a driver that maintains interrupt counters (TIOCGICOUNT), as most UART drivers
+   * and common USB adapters (FTDI, CP210x, PL2303) do; pseudo terminals do not.
+   *
+   * @return the number of RI edges since open
+   * @throws IOException if the driver does not count RI edges, the operation fails, the port is
+   *                     not open, or the platform is not Linux
+   */
+  public long getRingCount() throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    long result = NativeSerialPort.getRingCount(handle);
+    if (result < 0) {
+      throw createIOException("Failed to get ring count");
+    }
+    return result;
+  }
+
+  /**
+   * Read the CTS (Clear To Send) input pin state.
+   *
+   * @return true if CTS is asserted
+   * @throws IOException if the operation fails or the port is not open
+   */
+  public boolean readCTS() throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    int result = NativeSerialPort.readCTS(handle);
+    if (result < 0) {
+      throw createIOException("Failed to read CTS");
+    }
+    return result == 1;
+  }
+
+  /**
+   * Check that the RTS/CTS lines needed for hardware flow control are actually wired.
+   *
+   * <p>Hardware flow control silently has no effect when a cable lacks the RTS/CTS lines,
+   * which typically shows up later as buffer overruns. This diagnostic toggles RTS a few times
+   * and checks that CTS follows each level. It requires either:
+   * <ul>
+   *   <li>a loopback with this port's RTS connected to its own CTS, or</li>
+   *   <li>a cooperating peer whose RTS output mirrors this port's RTS (a null-modem cable
+   *       connects our RTS to its CTS and its RTS to our CTS)</li>
+   * </ul>
+   *
+   * <p>Run it before enabling traffic, since RTS is driven directly while it runs. RTS is left
+   * asserted afterwards.
+   *
+   * @return true if CTS tracked every RTS level, false if the lines do not appear to be wired
+   * @throws IOException if the lines cannot be controlled or read, or the port is not open
+   */
+  public boolean verifyHardwareFlowControl() throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    int result = NativeSerialPort.verifyHardwareFlowControl(handle);
+    if (result < 0) {
+      throw createIOException("Failed to verify hardware flow control");
+    }
+    return result == 1;
   }
 
   /**
-   * Write a string followed by a newline to the serial port using UTF-8 encoding.
+   * Set how writes react when the port cannot accept more data right now.
    *
-   * @param text the string to write (newline will be appended)
-   * @return the number of bytes written
-   * @throws IOException if the write fails or the port is not open
+   * <p>This matters for flow-controlled ports, where the receiver may pause transmission.
+   * The default is {@link WriteBlockingPolicy#BLOCK}, which retries until the timeout expires.
+   *
+   * @param policy the write blocking policy
+   * @throws IOException if the operation fails or the port is not open
+   * @see WriteBlockingPolicy
    */
-  public int writeLine(String text) throws IOException {
-    return writeLine(text, java.nio.charset.StandardCharsets.UTF_8);
+  public void setWriteBlockingPolicy(WriteBlockingPolicy policy) throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    if (policy == null) {
+      throw new IllegalArgumentException("Policy cannot be null");
+    }
+    if (!NativeSerialPort.setWriteBlockingPolicy(handle, policy.getValue())) {
+      throw createIOException("Failed to set write blocking policy");
+    }
   }
 
   /**
-   * Read data from the serial port.
+   * Pace writes for receivers with small buffers and no flow control.
    *
-   * @param buffer the buffer to read into
-   * @return the number of bytes read, or 0 if no data is available
-   * @throws IOException if the read fails or the port is not open
+   * <p>Some microcontrollers drop bytes when a long frame arrives faster than they can process
+   * it. With pacing enabled, each write is split into chunks of at most {@code chunkBytes},
+   * and after each chunk has been transmitted the port pauses for {@code gapMicros} before
+   * sending the next one. A write therefore takes longer but still returns only when the whole
+   * buffer has been handed over (or a chunk is only partially accepted). With RS-485 control,
+   * the direction pin stays asserted across all chunks of one write, so a frame is never split
+   * into separate transmissions on the bus.
+   *
+   * @param chunkBytes the maximum bytes per chunk, or 0 to disable pacing (default)
+   * @param gapMicros  the pause between chunks in microseconds
+   * @throws IllegalArgumentException if chunkBytes or gapMicros is negative
+   * @throws IOException              if the operation fails or the port is not open
    */
-  public int read(byte[] buffer) throws IOException {
-    return read(buffer, 0, buffer.length);
+  public void setWritePacing(int chunkBytes, int gapMicros) throws IOException {
+    if (chunkBytes < 0 || gapMicros < 0) {
+      throw new IllegalArgumentException("chunkBytes and gapMicros must not be negative");
+    }
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    if (!NativeSerialPort.setWritePacing(handle, chunkBytes, gapMicros)) {
+      throw createIOException("Failed to set write pacing");
+    }
   }
 
   /**
-   * Read data from the serial port.
+   * Limit the sustained rate at which data is written, to emulate a slow link or to protect a
+   * device that overflows when fed at full line rate.
    *
-   * @param buffer the buffer to read into
-   * @param offset the offset in the buffer
-   * @param length the maximum number of bytes to read
-   * @return the number of bytes read, or 0 if no data is available
-   * @throws IOException               if the read fails or the port is not open
-   * @throws IndexOutOfBoundsException if offset or length are invalid
-   * @throws NullPointerException      if buffer is null
+   * <p>The limit is enforced with a token bucket. Each write is sent as a whole and then paid
+   * for: a write that exceeds the available allowance still goes out at line rate, and the next
+   * write waits until the sustained rate is back within the limit. Up to a tenth of a second's
+   * worth of data can be sent without delay after the port has been idle. The wait happens
+   * before the RS-485 direction pin is asserted, so frames are throttled between each other,
+   * never within one. To spread a single large write out as well, combine this with
+   * {@link #setWritePacing(int, int)}. Writes with a deadline wait at most until the deadline.
+   *
+   * @param bytesPerSec the maximum sustained rate in bytes per second, or 0 for unlimited
+   *                    (default)
+   * @throws IllegalArgumentException if bytesPerSec is negative
+   * @throws IOException              if the operation fails or the port is not open
    */
-  public int read(byte[] buffer, int offset, int length) throws IOException {
-    if (buffer == null) {
-      throw new NullPointerException("buffer cannot be null");
-    }
-    if (offset < 0 || length < 0 || offset + length > buffer.length) {
-      throw new IndexOutOfBoundsException(
-          "offset=" + offset + ", length=" + length + ", buffer.length=" + buffer.length);
+  public void setMaxThroughput(int bytesPerSec) throws IOException {
+    if (bytesPerSec < 0) {
+      throw new IllegalArgumentException("bytesPerSec must not be negative");
     }
     if (!isOpen) {
       throw new IOException("Port is not open");
     }
-
-    int result = NativeSerialPort.read(handle, buffer, offset, length);
-    if (result < 0) {
-      throw createIOException("Failed to read from serial port");
+    if (!NativeSerialPort.setMaxThroughput(handle, bytesPerSec)) {
+      throw createIOException("Failed to set max throughput");
     }
-
-    return result;
   }
 
   /**
-   * Get the number of bytes available to read.
+   * Hold the line idle for a number of bit periods before each write, for protocols that need
+   * an idle (mark) preamble to wake up or synchronize receivers before a frame.
    *
-   * @return the number of bytes available
-   * @throws IOException if the port is not open
+   * <p>The time is derived from the current baud rate and timed with microsecond precision,
+   * counted from when the previous frame has left the transmitter. With manual RS-485 control
+   * it is added after the direction pin is asserted and after the delay before sending, so the
+   * driver holds the bus at idle for the whole preamble. In Linux kernel RS-485 mode the
+   * driver only enables the transmitter once data is sent, so the preamble is added to the
+   * kernel's delay before sending instead, rounded up to the whole milliseconds it counts in.
+   *
+   * <p>If a write with a deadline has to discard output of the previous frame that has not left
+   * by then, the next write's returned count is reduced by those bytes, so that the counts of
+   * all writes add up to the bytes actually sent.
+   *
+   * @param bits the preamble length in bit periods, or 0 for none (default)
+   * @throws IllegalArgumentException if bits is negative
+   * @throws IOException              if the operation fails or the port is not open
    */
-  public int available() throws IOException {
+  public void setPreambleIdleBits(int bits) throws IOException {
+    if (bits < 0) {
+      throw new IllegalArgumentException("bits must not be negative");
+    }
     if (!isOpen) {
       throw new IOException("Port is not open");
     }
-
-    return NativeSerialPort.bytesAvailable(handle);
+    if (!NativeSerialPort.setPreambleIdleBits(handle, bits)) {
+      throw createIOException("Failed to set preamble idle bits");
+    }
   }
 
   /**
-   * Read exactly the specified number of bytes, blocking until all bytes are received.
+   * Reverse the bit order of each byte written and read, for legacy protocols that expect
+   * pre-reversed bytes (for example {@code 0x01} sent as {@code 0x80}).
    *
-   * <p>This method will repeatedly call {@link #read(byte[], int, int)} until exactly
-   * {@code length} bytes have been read. If a timeout occurs before all bytes are received,
-   * an IOException is thrown.
+   * <p>This is a software transformation of the data, applied with a lookup table; it does not
+   * change how the UART puts bits on the wire, which is always least significant bit first.
+   * Written data is reversed in a copy, so the caller's array is left unchanged, and the bytes
+   * returned by reads and by {@link #nextFrame(byte[], int)} are reversed back. Bytes already
+   * buffered by an earlier read are not reversed a second time. DMX512 frames are sent as
+   * given, and a {@linkplain #startCapture(String) traffic capture} records the bytes as they
+   * are on the wire.
    *
-   * @param buffer the buffer to read into
-   * @param offset the offset in the buffer
-   * @param length the exact number of bytes to read
-   * @return the number of bytes read (always equals length on success)
-   * @throws IOException               if a timeout occurs or the port is not open
-   * @throws IndexOutOfBoundsException if offset or length are invalid
-   * @throws NullPointerException      if buffer is null
+   * @param enabled true to reverse the bits of each byte, false to send and receive bytes as
+   *                is (default)
+   * @throws IOException if the operation fails or the port is not open
    */
-  public int readExactly(byte[] buffer, int offset, int length) throws IOException {
-    if (buffer == null) {
-      throw new NullPointerException("buffer cannot be null");
+  public void setBitReversal(boolean enabled) throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
     }
-    if (offset < 0 || length < 0 || offset + length > buffer.length) {
-      throw new IndexOutOfBoundsException(
-          "offset=" + offset + ", length=" + length + ", buffer.length=" + buffer.length);
+    if (!NativeSerialPort.setBitReversal(handle, enabled)) {
+      throw createIOException("Failed to set bit reversal");
     }
+  }
 
-    int total = 0;
-    while (total < length) {
-      int bytesRead = read(buffer, offset + total, length - total);
-      if (bytesRead == 0) {
-        throw new IOException("Timeout waiting for data (received " + total + " of " + length + " bytes)");
-      }
-      total += bytesRead;
+  /**
+   * Set the line terminator appended by {@link #writeLine(byte[], int, int)} and the other
+   * {@code writeLine} methods.
+   *
+   * <p>Line-based text protocols typically end each command with {@code "\r\n"} or
+   * {@code "\r"}; setting the terminator once avoids concatenating it to every command. The
+   * default is a single newline. An empty terminator makes {@code writeLine} behave like
+   * {@code write}. The bytes are copied, so later changes to the array have no effect.
+   *
+   * @param terminator the bytes to append to each line
+   * @throws NullPointerException if terminator is null
+   * @throws IOException          if the operation fails or the port is not open
+   */
+  public void setWriteTerminator(byte[] terminator) throws IOException {
+    if (terminator == null) {
+      throw new NullPointerException("terminator cannot be null");
+    }
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    if (!NativeSerialPort.setWriteTerminator(handle, terminator)) {
+      throw createIOException("Failed to set write terminator");
     }
-    return total;
   }
 
   /**
-   * Read exactly the specified number of bytes into a new buffer.
+   * Retry reads that fail with a transient error.
    *
-   * <p>This is a convenience method that creates a buffer and calls
-   * {@link #readExactly(byte[], int, int)}.
+   * <p>Some USB serial drivers occasionally fail a read with an I/O error (EIO) that does not
+   * recur when the read is repeated. With retries enabled, {@link #read(byte[], int, int)} and
+   * the reads built on it repeat a read that fails this way up to {@code count} times, pausing
+   * about a millisecond before each attempt, and only report the error if every attempt fails.
+   * Errors that mean the device is gone (such as ENODEV after unplugging), timeouts, end of file
+   * and cancellation are never retried. Use {@link #getReadRetryCount()} to see how often a
+   * driver needed this.
    *
-   * @param length the exact number of bytes to read
-   * @return a new byte array containing exactly {@code length} bytes
-   * @throws IOException if a timeout occurs or the port is not open
+   * <p>Telling EIO from other errors needs the error number of the failed read, which only the
+   * Linux implementation keeps; on other platforms no read is retried.
+   *
+   * @param count the number of retries per read, or 0 to disable retrying (default)
+   * @throws IllegalArgumentException if count is negative
+   * @throws IOException              if the operation fails or the port is not open
    */
-  public byte[] readExactly(int length) throws IOException {
-    byte[] buffer = new byte[length];
-    readExactly(buffer, 0, length);
-    return buffer;
+  public void setReadRetries(int count) throws IOException {
+    if (count < 0) {
+      throw new IllegalArgumentException("count must not be negative");
+    }
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    if (!NativeSerialPort.setReadRetries(handle, count)) {
+      throw createIOException("Failed to set read retries");
+    }
   }
 
   /**
-   * Read exactly the specified number of bytes with a custom timeout.
+   * Get the number of times reads were retried after a transient error since the port was
+   * opened.
    *
-   * <p>This method temporarily changes the port timeout for this operation,
-   * then restores the original timeout when done.
+   * @return the number of retries
+   * @throws IOException if the operation fails or the port is not open
+   * @see #setReadRetries(int)
+   */
+  public long getReadRetryCount() throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    long count = NativeSerialPort.getReadRetryCount(handle);
+    if (count < 0) {
+      throw createIOException("Failed to get read retry count");
+    }
+    return count;
+  }
+
+  /**
+   * Enable or disable DTR/DSR handshaking (Linux only).
    *
-   * @param buffer  the buffer to read into
-   * @param offset  the offset in the buffer
-   * @param length  the exact number of bytes to read
-   * @param timeout the timeout in milliseconds for this operation
-   * @return the number of bytes read (always equals length on success)
-   * @throws IOException               if a timeout occurs or the port is not open
-   * @throws IndexOutOfBoundsException if offset or length are invalid
-   * @throws NullPointerException      if buffer is null
+   * <p>This is independent of the {@link FlowControl} set on the builder, so a device can use
+   * DTR/DSR for handshaking while RTS drives an RS-485 transceiver, or combine both handshakes.
+   * Linux termios has no DTR/DSR flow control, so the library implements it: while enabled, DTR
+   * is held asserted to signal that this side is ready to receive, and each write first waits for
+   * the peer to assert DSR, failing like a write timeout if it does not do so within the port
+   * timeout. The wait happens before the RS-485 direction pin is asserted, so the bus is never
+   * driven while waiting. Longer writes go out in chunks of 64 bytes, each sent completely before
+   * DSR is checked again, so at most one chunk follows the peer dropping DSR. DTR is not dropped
+   * when the input buffer fills up, so this does not throttle a peer that ignores the receive
+   * buffer size.
+   *
+   * <p>DTR cannot serve both purposes: enabling this fails if DTR is the manual RS-485 direction
+   * pin, {@link #setRs485Config(Rs485Config)} fails to make DTR the direction pin while this is
+   * enabled, and {@link #setDTR(boolean)} should not be used while it is enabled.
+   *
+   * @param enabled true to enable DTR/DSR handshaking
+   * @throws IOException if the operation fails, the port is not open, or the platform is not Linux
    */
-  public int readExactly(byte[] buffer, int offset, int length, long timeout) throws IOException {
-    int originalTimeout = this.timeoutMs;
-    try {
-      setTimeout((int) timeout);
-      return readExactly(buffer, offset, length);
+  public void setDtrFlowControl(boolean enabled) throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
     }
-    finally {
-      setTimeout(originalTimeout);
+    if (!NativeSerialPort.setDtrFlowControl(handle, enabled)) {
+      throw createIOException("Failed to set DTR flow control");
     }
   }
 
   /**
-   * Read all currently available bytes without blocking.
+   * Set the direction pin timing used with manual RS-485 control.
    *
-   * <p>This method returns immediately with whatever data is available in the
-   * receive buffer. If no data is available, it returns 0 without waiting.
+   * <p>In manual mode the library asserts the direction pin, waits {@code preMicros}, sends the
+   * data, waits until it has left the transmitter, waits {@code postMicros} and then releases the
+   * pin. Unlike the kernel RS-485 delays configured through {@link Rs485Config}, which the kernel
+   * applies in whole milliseconds, these delays keep their microsecond value, which gives a
+   * tighter turnaround with fast transceivers. The delays are implemented with a thread sleep, so
+   * the actual precision depends on the OS scheduler; a delay may be longer than requested, but
+   * never shorter. Both delays are 0 by default and have no effect in kernel RS-485 mode.
    *
-   * @param buffer the buffer to read into
-   * @return the number of bytes read, or 0 if no data is available
-   * @throws IOException if the port is not open
+   * @param preMicros  the delay between asserting the direction pin and sending, in microseconds
+   * @param postMicros the delay between the end of the data and releasing the pin, in microseconds
+   * @throws IllegalArgumentException if preMicros or postMicros is negative
+   * @throws IOException              if the operation fails or the port is not open
    */
-  public int readAvailable(byte[] buffer) throws IOException {
-    return readAvailable(buffer, 0, buffer.length);
+  public void setManualRs485Timing(int preMicros, int postMicros) throws IOException {
+    if (preMicros < 0 || postMicros < 0) {
+      throw new IllegalArgumentException("preMicros and postMicros must not be negative");
+    }
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    if (!NativeSerialPort.setManualRs485Timing(handle, preMicros, postMicros)) {
+      throw createIOException("Failed to set manual RS-485 timing");
+    }
   }
 
   /**
-   * Read all currently available bytes without blocking.
+   * Enable or disable clearing the input buffer at the start of each write.
    *
-   * <p>This method returns immediately with whatever data is available in the
-   * receive buffer, up to {@code length} bytes. If no data is available, it
-   * returns 0 without waiting.
+   * <p>In half-duplex request/response protocols, leftover bytes or the echo of a previous
+   * request can pollute the next response. When enabled, any pending input is discarded
+   * right before each write, so subsequent reads only see data received after the request.
+   * Disabled by default.
    *
-   * @param buffer the buffer to read into
-   * @param offset the offset in the buffer
-   * @param length the maximum number of bytes to read
-   * @return the number of bytes read, or 0 if no data is available
-   * @throws IOException               if the port is not open
-   * @throws IndexOutOfBoundsException if offset or length are invalid
+   * @param enabled true to clear pending input before each write
+   * @throws IOException if the operation fails or the port is not open
+   * @see #transact(byte[], byte[], int, int)
    */
-  public int readAvailable(byte[] buffer, int offset, int length) throws IOException {
-    int avail = available();
-    if (avail == 0) {
-      return 0;
+  public void setClearInputBeforeWrite(boolean enabled) throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    if (!NativeSerialPort.setClearInputBeforeWrite(handle, enabled)) {
+      throw createIOException("Failed to set clear input before write");
     }
-    return read(buffer, offset, Math.min(avail, length));
   }
 
   /**
-   * Read a line of text, terminated by a newline character, using the specified charset.
+   * Enable or disable draining the output at the end of each write.
    *
-   * <p>This method reads bytes until a newline character ({@code \n}) is encountered.
-   * The newline character is not included in the returned string. Carriage return
-   * characters ({@code \r}) immediately before the newline are also stripped.
+   * <p>With RS-485 direction control, each write waits until its data has been sent before
+   * returning. With auto-flush off, writes return as soon as the data is queued, and
+   * {@link #flush()} waits for it to be sent; this lets callers batch several writes and
+   * decide when to pay for the drain. Without RS-485 control, writes never drain, so this
+   * setting has no effect. Writes with a deadline, such as
+   * {@link #writeWithDeadline(byte[], int, int, long)}, always drain. Enabled by default.
    *
-   * <p>If a timeout occurs before a complete line is received, an IOException is thrown.
+   * <p><b>RS-485 caveat:</b> in manual RS-485 mode the direction pin must not be released
+   * while data is still queued, or the end of the frame is cut off. With auto-flush off, the
+   * pin therefore stays asserted after a write, keeping the transceiver in transmit mode and
+   * blocking replies, until {@link #flush()} drains the output, waits the post-send delay and
+   * releases it. Always call {@link #flush()} after the last write of a frame. Turning
+   * auto-flush back on flushes a write that is still waiting for it.
    *
-   * @param charset the charset to use for decoding
-   * @return the line of text without the line terminator
-   * @throws IOException if a timeout occurs or the port is not open
+   * @param enabled true to drain after each write, false to leave it to {@link #flush()}
+   * @throws IOException if the operation fails or the port is not open
    */
-  public String readLine(java.nio.charset.Charset charset) throws IOException {
-    java.io.ByteArrayOutputStream baos = new java.io.ByteArrayOutputStream();
-    byte[] single = new byte[1];
-
-    while (true) {
-      int bytesRead = read(single, 0, 1);
-      if (bytesRead == 0) {
-        if (baos.size() == 0) {
-          throw new IOException("Timeout waiting for line data");
-        }
-        // Return partial line on timeout if we have some data
-        break;
-      }
-
-      byte b = single[0];
-      if (b == '\n') {
-        break;
-      }
-      if (b != '\r') {
-        baos.write(b);
-      }
+  public void setAutoFlush(boolean enabled) throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    if (!NativeSerialPort.setAutoFlush(handle, enabled)) {
+      throw createIOException("Failed to set auto flush");
     }
+  }
 
-    return baos.toString(charset.name());
+  /**
+   * Set whether closing the port hangs up the line by dropping DTR/RTS (Linux only).
+   *
+   * <p>Some modems must keep the line up across reconnects, while other devices must see
+   * DTR drop to reset. This sets or clears the HUPCL termios flag, which the driver applies
+   * when the port is closed. Whether the port hangs up by default depends on the driver.
+   *
+   * @param enabled true to drop DTR/RTS on close, false to keep them asserted
+   * @throws IOException if the operation fails, the port is not open, or the platform is not Linux
+   */
+  public void setHangupOnClose(boolean enabled) throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    if (!NativeSerialPort.setHangupOnClose(handle, enabled)) {
+      throw createIOException("Failed to set hangup on close");
+    }
   }
 
   /**
-   * Read a line of text, terminated by a newline character, using UTF-8 encoding.
+   * Set whether closing the port restores the settings it had before it was opened (Linux only).
    *
-   * <p>This method reads bytes until a newline character ({@code \n}) is encountered.
-   * The newline character is not included in the returned string. Carriage return
-   * characters ({@code \r}) immediately before the newline are also stripped.
+   * <p>Opening a port reconfigures the terminal settings (baud rate, framing, raw mode), and
+   * they stay changed after close, so the next program to open a shared device, such as
+   * {@code minicom}, finds this library's configuration instead of its own. When enabled, the
+   * settings the device had just before it was opened are put back when the port is closed.
+   * The hangup flag is left as it is, so the close still follows
+   * {@link #setHangupOnClose(boolean)}. Disabled by default.
    *
-   * <p>If a timeout occurs before a complete line is received, an IOException is thrown.
+   * <p>On other platforms this has no effect: the operating system or driver decides what
+   * settings the next user sees.
    *
-   * @return the line of text without the line terminator
-   * @throws IOException if a timeout occurs or the port is not open
+   * @param enabled true to restore the original settings on close
+   * @throws IOException if the original settings could not be captured when the port was
+   *                     opened, or the port is not open
    */
-  public String readLine() throws IOException {
-    return readLine(java.nio.charset.StandardCharsets.UTF_8);
+  public void setRestoreOnClose(boolean enabled) throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    if (!NativeSerialPort.setRestoreOnClose(handle, enabled)) {
+      throw createIOException("Failed to set restore on close");
+    }
   }
 
   /**
-   * Flush the output buffer, ensuring all data is written.
+   * Set whether the port ignores the modem control lines (Linux only).
    *
-   * @throws IOException if the flush fails or the port is not open
+   * <p>Ports are opened with the CLOCAL termios flag set, so they ignore the modem control
+   * lines by default and a 3-wire connection (TX, RX and GND only) without carrier detect works
+   * without waiting for DCD. Passing false clears CLOCAL so the driver honors carrier detect:
+   * a later open may block until DCD is asserted, and the port is hung up when DCD drops, as
+   * needed for modems that signal a lost connection through DCD.
+   *
+   * @param ignore true to ignore the modem control lines (the default), false to honor DCD
+   * @throws IOException if the operation fails, the port is not open, or the platform is not Linux
    */
-  public void flush() throws IOException {
+  public void setIgnoreModemLines(boolean ignore) throws IOException {
     if (!isOpen) {
       throw new IOException("Port is not open");
     }
+    if (!NativeSerialPort.setIgnoreModemLines(handle, ignore)) {
+      throw createIOException("Failed to set ignore modem lines");
+    }
+  }
 
-    if (!NativeSerialPort.flush(handle)) {
-      throw createIOException("Failed to flush serial port");
+  /**
+   * Give the calling thread real-time scheduling priority while it reads this port (Linux only).
+   *
+   * <p>Under load, the JVM thread that reads a high-rate bus may not be scheduled in time to
+   * empty the driver's buffer, and data is lost to overruns. Call this from the reading thread
+   * to move it to the {@code SCHED_FIFO} real-time policy, so it runs as soon as a read
+   * completes instead of waiting for a time slice. Call it with false from the same thread to
+   * return to normal scheduling, for example before the thread goes back to a pool.
+   *
+   * <p>The change applies to the calling thread, not to the port, and needs the
+   * {@code CAP_SYS_NICE} capability or an {@code RLIMIT_RTPRIO} limit (for example via
+   * {@code /etc/security/limits.conf}); without them, enabling fails with a permission error.
+   *
+   * <p><b>Risks:</b> a real-time thread preempts every normally scheduled thread on its CPU,
+   * including the JVM's garbage collector and JIT compiler threads. A real-time thread that
+   * spins instead of blocking in reads can starve them and stall the whole application, or the
+   * machine if it has a single CPU. Keep the thread's work per read small, and hand data off to
+   * normally scheduled threads for processing.
+   *
+   * @param enabled true for real-time scheduling, false for normal scheduling
+   * @throws IOException if the scheduling change is denied, the port is not open, or the
+   *                     platform is not Linux
+   */
+  public void setReadThreadRealtime(boolean enabled) throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    if (!NativeSerialPort.setReadThreadRealtime(handle, enabled)) {
+      throw createIOException("Failed to set read thread scheduling");
     }
   }
 
   /**
-   * Set the timeout for read operations.
+   * Set the raw termios VMIN and VTIME values (Linux only).
    *
-   * <p><b>Note:</b> On Linux, timeouts are rounded up to the nearest 100ms due to
-   * POSIX termios limitations. For example, 50ms becomes 100ms, and 150ms becomes 200ms.
+   * <p>This is the lowest-level framing control, for users who know exactly which termios read
+   * behavior they want. VMIN is the minimum number of bytes a read returns, and VTIME is a timer
+   * in tenths of a second. The four classic combinations are:
+   * <ul>
+   *   <li>VMIN &gt; 0, VTIME = 0 (blocking): a read waits until at least VMIN bytes arrive</li>
+   *   <li>VMIN = 0, VTIME = 0 (polling): a read returns whatever is available, possibly
+   *       nothing, without waiting</li>
+   *   <li>VMIN = 0, VTIME &gt; 0 (timed): a read returns as soon as one byte arrives, or with
+   *       nothing after VTIME tenths of a second</li>
+   *   <li>VMIN &gt; 0, VTIME &gt; 0 (inter-character): a read returns when VMIN bytes have
+   *       arrived, or when VTIME tenths of a second pass between two bytes after the first one;
+   *       this ends a read at a gap in the data</li>
+   * </ul>
    *
-   * @param timeoutMs the timeout in milliseconds
-   * @throws IOException if the operation fails or the port is not open
-   * @see <a href="#platform-notes">Platform Notes</a>
+   * <p>Reads first wait up to the port timeout for the first byte to arrive, so VMIN and VTIME
+   * govern how much data a read collects from then on; with VMIN &gt; 1, a read may block past
+   * the port timeout waiting for more bytes. Ports are opened with VMIN = 1 and VTIME = 0, and
+   * the higher-level read helpers of this class assume those values.
+   *
+   * @param vmin  the minimum number of bytes for a read to return (0-255)
+   * @param vtime the read timer in tenths of a second (0-255)
+   * @throws IllegalArgumentException if a value is outside 0-255
+   * @throws IOException              if the operation fails, the port is not open, or the
+   *                                  platform is not Linux
    */
-  public void setTimeout(int timeoutMs) throws IOException {
+  public void setVminVtime(int vmin, int vtime) throws IOException {
+    if (vmin < 0 || vmin > 255) {
+      throw new IllegalArgumentException("vmin must be between 0 and 255: " + vmin);
+    }
+    if (vtime < 0 || vtime > 255) {
+      throw new IllegalArgumentException("vtime must be between 0 and 255: " + vtime);
+    }
     if (!isOpen) {
       throw new IOException("Port is not open");
     }
-
-    if (!NativeSerialPort.setTimeout(handle, timeoutMs)) {
-      throw createIOException("Failed to set timeout");
+    if (!NativeSerialPort.setVminVtime(handle, vmin, vtime)) {
+      throw createIOException("Failed to set VMIN/VTIME");
     }
   }
 
   /**
-   * Clear the input buffer.
+   * Cap how many bytes a single underlying read requests from the operating system.
    *
-   * @throws IOException if the operation fails or the port is not open
+   * <p>Some drivers wait to fill more of a large read request than necessary, which adds latency
+   * when {@link #read(byte[], int, int)} is called with a big buffer. With a cap, each read
+   * requests at most {@code maxChunk} bytes and returns sooner with whatever has arrived. Reads
+   * that fill a buffer until a deadline, such as {@link #readFully(byte[], int, int, int)}, just
+   * perform more iterations. The read method signatures are unchanged.
+   *
+   * @param maxChunk the maximum bytes per underlying read, or 0 for no limit (default)
+   * @throws IllegalArgumentException if maxChunk is negative
+   * @throws IOException              if the operation fails or the port is not open
    */
-  public void clearInput() throws IOException {
+  public void setMaxReadChunk(int maxChunk) throws IOException {
+    if (maxChunk < 0) {
+      throw new IllegalArgumentException("maxChunk must not be negative");
+    }
     if (!isOpen) {
       throw new IOException("Port is not open");
     }
-
-    if (!NativeSerialPort.clearInput(handle)) {
-      throw createIOException("Failed to clear input buffer");
+    if (!NativeSerialPort.setMaxReadChunk(handle, maxChunk)) {
+      throw createIOException("Failed to set max read chunk");
     }
   }
 
   /**
-   * Clear the output buffer.
+   * Warn when the input queue grows beyond a number of bytes.
    *
-   * @throws IOException if the operation fails or the port is not open
+   * <p>This is an early signal that the application reads too slowly, before the driver's
+   * buffers fill up and input is lost to an overrun. Operations that already query the input
+   * queue, namely the reads, {@link #available()}, {@link #getPortStatus()} and
+   * {@link #waitForBytes(int, int)}, compare its depth with the threshold and record a warning
+   * for {@link #getLastWarning()} when it is exceeded. No extra system calls are made.
+   *
+   * @param bytes the threshold in bytes, or 0 to disable the warning (default)
+   * @throws IllegalArgumentException if bytes is negative
+   * @throws IOException              if the operation fails or the port is not open
    */
-  public void clearOutput() throws IOException {
+  public void setOverflowWarnThreshold(int bytes) throws IOException {
+    if (bytes < 0) {
+      throw new IllegalArgumentException("bytes must not be negative");
+    }
     if (!isOpen) {
       throw new IOException("Port is not open");
     }
-
-    if (!NativeSerialPort.clearOutput(handle)) {
-      throw createIOException("Failed to clear output buffer");
+    if (!NativeSerialPort.setOverflowWarnThreshold(handle, bytes)) {
+      throw createIOException("Failed to set overflow warning threshold");
     }
   }
 
   /**
-   * Clear both input and output buffers.
+   * Limit how long a write waits for its output to drain.
    *
-   * @throws IOException if the operation fails or the port is not open
+   * <p>With RS-485 direction control, each write waits for the output to leave the port before
+   * releasing the direction pin. A peer holding flow control can keep the output from ever
+   * draining, which would otherwise hang the write. Once the timeout passes, the bytes still
+   * queued are discarded, the direction pin is released anyway, the discarded bytes are left out
+   * of the count returned by the write, and a {@code "drain timeout"} warning is recorded for
+   * {@link #getLastWarning()}.
+   *
+   * @param timeoutMs the drain timeout in milliseconds, or 0 to wait indefinitely (default 30000)
+   * @throws IllegalArgumentException if timeoutMs is negative
+   * @throws IOException              if the operation fails or the port is not open
    */
-  public void clearAll() throws IOException {
+  public void setDrainTimeout(int timeoutMs) throws IOException {
+    if (timeoutMs < 0) {
+      throw new IllegalArgumentException("timeoutMs must not be negative");
+    }
     if (!isOpen) {
       throw new IOException("Port is not open");
     }
-
-    if (!NativeSerialPort.clearAll(handle)) {
-      throw createIOException("Failed to clear buffers");
+    if (!NativeSerialPort.setDrainTimeout(handle, timeoutMs)) {
+      throw createIOException("Failed to set drain timeout");
     }
   }
 
   /**
-   * Get an InputStream for reading from the serial port.
-   * <p>
-   * The same InputStream instance is returned on subsequent calls. The stream's read methods
-   * will return -1 when no data is available within the configured timeout.
+   * Switch between raw mode and text output processing (Linux only).
    *
-   * @return an InputStream
+   * <p>Ports are opened in raw mode, so every byte is transmitted exactly as written. This is
+   * required for binary protocols, which would otherwise be corrupted by the terminal layer's
+   * output processing. Disabling raw mode enables that processing (OPOST/ONLCR), which
+   * translates {@code \n} to {@code \r\n} for terminals that expect CR/LF line endings.
+   * Re-enabling raw mode turns all input and output processing off again.
+   *
+   * @param raw true for raw mode (default), false to translate {@code \n} to {@code \r\n} on output
+   * @throws IOException if the operation fails, the port is not open, or the platform is not Linux
    */
-  public InputStream getInputStream() {
-    if (cachedInputStream == null) {
-      cachedInputStream = new SerialInputStream(this);
+  public void setRawMode(boolean raw) throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    if (!NativeSerialPort.setRawMode(handle, raw)) {
+      throw createIOException("Failed to set raw mode");
     }
-    return cachedInputStream;
   }
 
   /**
-   * Get an OutputStream for writing to the serial port.
-   * <p>
-   * The same OutputStream instance is returned on subsequent calls.
+   * Switch the input side between canonical (line-buffered) and raw mode (Linux only).
    *
-   * @return an OutputStream
+   * <p>Ports are opened in raw mode, where {@link #read(byte[])} returns bytes as they arrive.
+   * This is required for binary protocols. In canonical mode, the terminal layer collects
+   * input into lines and a read only returns once a complete line, ending in {@code \n}, has
+   * been received; it then returns that line, including the terminator, or as much of it as
+   * fits in the buffer. This is convenient for interactive text devices, but changes read
+   * semantics significantly:
+   * <ul>
+   *   <li>a read times out if no complete line arrives within the timeout, even if part of a
+   *       line has been received;</li>
+   *   <li>the erase ({@code DEL}), kill ({@code Ctrl-U}) and end-of-file ({@code Ctrl-D})
+   *       characters are interpreted instead of delivered, which corrupts binary data;</li>
+   *   <li>{@link #setVminVtime(int, int)} has no effect, and {@link #readUntilIdle} only sees
+   *       whole lines;</li>
+   *   <li>{@link #readLine()} still works, but never sees a partial line.</li>
+   * </ul>
+   *
+   * <p>This only affects input. Output processing is controlled separately by
+   * {@link #setRawMode(boolean)}, and {@code setRawMode(true)} also turns canonical mode off.
+   *
+   * @param canonical true for canonical (line-buffered) input, false for raw input (default)
+   * @throws IOException if the operation fails, the port is not open, or the platform is not Linux
    */
-  public OutputStream getOutputStream() {
-    if (cachedOutputStream == null) {
-      cachedOutputStream = new SerialOutputStream(this);
+  public void setCanonicalMode(boolean canonical) throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    if (!NativeSerialPort.setCanonicalMode(handle, canonical)) {
+      throw createIOException("Failed to set canonical mode");
     }
-    return cachedOutputStream;
   }
 
   /**
-   * Check if the port is open.
+   * Change the baud rate, data bits, stop bits and parity of an open port without dropping or
+   * mangling data.
    *
-   * @return true if the port is open
-   */
-  public boolean isOpen() {
-    return isOpen;
-  }
-
-  /**
-   * Get the port name.
+   * <p>Changing the line settings while a character is being transmitted can cut it off or send
+   * it with the wrong framing, and bytes received under the old settings are garbage under the
+   * new ones. This method first waits for pending output to be transmitted (up to the drain
+   * timeout, see {@link #setDrainTimeout(int)}), then discards pending input, and then applies
+   * all four settings together. On Linux they are applied in a single termios update that waits
+   * for the transmitter to finish; if the driver does not apply one of them, all of them are
+   * rolled back. On other platforms the settings are applied one at a time, so a failure can
+   * leave the earlier ones applied.
    *
-   * @return the port name
-   */
-  public String getPortName() {
-    return portName;
-  }
-
-  /**
-   * Get the baud rate.
+   * <p>On success, the getters such as {@link #getBaudRate()} return the new settings, and the
+   * port uses them if it is reopened. If a setting fails, the exception message names it.
    *
-   * @return the baud rate
+   * @param baudRate the new baud rate
+   * @param dataBits the new data bits
+   * @param stopBits the new stop bits
+   * @param parity   the new parity
+   * @throws IllegalArgumentException if baudRate is not positive
+   * @throws NullPointerException     if dataBits, stopBits or parity is null
+   * @throws IOException              if a setting could not be applied or the port is not open
    */
-  public int getBaudRate() {
-    return baudRate;
+  public void reconfigure(int baudRate, DataBits dataBits, StopBits stopBits, Parity parity)
+      throws IOException {
+    if (baudRate <= 0) {
+      throw new IllegalArgumentException("baudRate must be positive");
+    }
+    if (dataBits == null || stopBits == null || parity == null) {
+      throw new NullPointerException("dataBits, stopBits and parity cannot be null");
+    }
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    if (!NativeSerialPort.reconfigure(
+        handle, baudRate, dataBits.getValue(), stopBits.getValue(), parity.getValue())) {
+      throw createIOException("Failed to reconfigure port");
+    }
+    this.baudRate = baudRate;
+    this.dataBits = dataBits;
+    this.stopBits = stopBits;
+    this.parity = parity;
   }
 
   /**
-   * Get the data bits.
+   * Change the data bits of an open port.
    *
-   * @return the data bits
-   */
-  public DataBits getDataBits() {
-    return dataBits;
-  }
-
-  /**
-   * Get the stop bits.
+   * <p>Pending output is transmitted first (up to the drain timeout, see
+   * {@link #setDrainTimeout(int)}) so a character in flight is not cut off. Unlike
+   * {@link #reconfigure(int, DataBits, StopBits, Parity)}, received input is kept. The setting is
+   * read back after it is applied; if the driver did not apply it, the previous data bits
+   * is restored and an exception is thrown.
    *
-   * @return the stop bits
-   */
-  public StopBits getStopBits() {
-    return stopBits;
-  }
-
-  /**
-   * Get the parity.
+   * <p>On success, {@link #getDataBits()} returns the new setting, and the port uses it if it is
+   * reopened.
    *
-   * @return the parity
+   * @param dataBits the new data bits
+   * @throws NullPointerException if dataBits is null
+   * @throws IOException          if the setting could not be applied or the port is not open
    */
-  public Parity getParity() {
-    return parity;
+  public void setDataBits(DataBits dataBits) throws IOException {
+    if (dataBits == null) {
+      throw new NullPointerException("dataBits cannot be null");
+    }
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    if (!NativeSerialPort.setDataBits(handle, dataBits.getValue())) {
+      throw createIOException("Failed to set data bits");
+    }
+    this.dataBits = dataBits;
   }
 
   /**
-   * Get the flow control mode.
+   * Change the stop bits of an open port.
    *
-   * @return the flow control mode
+   * <p>Pending output is transmitted first (up to the drain timeout, see
+   * {@link #setDrainTimeout(int)}) so a character in flight is not cut off. Unlike
+   * {@link #reconfigure(int, DataBits, StopBits, Parity)}, received input is kept. The setting is
+   * read back after it is applied; if the driver did not apply it, the previous stop bits
+   * is restored and an exception is thrown.
+   *
+   * <p>On success, {@link #getStopBits()} returns the new setting, and the port uses it if it is
+   * reopened.
+   *
+   * @param stopBits the new stop bits
+   * @throws NullPointerException if stopBits is null
+   * @throws IOException          if the setting could not be applied or the port is not open
    */
-  public FlowControl getFlowControl() {
-    return flowControl;
+  public void setStopBits(StopBits stopBits) throws IOException {
+    if (stopBits == null) {
+      throw new NullPointerException("stopBits cannot be null");
+    }
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    if (!NativeSerialPort.setStopBits(handle, stopBits.getValue())) {
+      throw createIOException("Failed to set stop bits");
+    }
+    this.stopBits = stopBits;
   }
 
   /**
-   * Check if DTR is asserted when opening the port.
+   * Change the parity of an open port.
    *
-   * @return true if DTR is asserted on open (default), false if suppressed
+   * <p>Pending output is transmitted first (up to the drain timeout, see
+   * {@link #setDrainTimeout(int)}) so a character in flight is not cut off. Unlike
+   * {@link #reconfigure(int, DataBits, StopBits, Parity)}, received input is kept. The setting is
+   * read back after it is applied; if the driver did not apply it, the previous parity
+   * is restored and an exception is thrown.
+   *
+   * <p>On success, {@link #getParity()} returns the new setting, and the port uses it if it is
+   * reopened.
+   *
+   * @param parity the new parity
+   * @throws NullPointerException if parity is null
+   * @throws IOException          if the setting could not be applied or the port is not open
    */
-  public boolean isDtrOnOpen() {
-    return dtrOnOpen;
+  public void setParity(Parity parity) throws IOException {
+    if (parity == null) {
+      throw new NullPointerException("parity cannot be null");
+    }
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    if (!NativeSerialPort.setParity(handle, parity.getValue())) {
+      throw createIOException("Failed to set parity");
+    }
+    this.parity = parity;
   }
 
   /**
-   * Get the RS-485 configuration.
+   * Get the line speeds the driver reports as applied, next to the configured baud rate.
    *
-   * @return the RS-485 configuration, or null if not set
+   * <p>Custom baud rates are approximated by dividing the adapter's clock, and the achievable
+   * rate can be off by a few percent; enough to cause framing errors at high speeds. On Linux
+   * the speeds are read back from the kernel, so a driver that reports its rounded rate
+   * reveals the error. On other platforms both speeds are the rate the OS reports for the
+   * port.
+   *
+   * @return the configured and applied line speeds
+   * @throws IOException if the speeds cannot be read, or the port is not open
+   * @see LineSpeed#getErrorPercent()
    */
-  public Rs485Config getRs485Config() {
-    return rs485Config;
+  public LineSpeed getActualBaudRate() throws IOException {
+    if (!isOpen) {
+      throw new IOException("Port is not open");
+    }
+    String data = NativeSerialPort.getActualBaudRate(handle);
+    if (data == null) {
+      throw createIOException("Failed to get actual baud rate");
+    }
+    return LineSpeed.parse(baudRate, data);
   }
 
   /**
-   * Check if kernel RS-485 mode is active.
+   * Receive and transmit at different baud rates (Linux only).
    *
-   * <p>This returns true only on Linux when the hardware supports kernel-level RS-485
-   * and it was successfully enabled. When kernel mode is active, the hardware handles
-   * RTS timing automatically for optimal performance.
+   * <p>A few legacy setups, such as some videotex terminals and split-speed modems, receive at
+   * one rate and transmit at another. Most hardware cannot do this: typical UARTs derive both
+   * directions from a single clock divisor, and USB adapters configure one line rate. Use this
+   * only for devices that are documented to run split speeds, and with a driver that supports
+   * them. Rates outside the standard termios table are set as custom rates.
    *
-   * @return true if kernel RS-485 mode is active
-   * @throws IOException if the port is not open
+   * <p>The driver's applied speeds are read back; if it did not apply both rates (within 2%),
+   * the previous speeds are restored and an IOException is thrown. {@link #getBaudRate()} keeps
+   * returning the rate set on the builder or by {@link #reconfigure}.
+   *
+   * @param inputBaud  the baud rate for received data
+   * @param outputBaud the baud rate for transmitted data
+   * @throws IllegalArgumentException if a baud rate is not positive
+   * @throws IOException              if the driver cannot use split speeds, the operation fails,
+   *                                  the port is not open, or the platform is not Linux
    */
-  public boolean isKernelRs485Active() throws IOException {
+  public void setSplitBaud(int inputBaud, int outputBaud) throws IOException {
+    if (inputBaud <= 0 || outputBaud <= 0) {
+      throw new IllegalArgumentException("inputBaud and outputBaud must be positive");
+    }
     if (!isOpen) {
       throw new IOException("Port is not open");
     }
-    return NativeSerialPort.isKernelRs485Active(handle);
+    if (!NativeSerialPort.setSplitBaud(handle, inputBaud, outputBaud)) {
+      throw createIOException("Failed to set split baud rates");
+    }
   }
 
   /**
-   * Set RS-485 timing delays (Linux kernel mode only).
+   * Trim the line rate with a custom baud divisor (Linux only).
    *
-   * <p>These delays control the timing of RTS signal transitions relative to data
-   * transmission. They are only effective when kernel RS-485 mode is active.
+   * <p>The effective rate becomes {@code baseBaud / divisor}, which allows matching devices with
+   * nonstandard or slightly off crystals. This uses the legacy {@code ASYNC_SPD_CUST} mechanism:
+   * the kernel substitutes the custom rate whenever the terminal speed is 38400, so this method
+   * also switches the port to 38400. The baud rate configured on the builder is therefore
+   * replaced, and any later change of the terminal speed (including reopening the port) cancels
+   * the custom divisor. Passing a divisor of 0 clears the custom speed but leaves the port at
+   * 38400 baud until it is reopened.
    *
-   * @param delayBeforeSendMicros delay in microseconds before sending (RTS assertion to first data bit)
-   * @param delayAfterSendMicros  delay in microseconds after sending (last data bit to RTS de-assertion)
-   * @throws IOException if the operation fails or the port is not open
+   * <p>Only drivers that implement TIOCSSERIAL support this (e.g. 8250/16550 UARTs and some
+   * USB adapters such as FTDI). Changing the base baud usually requires CAP_SYS_ADMIN.
+   *
+   * @param baseBaud the UART base baud (e.g. 115200), or 0 to keep the driver's value
+   * @param divisor  the custom divisor, or 0 to clear the custom speed
+   * @throws IllegalArgumentException if baseBaud or divisor is negative
+   * @throws IOException if the operation fails, the port is not open, or the platform is not Linux
    */
-  public void setRs485Delays(int delayBeforeSendMicros, int delayAfterSendMicros) throws IOException {
+  public void setCustomDivisor(int baseBaud, int divisor) throws IOException {
+    if (baseBaud < 0 || divisor < 0) {
+      throw new IllegalArgumentException("baseBaud and divisor must not be negative");
+    }
     if (!isOpen) {
       throw new IOException("Port is not open");
     }
-    if (!NativeSerialPort.setRs485Delays(handle, delayBeforeSendMicros, delayAfterSendMicros)) {
-      throw createIOException("Failed to set RS-485 delays");
+    if (!NativeSerialPort.setCustomDivisor(handle, baseBaud, divisor)) {
+      throw createIOException("Failed to set custom divisor");
     }
   }
 
   /**
-   * Set RS-485 configuration at runtime.
+   * Set how long the kernel waits when the port is closed (Linux only).
    *
-   * <p>This allows changing all RS-485 parameters on an open port, including
-   * RTS polarity, RX during TX, termination, and timing delays.
+   * <p>Both values are in hundredths of a second, as in the kernel's {@code serial_struct}.
+   * {@code closingWait} limits how long closing waits for pending output to be transmitted:
+   * too short a value truncates the last bytes written, while {@link #CLOSING_WAIT_INFINITE}
+   * can make {@link #close()} hang if flow control never lets the output drain. Use
+   * {@link #CLOSING_WAIT_NONE} to discard pending output immediately. {@code closeDelay} is
+   * how long DTR and RTS stay low after closing before the port can be reopened, which gives
+   * a modem time to notice the hang-up.
    *
-   * <p>Note: This is only fully supported on Linux with kernel RS-485 support.
-   * On other platforms, only the delay values may be effective.
+   * <p>Only drivers that implement TIOCSSERIAL support this (e.g. 8250/16550 UARTs and some
+   * USB adapters such as FTDI). Changing these values usually requires CAP_SYS_ADMIN.
    *
-   * @param config the RS-485 configuration to apply
-   * @throws IOException if the operation fails or the port is not open
+   * @param closeDelay  the close delay in hundredths of a second (0-65535)
+   * @param closingWait the closing wait in hundredths of a second (0-65535), or one of
+   *                    {@link #CLOSING_WAIT_INFINITE} and {@link #CLOSING_WAIT_NONE}
+   * @throws IllegalArgumentException if a value is outside 0-65535
+   * @throws IOException if the operation fails, the port is not open, or the platform is not Linux
    */
-  public void setRs485Config(Rs485Config config) throws IOException {
+  public void setCloseTiming(int closeDelay, int closingWait) throws IOException {
+    if (closeDelay < 0 || closeDelay > 0xFFFF || closingWait < 0 || closingWait > 0xFFFF) {
+      throw new IllegalArgumentException("closeDelay and closingWait must be between 0 and 65535");
+    }
     if (!isOpen) {
       throw new IOException("Port is not open");
     }
-    if (config == null) {
-      throw new IllegalArgumentException("Config cannot be null");
-    }
-    if (!NativeSerialPort.setRs485Config(
-        handle,
-        config.isEnabled(),
-        config.getControlPin().getValue(),
-        config.isRtsActiveHigh(),
-        config.isRxDuringTx(),
-        config.isTerminationEnabled(),
-        config.getDelayBeforeSendMicros(),
-        config.getDelayAfterSendMicros())) {
-      throw createIOException("Failed to set RS-485 config");
+    if (!NativeSerialPort.setCloseTiming(handle, closeDelay, closingWait)) {
+      throw createIOException("Failed to set close timing");
     }
   }
 
   /**
-   * Set the RTS (Request To Send) pin state.
-   *
-   * <p>This is useful for manual RS-485 control or other applications that need
-   * direct control of the RTS pin.
+   * Get the receive FIFO trigger level of the UART (Linux only).
    *
-   * @param level true to set RTS high, false to set RTS low
-   * @throws IOException if the operation fails or the port is not open
+   * @return the trigger level in bytes
+   * @throws IOException if the driver does not expose the trigger level, the operation fails,
+   *                     the port is not open, or the platform is not Linux
+   * @see #setFifoTriggerLevel(int)
    */
-  public void setRTS(boolean level) throws IOException {
+  public int getFifoTriggerLevel() throws IOException {
     if (!isOpen) {
       throw new IOException("Port is not open");
     }
-    if (!NativeSerialPort.setRTS(handle, level)) {
-      throw createIOException("Failed to set RTS");
+    int level = NativeSerialPort.getFifoTriggerLevel(handle);
+    if (level < 0) {
+      throw createIOException("Failed to get FIFO trigger level");
     }
+    return level;
   }
 
   /**
-   * Set the DTR (Data Terminal Ready) pin state.
+   * Set the receive FIFO trigger level of the UART (Linux only).
    *
-   * <p>This is useful for manual RS-485 control when DTR is used instead of RTS,
-   * or for other applications that need direct control of the DTR pin.
+   * <p>A UART raises a receive interrupt once this many bytes are waiting in its hardware FIFO
+   * (or after a few character times of silence). A low level hands each byte to the driver
+   * sooner, which reduces latency for short request/response exchanges; a high level batches
+   * bytes and reduces interrupt load at high baud rates, at the risk of overruns on a busy
+   * system.
    *
-   * @param level true to set DTR high, false to set DTR low
-   * @throws IOException if the operation fails or the port is not open
+   * <p>Only the Linux 8250 driver exposes this knob (as {@code rx_trig_bytes} in sysfs, since
+   * Linux 3.17), for 16550A-compatible and larger-FIFO UARTs such as the 16650, 16750, 16C950
+   * and the Exar XR17V35x family. Other drivers, including USB serial adapters and pseudo
+   * terminals, do not, and this method throws. The driver rounds the level down to one the UART
+   * supports (1, 4, 8 or 14 bytes on a 16550A) and returns it. Writing the setting usually
+   * requires root, and it applies to the UART until changed again or the system restarts.
+   *
+   * @param level the requested trigger level in bytes
+   * @return the trigger level the driver applied
+   * @throws IllegalArgumentException if level is not positive
+   * @throws IOException              if the driver does not expose the trigger level, the
+   *                                  operation fails, the port is not open, or the platform is
+   *                                  not Linux
    */
-  public void setDTR(boolean level) throws IOException {
+  public int setFifoTriggerLevel(int level) throws IOException {
+    if (level <= 0) {
+      throw new IllegalArgumentException("level must be positive");
+    }
     if (!isOpen) {
       throw new IOException("Port is not open");
     }
-    if (!NativeSerialPort.setDTR(handle, level)) {
-      throw createIOException("Failed to set DTR");
+    int applied = NativeSerialPort.setFifoTriggerLevel(handle, level);
+    if (applied < 0) {
+      throw createIOException("Failed to set FIFO trigger level");
     }
+    return applied;
   }
 
   /**
@@ -811,6 +4750,7 @@ public void setDTR(boolean level) throws IOException {
   public static class Builder {
 
     private String portName;
+    private String usbSerialNumber;
     private int baudRate = 9600;
     private DataBits dataBits = DataBits.EIGHT;
     private StopBits stopBits = StopBits.ONE;
@@ -819,6 +4759,10 @@ public static class Builder {
     private boolean dtrOnOpen = true;
     private int timeoutMs = 1000;
     private Rs485Config rs485Config = null;
+    private boolean quietOpen = false;
+    private int settleMs = 0;
+    private AccessMode accessMode = AccessMode.READ_WRITE;
+    private int baudTolerancePercent = 0;
 
     /**
      * Set the port name.
@@ -831,6 +4775,24 @@ public Builder portName(String portName) {
       return this;
     }
 
+    /**
+     * Select the device by USB serial number instead of by port name.
+     *
+     * <p>Device paths such as {@code /dev/ttyUSB0} can change across reboots or when adapters
+     * are plugged in a different order. With a USB serial number, the matching device is looked
+     * up among the available ports each time the port is opened. Opening fails if no port or
+     * more than one port has this serial number.
+     *
+     * <p>This takes precedence over {@link #portName(String)}.
+     *
+     * @param usbSerialNumber the USB serial number reported by the adapter, not blank
+     * @return this builder
+     */
+    public Builder usbSerialNumber(String usbSerialNumber) {
+      this.usbSerialNumber = usbSerialNumber;
+      return this;
+    }
+
     /**
      * Set the baud rate.
      *
@@ -976,21 +4938,168 @@ public Builder rs485() {
       return this;
     }
 
+    /**
+     * Open the port without the library driving DTR/RTS, and suppress the DTR pulse on close.
+     *
+     * <p>Normally, opening a port asserts DTR and RTS and closing it drops them again, which
+     * resets Arduinos and can trigger bootloaders. A quiet open never writes to the control
+     * lines itself and keeps a close from dropping them, so repeated open/close cycles do not
+     * pulse DTR. It does not stop the operating system from raising the lines on an open; that
+     * is not verified and depends on the driver. The {@link #dtrOnOpen(boolean)} setting is
+     * ignored and RS-485 control cannot be combined with a quiet open.
+     *
+     * <p><b>Platform behavior:</b>
+     * <ul>
+     *   <li><b>Linux:</b> HUPCL is cleared, so closing the port no longer drops DTR/RTS. The
+     *       kernel still raises both lines on the first open after they were dropped, but
+     *       subsequent close/open cycles leave them untouched.</li>
+     *   <li><b>Other platforms:</b> the library does not change the lines, but the operating
+     *       system driver may still apply its own defaults on open.</li>
+     * </ul>
+     *
+     * <p>With {@link FlowControl#HARDWARE}, the driver controls RTS itself.
+     *
+     * @param quietOpen true to open without touching the control lines
+     * @return this builder
+     */
+    public Builder quietOpen(boolean quietOpen) {
+      this.quietOpen = quietOpen;
+      return this;
+    }
+
+    /**
+     * Set a delay to wait after opening the port, before {@link SerialPort#open()} returns.
+     *
+     * <p>Some USB-serial chips lose the first bytes sent right after the port is opened. The
+     * delay is applied in native code after the port is configured (including DTR suppression
+     * and RS-485 setup), which makes the timing consistent across platforms and replaces
+     * {@code Thread.sleep} calls after open.
+     *
+     * @param settleMs the settle delay in milliseconds (default: 0, no delay)
+     * @return this builder
+     * @throws IllegalArgumentException if settleMs is negative
+     */
+    public Builder settleMs(int settleMs) {
+      if (settleMs < 0) {
+        throw new IllegalArgumentException("Settle delay must not be negative");
+      }
+      this.settleMs = settleMs;
+      return this;
+    }
+
+    /**
+     * Allow opening at a nearby standard baud rate if the driver rejects the exact one.
+     *
+     * <p>Some drivers refuse baud rates they cannot generate exactly instead of rounding them.
+     * With a tolerance set, if opening at the exact rate fails, the standard rates within the
+     * tolerance are tried, nearest first, and the port opens at the first one the driver
+     * accepts. The substitution is reported by {@link SerialPort#getLastWarning()}, for example
+     * {@code "baud rate 115000 could not be set, opened at 115200 instead (+0.17%, within the
+     * tolerance of 1%)"}. From then on {@link SerialPort#getBaudRate()} returns the rate in
+     * use, which is also the rate requested when the port is reopened. Failures that are not
+     * about the settings, such as a missing or busy port, are not retried.
+     *
+     * <p>The tolerance is not applied with {@linkplain #quietOpen(boolean) quiet open}.
+     *
+     * @param baudTolerancePercent the largest acceptable difference from the requested rate in
+     *                             percent (default: 0, the exact rate is required)
+     * @return this builder
+     * @throws IllegalArgumentException if baudTolerancePercent is negative
+     */
+    public Builder baudTolerancePercent(int baudTolerancePercent) {
+      if (baudTolerancePercent < 0) {
+        throw new IllegalArgumentException("Baud rate tolerance must not be negative");
+      }
+      this.baudTolerancePercent = baudTolerancePercent;
+      return this;
+    }
+
+    /**
+     * Set which directions of I/O the port is opened for.
+     *
+     * <p>A passive monitor can use {@link AccessMode#READ_ONLY} to guarantee it never transmits,
+     * and a one-way sender {@link AccessMode#WRITE_ONLY}. On Linux this is enforced by opening
+     * the device with {@code O_RDONLY} or {@code O_WRONLY}. RS-485 direction control needs write
+     * access, and a quiet open always uses {@link AccessMode#READ_WRITE}.
+     *
+     * @param accessMode the access mode (default: READ_WRITE)
+     * @return this builder
+     * @throws IllegalArgumentException if accessMode is null
+     */
+    public Builder accessMode(AccessMode accessMode) {
+      if (accessMode == null) {
+        throw new IllegalArgumentException("accessMode cannot be null");
+      }
+      this.accessMode = accessMode;
+      return this;
+    }
+
     /**
      * Build the SerialPort.
      *
      * @return a new SerialPort instance
-     * @throws IllegalArgumentException if the port name is not set
+     * @throws IllegalArgumentException if neither the port name nor the USB serial number is set,
+     *                                  if the USB serial number is blank, if quiet open is combined
+     *                                  with RS-485 control or a restricted access mode, or if a
+     *                                  read-only port uses RS-485 control
      */
     public SerialPort build() {
-      if (portName == null || portName.isEmpty()) {
+      if (usbSerialNumber != null && usbSerialNumber.trim().isEmpty()) {
+        throw new IllegalArgumentException("USB serial number must not be blank");
+      }
+      if ((portName == null || portName.isEmpty()) && usbSerialNumber == null) {
         throw new IllegalArgumentException("Port name must be set");
       }
+      if (quietOpen && rs485Config != null && rs485Config.isEnabled()) {
+        throw new IllegalArgumentException("Quiet open cannot be combined with RS-485 control");
+      }
+      if (quietOpen && accessMode != AccessMode.READ_WRITE) {
+        throw new IllegalArgumentException("Quiet open cannot be combined with a restricted access mode");
+      }
+      if (accessMode == AccessMode.READ_ONLY && rs485Config != null && rs485Config.isEnabled()) {
+        throw new IllegalArgumentException("RS-485 control requires write access");
+      }
       return new SerialPort(this);
     }
 
   }
 
+  /**
+   * RS-485 settings decoded into the values the native open and configure calls take.
+   */
+  private static final class NativeRs485Settings {
+
+    final boolean enabled;
+    /** 1 for AUTO, 0 for NONE. */
+    final int mode;
+    final int controlPin;
+    final boolean rtsActiveHigh;
+    final boolean rxDuringTx;
+    final boolean terminationEnabled;
+    final int delayBeforeMicros;
+    final int delayAfterMicros;
+
+    /**
+     * Decode an RS-485 configuration.
+     *
+     * @param config the configuration to decode; null gives the defaults of a disabled one
+     */
+    NativeRs485Settings(Rs485Config config) {
+      if (config == null) {
+        config = Rs485Config.disabled();
+      }
+      enabled = config.isEnabled();
+      mode = enabled ? 1 : 0;
+      controlPin = config.getControlPin().getValue();
+      rtsActiveHigh = config.isRtsActiveHigh();
+      rxDuringTx = config.isRxDuringTx();
+      terminationEnabled = config.isTerminationEnabled();
+      delayBeforeMicros = config.getDelayBeforeSendMicros();
+      delayAfterMicros = config.getDelayAfterSendMicros();
+    }
+
+  }
+
   /**
    * InputStream wrapper for SerialPort.
    */
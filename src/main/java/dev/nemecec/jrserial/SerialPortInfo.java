@@ -28,12 +28,21 @@ public final class SerialPortInfo {
   private final boolean symlink;
   private final boolean pseudoTerminal;
   private final boolean bluetooth;
+  private final String bluetoothAddress;
+  private final int bluetoothChannel;
 
   SerialPortInfo(String portName, boolean symlink, boolean pseudoTerminal, boolean bluetooth) {
+    this(portName, symlink, pseudoTerminal, bluetooth, null, -1);
+  }
+
+  SerialPortInfo(String portName, boolean symlink, boolean pseudoTerminal, boolean bluetooth,
+                 String bluetoothAddress, int bluetoothChannel) {
     this.portName = portName;
     this.symlink = symlink;
     this.pseudoTerminal = pseudoTerminal;
     this.bluetooth = bluetooth;
+    this.bluetoothAddress = bluetoothAddress;
+    this.bluetoothChannel = bluetoothChannel;
   }
 
   /**
@@ -92,6 +101,28 @@ public boolean isBluetooth() {
     return bluetooth;
   }
 
+  /**
+   * Get the MAC address of the remote device a Bluetooth RFCOMM port is bound to.
+   *
+   * <p>This is only available on Linux, for {@code /dev/rfcomm*} ports (or symlinks to them),
+   * and helps identify which paired device a port corresponds to.
+   *
+   * @return the remote address (e.g. "00:1A:7D:DA:71:13"), or null if unknown
+   */
+  public String getBluetoothAddress() {
+    return bluetoothAddress;
+  }
+
+  /**
+   * Get the RFCOMM channel a Bluetooth port is bound to.
+   *
+   * @return the channel (1-30), or -1 if unknown
+   * @see #getBluetoothAddress()
+   */
+  public int getBluetoothChannel() {
+    return bluetoothChannel;
+  }
+
   @Override
   public String toString() {
     StringBuilder sb = new StringBuilder(portName);
@@ -110,6 +141,9 @@ public String toString() {
       if (bluetooth) {
         if (needComma) sb.append(", ");
         sb.append("bluetooth");
+        if (bluetoothAddress != null) {
+          sb.append(" ").append(bluetoothAddress).append(" channel ").append(bluetoothChannel);
+        }
       }
       sb.append(")");
     }
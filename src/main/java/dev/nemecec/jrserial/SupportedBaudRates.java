@@ -0,0 +1,74 @@
+/*
+ * Copyright (C) 2026 Neeme Praks
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package dev.nemecec.jrserial;
+
+import java.util.Collections;
+import java.util.List;
+
+/**
+ * The baud rates a serial port's driver accepts, as probed by
+ * {@link SerialPort#getSupportedBaudRates(String)}.
+ */
+public final class SupportedBaudRates {
+
+  private final List<Integer> standardRates;
+  private final boolean customRatesSupported;
+
+  SupportedBaudRates(List<Integer> standardRates, boolean customRatesSupported) {
+    this.standardRates = Collections.unmodifiableList(standardRates);
+    this.customRatesSupported = customRatesSupported;
+  }
+
+  /**
+   * Get the standard baud rates the driver accepted, in ascending order.
+   *
+   * @return the accepted standard rates
+   */
+  public List<Integer> getStandardRates() {
+    return standardRates;
+  }
+
+  /**
+   * Check whether the driver accepts arbitrary baud rates besides the standard ones.
+   *
+   * <p>This is typically true for USB serial adapters, whose rate generators can produce
+   * almost any rate. The actual rate may still be rounded; see
+   * {@link SerialPort#getConfigDiscrepancies()}.
+   *
+   * @return true if custom baud rates are available
+   */
+  public boolean isCustomRatesSupported() {
+    return customRatesSupported;
+  }
+
+  /**
+   * Check whether a baud rate can be used: it is one of the accepted standard rates, or custom
+   * rates are available.
+   *
+   * @param baudRate the baud rate
+   * @return true if the rate is supported
+   */
+  public boolean supports(int baudRate) {
+    return standardRates.contains(baudRate) || (customRatesSupported && baudRate > 0);
+  }
+
+  @Override
+  public String toString() {
+    return "SupportedBaudRates{standardRates=" + standardRates
+        + ", customRatesSupported=" + customRatesSupported + "}";
+  }
+
+}
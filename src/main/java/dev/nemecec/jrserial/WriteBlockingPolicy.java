@@ -0,0 +1,59 @@
+/*
+ * Copyright (C) 2026 Neeme Praks
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package dev.nemecec.jrserial;
+
+/**
+ * How a write reacts when the port cannot accept more data right now.
+ *
+ * <p>On non-blocking or flow-controlled ports, the operating system may report that
+ * a write would block (for example, while the receiver holds CTS low or has sent XOFF).
+ * This policy decides whether the write waits, fails, or returns what it managed to send.
+ */
+public enum WriteBlockingPolicy {
+
+  /**
+   * Retry the write until the port timeout expires (default).
+   *
+   * <p>If the timeout expires before the port accepts data, the write fails.
+   */
+  BLOCK(0),
+
+  /**
+   * Fail immediately with an {@link java.io.IOException} when the write would block.
+   */
+  ERROR(1),
+
+  /**
+   * Return immediately with the number of bytes written so far, which may be 0.
+   */
+  PARTIAL(2);
+
+  private final int value;
+
+  WriteBlockingPolicy(int value) {
+    this.value = value;
+  }
+
+  /**
+   * Get the native value for this policy.
+   *
+   * @return the native value (0=Block, 1=Error, 2=Partial)
+   */
+  public int getValue() {
+    return value;
+  }
+
+}
@@ -0,0 +1,60 @@
+/*
+ * Copyright (C) 2026 Neeme Praks
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package dev.nemecec.jrserial;
+
+/**
+ * Why writes on a port may not be making progress, as reported by
+ * {@link SerialPort#getWriteStallReason()}.
+ */
+public enum WriteStallReason {
+
+  /**
+   * The output queue is empty; nothing is waiting to be transmitted.
+   */
+  IDLE(0),
+
+  /**
+   * Bytes are waiting in the output queue and the line is still transmitting them.
+   *
+   * <p>If this persists without hardware flow control, the device or driver is not draining
+   * output (for example, a disconnected USB adapter or software flow control paused by XOFF).
+   */
+  BUFFER_FULL(1),
+
+  /**
+   * Hardware flow control is enabled and the peer has deasserted CTS, so transmission is on hold.
+   *
+   * <p>If this persists, check that the peer is ready to receive and that the cable carries the
+   * RTS/CTS lines (see {@link SerialPort#verifyHardwareFlowControl()}).
+   */
+  FLOW_CONTROL_BLOCKED(2);
+
+  private final int value;
+
+  WriteStallReason(int value) {
+    this.value = value;
+  }
+
+  /**
+   * Get the native value for this stall reason.
+   *
+   * @return the native value (0=Idle, 1=BufferFull, 2=FlowControlBlocked)
+   */
+  public int getValue() {
+    return value;
+  }
+
+}
@@ -45,4 +45,77 @@ void testParityValues() {
     assertThat(Parity.EVEN.getValue()).isEqualTo(2);
   }
 
+  @Test
+  void testWriteBlockingPolicyValues() {
+    assertThat(WriteBlockingPolicy.BLOCK.getValue()).isZero();
+    assertThat(WriteBlockingPolicy.ERROR.getValue()).isEqualTo(1);
+    assertThat(WriteBlockingPolicy.PARTIAL.getValue()).isEqualTo(2);
+  }
+
+  @Test
+  void testCrcTypeValues() {
+    assertThat(CrcType.CRC_CCITT.getValue()).isZero();
+    assertThat(CrcType.CRC_CCITT.getLength()).isEqualTo(2);
+    assertThat(CrcType.CRC32.getValue()).isEqualTo(1);
+    assertThat(CrcType.CRC32.getLength()).isEqualTo(4);
+  }
+
+  @Test
+  void testPortAccessValues() {
+    assertThat(PortAccess.OK.getValue()).isZero();
+    assertThat(PortAccess.NO_SUCH_FILE.getValue()).isEqualTo(1);
+    assertThat(PortAccess.NO_PERMISSION.getValue()).isEqualTo(2);
+  }
+
+  @Test
+  void testWriteStallReasonValues() {
+    assertThat(WriteStallReason.IDLE.getValue()).isZero();
+    assertThat(WriteStallReason.BUFFER_FULL.getValue()).isEqualTo(1);
+    assertThat(WriteStallReason.FLOW_CONTROL_BLOCKED.getValue()).isEqualTo(2);
+  }
+
+  @Test
+  void testConnectionStateValues() {
+    assertThat(ConnectionState.CONNECTED.getValue()).isZero();
+    assertThat(ConnectionState.RECONNECTING.getValue()).isEqualTo(1);
+    assertThat(ConnectionState.FAILED.getValue()).isEqualTo(2);
+  }
+
+  @Test
+  void testCapabilityValues() {
+    assertThat(Capability.KERNEL_RS485.getValue()).isZero();
+    assertThat(Capability.CUSTOM_BAUD_RATE.getValue()).isEqualTo(1);
+    assertThat(Capability.CUSTOM_DIVISOR.getValue()).isEqualTo(2);
+    assertThat(Capability.READ_CANCELLATION.getValue()).isEqualTo(3);
+    assertThat(Capability.AUTO_RECONNECT.getValue()).isEqualTo(4);
+    assertThat(Capability.NINE_BIT.getValue()).isEqualTo(5);
+    assertThat(Capability.MODEM_EVENT_COUNTERS.getValue()).isEqualTo(6);
+    assertThat(Capability.TERMIOS_CONTROL.getValue()).isEqualTo(7);
+    assertThat(Capability.SOFTWARE_FLOW_CONTROL.getValue()).isEqualTo(8);
+    assertThat(Capability.REALTIME_SCHEDULING.getValue()).isEqualTo(9);
+    assertThat(Capability.ACCESS_CHECK.getValue()).isEqualTo(10);
+    assertThat(Capability.DTR_FLOW_CONTROL.getValue()).isEqualTo(11);
+  }
+
+  @Test
+  void testOpenFailureReasonValues() {
+    assertThat(OpenFailureReason.OTHER.getValue()).isZero();
+    assertThat(OpenFailureReason.PORT_BUSY.getValue()).isEqualTo(1);
+    assertThat(OpenFailureReason.NOT_FOUND.getValue()).isEqualTo(2);
+    assertThat(OpenFailureReason.PERMISSION_DENIED.getValue()).isEqualTo(3);
+  }
+
+  @Test
+  void testControlLineValues() {
+    assertThat(ControlLine.RTS.getValue()).isZero();
+    assertThat(ControlLine.DTR.getValue()).isEqualTo(1);
+  }
+
+  @Test
+  void testAccessModeValues() {
+    assertThat(AccessMode.READ_WRITE.getValue()).isZero();
+    assertThat(AccessMode.READ_ONLY.getValue()).isEqualTo(1);
+    assertThat(AccessMode.WRITE_ONLY.getValue()).isEqualTo(2);
+  }
+
 }
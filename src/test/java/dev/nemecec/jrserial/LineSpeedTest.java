@@ -0,0 +1,52 @@
+/*
+ * Copyright (C) 2026 Neeme Praks
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package dev.nemecec.jrserial;
+
+import org.junit.jupiter.api.Test;
+
+import static org.assertj.core.api.Assertions.assertThat;
+import static org.assertj.core.api.Assertions.assertThatThrownBy;
+import static org.assertj.core.api.Assertions.within;
+
+/**
+ * Tests for LineSpeed.
+ */
+class LineSpeedTest {
+
+  @Test
+  void testParse() {
+    LineSpeed speed = LineSpeed.parse(250000, "250000\t250000");
+
+    assertThat(speed.getRequestedBaudRate()).isEqualTo(250000);
+    assertThat(speed.getInputBaudRate()).isEqualTo(250000);
+    assertThat(speed.getOutputBaudRate()).isEqualTo(250000);
+    assertThat(speed.getErrorPercent()).isZero();
+  }
+
+  @Test
+  void testErrorPercentOfRoundedRate() {
+    LineSpeed speed = LineSpeed.parse(921600, "923076\t923076");
+
+    assertThat(speed.getErrorPercent()).isCloseTo(0.16, within(0.01));
+  }
+
+  @Test
+  void testParseRejectsMalformedData() {
+    assertThatThrownBy(() -> LineSpeed.parse(9600, "9600"))
+        .isInstanceOf(IllegalArgumentException.class);
+  }
+
+}
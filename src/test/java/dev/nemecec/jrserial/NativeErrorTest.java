@@ -0,0 +1,53 @@
+/*
+ * Copyright (C) 2026 Neeme Praks
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package dev.nemecec.jrserial;
+
+import org.junit.jupiter.api.Test;
+
+import static org.assertj.core.api.Assertions.assertThat;
+import static org.assertj.core.api.Assertions.assertThatThrownBy;
+
+/**
+ * Tests for NativeError.
+ */
+class NativeErrorTest {
+
+  @Test
+  void testParse() {
+    NativeError error = NativeError.parse("Read failed: Operation timed out\tsrc/lib.rs\t1542");
+
+    assertThat(error.getMessage()).isEqualTo("Read failed: Operation timed out");
+    assertThat(error.getFile()).isEqualTo("src/lib.rs");
+    assertThat(error.getLine()).isEqualTo(1542);
+    assertThat(error).hasToString("Read failed: Operation timed out (at src/lib.rs:1542)");
+  }
+
+  @Test
+  void testParseKeepsTabsInMessage() {
+    NativeError error = NativeError.parse("a\tb\tsrc/lib.rs\t7");
+
+    assertThat(error.getMessage()).isEqualTo("a\tb");
+    assertThat(error.getFile()).isEqualTo("src/lib.rs");
+    assertThat(error.getLine()).isEqualTo(7);
+  }
+
+  @Test
+  void testParseRejectsMalformedData() {
+    assertThatThrownBy(() -> NativeError.parse("src/lib.rs\t12"))
+        .isInstanceOf(IllegalArgumentException.class);
+  }
+
+}
@@ -0,0 +1,53 @@
+/*
+ * Copyright (C) 2026 Neeme Praks
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package dev.nemecec.jrserial;
+
+import org.junit.jupiter.api.Test;
+
+import static org.assertj.core.api.Assertions.assertThat;
+import static org.assertj.core.api.Assertions.assertThatThrownBy;
+
+/**
+ * Tests for PortStatus.
+ */
+class PortStatusTest {
+
+  @Test
+  void testParse() {
+    PortStatus status = PortStatus.parse("12\t3\t9\t1");
+
+    assertThat(status.getBytesToRead()).isEqualTo(12);
+    assertThat(status.getBytesToWrite()).isEqualTo(3);
+    assertThat(status.getModemStatus()).isEqualTo(PortStatus.CTS | PortStatus.CD);
+    assertThat(status.isModemStatusAvailable()).isTrue();
+    assertThat(status.isKernelRs485Active()).isTrue();
+  }
+
+  @Test
+  void testParseUnavailableModemStatus() {
+    PortStatus status = PortStatus.parse("0\t0\t-1\t0");
+
+    assertThat(status.isModemStatusAvailable()).isFalse();
+    assertThat(status.isKernelRs485Active()).isFalse();
+  }
+
+  @Test
+  void testParseRejectsMalformedData() {
+    assertThatThrownBy(() -> PortStatus.parse("1\t2"))
+        .isInstanceOf(IllegalArgumentException.class);
+  }
+
+}
@@ -0,0 +1,42 @@
+/*
+ * Copyright (C) 2026 Neeme Praks
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package dev.nemecec.jrserial;
+
+import org.junit.jupiter.api.Test;
+
+import static org.assertj.core.api.Assertions.assertThat;
+import static org.assertj.core.api.Assertions.assertThatThrownBy;
+
+/**
+ * Tests for QueueHighWater.
+ */
+class QueueHighWaterTest {
+
+  @Test
+  void testParse() {
+    QueueHighWater highWater = QueueHighWater.parse("4095\t17");
+
+    assertThat(highWater.getMaxBytesToRead()).isEqualTo(4095);
+    assertThat(highWater.getMaxBytesToWrite()).isEqualTo(17);
+  }
+
+  @Test
+  void testParseRejectsMalformedData() {
+    assertThatThrownBy(() -> QueueHighWater.parse("12"))
+        .isInstanceOf(IllegalArgumentException.class);
+  }
+
+}
@@ -0,0 +1,43 @@
+/*
+ * Copyright (C) 2026 Neeme Praks
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package dev.nemecec.jrserial;
+
+import org.junit.jupiter.api.Test;
+
+import static org.assertj.core.api.Assertions.assertThat;
+import static org.assertj.core.api.Assertions.assertThatThrownBy;
+
+/**
+ * Tests for RoundTripLatency.
+ */
+class RoundTripLatencyTest {
+
+  @Test
+  void testParse() {
+    RoundTripLatency latency = RoundTripLatency.parse("850\t1200\t4100");
+
+    assertThat(latency.getMinMicros()).isEqualTo(850);
+    assertThat(latency.getAvgMicros()).isEqualTo(1200);
+    assertThat(latency.getMaxMicros()).isEqualTo(4100);
+  }
+
+  @Test
+  void testParseRejectsMalformedData() {
+    assertThatThrownBy(() -> RoundTripLatency.parse("850\t1200"))
+        .isInstanceOf(IllegalArgumentException.class);
+  }
+
+}
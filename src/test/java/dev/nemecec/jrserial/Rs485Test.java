@@ -22,6 +22,7 @@
 import org.slf4j.LoggerFactory;
 
 import static org.assertj.core.api.Assertions.assertThat;
+import static org.assertj.core.api.Assertions.assertThatThrownBy;
 import static org.junit.jupiter.api.Assumptions.assumeTrue;
 
 /**
@@ -130,6 +131,18 @@ void testBuilderWithRs485Config() {
     assertThat(port.getRs485Config().getDelayBeforeSendMicros()).isEqualTo(50);
   }
 
+  @Test
+  void testBuilderRejectsQuietOpenWithRs485() {
+    SerialPort.Builder builder = SerialPort.builder()
+        .portName("/dev/ttyUSB0")
+        .quietOpen(true)
+        .rs485();
+
+    assertThatThrownBy(builder::build)
+        .isInstanceOf(IllegalArgumentException.class)
+        .hasMessageContaining("Quiet open");
+  }
+
   @Test
   @EnabledOnOs({OS.MAC, OS.LINUX})
   void testKernelRs485NotActiveWhenNoPort() {
@@ -16,9 +16,13 @@
 package dev.nemecec.jrserial;
 
 import org.junit.jupiter.api.Test;
+import org.junit.jupiter.api.condition.EnabledOnOs;
+import org.junit.jupiter.api.condition.OS;
 import org.slf4j.Logger;
 import org.slf4j.LoggerFactory;
 
+import java.io.IOException;
+import java.nio.charset.StandardCharsets;
 import java.util.List;
 
 import static org.assertj.core.api.Assertions.assertThat;
@@ -46,6 +50,99 @@ void testListPorts() {
     }
   }
 
+  @Test
+  void testListPortsJson() {
+    try {
+      String json = SerialPort.listPortsJson();
+      assertThat(json).startsWith("[").endsWith("]");
+    }
+    catch (UnsatisfiedLinkError e) {
+      // Skip test if native library can't be loaded (architecture mismatch)
+      LOG.warn("Skipping test - native library not available for current architecture: {}", e.getMessage());
+      org.junit.jupiter.api.Assumptions.assumeTrue(false, "Native library not available for current architecture");
+    }
+  }
+
+  @Test
+  void testGetEnumerationWarnings() {
+    try {
+      SerialPort.listPorts();
+      List<String> warnings = SerialPort.getEnumerationWarnings();
+      assertThat(warnings).isNotNull().doesNotContain("");
+    }
+    catch (UnsatisfiedLinkError e) {
+      // Skip test if native library can't be loaded (architecture mismatch)
+      LOG.warn("Skipping test - native library not available for current architecture: {}", e.getMessage());
+      org.junit.jupiter.api.Assumptions.assumeTrue(false, "Native library not available for current architecture");
+    }
+  }
+
+  @Test
+  void testFormatDump() {
+    try {
+      byte[] data = "xHello\r\nWorld!".getBytes(StandardCharsets.US_ASCII);
+      assertThat(SerialPort.formatDump(data, 1, data.length - 1, 8)).isEqualTo(
+          "00000000  48 65 6c 6c 6f 0d 0a 57  |Hello..W|\n"
+              + "00000008  6f 72 6c 64 21           |orld!|\n");
+      assertThat(SerialPort.formatDump(new byte[0])).isEmpty();
+      assertThatThrownBy(() -> SerialPort.formatDump(data, 0, data.length, 0))
+          .isInstanceOf(IllegalArgumentException.class);
+    }
+    catch (UnsatisfiedLinkError e) {
+      // Skip test if native library can't be loaded (architecture mismatch)
+      LOG.warn("Skipping test - native library not available for current architecture: {}", e.getMessage());
+      org.junit.jupiter.api.Assumptions.assumeTrue(false, "Native library not available for current architecture");
+    }
+  }
+
+  @Test
+  void testCrcCheckValues() {
+    try {
+      byte[] data = "x123456789".getBytes(StandardCharsets.US_ASCII);
+      assertThat(SerialPort.crc32(data, 1, 9)).isEqualTo(0xCBF43926L);
+      assertThat(SerialPort.crcCcitt(data, 1, 9)).isEqualTo(0x29B1);
+
+      java.util.zip.CRC32 reference = new java.util.zip.CRC32();
+      reference.update(data, 0, data.length);
+      assertThat(SerialPort.crc32(data, 0, data.length)).isEqualTo(reference.getValue());
+      assertThatThrownBy(() -> SerialPort.crcCcitt(data, 2, 9))
+          .isInstanceOf(IndexOutOfBoundsException.class);
+    }
+    catch (UnsatisfiedLinkError e) {
+      // Skip test if native library can't be loaded (architecture mismatch)
+      LOG.warn("Skipping test - native library not available for current architecture: {}", e.getMessage());
+      org.junit.jupiter.api.Assumptions.assumeTrue(false, "Native library not available for current architecture");
+    }
+  }
+
+  @Test
+  @EnabledOnOs(OS.LINUX)
+  void testGetCapabilitiesOnLinux() {
+    try {
+      assertThat(SerialPort.getCapabilities())
+          .contains(Capability.KERNEL_RS485, Capability.CUSTOM_BAUD_RATE,
+              Capability.TERMIOS_CONTROL);
+    }
+    catch (UnsatisfiedLinkError e) {
+      // Skip test if native library can't be loaded (architecture mismatch)
+      LOG.warn("Skipping test - native library not available for current architecture: {}", e.getMessage());
+      org.junit.jupiter.api.Assumptions.assumeTrue(false, "Native library not available for current architecture");
+    }
+  }
+
+  @Test
+  @EnabledOnOs(OS.LINUX)
+  void testCheckAccessReportsMissingDevice() throws IOException {
+    try {
+      assertThat(SerialPort.checkAccess("/dev/ttyDoesNotExist99")).isEqualTo(PortAccess.NO_SUCH_FILE);
+    }
+    catch (UnsatisfiedLinkError e) {
+      // Skip test if native library can't be loaded (architecture mismatch)
+      LOG.warn("Skipping test - native library not available for current architecture: {}", e.getMessage());
+      org.junit.jupiter.api.Assumptions.assumeTrue(false, "Native library not available for current architecture");
+    }
+  }
+
   @Test
   void testBuilderRequiresPortName() {
     SerialPort.Builder builder = SerialPort.builder();
@@ -54,6 +151,27 @@ void testBuilderRequiresPortName() {
         .hasMessageContaining("Port name must be set");
   }
 
+  @Test
+  void testBuilderAcceptsUsbSerialNumberInsteadOfPortName() {
+    SerialPort port = SerialPort.builder()
+        .usbSerialNumber("A12345")
+        .build();
+
+    assertThat(port.getUsbSerialNumber()).isEqualTo("A12345");
+    assertThat(port.getPortName()).isNull();
+    assertThat(port.getSelector()).isEqualTo("A12345");
+  }
+
+  @Test
+  void testBuilderRejectsBlankUsbSerialNumber() {
+    assertThatThrownBy(() -> SerialPort.builder()
+        .portName("COM1")
+        .usbSerialNumber("  ")
+        .build())
+        .isInstanceOf(IllegalArgumentException.class)
+        .hasMessageContaining("USB serial number must not be blank");
+  }
+
   @Test
   void testBuilderDefaults() {
     SerialPort port = SerialPort.builder()
@@ -61,6 +179,7 @@ void testBuilderDefaults() {
         .build();
 
     assertThat(port.getPortName()).isEqualTo("COM1");
+    assertThat(port.getSelector()).isEqualTo("COM1");
     assertThat(port.getBaudRate()).isEqualTo(9600);
     assertThat(port.getDataBits()).isEqualTo(DataBits.EIGHT);
     assertThat(port.getStopBits()).isEqualTo(StopBits.ONE);
@@ -86,6 +205,107 @@ void testBuilderCustomValues() {
     assertThat(port.getParity()).isEqualTo(Parity.EVEN);
   }
 
+  @Test
+  void testBuilderSettleDelay() {
+    SerialPort port = SerialPort.builder()
+        .portName("COM1")
+        .settleMs(50)
+        .build();
+
+    assertThat(port.getSettleMs()).isEqualTo(50);
+    assertThat(SerialPort.builder().portName("COM1").build().getSettleMs()).isZero();
+    assertThatThrownBy(() -> SerialPort.builder().settleMs(-1))
+        .isInstanceOf(IllegalArgumentException.class);
+  }
+
+  @Test
+  void testBuilderBaudTolerance() {
+    SerialPort port = SerialPort.builder()
+        .portName("COM1")
+        .baudTolerancePercent(2)
+        .build();
+
+    assertThat(port.getBaudTolerancePercent()).isEqualTo(2);
+    assertThat(SerialPort.builder().portName("COM1").build().getBaudTolerancePercent()).isZero();
+    assertThatThrownBy(() -> SerialPort.builder().baudTolerancePercent(-1))
+        .isInstanceOf(IllegalArgumentException.class);
+  }
+
+  @Test
+  void testBuilderAccessMode() {
+    assertThat(SerialPort.builder().portName("COM1").build().getAccessMode())
+        .isEqualTo(AccessMode.READ_WRITE);
+    assertThat(SerialPort.builder().portName("COM1").accessMode(AccessMode.READ_ONLY).build().getAccessMode())
+        .isEqualTo(AccessMode.READ_ONLY);
+    assertThatThrownBy(() -> SerialPort.builder().portName("COM1")
+        .accessMode(AccessMode.READ_ONLY)
+        .rs485Config(Rs485Config.builder().enabled(true).build())
+        .build())
+        .isInstanceOf(IllegalArgumentException.class)
+        .hasMessageContaining("write access");
+    assertThatThrownBy(() -> SerialPort.builder().portName("COM1")
+        .accessMode(AccessMode.WRITE_ONLY)
+        .quietOpen(true)
+        .build())
+        .isInstanceOf(IllegalArgumentException.class);
+  }
+
+  @Test
+  void testOpenManyRejectsInvalidArguments() {
+    assertThatThrownBy(() -> SerialPort.openMany(null, "COM1"))
+        .isInstanceOf(IllegalArgumentException.class);
+    assertThatThrownBy(() -> SerialPort.openMany(SerialPort.builder(), (String[]) null))
+        .isInstanceOf(IllegalArgumentException.class);
+    assertThatThrownBy(() -> SerialPort.openMany(SerialPort.builder(), "COM1", null))
+        .isInstanceOf(IllegalArgumentException.class);
+    assertThatThrownBy(() -> SerialPort.openMany(SerialPort.builder().quietOpen(true), "COM1"))
+        .isInstanceOf(IllegalArgumentException.class)
+        .hasMessageContaining("quiet open");
+  }
+
+  @Test
+  void testGetSupportedBaudRatesRejectsNullPortName() {
+    assertThatThrownBy(() -> SerialPort.getSupportedBaudRates(null))
+        .isInstanceOf(NullPointerException.class);
+  }
+
+  @Test
+  void testSetCloseTimingRejectsOutOfRangeValues() {
+    SerialPort port = SerialPort.builder().portName("COM1").build();
+
+    assertThatThrownBy(() -> port.setCloseTiming(-1, 0))
+        .isInstanceOf(IllegalArgumentException.class);
+    assertThatThrownBy(() -> port.setCloseTiming(0, SerialPort.CLOSING_WAIT_NONE + 1))
+        .isInstanceOf(IllegalArgumentException.class);
+    assertThatThrownBy(() -> port.setCloseTiming(50, SerialPort.CLOSING_WAIT_NONE))
+        .isInstanceOf(IOException.class)
+        .hasMessageContaining("not open");
+  }
+
+  @Test
+  void testSetVminVtimeRejectsOutOfRangeValues() {
+    SerialPort port = SerialPort.builder().portName("COM1").build();
+
+    assertThatThrownBy(() -> port.setVminVtime(256, 0))
+        .isInstanceOf(IllegalArgumentException.class);
+    assertThatThrownBy(() -> port.setVminVtime(0, -1))
+        .isInstanceOf(IllegalArgumentException.class);
+    assertThatThrownBy(() -> port.setVminVtime(1, 0))
+        .isInstanceOf(IOException.class)
+        .hasMessageContaining("not open");
+  }
+
+  @Test
+  void testEnableAutoReconnectRejectsNonPositiveInterval() {
+    SerialPort port = SerialPort.builder().portName("COM1").build();
+
+    assertThatThrownBy(() -> port.enableAutoReconnect(0))
+        .isInstanceOf(IllegalArgumentException.class);
+    assertThatThrownBy(() -> port.enableAutoReconnect(100))
+        .isInstanceOf(IOException.class)
+        .hasMessageContaining("not open");
+  }
+
   @Test
   void testOperationsOnClosedPortThrowException() {
     SerialPort port = SerialPort.builder()
@@ -23,10 +23,22 @@
 import org.slf4j.Logger;
 import org.slf4j.LoggerFactory;
 
+import java.io.EOFException;
 import java.io.IOException;
+import java.nio.ByteBuffer;
+import java.nio.ByteOrder;
 import java.nio.charset.StandardCharsets;
+import java.nio.file.Files;
+import java.nio.file.Path;
+import java.nio.file.Paths;
+import java.util.ArrayList;
+import java.util.Arrays;
+import java.util.Collections;
+import java.util.List;
+import java.util.concurrent.CountDownLatch;
 
 import static org.assertj.core.api.Assertions.assertThat;
+import static org.assertj.core.api.Assertions.assertThatThrownBy;
 import static org.junit.jupiter.api.Assumptions.assumeTrue;
 
 /**
@@ -845,4 +857,1899 @@ void testReadLineWithCharset() throws IOException, InterruptedException {
       LOG.info("readLine(charset) test passed");
     }
   }
+
+  @Test
+  void testTransactDiscardsStaleInput() throws IOException, InterruptedException {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort master = support.createPort(support.getPort1());
+         SerialPort slave = support.createPort(support.getPort2())) {
+
+      master.open();
+      slave.open();
+
+      // Leave stale bytes in the master's input buffer
+      slave.write("stale".getBytes(StandardCharsets.UTF_8));
+      slave.flush();
+      Thread.sleep(100);
+
+      Thread responder = new Thread(() -> {
+        try {
+          byte[] request = slave.readExactly(4);
+          if ("PING".equals(new String(request, StandardCharsets.UTF_8))) {
+            slave.write("PONG".getBytes(StandardCharsets.UTF_8));
+            slave.flush();
+          }
+        }
+        catch (IOException e) {
+          LOG.warn("Responder failed", e);
+        }
+      });
+      responder.start();
+
+      byte[] response = new byte[4];
+      int bytesRead = master.transact("PING".getBytes(StandardCharsets.UTF_8), response, 2000);
+      responder.join();
+
+      assertThat(bytesRead).isEqualTo(4);
+      assertThat(new String(response, StandardCharsets.UTF_8)).isEqualTo("PONG");
+
+      LOG.info("transact() test passed");
+    }
+  }
+
+  @Test
+  void testReadTimestamped() throws IOException, InterruptedException {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort sender = support.createPort(support.getPort1());
+         SerialPort receiver = support.createPort(support.getPort2())) {
+
+      sender.open();
+      receiver.open();
+
+      long before = SerialPort.monotonicNanos();
+      sender.write("tick".getBytes(StandardCharsets.UTF_8));
+      sender.flush();
+
+      byte[] buffer = new byte[4];
+      int bytesRead = receiver.readTimestamped(buffer, 0, buffer.length);
+      long after = SerialPort.monotonicNanos();
+
+      assertThat(bytesRead).isPositive();
+      assertThat(receiver.getLastReceiveTimeNanos()).isBetween(before, after);
+
+      LOG.info("readTimestamped() test passed");
+    }
+  }
+
+  @Test
+  @EnabledOnOs(OS.LINUX)
+  void testCancelRead() throws IOException, InterruptedException {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort port = support.createPort(support.getPort1(), 10000)) {
+      port.open();
+
+      Thread canceller = new Thread(() -> {
+        try {
+          Thread.sleep(200);
+          port.cancelRead();
+        }
+        catch (IOException | InterruptedException e) {
+          LOG.warn("Cancel failed", e);
+        }
+      });
+      canceller.start();
+
+      long start = System.nanoTime();
+      assertThatThrownBy(() -> port.read(new byte[16]))
+          .isInstanceOf(java.io.InterruptedIOException.class)
+          .hasMessageContaining("cancelled");
+      long elapsedMs = (System.nanoTime() - start) / 1_000_000;
+      canceller.join();
+
+      assertThat(elapsedMs).isLessThan(5000);
+
+      LOG.info("cancelRead() test passed after {}ms", elapsedMs);
+    }
+  }
+
+  @Test
+  void testPollReadable() throws IOException, InterruptedException {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort sender = support.createPort(support.getPort1());
+         SerialPort receiver = support.createPort(support.getPort2())) {
+
+      sender.open();
+      receiver.open();
+
+      List<SerialPort> ports = Arrays.asList(sender, receiver);
+      assertThat(SerialPort.pollReadable(ports, 100)).isEmpty();
+
+      sender.write("ping".getBytes(StandardCharsets.UTF_8));
+      sender.flush();
+
+      assertThat(SerialPort.pollReadable(ports, 2000)).containsExactly(receiver);
+
+      LOG.info("pollReadable() test passed");
+    }
+  }
+
+  @Test
+  @EnabledOnOs(OS.LINUX)
+  void testRawModeTransmitsLineEndingsExactly() throws IOException, InterruptedException {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort sender = support.createPort(support.getPort1());
+         SerialPort receiver = support.createPort(support.getPort2())) {
+
+      sender.open();
+      receiver.open();
+
+      byte[] data = new byte[] { 0x0A, 0x0D, 0x0A, 0x0A, 0x0D };
+      sender.write(data);
+      sender.flush();
+
+      byte[] raw = receiver.readExactly(data.length);
+      assertThat(raw).containsExactly(data);
+
+      // Text mode translates LF to CR LF on output
+      sender.setRawMode(false);
+      sender.write(new byte[] { 0x0A });
+      sender.flush();
+
+      byte[] translated = receiver.readExactly(2);
+      assertThat(translated).containsExactly(0x0D, 0x0A);
+
+      LOG.info("Raw mode test passed");
+    }
+  }
+
+  @Test
+  void testReadFullyReportsPartialTimeout() throws IOException, InterruptedException {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort sender = support.createPort(support.getPort1());
+         SerialPort receiver = support.createPort(support.getPort2())) {
+
+      sender.open();
+      receiver.open();
+
+      sender.write("abc".getBytes(StandardCharsets.UTF_8));
+      sender.flush();
+
+      byte[] buffer = new byte[8];
+      int count = receiver.readFully(buffer, 500);
+      assertThat(count).isEqualTo(3);
+      assertThat(receiver.lastReadTimedOut()).isTrue();
+
+      sender.write("12345678".getBytes(StandardCharsets.UTF_8));
+      sender.flush();
+
+      count = receiver.readFully(buffer, 2000);
+      assertThat(count).isEqualTo(8);
+      assertThat(receiver.lastReadTimedOut()).isFalse();
+
+      // A zero timeout takes what has arrived without waiting, and does not time out
+      sender.write("xy".getBytes(StandardCharsets.UTF_8));
+      sender.flush();
+      Thread.sleep(100);
+      assertThat(receiver.readFully(buffer, 0)).isEqualTo(2);
+      assertThat(receiver.lastReadTimedOut()).isFalse();
+      assertThat(receiver.readFully(buffer, 0)).isZero();
+      assertThat(receiver.lastReadTimedOut()).isFalse();
+
+      LOG.info("readFully() partial timeout test passed");
+    }
+  }
+
+  @Test
+  void testListenOnlyRejectsWrites() throws IOException, InterruptedException {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort sender = support.createPort(support.getPort1());
+         SerialPort receiver = support.createPort(support.getPort2())) {
+
+      sender.open();
+      receiver.open();
+
+      sender.setListenOnly(true);
+      assertThatThrownBy(() -> sender.write("blocked".getBytes(StandardCharsets.UTF_8)))
+          .isInstanceOf(IOException.class)
+          .hasMessageContaining("listen-only");
+
+      sender.setListenOnly(false);
+      sender.write("ok".getBytes(StandardCharsets.UTF_8));
+      sender.flush();
+
+      assertThat(receiver.readExactly(2)).containsExactly('o', 'k');
+
+      LOG.info("Listen-only test passed");
+    }
+  }
+
+  @Test
+  void testOpenAndVerifyAcceptsPseudoTerminal() throws IOException {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort port = support.createPort(support.getPort1())) {
+      port.openAndVerify();
+
+      assertThat(port.isOpen()).isTrue();
+
+      LOG.info("openAndVerify() test passed");
+    }
+  }
+
+  @Test
+  void testOpenThroughSymlinkWithSpecialCharacters() throws IOException, InterruptedException {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+    // The native side opens paths as UTF-8, so the JVM must create the link with the same bytes
+    assumeTrue("UTF-8".equalsIgnoreCase(System.getProperty("sun.jnu.encoding")),
+        "File system encoding is not UTF-8");
+
+    Path dir = Files.createTempDirectory("jrserial");
+    Path link = dir.resolve("serial port ä€🔌");
+    Files.createSymbolicLink(link, Paths.get(support.getPort1()));
+
+    try (SerialPort sender = support.createPort(link.toString());
+         SerialPort receiver = support.createPort(support.getPort2())) {
+      sender.open();
+      receiver.open();
+
+      byte[] message = "via symlink".getBytes(StandardCharsets.UTF_8);
+      sender.write(message);
+      sender.flush();
+
+      byte[] buffer = new byte[message.length];
+      int bytesRead = receiver.readFully(buffer, 1000);
+
+      assertThat(bytesRead).isEqualTo(message.length);
+      assertThat(buffer).isEqualTo(message);
+
+      LOG.info("Opened port through symlink '{}'", link);
+    }
+    finally {
+      Files.deleteIfExists(link);
+      Files.deleteIfExists(dir);
+    }
+  }
+
+  @Test
+  void testQueueHighWater() throws IOException, InterruptedException {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort sender = support.createPort(support.getPort1());
+         SerialPort receiver = support.createPort(support.getPort2())) {
+      sender.open();
+      receiver.open();
+
+      byte[] message = "high water".getBytes(StandardCharsets.UTF_8);
+      sender.write(message);
+      sender.flush();
+      Thread.sleep(100);
+
+      byte[] buffer = new byte[message.length];
+      receiver.readFully(buffer, 1000);
+
+      assertThat(receiver.getQueueHighWater().getMaxBytesToRead()).isEqualTo(message.length);
+
+      receiver.resetQueueHighWater();
+
+      assertThat(receiver.getQueueHighWater().getMaxBytesToRead()).isZero();
+
+      LOG.info("Queue high-water test passed");
+    }
+  }
+
+  @Test
+  void testZeroLengthReadAndWriteReturnImmediately() throws IOException {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort port = support.createPort(support.getPort1(), 5000)) {
+      port.open();
+
+      long start = System.nanoTime();
+      assertThat(port.read(new byte[8], 0, 0)).isZero();
+      assertThat(port.readTimestamped(new byte[8], 0, 0)).isZero();
+      assertThat(port.getLastReceiveTimeNanos()).isZero();
+      assertThat(port.write(new byte[8], 0, 0)).isZero();
+      long elapsedMs = (System.nanoTime() - start) / 1_000_000;
+
+      // A real read would block for the 5 s timeout
+      assertThat(elapsedMs).isLessThan(1000);
+
+      LOG.info("Zero-length read/write test passed in {} ms", elapsedMs);
+    }
+  }
+
+  @Test
+  void testIsValid() throws IOException {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort port = support.createPort(support.getPort1())) {
+      assertThat(port.isValid()).isFalse();
+
+      port.open();
+      assertThat(port.isValid()).isTrue();
+
+      port.close();
+      assertThat(port.isValid()).isFalse();
+
+      LOG.info("isValid() test passed");
+    }
+  }
+
+  @Test
+  @EnabledOnOs(OS.LINUX)
+  void testReadOnlyAndWriteOnlyAccess() throws IOException {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    SerialPort sender = SerialPort.builder()
+        .portName(support.getPort1())
+        .baudRate(115200)
+        .timeout(1000)
+        .accessMode(AccessMode.WRITE_ONLY)
+        .build();
+    SerialPort monitor = SerialPort.builder()
+        .portName(support.getPort2())
+        .baudRate(115200)
+        .timeout(1000)
+        .accessMode(AccessMode.READ_ONLY)
+        .build();
+
+    try (SerialPort s = sender; SerialPort m = monitor) {
+      s.open();
+      m.open();
+
+      byte[] message = "one way".getBytes(StandardCharsets.UTF_8);
+      s.write(message);
+
+      byte[] buffer = new byte[message.length];
+      assertThat(m.readFully(buffer, 1000)).isEqualTo(message.length);
+      assertThat(buffer).isEqualTo(message);
+
+      assertThatThrownBy(() -> m.write(message))
+          .isInstanceOf(IOException.class)
+          .hasMessageContaining("read-only");
+      assertThatThrownBy(() -> s.read(buffer))
+          .isInstanceOf(IOException.class)
+          .hasMessageContaining("write-only");
+
+      LOG.info("Access mode test passed");
+    }
+  }
+
+  @Test
+  void testCloseAll() throws IOException {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort port1 = support.createPort(support.getPort1());
+         SerialPort port2 = support.createPort(support.getPort2())) {
+      port1.open();
+      port2.open();
+
+      assertThat(SerialPort.closeAll()).isGreaterThanOrEqualTo(2);
+
+      assertThat(port1.isOpen()).isFalse();
+      assertThat(port2.isOpen()).isFalse();
+      assertThatThrownBy(() -> port1.write(new byte[] {1}))
+          .isInstanceOf(IOException.class)
+          .hasMessageContaining("not open");
+
+      // Double close after closeAll is harmless, and the ports can be reopened
+      port1.close();
+      port1.open();
+      assertThat(port1.isOpen()).isTrue();
+
+      LOG.info("closeAll() test passed");
+    }
+  }
+
+  @Test
+  void testWriteWithDeadline() throws IOException {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort sender = support.createPort(support.getPort1());
+         SerialPort receiver = support.createPort(support.getPort2())) {
+
+      sender.open();
+      receiver.open();
+
+      byte[] data = "deadline".getBytes(StandardCharsets.UTF_8);
+      int written = sender.writeWithDeadline(data, 0, data.length, System.currentTimeMillis() + 2000);
+      assertThat(written).isEqualTo(data.length);
+
+      byte[] buffer = new byte[data.length];
+      assertThat(receiver.readFully(buffer, 2000)).isEqualTo(data.length);
+      assertThat(buffer).isEqualTo(data);
+
+      // A deadline that has already passed sends nothing
+      assertThat(sender.writeWithDeadline(data, 0, data.length, System.currentTimeMillis() - 1)).isZero();
+
+      LOG.info("writeWithDeadline() test passed");
+    }
+  }
+
+  @Test
+  void testGetLastWarningWithoutOverrun() throws IOException {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort sender = support.createPort(support.getPort1());
+         SerialPort receiver = support.createPort(support.getPort2())) {
+
+      assertThat(receiver.getLastWarning()).isNull();
+
+      sender.open();
+      receiver.open();
+
+      sender.write("ok".getBytes(StandardCharsets.UTF_8));
+      sender.flush();
+      assertThat(receiver.readFully(new byte[2], 2000)).isEqualTo(2);
+
+      // Pseudo terminals report no overrun counters, so a read never records a warning
+      assertThat(receiver.getLastWarning()).isNull();
+
+      LOG.info("getLastWarning() test passed");
+    }
+  }
+
+  @Test
+  @EnabledOnOs(OS.LINUX)
+  void testAutoReconnectReportsConnectedState() throws IOException {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort port = support.createPort(support.getPort1())) {
+      port.open();
+      assertThat(port.getConnectionState()).isEqualTo(ConnectionState.CONNECTED);
+
+      port.enableAutoReconnect(50);
+      assertThat(port.getConnectionState()).isEqualTo(ConnectionState.CONNECTED);
+
+      port.disableAutoReconnect();
+      assertThat(port.getConnectionState()).isEqualTo(ConnectionState.CONNECTED);
+
+      // Closing must stop a running reconnect thread
+      port.enableAutoReconnect(50);
+
+      LOG.info("Auto reconnect state test passed");
+    }
+  }
+
+  @Test
+  @EnabledOnOs(OS.LINUX)
+  void testThreeWirePtyOpensAndIgnoresModemLines() throws IOException {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    // A PTY has no carrier detect, like a 3-wire TX/RX/GND connection
+    try (SerialPort sender = support.createPort(support.getPort1());
+         SerialPort receiver = support.createPort(support.getPort2())) {
+
+      long start = System.nanoTime();
+      sender.open();
+      receiver.open();
+      assertThat((System.nanoTime() - start) / 1_000_000).isLessThan(2000);
+
+      sender.setIgnoreModemLines(true);
+      sender.write("3w".getBytes(StandardCharsets.UTF_8));
+      sender.flush();
+      assertThat(receiver.readFully(new byte[2], 2000)).isEqualTo(2);
+
+      sender.setIgnoreModemLines(false);
+      sender.setIgnoreModemLines(true);
+
+      LOG.info("3-wire PTY open test passed");
+    }
+  }
+
+  @Test
+  void testOpenFailureReasons() throws IOException {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort port = support.createPort(support.getPort1());
+         SerialPort other = support.createPort(support.getPort1());
+         SerialPort missing = support.createPort("/dev/jrserial-no-such-port")) {
+
+      port.open();
+
+      // The first port holds exclusive access
+      assertThatThrownBy(other::open)
+          .isInstanceOf(PortOpenException.class)
+          .hasMessageContaining("in use")
+          .extracting(e -> ((PortOpenException) e).getReason())
+          .isEqualTo(OpenFailureReason.PORT_BUSY);
+
+      assertThatThrownBy(missing::open)
+          .isInstanceOf(PortOpenException.class)
+          .extracting(e -> ((PortOpenException) e).getReason())
+          .isEqualTo(OpenFailureReason.NOT_FOUND);
+
+      LOG.info("Open failure reason test passed");
+    }
+  }
+
+  @Test
+  void testReadUntilIdleEndsFrameOnGap() throws Exception {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort sender = support.createPort(support.getPort1());
+         SerialPort receiver = support.createPort(support.getPort2())) {
+
+      sender.open();
+      receiver.open();
+
+      byte[] buffer = new byte[64];
+      // Nothing arrives: wait for the overall timeout
+      assertThat(receiver.readUntilIdle(buffer, 0, buffer.length, 20, 200)).isZero();
+
+      Thread writer = new Thread(() -> {
+        try {
+          sender.write("frame1".getBytes(StandardCharsets.UTF_8));
+          sender.flush();
+          Thread.sleep(300);
+          sender.write("frame2".getBytes(StandardCharsets.UTF_8));
+          sender.flush();
+        }
+        catch (IOException | InterruptedException e) {
+          throw new RuntimeException(e);
+        }
+      });
+      writer.start();
+
+      int count = receiver.readUntilIdle(buffer, 0, buffer.length, 100, 2000);
+      assertThat(new String(buffer, 0, count, StandardCharsets.UTF_8)).isEqualTo("frame1");
+      count = receiver.readUntilIdle(buffer, 0, buffer.length, 100, 2000);
+      assertThat(new String(buffer, 0, count, StandardCharsets.UTF_8)).isEqualTo("frame2");
+      writer.join();
+
+      // maxLen ends the frame early
+      sender.write("abcdef".getBytes(StandardCharsets.UTF_8));
+      sender.flush();
+      assertThat(receiver.readUntilIdle(buffer, 0, 4, 100, 2000)).isEqualTo(4);
+
+      LOG.info("readUntilIdle() test passed");
+    }
+  }
+
+  @Test
+  @EnabledOnOs(OS.LINUX)
+  void testSetVminVtimeInterCharacterTimer() throws IOException {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort sender = support.createPort(support.getPort1());
+         SerialPort receiver = support.createPort(support.getPort2(), 2000)) {
+
+      sender.open();
+      receiver.open();
+
+      // Wait for 8 bytes, or 0.2 s of silence after the first one
+      receiver.setVminVtime(8, 2);
+      sender.write("abc".getBytes(StandardCharsets.UTF_8));
+      sender.flush();
+
+      byte[] buffer = new byte[8];
+      assertThat(receiver.read(buffer)).isEqualTo(3);
+
+      receiver.setVminVtime(1, 0);
+
+      LOG.info("setVminVtime() test passed");
+    }
+  }
+
+  @Test
+  @EnabledOnOs(OS.LINUX)
+  void testGetRingCountFailsWithoutDriverCounters() throws IOException {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort port = support.createPort(support.getPort1())) {
+      port.open();
+
+      // Pseudo terminals have no modem lines and keep no interrupt counters
+      assertThatThrownBy(port::getRingCount)
+          .isInstanceOf(IOException.class)
+          .hasMessageContaining("ring count");
+
+      LOG.info("getRingCount() unsupported driver test passed");
+    }
+  }
+
+  @Test
+  void testOpenManyReportsFailuresWithoutAbortingOthers() throws IOException {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    OpenManyResult result = SerialPort.openMany(
+        SerialPort.builder().baudRate(9600),
+        support.getPort1(), "/dev/jrserial-no-such-port", support.getPort2());
+    try {
+      assertThat(result.getPorts()).hasSize(3);
+      assertThat(result.getOpenedPorts()).hasSize(2);
+      assertThat(result.getPorts().get(1).isOpen()).isFalse();
+      assertThat(result.isAllOpened()).isFalse();
+      assertThat(result.getErrorSummary())
+          .contains("1 of 3")
+          .contains("/dev/jrserial-no-such-port");
+
+      SerialPort sender = result.getPorts().get(0);
+      SerialPort receiver = result.getPorts().get(2);
+      sender.write("bulk".getBytes(StandardCharsets.UTF_8));
+      sender.flush();
+      byte[] buffer = new byte[4];
+      assertThat(receiver.read(buffer)).isEqualTo(4);
+
+      LOG.info("openMany() test passed");
+    }
+    finally {
+      for (SerialPort port : result.getPorts()) {
+        port.close();
+      }
+    }
+  }
+
+  @Test
+  @EnabledOnOs(OS.LINUX)
+  void testSetReadThreadRealtime() throws IOException {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort port = support.createPort(support.getPort1())) {
+      port.open();
+
+      // Enabling needs CAP_SYS_NICE, which the test environment may not grant
+      try {
+        port.setReadThreadRealtime(true);
+      }
+      catch (IOException e) {
+        assertThat(e).hasMessageContaining("denied");
+      }
+      finally {
+        port.setReadThreadRealtime(false);
+      }
+
+      LOG.info("setReadThreadRealtime() test passed");
+    }
+  }
+
+  @Test
+  @EnabledOnOs(OS.LINUX)
+  void testCanonicalModeReturnsWholeLines() throws Exception {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort sender = support.createPort(support.getPort1());
+         SerialPort receiver = support.createPort(support.getPort2(), 2000)) {
+
+      sender.open();
+      receiver.open();
+      receiver.setCanonicalMode(true);
+
+      Thread writer = new Thread(() -> {
+        try {
+          sender.write("par".getBytes(StandardCharsets.UTF_8));
+          sender.flush();
+          Thread.sleep(200);
+          sender.write("tial\n".getBytes(StandardCharsets.UTF_8));
+          sender.flush();
+        }
+        catch (Exception e) {
+          throw new RuntimeException(e);
+        }
+      });
+      writer.start();
+
+      // The first chunk is held back until the line is complete
+      byte[] buffer = new byte[64];
+      int n = receiver.read(buffer);
+      writer.join();
+      assertThat(new String(buffer, 0, n, StandardCharsets.UTF_8)).isEqualTo("partial\n");
+
+      receiver.setCanonicalMode(false);
+
+      LOG.info("setCanonicalMode() test passed");
+    }
+  }
+
+  @Test
+  void testGetConfigDiscrepanciesEmptyWhenApplied() throws IOException {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort port = support.createPort(support.getPort1())) {
+      port.open();
+
+      // Pseudo terminals store whatever line settings they are given
+      assertThat(port.getConfigDiscrepancies()).isEmpty();
+
+      LOG.info("getConfigDiscrepancies() test passed");
+    }
+  }
+
+  @Test
+  @EnabledOnOs(OS.LINUX)
+  void testGetConfigDiscrepanciesReportsCoercedSettings() throws IOException {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort port = SerialPort.builder()
+        .portName(support.getPort1())
+        .baudRate(9600)
+        .dataBits(DataBits.SEVEN)
+        .build()) {
+      port.open();
+
+      // The Linux pseudo-terminal driver forces 8 data bits without parity
+      assertThat(port.getConfigDiscrepancies())
+          .contains("data bits: requested 7, driver applied 8");
+
+      LOG.info("getConfigDiscrepancies() coercion test passed");
+    }
+  }
+
+  @Test
+  @EnabledOnOs(OS.LINUX)
+  void testIsOutputStoppedAfterXoff() throws IOException {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort sender = SerialPort.builder()
+        .portName(support.getPort1())
+        .baudRate(9600)
+        .flowControl(FlowControl.SOFTWARE)
+        .build();
+         SerialPort peer = support.createPort(support.getPort2())) {
+
+      sender.open();
+      peer.open();
+      assertThat(sender.isOutputStopped()).isFalse();
+
+      // XOFF from the peer pauses our output
+      peer.write(new byte[] {0x13});
+      peer.flush();
+      assertThat(sender.isOutputStopped()).isTrue();
+
+      peer.write(new byte[] {0x11});
+      peer.flush();
+      assertThat(sender.isOutputStopped()).isFalse();
+
+      LOG.info("isOutputStopped() test passed");
+    }
+  }
+
+  @Test
+  @EnabledOnOs(OS.LINUX)
+  void testSendXonXoff() throws IOException {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort sender = support.createPort(support.getPort1());
+         SerialPort receiver = support.createPort(support.getPort2(), 2000)) {
+
+      sender.open();
+      receiver.open();
+
+      byte[] buffer = new byte[1];
+      sender.sendXoff();
+      assertThat(receiver.read(buffer)).isEqualTo(1);
+      assertThat(buffer[0]).isEqualTo((byte) 0x13);
+
+      sender.sendXon();
+      assertThat(receiver.read(buffer)).isEqualTo(1);
+      assertThat(buffer[0]).isEqualTo((byte) 0x11);
+
+      LOG.info("sendXon()/sendXoff() test passed");
+    }
+  }
+
+  @Test
+  void testGetSupportedBaudRates() throws IOException {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    // Pseudo terminals accept any rate
+    SupportedBaudRates rates = SerialPort.getSupportedBaudRates(support.getPort1());
+    assertThat(rates.getStandardRates()).contains(9600, 115200);
+    assertThat(rates.supports(9600)).isTrue();
+
+    // The probe leaves the port free to be opened
+    try (SerialPort port = support.createPort(support.getPort1())) {
+      port.open();
+    }
+
+    LOG.info("getSupportedBaudRates() test passed: {}", rates);
+  }
+
+  @Test
+  void testTimeoutIsNotEof() throws IOException {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort port = support.createPort(support.getPort1(), 100)) {
+      port.open();
+      assertThat(port.isEof()).isFalse();
+
+      // A read that times out must not look like a vanished peer
+      assertThatThrownBy(() -> port.read(new byte[8]))
+          .isInstanceOf(IOException.class)
+          .isNotInstanceOf(EOFException.class);
+      assertThat(port.isEof()).isFalse();
+
+      LOG.info("isEof() timeout test passed");
+    }
+  }
+
+  @Test
+  void testWriteTerminator() throws IOException, InterruptedException {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort sender = support.createPort(support.getPort1());
+         SerialPort receiver = support.createPort(support.getPort2(), 2000)) {
+
+      sender.open();
+      receiver.open();
+
+      sender.setWriteTerminator(new byte[] {'\r', '\n'});
+      assertThat(sender.writeLine(new byte[] {'x', 'O', 'K'}, 1, 2)).isEqualTo(4);
+      // Plain writes are left alone
+      assertThat(sender.write(new byte[] {'!'})).isEqualTo(1);
+      sender.flush();
+
+      Thread.sleep(100);
+
+      byte[] buffer = new byte[5];
+      assertThat(receiver.readFully(buffer, 2000)).isEqualTo(5);
+      assertThat(buffer).isEqualTo(new byte[] {'O', 'K', '\r', '\n', '!'});
+
+      LOG.info("setWriteTerminator() test passed");
+    }
+  }
+
+  @Test
+  void testReadRetries() throws IOException {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort sender = support.createPort(support.getPort1());
+         SerialPort receiver = support.createPort(support.getPort2(), 2000)) {
+
+      sender.open();
+      receiver.open();
+
+      receiver.setReadRetries(3);
+      assertThatThrownBy(() -> receiver.setReadRetries(-1))
+          .isInstanceOf(IllegalArgumentException.class);
+
+      // Healthy reads need no retries
+      sender.write(new byte[] {1, 2, 3});
+      sender.flush();
+      byte[] buffer = new byte[3];
+      assertThat(receiver.readFully(buffer, 2000)).isEqualTo(3);
+      assertThat(receiver.getReadRetryCount()).isZero();
+
+      LOG.info("setReadRetries() test passed");
+    }
+  }
+
+  @Test
+  void testMeasureRoundTripWithoutResponse() throws IOException {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort sender = support.createPort(support.getPort1());
+         SerialPort peer = support.createPort(support.getPort2())) {
+
+      sender.open();
+      peer.open();
+
+      // The peer never answers, so the first round trip times out
+      assertThatThrownBy(() -> sender.measureRoundTrip(new byte[] {0x55}, 1, 100, 3))
+          .isInstanceOf(IOException.class)
+          .hasMessageContaining("received 0 of 1 bytes");
+
+      LOG.info("measureRoundTrip() timeout test passed");
+    }
+  }
+
+  @Test
+  @EnabledOnOs(OS.LINUX)
+  void testSetSplitBaud() throws IOException {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort port = support.createPort(support.getPort1())) {
+      port.open();
+
+      // Pseudo-terminals keep whatever speeds they are given
+      port.setSplitBaud(1200, 75);
+      port.setSplitBaud(31250, 9600);
+      assertThatThrownBy(() -> port.setSplitBaud(0, 9600))
+          .isInstanceOf(IllegalArgumentException.class);
+
+      LOG.info("setSplitBaud() test passed");
+    }
+  }
+
+  @Test
+  void testFrameReader() throws IOException, InterruptedException {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort sender = support.createPort(support.getPort1());
+         SerialPort receiver = support.createPort(support.getPort2())) {
+
+      sender.open();
+      receiver.open();
+      receiver.startFrameReader(20_000);
+      assertThatThrownBy(() -> receiver.startFrameReader(20_000)).isInstanceOf(IOException.class);
+
+      // Two frames separated by a gap well above the 20 ms idle gap
+      sender.write(new byte[] {1, 2, 3});
+      sender.flush();
+      Thread.sleep(100);
+      sender.write(new byte[] {4, 5});
+      sender.flush();
+
+      byte[] buffer = new byte[16];
+      assertThat(receiver.nextFrame(buffer, 1000)).isEqualTo(3);
+      assertThat(Arrays.copyOf(buffer, 3)).containsExactly(1, 2, 3);
+      assertThat(receiver.nextFrame(buffer, 1000)).isEqualTo(2);
+      assertThat(Arrays.copyOf(buffer, 2)).containsExactly(4, 5);
+      assertThat(receiver.nextFrame(buffer, 50)).isZero();
+      assertThat(receiver.getDroppedFrameCount()).isZero();
+
+      receiver.stopFrameReader();
+      assertThatThrownBy(() -> receiver.nextFrame(buffer, 0)).isInstanceOf(IOException.class);
+
+      LOG.info("Frame reader test passed");
+    }
+  }
+
+  @Test
+  @EnabledOnOs(OS.LINUX)
+  void testFifoTriggerLevelUnsupportedOnPty() throws IOException {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort port = support.createPort(support.getPort1())) {
+      port.open();
+
+      // Only 8250-family UARTs expose the trigger level
+      assertThatThrownBy(port::getFifoTriggerLevel)
+          .isInstanceOf(IOException.class)
+          .hasMessageContaining("rx_trig_bytes");
+      assertThatThrownBy(() -> port.setFifoTriggerLevel(8))
+          .isInstanceOf(IOException.class)
+          .hasMessageContaining("rx_trig_bytes");
+
+      LOG.info("FIFO trigger level test passed");
+    }
+  }
+
+  @Test
+  void testClearInputCounted() throws IOException, InterruptedException {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort sender = support.createPort(support.getPort1());
+         SerialPort receiver = support.createPort(support.getPort2())) {
+
+      sender.open();
+      receiver.open();
+
+      sender.write(new byte[] {1, 2, 3, 4, 5});
+      sender.flush();
+      Thread.sleep(100);
+
+      assertThat(receiver.clearInputCounted()).isEqualTo(5);
+      assertThat(receiver.clearInputCounted()).isZero();
+      assertThat(receiver.clearAllCounted()).isZero();
+
+      LOG.info("clearInputCounted() test passed");
+    }
+  }
+
+  @Test
+  void testRs485ManualModeWritesWholeLargeFrame() throws Exception {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+    assumeTrue(support.isRtsControlSupported(), "RTS/DTR control not supported on PTY devices");
+
+    Rs485Config rs485Config = Rs485Config.builder()
+        .enabled(true)
+        .controlPin(Rs485ControlPin.DTR)
+        .build();
+
+    try (SerialPort sender = SerialPort.builder()
+            .portName(support.getPort1())
+            .baudRate(115200)
+            .timeout(2000)
+            .rs485Config(rs485Config)
+            .build();
+         SerialPort receiver = support.createPort(support.getPort2(), 200)) {
+
+      sender.open();
+      receiver.open();
+
+      // Larger than the PTY buffer, so the driver cannot take the frame in one write
+      byte[] frame = new byte[64 * 1024];
+      for (int i = 0; i < frame.length; i++) {
+        frame[i] = (byte) ('A' + i % 26);
+      }
+
+      byte[] received = new byte[frame.length];
+      int[] total = new int[1];
+      Thread reader = new Thread(() -> {
+        long deadline = System.currentTimeMillis() + 5000;
+        try {
+          while (total[0] < received.length && System.currentTimeMillis() < deadline) {
+            total[0] += receiver.read(received, total[0], received.length - total[0]);
+          }
+        }
+        catch (IOException e) {
+          LOG.warn("Reader stopped: {}", e.getMessage());
+        }
+      });
+      reader.start();
+
+      assertThat(sender.write(frame)).isEqualTo(frame.length);
+      reader.join(10000);
+
+      assertThat(total[0]).isEqualTo(frame.length);
+      assertThat(received).isEqualTo(frame);
+    }
+  }
+
+  @Test
+  void testHoldTransmitEnableRequiresRs485Control() throws IOException {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort port = support.createPort(support.getPort1())) {
+      port.open();
+
+      assertThatThrownBy(() -> port.holdTransmitEnable(true))
+          .isInstanceOf(IOException.class)
+          .hasMessageContaining("not enabled");
+      // Releasing a pin that is not held is a no-op
+      port.holdTransmitEnable(false);
+    }
+  }
+
+  @Test
+  void testPtyIsVirtualPort() {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    assertThat(SerialPort.isVirtualPort(support.getPort1())).isTrue();
+    assertThat(SerialPort.isVirtualPort(support.getPort2())).isTrue();
+  }
+
+  @Test
+  void testCloseWhileOtherThreadsUseThePort() throws Exception {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    SerialPort port = support.createPort(support.getPort1(), 50);
+    port.open();
+
+    int threadCount = 4;
+    CountDownLatch started = new CountDownLatch(threadCount);
+    List<Thread> threads = new ArrayList<>();
+    List<Throwable> unexpected = Collections.synchronizedList(new ArrayList<>());
+    for (int i = 0; i < threadCount; i++) {
+      boolean reader = i % 2 == 0;
+      Thread thread = new Thread(() -> {
+        started.countDown();
+        byte[] buffer = new byte[16];
+        try {
+          while (true) {
+            if (reader) {
+              port.read(buffer);
+            }
+            else {
+              port.available();
+            }
+          }
+        }
+        catch (IOException e) {
+          // Expected once the port is closed
+        }
+        catch (Throwable t) {
+          unexpected.add(t);
+        }
+      });
+      threads.add(thread);
+      thread.start();
+    }
+
+    started.await();
+    Thread.sleep(100);
+    port.close();
+    for (Thread thread : threads) {
+      thread.join(5000);
+      assertThat(thread.isAlive()).isFalse();
+    }
+    assertThat(unexpected).isEmpty();
+  }
+
+  @Test
+  void testReadAndWriteThroughNativeAddresses() throws Exception {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    java.lang.reflect.Field field = sun.misc.Unsafe.class.getDeclaredField("theUnsafe");
+    field.setAccessible(true);
+    sun.misc.Unsafe unsafe = (sun.misc.Unsafe) field.get(null);
+
+    byte[] message = "Off-heap!".getBytes(StandardCharsets.US_ASCII);
+    long source = unsafe.allocateMemory(message.length + 4);
+    long target = unsafe.allocateMemory(message.length);
+    try (SerialPort sender = support.createPort(support.getPort1());
+         SerialPort receiver = support.createPort(support.getPort2(), 1000)) {
+      sender.open();
+      receiver.open();
+
+      for (int i = 0; i < message.length; i++) {
+        unsafe.putByte(source + 4 + i, message[i]);
+      }
+      assertThat(sender.writeFromAddress(source, 4, message.length)).isEqualTo(message.length);
+      assertThat(sender.writeFromAddress(source, 0, 0)).isZero();
+
+      int total = 0;
+      long deadline = System.currentTimeMillis() + 2000;
+      while (total < message.length && System.currentTimeMillis() < deadline) {
+        total += receiver.readToAddress(target + total, message.length - total);
+      }
+      byte[] received = new byte[total];
+      for (int i = 0; i < total; i++) {
+        received[i] = unsafe.getByte(target + i);
+      }
+      assertThat(received).isEqualTo(message);
+
+      assertThatThrownBy(() -> receiver.readToAddress(0, 1))
+          .isInstanceOf(IllegalArgumentException.class);
+      assertThatThrownBy(() -> sender.writeFromAddress(source, -1, 1))
+          .isInstanceOf(IllegalArgumentException.class);
+    }
+    finally {
+      unsafe.freeMemory(source);
+      unsafe.freeMemory(target);
+    }
+  }
+
+  @Test
+  void testDrainTimeoutKeepsWritesWorking() throws Exception {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort sender = support.createPort(support.getPort1());
+         SerialPort receiver = support.createPort(support.getPort2(), 1000)) {
+      sender.open();
+      receiver.open();
+
+      assertThatThrownBy(() -> sender.setDrainTimeout(-1))
+          .isInstanceOf(IllegalArgumentException.class);
+
+      sender.setDrainTimeout(500);
+      byte[] message = "drained".getBytes(StandardCharsets.US_ASCII);
+      assertThat(sender.write(message)).isEqualTo(message.length);
+
+      byte[] received = new byte[message.length];
+      assertThat(receiver.readFully(received, 0, received.length, 2000)).isEqualTo(message.length);
+      assertThat(received).isEqualTo(message);
+      assertThat(sender.getLastWarning()).isNull();
+
+      sender.setDrainTimeout(0);
+      assertThat(sender.write(message)).isEqualTo(message.length);
+    }
+  }
+
+  @Test
+  @EnabledOnOs(OS.LINUX)
+  void testReadableEventFdIsSignalledByInput() throws Exception {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort sender = support.createPort(support.getPort1());
+         SerialPort receiver = support.createPort(support.getPort2(), 1000)) {
+      sender.open();
+      receiver.open();
+
+      int fd = receiver.getReadableEventFd();
+      assertThat(fd).isGreaterThanOrEqualTo(0);
+      assertThat(receiver.getReadableEventFd()).isEqualTo(fd);
+      assertThat(receiver.resetReadableEvent()).isFalse();
+
+      sender.write("event".getBytes(StandardCharsets.US_ASCII));
+      boolean signalled = false;
+      long deadline = System.currentTimeMillis() + 2000;
+      while (!signalled && System.currentTimeMillis() < deadline) {
+        signalled = receiver.resetReadableEvent();
+        Thread.sleep(5);
+      }
+      assertThat(signalled).isTrue();
+
+      byte[] received = new byte[5];
+      assertThat(receiver.readFully(received, 0, received.length, 2000)).isEqualTo(5);
+      assertThat(new String(received, StandardCharsets.US_ASCII)).isEqualTo("event");
+    }
+  }
+
+  @Test
+  void testGetRtsAndDtrReportTheLastSetLevel() throws Exception {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort port = support.createPort(support.getPort1())) {
+      port.open();
+
+      if (!support.isRtsControlSupported()) {
+        // Neither set nor readable from the driver
+        assertThatThrownBy(port::getRTS).isInstanceOf(IOException.class);
+        assertThatThrownBy(port::getDTR).isInstanceOf(IOException.class);
+        return;
+      }
+
+      port.setRTS(true);
+      port.setDTR(false);
+      assertThat(port.getRTS()).isTrue();
+      assertThat(port.getDTR()).isFalse();
+
+      port.setRTS(false);
+      port.setDTR(true);
+      assertThat(port.getRTS()).isFalse();
+      assertThat(port.getDTR()).isTrue();
+    }
+  }
+
+  @Test
+  void testWritesWithAutoFlushOffArriveAfterFlush() throws Exception {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort sender = support.createPort(support.getPort1());
+         SerialPort receiver = support.createPort(support.getPort2(), 1000)) {
+      sender.open();
+      receiver.open();
+
+      sender.setAutoFlush(false);
+      sender.write("batch-".getBytes(StandardCharsets.US_ASCII));
+      sender.write("one".getBytes(StandardCharsets.US_ASCII));
+      sender.flush();
+      sender.setAutoFlush(true);
+
+      byte[] received = new byte[9];
+      assertThat(receiver.readFully(received, 0, received.length, 2000)).isEqualTo(9);
+      assertThat(new String(received, StandardCharsets.US_ASCII)).isEqualTo("batch-one");
+    }
+  }
+
+  @Test
+  void testOverflowWarnThresholdRecordsWarning() throws Exception {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort sender = support.createPort(support.getPort1());
+         SerialPort receiver = support.createPort(support.getPort2(), 1000)) {
+      sender.open();
+      receiver.open();
+
+      assertThatThrownBy(() -> receiver.setOverflowWarnThreshold(-1))
+          .isInstanceOf(IllegalArgumentException.class);
+      receiver.setOverflowWarnThreshold(4);
+
+      sender.write("0123".getBytes(StandardCharsets.US_ASCII));
+      receiver.waitForBytes(4, 2000);
+      assertThat(receiver.getLastWarning()).isNull();
+
+      sender.write("456789".getBytes(StandardCharsets.US_ASCII));
+      assertThat(receiver.waitForBytes(10, 2000)).isEqualTo(10);
+      assertThat(receiver.getLastWarning()).contains("input queue at 10 bytes");
+
+      receiver.setOverflowWarnThreshold(0);
+      assertThat(receiver.available()).isEqualTo(10);
+      assertThat(receiver.getLastWarning()).isNull();
+    }
+  }
+
+  @Test
+  void testSendDmxFrameDeliversThePacket() throws Exception {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort sender = SerialPort.builder()
+            .portName(support.getPort1())
+            .baudRate(250000)
+            .stopBits(StopBits.TWO)
+            .timeout(1000)
+            .build();
+         SerialPort receiver = support.createPort(support.getPort2(), 1000)) {
+      sender.open();
+      receiver.open();
+
+      byte[] packet = new byte[513];
+      for (int i = 1; i < packet.length; i++) {
+        packet[i] = (byte) i;
+      }
+      assertThat(sender.sendDmxFrame(packet)).isEqualTo(packet.length);
+
+      byte[] received = new byte[packet.length];
+      assertThat(receiver.readFully(received, 0, received.length, 2000)).isEqualTo(packet.length);
+      assertThat(received).isEqualTo(packet);
+
+      assertThatThrownBy(() -> sender.sendDmxFrame(new byte[514]))
+          .isInstanceOf(IllegalArgumentException.class);
+      assertThatThrownBy(() -> receiver.sendDmxFrame(new byte[] { 0 }))
+          .isInstanceOf(IOException.class)
+          .hasMessageContaining("250000 baud");
+
+      sender.setStopBits(StopBits.ONE);
+      assertThatThrownBy(() -> sender.sendDmxFrame(packet))
+          .isInstanceOf(IOException.class)
+          .hasMessageContaining("port is at 250000 baud 8N1");
+    }
+  }
+
+  @Test
+  void testWriteUnsignedValuesInBothByteOrders() throws Exception {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort sender = support.createPort(support.getPort1());
+         SerialPort receiver = support.createPort(support.getPort2(), 1000)) {
+      sender.open();
+      receiver.open();
+
+      assertThat(sender.writeU16BE(0x1234)).isEqualTo(2);
+      assertThat(sender.writeU16LE(0x1234)).isEqualTo(2);
+      assertThat(sender.writeU32BE(0xDEADBEEFL)).isEqualTo(4);
+      assertThat(sender.writeU32LE(0xDEADBEEFL)).isEqualTo(4);
+
+      byte[] received = new byte[12];
+      assertThat(receiver.readFully(received, 0, received.length, 2000)).isEqualTo(12);
+      assertThat(received).containsExactly(
+          0x12, 0x34, 0x34, 0x12,
+          0xDE, 0xAD, 0xBE, 0xEF,
+          0xEF, 0xBE, 0xAD, 0xDE);
+
+      assertThatThrownBy(() -> sender.writeU16BE(0x10000))
+          .isInstanceOf(IllegalArgumentException.class);
+      assertThatThrownBy(() -> sender.writeU32LE(-1))
+          .isInstanceOf(IllegalArgumentException.class);
+    }
+  }
+
+  @Test
+  void testPtyChipsetIsUnknown() {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    assertThat(SerialPort.getChipset(support.getPort1())).isEqualTo("unknown");
+    assertThat(SerialPort.getChipset("/dev/does-not-exist")).isEqualTo("unknown");
+  }
+
+  @Test
+  void testReconfigureDrainsOutputAndAppliesSettings() throws Exception {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort writer = support.createPort(support.getPort1());
+         SerialPort reader = support.createPort(support.getPort2(), 1000)) {
+      writer.open();
+      reader.open();
+
+      byte[] data = "before reconfigure".getBytes(StandardCharsets.US_ASCII);
+      writer.write(data);
+
+      // PTYs keep any speed and stop bits, but Linux forces 8 data bits without parity
+      writer.reconfigure(9600, DataBits.EIGHT, StopBits.TWO, Parity.NONE);
+
+      assertThat(writer.getBaudRate()).isEqualTo(9600);
+      assertThat(writer.getStopBits()).isEqualTo(StopBits.TWO);
+
+      byte[] received = new byte[data.length];
+      int total = 0;
+      while (total < data.length) {
+        int n = reader.read(received, total, data.length - total);
+        assertThat(n).isPositive();
+        total += n;
+      }
+      assertThat(received).isEqualTo(data);
+
+      assertThatThrownBy(() -> writer.reconfigure(0, DataBits.EIGHT, StopBits.ONE, Parity.NONE))
+          .isInstanceOf(IllegalArgumentException.class);
+    }
+  }
+
+  @Test
+  @EnabledOnOs(OS.LINUX)
+  void testReconfigureRollsBackSettingsTheDriverRejects() throws Exception {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort port = support.createPort(support.getPort1())) {
+      port.open();
+
+      // The Linux PTY driver forces 8 data bits, so the whole update is rolled back
+      assertThatThrownBy(() -> port.reconfigure(9600, DataBits.SEVEN, StopBits.TWO, Parity.NONE))
+          .isInstanceOf(IOException.class)
+          .hasMessageContaining("data bits 7");
+
+      assertThat(port.getBaudRate()).isEqualTo(115200);
+      assertThat(port.getDataBits()).isEqualTo(DataBits.EIGHT);
+      assertThat(port.getConfigDiscrepancies()).isEmpty();
+    }
+  }
+
+  @Test
+  void testReadUntilPatternKeepsBytesPastTheMatch() throws Exception {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort writer = support.createPort(support.getPort1());
+         SerialPort reader = support.createPort(support.getPort2(), 1000)) {
+      writer.open();
+      reader.open();
+      byte[] crlf = {'\r', '\n'};
+      writer.write("first\r\nsecond\r".getBytes(StandardCharsets.US_ASCII));
+      Thread.sleep(50);
+
+      byte[] buffer = new byte[64];
+      int n = reader.readUntilPattern(crlf, buffer, 1000);
+      assertThat(new String(buffer, 0, n, StandardCharsets.US_ASCII)).isEqualTo("first\r\n");
+      assertThat(reader.available()).isEqualTo("second\r".length());
+
+      // Single-byte reads are served from the bytes past the match first
+      assertThat(reader.readOne(100)).isEqualTo('s');
+
+      // The pattern is split across the residual and the next write
+      writer.write("\nthird".getBytes(StandardCharsets.US_ASCII));
+      n = reader.readUntilPattern(crlf, buffer, 1000);
+      assertThat(new String(buffer, 0, n, StandardCharsets.US_ASCII)).isEqualTo("econd\r\n");
+
+      n = reader.readUntilPattern(crlf, buffer, 200);
+      assertThat(new String(buffer, 0, n, StandardCharsets.US_ASCII)).isEqualTo("third");
+      assertThat(reader.lastReadTimedOut()).isTrue();
+
+      // Bytes past the match start the first frame of a frame reader
+      writer.write("x\r\nyz".getBytes(StandardCharsets.US_ASCII));
+      n = reader.readUntilPattern(crlf, buffer, 1000);
+      assertThat(new String(buffer, 0, n, StandardCharsets.US_ASCII)).isEqualTo("x\r\n");
+      reader.startFrameReader(20_000);
+      n = reader.nextFrame(buffer, 1000);
+      assertThat(new String(buffer, 0, n, StandardCharsets.US_ASCII)).isEqualTo("yz");
+      reader.stopFrameReader();
+
+      assertThatThrownBy(() -> reader.readUntilPattern(new byte[0], buffer, 100))
+          .isInstanceOf(IllegalArgumentException.class);
+    }
+  }
+
+  @Test
+  void testRestoreOnCloseIsAcceptedOnOpenPort() throws Exception {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    SerialPort port = support.createPort(support.getPort1());
+    port.open();
+    try {
+      port.setRestoreOnClose(true);
+      port.setRestoreOnClose(false);
+    } finally {
+      port.close();
+    }
+    assertThatThrownBy(() -> port.setRestoreOnClose(true)).isInstanceOf(IOException.class);
+  }
+
+  @Test
+  void testMaxThroughputDelaysWritesBetweenFrames() throws Exception {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    SerialPort writer = support.createPort(support.getPort1());
+    SerialPort reader = support.createPort(support.getPort2(), 1000);
+    writer.open();
+    reader.open();
+    try {
+      writer.setMaxThroughput(1000);
+      byte[] frame = new byte[200];
+
+      long start = System.nanoTime();
+      writer.write(frame);
+      writer.write(frame);
+      writer.write(frame);
+      long elapsedMs = (System.nanoTime() - start) / 1_000_000;
+
+      // 100 bytes of burst allowance, then 500 bytes at 1000 bytes/s before the third frame
+      assertThat(elapsedMs).isGreaterThanOrEqualTo(250);
+
+      byte[] received = new byte[600];
+      assertThat(reader.readFully(received, 0, received.length, 2000)).isEqualTo(600);
+
+      writer.setMaxThroughput(0);
+      start = System.nanoTime();
+      writer.write(frame);
+      writer.write(frame);
+      assertThat((System.nanoTime() - start) / 1_000_000).isLessThan(250);
+
+      assertThatThrownBy(() -> writer.setMaxThroughput(-1))
+          .isInstanceOf(IllegalArgumentException.class);
+    } finally {
+      writer.close();
+      reader.close();
+    }
+  }
+
+  @Test
+  void testRs485ActualModeExplainsManualControl() throws IOException {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort plain = support.createPort(support.getPort1())) {
+      plain.open();
+      assertThat(plain.getRs485ActualMode()).isEqualTo(Rs485ActualMode.NONE);
+    }
+
+    // A PTY has no kernel RS-485 support, so RTS control falls back to manual
+    Rs485Config rtsConfig = Rs485Config.builder().enabled(true).build();
+    try (SerialPort port = SerialPort.builder()
+            .portName(support.getPort1())
+            .baudRate(115200)
+            .rs485Config(rtsConfig)
+            .build()) {
+      port.open();
+      assertThat(port.getRs485ActualMode()).isEqualTo(Rs485ActualMode.MANUAL_BY_FALLBACK);
+    }
+
+    Rs485Config dtrConfig = Rs485Config.builder()
+        .enabled(true)
+        .controlPin(Rs485ControlPin.DTR)
+        .build();
+    try (SerialPort port = SerialPort.builder()
+            .portName(support.getPort1())
+            .baudRate(115200)
+            .rs485Config(dtrConfig)
+            .build()) {
+      port.open();
+      assertThat(port.getRs485ActualMode()).isEqualTo(Rs485ActualMode.MANUAL_BY_PIN);
+    }
+  }
+
+  @Test
+  void testPreambleIdleBitsDelayEachWrite() throws Exception {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    SerialPort writer = support.createPort(support.getPort1());
+    SerialPort reader = support.createPort(support.getPort2(), 1000);
+    writer.open();
+    reader.open();
+    try {
+      // 11520 bit periods at 115200 baud are 100 ms of idle line before each write
+      writer.setPreambleIdleBits(11520);
+      byte[] frame = "preamble".getBytes(StandardCharsets.UTF_8);
+
+      long start = System.nanoTime();
+      writer.write(frame);
+      writer.write(frame);
+      assertThat((System.nanoTime() - start) / 1_000_000).isGreaterThanOrEqualTo(200);
+
+      byte[] received = new byte[frame.length * 2];
+      assertThat(reader.readFully(received, 0, received.length, 2000)).isEqualTo(received.length);
+
+      writer.setPreambleIdleBits(0);
+      start = System.nanoTime();
+      writer.write(frame);
+      assertThat((System.nanoTime() - start) / 1_000_000).isLessThan(100);
+
+      assertThatThrownBy(() -> writer.setPreambleIdleBits(-1))
+          .isInstanceOf(IllegalArgumentException.class);
+    } finally {
+      writer.close();
+      reader.close();
+    }
+  }
+
+  @Test
+  void testActualBaudRateReportsCustomRate() throws IOException {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort port = support.createPort(support.getPort1())) {
+      port.open();
+      LineSpeed speed = port.getActualBaudRate();
+      assertThat(speed.getRequestedBaudRate()).isEqualTo(115200);
+      assertThat(speed.getOutputBaudRate()).isEqualTo(115200);
+      assertThat(speed.getInputBaudRate()).isEqualTo(115200);
+
+      // A PTY has no clock divisor, so a custom rate is applied exactly
+      port.reconfigure(250000, DataBits.EIGHT, StopBits.ONE, Parity.NONE);
+      speed = port.getActualBaudRate();
+      assertThat(speed.getRequestedBaudRate()).isEqualTo(250000);
+      assertThat(speed.getOutputBaudRate()).isEqualTo(250000);
+      assertThat(speed.getErrorPercent()).isZero();
+    }
+  }
+
+  @Test
+  void testByteBufferFallsBackWhenAddressIsUnavailable() throws IOException {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort writer = support.createPort(support.getPort1());
+         SerialPort reader = support.createPort(support.getPort2(), 1000)) {
+      writer.open();
+      reader.open();
+
+      // A read-only heap buffer has no accessible array, so it goes down the direct path,
+      // where the JVM reports no address
+      ByteBuffer heap = ByteBuffer.wrap("fallback".getBytes(StandardCharsets.UTF_8))
+          .asReadOnlyBuffer();
+      assertThat(writer.write(heap)).isEqualTo(8);
+      assertThat(heap.hasRemaining()).isFalse();
+      assertThat(writer.getLastWarning()).contains("direct buffer address unavailable");
+
+      // The warning is only recorded once per port
+      heap.rewind();
+      assertThat(writer.write(heap)).isEqualTo(8);
+      assertThat(writer.getLastWarning()).isNull();
+
+      ByteBuffer direct = ByteBuffer.allocateDirect(16);
+      assertThat(reader.readFully(new byte[8], 0, 8, 2000)).isEqualTo(8);
+      int n = 0;
+      while (n < 8) {
+        n += reader.read(direct);
+      }
+      direct.flip();
+      byte[] received = new byte[direct.remaining()];
+      direct.get(received);
+      assertThat(new String(received, StandardCharsets.UTF_8)).isEqualTo("fallback");
+      assertThat(reader.getLastWarning()).isNull();
+
+      assertThatThrownBy(() -> reader.read(heap))
+          .isInstanceOf(java.nio.ReadOnlyBufferException.class);
+    }
+  }
+
+  @Test
+  void testReadLengthPrefixedFrame() throws IOException {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort writer = support.createPort(support.getPort1());
+         SerialPort reader = support.createPort(support.getPort2(), 1000)) {
+      writer.open();
+      reader.open();
+
+      // Sync byte, little-endian payload length, then the payload and a second frame
+      writer.write(new byte[] {(byte) 0xAA, 3, 0, 'a', 'b', 'c', (byte) 0xAA, 1, 0, 'z'});
+      byte[] buffer = new byte[16];
+      assertThat(reader.readLengthPrefixedFrame(buffer, 0, 3, 1, 2, false, false, 16, 2000))
+          .isEqualTo(6);
+      assertThat(Arrays.copyOfRange(buffer, 3, 6))
+          .isEqualTo("abc".getBytes(StandardCharsets.UTF_8));
+      assertThat(reader.readLengthPrefixedFrame(buffer, 0, 3, 1, 2, false, false, 16, 2000))
+          .isEqualTo(4);
+      assertThat(buffer[3]).isEqualTo((byte) 'z');
+      assertThat(reader.lastReadTimedOut()).isFalse();
+
+      // A frame longer than maxLen fails and leaves its header unread
+      writer.write(new byte[] {(byte) 0xAA, 0, 100});
+      assertThatThrownBy(
+          () -> reader.readLengthPrefixedFrame(buffer, 0, 3, 1, 2, true, false, 16, 2000))
+          .isInstanceOf(IOException.class);
+      assertThat(reader.readFully(buffer, 0, 3, 1000)).isEqualTo(3);
+      assertThat(buffer[2]).isEqualTo((byte) 100);
+
+      // The length counts the header; the payload never arrives
+      writer.write(new byte[] {(byte) 0xAA, 8, 0, 'x'});
+      assertThat(reader.readLengthPrefixedFrame(buffer, 0, 3, 1, 2, false, true, 16, 200))
+          .isEqualTo(4);
+      assertThat(reader.lastReadTimedOut()).isTrue();
+    }
+  }
+
+  @Test
+  void testFailsafeStaysQuietWhileNoPinIsHeld() throws Exception {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    try (SerialPort plain = support.createPort(support.getPort2())) {
+      plain.open();
+      assertThatThrownBy(() -> plain.setFailsafe(100)).isInstanceOf(IOException.class);
+    }
+
+    Rs485Config rs485Config = Rs485Config.builder().enabled(true).build();
+    try (SerialPort port = SerialPort.builder()
+            .portName(support.getPort1())
+            .baudRate(115200)
+            .rs485Config(rs485Config)
+            .build()) {
+      port.open();
+      // The timer only runs while a write leaves the pin asserted; PTYs have no RTS line, so
+      // it is never asserted and an idle port is not warned about
+      port.setFailsafe(50);
+      Thread.sleep(300);
+      assertThat(port.getLastWarning()).isNull();
+
+      port.setFailsafe(0);
+      assertThatThrownBy(() -> port.setFailsafe(-1))
+          .isInstanceOf(IllegalArgumentException.class);
+    }
+  }
+
+  @Test
+  void testSetStopBitsKeepsReceivedInput() throws Exception {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    SerialPort writer = support.createPort(support.getPort1());
+    SerialPort reader = support.createPort(support.getPort2(), 1000);
+    writer.open();
+    reader.open();
+    try {
+      byte[] data = "kept across the change".getBytes(StandardCharsets.US_ASCII);
+      writer.write(data);
+      Thread.sleep(50);
+
+      reader.setStopBits(StopBits.TWO);
+      assertThat(reader.getStopBits()).isEqualTo(StopBits.TWO);
+
+      byte[] received = new byte[data.length];
+      int total = 0;
+      while (total < data.length) {
+        int n = reader.read(received, total, data.length - total);
+        assertThat(n).isPositive();
+        total += n;
+      }
+      assertThat(received).isEqualTo(data);
+
+      assertThatThrownBy(() -> reader.setStopBits(null)).isInstanceOf(NullPointerException.class);
+    } finally {
+      writer.close();
+      reader.close();
+    }
+  }
+
+  @Test
+  @EnabledOnOs(OS.LINUX)
+  void testSetParityRestoresSettingTheDriverRejects() throws Exception {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    SerialPort port = support.createPort(support.getPort1());
+    port.open();
+    try {
+      // The Linux PTY driver forces no parity, so the previous setting is restored
+      assertThatThrownBy(() -> port.setParity(Parity.EVEN))
+          .isInstanceOf(IOException.class)
+          .hasMessageContaining("parity Even");
+
+      assertThat(port.getParity()).isEqualTo(Parity.NONE);
+      assertThat(port.getConfigDiscrepancies()).isEmpty();
+    } finally {
+      port.close();
+    }
+  }
+
+  @Test
+  void testCaptureRecordsWritesAndReads() throws Exception {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    Path capture = Files.createTempFile("jrserial-capture", ".pcap");
+    SerialPort writer = support.createPort(support.getPort1());
+    SerialPort reader = support.createPort(support.getPort2(), 1000);
+    writer.open();
+    reader.open();
+    try {
+      writer.startCapture(capture.toString());
+      writer.write("ping".getBytes(StandardCharsets.US_ASCII));
+      writer.stopCapture();
+      // Stopping again is harmless
+      writer.stopCapture();
+
+      byte[] bytes = Files.readAllBytes(capture);
+      ByteBuffer file = ByteBuffer.wrap(bytes).order(ByteOrder.LITTLE_ENDIAN);
+      assertThat(file.getInt(0)).isEqualTo(0xa1b2c3d4);
+      assertThat(file.getInt(20)).isEqualTo(147);
+      assertThat(file.getInt(24 + 8)).isEqualTo(5);
+      assertThat(Arrays.copyOfRange(bytes, 24 + 16, bytes.length))
+          .isEqualTo(new byte[] {1, 'p', 'i', 'n', 'g'});
+
+      reader.startCapture(capture.toString());
+      byte[] received = new byte[4];
+      int total = 0;
+      while (total < received.length) {
+        int n = reader.read(received, total, received.length - total);
+        assertThat(n).isPositive();
+        total += n;
+      }
+      reader.stopCapture();
+
+      bytes = Files.readAllBytes(capture);
+      assertThat(bytes[24 + 16]).isEqualTo((byte) 0);
+      assertThat(reader.getLastWarning()).isNull();
+
+      assertThatThrownBy(() -> writer.startCapture(null)).isInstanceOf(NullPointerException.class);
+    } finally {
+      writer.close();
+      reader.close();
+      Files.deleteIfExists(capture);
+    }
+  }
+
+  @Test
+  void testBitReversalReversesWrittenAndReadBytes() throws Exception {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    SerialPort writer = support.createPort(support.getPort1());
+    SerialPort reader = support.createPort(support.getPort2(), 1000);
+    writer.open();
+    reader.open();
+    try {
+      byte[] data = {0x01, 0x0f, (byte) 0xa5};
+      writer.setBitReversal(true);
+      writer.write(data);
+      assertThat(data).containsExactly(0x01, 0x0f, 0xa5);
+
+      byte[] received = new byte[data.length];
+      int total = 0;
+      while (total < data.length) {
+        int n = reader.read(received, total, data.length - total);
+        assertThat(n).isPositive();
+        total += n;
+      }
+      assertThat(received).containsExactly(0x80, 0xf0, 0xa5);
+
+      reader.setBitReversal(true);
+      writer.write(data);
+      total = 0;
+      while (total < data.length) {
+        int n = reader.read(received, total, data.length - total);
+        assertThat(n).isPositive();
+        total += n;
+      }
+      assertThat(received).isEqualTo(data);
+    } finally {
+      writer.close();
+      reader.close();
+    }
+  }
+
+  @Test
+  void testWaitOutputEmptyAfterWrite() throws Exception {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    SerialPort writer = support.createPort(support.getPort1());
+    SerialPort reader = support.createPort(support.getPort2(), 1000);
+    writer.open();
+    reader.open();
+    try {
+      writer.write("queued".getBytes(StandardCharsets.US_ASCII));
+      assertThat(writer.waitOutputEmpty(1000)).isTrue();
+      assertThat(writer.waitOutputEmpty(0)).isTrue();
+      assertThat(reader.waitForBytes(6, 1000)).isEqualTo(6);
+
+      assertThatThrownBy(() -> writer.waitOutputEmpty(-1))
+          .isInstanceOf(IllegalArgumentException.class);
+    } finally {
+      writer.close();
+      reader.close();
+    }
+  }
+
+  @Test
+  void testOpenWithBaudToleranceKeepsAnAcceptedRate() throws Exception {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    // PTYs accept any rate, so the exact one is used and nothing is substituted
+    SerialPort port = SerialPort.builder()
+        .portName(support.getPort1())
+        .baudRate(115000)
+        .baudTolerancePercent(5)
+        .build();
+    port.open();
+    try {
+      assertThat(port.getLastWarning()).isNull();
+      assertThat(port.getBaudRate()).isEqualTo(115000);
+      assertThat(port.getActualBaudRate().getRequestedBaudRate()).isEqualTo(115000);
+    } finally {
+      port.close();
+    }
+  }
+
+  @Test
+  void testCrcFramesAreAppendedAndVerified() throws Exception {
+    assumeTrue(support.isPtySupported(), "PTY devices not supported by serial library");
+
+    SerialPort writer = support.createPort(support.getPort1());
+    SerialPort reader = support.createPort(support.getPort2(), 1000);
+    writer.open();
+    reader.open();
+    try {
+      byte[] payload = "123456789".getBytes(StandardCharsets.US_ASCII);
+      assertThat(writer.writeWithCrc(payload, 0, payload.length, CrcType.CRC_CCITT, false))
+          .isEqualTo(11);
+
+      byte[] frame = new byte[32];
+      int n = reader.readAndVerifyCrc(frame, 0, frame.length, CrcType.CRC_CCITT, false, 50, 1000);
+      assertThat(n).isEqualTo(payload.length);
+      assertThat(Arrays.copyOfRange(frame, 0, n + 2))
+          .isEqualTo(new byte[] {'1', '2', '3', '4', '5', '6', '7', '8', '9', 0x29, (byte) 0xb1});
+
+      // The CRC-32 of the payload, sent in the wrong byte order
+      writer.write(new byte[] {'1', '2', '3', '4', '5', '6', '7', '8', '9',
+          (byte) 0xcb, (byte) 0xf4, 0x39, 0x26});
+      assertThatThrownBy(
+          () -> reader.readAndVerifyCrc(frame, 0, frame.length, CrcType.CRC32, true, 50, 1000))
+          .isInstanceOf(IOException.class)
+          .hasMessageContaining("CRC mismatch");
+
+      assertThat(reader.readAndVerifyCrc(frame, 0, frame.length, CrcType.CRC32, true, 50, 100))
+          .isZero();
+    } finally {
+      writer.close();
+      reader.close();
+    }
+  }
 }